@@ -0,0 +1,5 @@
+fn main() {
+    // Sets the linker flags Node addons need (undefined N-API symbols on macOS)
+    #[cfg(feature = "node")]
+    napi_build::setup();
+}
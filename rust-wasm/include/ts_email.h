@@ -0,0 +1,43 @@
+#ifndef TS_EMAIL_H
+#define TS_EMAIL_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Validates a NUL-terminated UTF-8 email address
+//
+// # Returns
+// A JSON `EmailParseResult`, or a JSON `EmailParseError` (error_type
+// "InvalidArgument" for a null or non-UTF-8 argument). Never null.
+//
+// # Safety
+// `email` must be null or point to a NUL-terminated string that stays valid
+// for the duration of the call.
+char *ts_email_validate(const char *email);
+
+// Validates an email address with a JSON `ValidationOptions` object
+//
+// `options_json` may be null, which uses the default options.
+//
+// # Returns
+// A JSON `EmailParseResult`, or a JSON `EmailParseError` (error_type
+// "InvalidOptions" for options that fail to parse). Never null.
+//
+// # Safety
+// Both arguments must be null or point to NUL-terminated strings that stay
+// valid for the duration of the call.
+char *ts_email_validate_with_options(const char *email, const char *options_json);
+
+// Releases a string returned by this library
+//
+// # Safety
+// `s` must be null or a pointer returned by a `ts_email_*` function that has
+// not been freed yet.
+void ts_email_string_free(char *s);
+
+#endif  /* TS_EMAIL_H */
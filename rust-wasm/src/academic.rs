@@ -0,0 +1,94 @@
+//! Academic institution detection
+//!
+//! Most universities use an academic naming pattern: `.edu`, a reserved
+//! second-level label under a ccTLD (`ac.uk`, `edu.au`, `ac.jp`), or a
+//! `uni-`/`univ-` prefix (`uni-heidelberg.de`, `univ-lyon1.fr`). The
+//! `academic-domains` feature embeds a dataset of institutions whose domains
+//! follow no pattern (`ethz.ch`, `utoronto.ca`).
+
+#[cfg(feature = "academic-domains")]
+use std::collections::HashSet;
+#[cfg(feature = "academic-domains")]
+use std::sync::OnceLock;
+
+/// Institution domains, one per line with `#` comments
+#[cfg(feature = "academic-domains")]
+const INSTITUTION_DOMAINS: &str = include_str!("../data/academic_domains.txt");
+
+/// Second-level labels under a ccTLD reserved for academic institutions
+const EDUCATIONAL_SLDS: [&str; 3] = ["ac", "edu", "sch"];
+
+/// Domain-name prefixes universities use, with the TLDs where the
+/// convention holds
+const UNIVERSITY_PREFIXES: [(&str, &[&str]); 2] = [
+    ("uni-", &["de", "at"]),
+    ("univ-", &["fr"])
+];
+
+/// Entries of the embedded dataset
+#[cfg(feature = "academic-domains")]
+pub(crate) fn institution_domains() -> &'static HashSet<&'static str> {
+    static DOMAINS: OnceLock<HashSet<&'static str>> = OnceLock::new();
+    DOMAINS.get_or_init(|| {
+        INSTITUTION_DOMAINS
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default().trim())
+            .filter(|domain| !domain.is_empty())
+            .collect()
+    })
+}
+
+/// Whether a lowercased domain belongs to a university or school
+pub(crate) fn is_academic(domain: &str) -> bool {
+    let domain = domain.trim_end_matches('.');
+    let mut labels = domain.rsplit('.');
+    let tld = labels.next().unwrap_or_default();
+    let second_level = labels.next().unwrap_or_default();
+
+    if tld == "edu" || (tld.len() == 2 && EDUCATIONAL_SLDS.contains(&second_level)) {
+        return true;
+    }
+    if UNIVERSITY_PREFIXES.iter().any(|(prefix, tlds)| tlds.contains(&tld) && second_level.len() > prefix.len() && second_level.starts_with(prefix)) {
+        return true;
+    }
+    is_listed_institution(domain)
+}
+
+/// Whether the domain or one of its parents is in the embedded dataset
+#[cfg(feature = "academic-domains")]
+fn is_listed_institution(domain: &str) -> bool {
+    let domains = institution_domains();
+    domains.contains(domain) || domain.match_indices('.').any(|(index, _)| domains.contains(&domain[index + 1..]))
+}
+
+#[cfg(not(feature = "academic-domains"))]
+fn is_listed_institution(_domain: &str) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the naming patterns
+    #[test]
+    fn test_academic_patterns() {
+        for domain in ["mit.edu", "cs.ox.ac.uk", "unimelb.edu.au", "u-tokyo.ac.jp", "uni-heidelberg.de", "univ-lyon1.fr"] {
+            assert!(is_academic(domain), "{}", domain);
+        }
+        for domain in ["example.com", "uni-.de", "unicredit.it", "uni-store.com", "edu.example.com"] {
+            assert!(!is_academic(domain), "{}", domain);
+        }
+    }
+
+    /// Tests institutions known only from the dataset, including subdomains
+    #[cfg(feature = "academic-domains")]
+    #[test]
+    fn test_academic_dataset() {
+        assert!(is_academic("ethz.ch"));
+        assert!(is_academic("student.ethz.ch"));
+        assert!(is_academic("utoronto.ca"));
+        assert!(!is_academic("notethz.ch"));
+        assert!(institution_domains().len() > 100);
+    }
+}
@@ -0,0 +1,181 @@
+//! Customer-defined allow/deny rules, evaluated before scoring
+
+use regex::Regex;
+use serde::{Serialize, Deserialize};
+
+use crate::EmailParseError;
+
+/// What happens to an address matching an access rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum AccessAction {
+    /// Accept the address with full trust, skipping domain risk scoring
+    Allow,
+    /// Reject the address
+    Deny
+}
+
+/// An allow or deny rule
+///
+/// `pattern` takes one of these forms:
+/// - `example.com`: exact domain (case-insensitive)
+/// - `*.example.com`: any subdomain of `example.com` (not the domain itself)
+/// - `local:noreply*`: local-part glob with `*` and `?` (case-insensitive)
+/// - `regex:^ops-.*@corp\.com$`: regular expression over the whole address
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AccessRule {
+    /// Action taken when the rule matches
+    pub action: AccessAction,
+    /// Pattern the address is matched against
+    pub pattern: String
+}
+
+/// Compiled form of a rule pattern
+#[derive(Debug, Clone)]
+enum Matcher {
+    Domain(String),
+    /// Domain suffix including the leading dot (`.example.com`)
+    Subdomain(String),
+    LocalPart(Regex),
+    Address(Regex)
+}
+
+impl Matcher {
+    fn compile(pattern: &str) -> Result<Self, EmailParseError> {
+        let compile_regex = |source: &str| Regex::new(source).map_err(|e| EmailParseError {
+            error_type: "InvalidAccessRule".to_string(),
+            message: format!("Invalid access rule pattern \"{}\"", pattern),
+            details: Some(e.to_string()),
+            position: None
+        });
+
+        if let Some(glob) = pattern.strip_prefix("local:") {
+            let source = regex::escape(glob).replace(r"\*", ".*").replace(r"\?", ".");
+            return Ok(Matcher::LocalPart(compile_regex(&format!("(?i)^{}$", source))?));
+        }
+        if let Some(source) = pattern.strip_prefix("regex:") {
+            return Ok(Matcher::Address(compile_regex(source)?));
+        }
+        if let Some(parent) = pattern.strip_prefix("*.") {
+            return Ok(Matcher::Subdomain(format!(".{}", parent.to_lowercase())));
+        }
+        Ok(Matcher::Domain(pattern.to_lowercase()))
+    }
+
+    fn matches(&self, local_part: &str, domain: &str) -> bool {
+        match self {
+            Matcher::Domain(expected) => domain.eq_ignore_ascii_case(expected),
+            Matcher::Subdomain(suffix) => domain.to_lowercase().ends_with(suffix.as_str()),
+            Matcher::LocalPart(regex) => regex.is_match(local_part),
+            Matcher::Address(regex) => regex.is_match(&format!("{}@{}", local_part, domain))
+        }
+    }
+}
+
+/// Ordered allow/deny rules; the first matching rule decides
+///
+/// Deserializes from (and serializes to) an array of `AccessRule`, so it can
+/// be passed in the JS options object as `access_policy`.
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::AccessPolicy;
+/// let policy = AccessPolicy::new()
+///     .allow("partner.com")?
+///     .deny("*.partner.com")?
+///     .deny("local:noreply*")?;
+/// # Ok::<(), email_validator_wasm::EmailParseError>(())
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(try_from = "Vec<AccessRule>", into = "Vec<AccessRule>")]
+pub struct AccessPolicy {
+    rules: Vec<(AccessRule, Matcher)>
+}
+
+impl AccessPolicy {
+    pub fn new() -> Self {
+        AccessPolicy::default()
+    }
+
+    /// Appends a rule, failing if its regex or glob does not compile
+    pub fn with_rule(mut self, rule: AccessRule) -> Result<Self, EmailParseError> {
+        let matcher = Matcher::compile(&rule.pattern)?;
+        self.rules.push((rule, matcher));
+        Ok(self)
+    }
+
+    /// Appends an allow rule
+    pub fn allow(self, pattern: &str) -> Result<Self, EmailParseError> {
+        self.with_rule(AccessRule { action: AccessAction::Allow, pattern: pattern.to_string() })
+    }
+
+    /// Appends a deny rule
+    pub fn deny(self, pattern: &str) -> Result<Self, EmailParseError> {
+        self.with_rule(AccessRule { action: AccessAction::Deny, pattern: pattern.to_string() })
+    }
+
+    /// Whether the policy has no rules
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The first rule matching the address, if any
+    pub fn evaluate(&self, local_part: &str, domain: &str) -> Option<&AccessRule> {
+        self.rules
+            .iter()
+            .find(|(_, matcher)| matcher.matches(local_part, domain))
+            .map(|(rule, _)| rule)
+    }
+}
+
+impl TryFrom<Vec<AccessRule>> for AccessPolicy {
+    type Error = EmailParseError;
+
+    fn try_from(rules: Vec<AccessRule>) -> Result<Self, Self::Error> {
+        rules.into_iter().try_fold(AccessPolicy::new(), AccessPolicy::with_rule)
+    }
+}
+
+impl From<AccessPolicy> for Vec<AccessRule> {
+    fn from(policy: AccessPolicy) -> Self {
+        policy.rules.into_iter().map(|(rule, _)| rule).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests each pattern form and first-match-wins ordering
+    #[test]
+    fn test_access_policy() {
+        let policy = AccessPolicy::new()
+            .allow("vip.partner.com").unwrap()
+            .deny("*.partner.com").unwrap()
+            .deny("local:no?reply*").unwrap()
+            .deny(r"regex:^ops-\d+@corp\.com$").unwrap();
+
+        assert_eq!(policy.evaluate("jane", "VIP.partner.com").map(|r| r.action), Some(AccessAction::Allow));
+        assert_eq!(policy.evaluate("jane", "eu.partner.com").map(|r| r.action), Some(AccessAction::Deny));
+        assert_eq!(policy.evaluate("jane", "partner.com"), None);
+        assert_eq!(policy.evaluate("No-Reply-7", "example.com").map(|r| r.pattern.as_str()), Some("local:no?reply*"));
+        assert_eq!(policy.evaluate("ops-12", "corp.com").map(|r| r.action), Some(AccessAction::Deny));
+        assert_eq!(policy.evaluate("ops-x", "corp.com"), None);
+
+        let error = AccessPolicy::new().deny("regex:(unclosed").unwrap_err();
+        assert_eq!(error.error_type, "InvalidAccessRule");
+    }
+
+    /// Tests that the policy round-trips through its JSON rule-array form
+    #[test]
+    fn test_access_policy_serde() {
+        let policy: AccessPolicy = serde_json::from_str(r#"[{"action": "deny", "pattern": "*.spam.example"}]"#).unwrap();
+        assert!(policy.evaluate("a", "x.spam.example").is_some());
+        assert_eq!(serde_json::to_string(&policy).unwrap(), r#"[{"action":"deny","pattern":"*.spam.example"}]"#);
+
+        assert!(serde_json::from_str::<AccessPolicy>(r#"[{"action": "deny", "pattern": "regex:["}]"#).is_err());
+    }
+}
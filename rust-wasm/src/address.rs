@@ -0,0 +1,147 @@
+//! `EmailAddress`: a parsed, guaranteed-valid address
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::message::Message;
+use crate::{parse_and_validate_email, EmailParseError};
+
+/// An email address that passed validation
+///
+/// Holds the normalized form: the domain is lowercased while the local part
+/// keeps its case, since RFC 5321 leaves local-part case significance to the
+/// receiving server. Equality, ordering and hashing use this form, so
+/// `user@Example.com` and `user@example.com` are the same address. Serializes
+/// as a plain string and validates when deserialized.
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::EmailAddress;
+/// let address: EmailAddress = "Jane.Doe@Example.com".parse()?;
+/// assert_eq!(address.to_string(), "Jane.Doe@example.com");
+/// assert_eq!(address.domain(), "example.com");
+/// # Ok::<(), email_validator_wasm::EmailParseError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EmailAddress {
+    address: String,
+    /// Byte offset of the `@` in `address`
+    at: usize
+}
+
+impl EmailAddress {
+    /// The full normalized address
+    pub fn as_str(&self) -> &str {
+        &self.address
+    }
+
+    /// The local part (before the `@`)
+    pub fn local_part(&self) -> &str {
+        &self.address[..self.at]
+    }
+
+    /// The lowercased domain (after the `@`)
+    pub fn domain(&self) -> &str {
+        &self.address[self.at + 1..]
+    }
+
+    /// Consumes the address, returning the normalized string
+    pub fn into_string(self) -> String {
+        self.address
+    }
+}
+
+impl FromStr for EmailAddress {
+    type Err = EmailParseError;
+
+    /// Validates `s` with `parse_and_validate_email`; invalid input yields an
+    /// "InvalidEmail" error carrying the validation message
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let result = parse_and_validate_email(s)?;
+        match (result.is_valid, result.local_part, result.domain) {
+            (true, Some(local_part), Some(domain)) => Ok(EmailAddress {
+                at: local_part.len(),
+                address: format!("{}@{}", local_part, domain.to_lowercase())
+            }),
+            _ => Err(EmailParseError {
+                error_type: "InvalidEmail".to_string(),
+                message: result.error_message.unwrap_or_else(|| Message::InvalidFormat.english().to_string()),
+                details: Some(s.to_string()),
+                position: None
+            })
+        }
+    }
+}
+
+impl TryFrom<&str> for EmailAddress {
+    type Error = EmailParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for EmailAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.address)
+    }
+}
+
+impl AsRef<str> for EmailAddress {
+    fn as_ref(&self) -> &str {
+        &self.address
+    }
+}
+
+impl From<EmailAddress> for String {
+    fn from(address: EmailAddress) -> Self {
+        address.address
+    }
+}
+
+impl Serialize for EmailAddress {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.address)
+    }
+}
+
+impl<'de> Deserialize<'de> for EmailAddress {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let address = String::deserialize(deserializer)?;
+        address.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// Tests parsing, normalization and equality on the normalized form
+    #[test]
+    fn test_email_address() {
+        let address: EmailAddress = "Jane.Doe+news@Example.COM".parse().unwrap();
+        assert_eq!(address.as_str(), "Jane.Doe+news@example.com");
+        assert_eq!(address.local_part(), "Jane.Doe+news");
+        assert_eq!(address.domain(), "example.com");
+
+        let same = EmailAddress::try_from("Jane.Doe+news@example.com").unwrap();
+        assert_eq!(address, same);
+        assert_eq!(HashSet::from([address, same]).len(), 1);
+
+        let error = "invalid-email".parse::<EmailAddress>().unwrap_err();
+        assert_eq!(error.error_type, "InvalidEmail");
+        assert_eq!(error.message, "Invalid email format");
+    }
+
+    /// Tests that serde round-trips a plain string and rejects invalid input
+    #[test]
+    fn test_email_address_serde() {
+        let address: EmailAddress = serde_json::from_str(r#""user@Example.com""#).unwrap();
+        assert_eq!(serde_json::to_string(&address).unwrap(), r#""user@example.com""#);
+
+        assert!(serde_json::from_str::<EmailAddress>(r#""not an email""#).is_err());
+    }
+}
@@ -0,0 +1,195 @@
+//! Append-only audit log of verification decisions
+//!
+//! Each decision is written as one JSON line: when it was made, a hash of the
+//! input, a version identifying the configuration that made it, the stages
+//! that ran with their outcomes, and the verdict with its reason. Only the
+//! hash of the address is written, so the log can be retained for as long as
+//! compliance requires and still be matched against a disputed address.
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+use serde::{Serialize, Deserialize};
+
+use crate::hash::{hash_email, sha256_hex};
+use crate::time::unix_time_ms;
+use crate::{build_info, EmailParseError, EmailParseResult, VerificationResult, Verdict};
+
+/// One recorded decision
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// When the decision was made, in Unix milliseconds
+    pub timestamp_ms: u64,
+    /// `hash_email` digest of the input
+    pub email_hash: String,
+    /// Identifies the crate version, domain lists and options in effect (see
+    /// `config_version`)
+    pub config_version: String,
+    /// Network stages that ran, in order (empty for syntax-only validation)
+    pub stages: Vec<AuditStage>,
+    /// Overall outcome; syntax-only validation of a valid address is `unknown`
+    pub verdict: Verdict,
+    /// What decided a negative verdict: the validation error, the domain
+    /// failure code, the SMTP rejection or the listing blocklist
+    pub reason: Option<String>,
+    /// Final domain score
+    pub domain_score: Option<f64>
+}
+
+/// A network stage and how it finished
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditStage {
+    pub stage: String,
+    pub outcome: String
+}
+
+impl AuditEntry {
+    /// Entry for a syntax-only validation
+    pub fn for_validation(email: &str, result: &EmailParseResult, config_version: &str) -> Self {
+        AuditEntry {
+            timestamp_ms: unix_time_ms(),
+            email_hash: hash_email(email),
+            config_version: config_version.to_string(),
+            stages: Vec::new(),
+            verdict: if result.is_valid { Verdict::Unknown } else { Verdict::Invalid },
+            reason: result.error_message.clone(),
+            domain_score: result.domain_score
+        }
+    }
+
+    /// Entry for a verification through the network stages in `stages`
+    pub fn for_verification(email: &str, result: &VerificationResult, stages: Vec<AuditStage>, config_version: &str) -> Self {
+        let checks = &result.domain_checks;
+        let smtp_rejection = result
+            .smtp
+            .as_ref()
+            .filter(|smtp| smtp.is_deliverable == Some(false))
+            .map(|smtp| format!("SMTP {} {}", smtp.response_code.unwrap_or_default(), smtp.response_message.as_deref().unwrap_or_default()));
+        let reason = result
+            .email
+            .error_message
+            .clone()
+            .or_else(|| checks.error_code.clone())
+            .or(smtp_rejection)
+            .or_else(|| checks.blocklist.as_ref().map(|blocklist| format!("Listed on {}", blocklist)));
+        AuditEntry {
+            timestamp_ms: result.verified_at.unwrap_or_else(unix_time_ms),
+            email_hash: hash_email(email),
+            config_version: config_version.to_string(),
+            stages,
+            verdict: result.verdict(),
+            reason,
+            domain_score: result.email.domain_score
+        }
+    }
+}
+
+/// Version string for a decision-making configuration
+///
+/// Combines the crate version with a digest of the built-in and loaded domain
+/// lists and of `config` (typically the validation options and stages), so
+/// two decisions share a version exactly when the same rules made them.
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::{config_version, Stages, ValidationOptions};
+/// # let (options, stages) = (ValidationOptions::default(), Stages::default());
+/// let version = config_version(&(&options, &stages));
+/// // "0.1.0+3f2a9c01d4e5b6a7"
+/// ```
+pub fn config_version(config: &impl Serialize) -> String {
+    let lists: Vec<String> = build_info().domain_lists.into_iter().map(|list| format!("{}={}", list.name, list.digest)).collect();
+    let config = serde_json::to_string(&(lists, config)).unwrap_or_default();
+    format!("{}+{}", crate::version(), &sha256_hex(config.as_bytes())[..16])
+}
+
+/// Append-only JSONL file of `AuditEntry` records
+///
+/// Entries are written with a single write each, so concurrent writers
+/// (including other processes appending to the same file) never interleave
+/// within a line.
+///
+/// # Examples
+/// ```no_run
+/// # use email_validator_wasm::{config_version, AuditEntry, AuditLog, Stages, ValidationOptions, VerificationResult};
+/// # fn example(email: &str, result: &VerificationResult, options: &ValidationOptions, stages: &Stages) -> Result<(), email_validator_wasm::EmailParseError> {
+/// # let stages_run = Vec::new();
+/// let audit = AuditLog::open("audit.jsonl")?;
+/// let version = config_version(&(&options, &stages));
+/// audit.record(&AuditEntry::for_verification(email, &result, stages_run, &version))?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AuditLog {
+    file: Mutex<File>
+}
+
+impl AuditLog {
+    /// Opens `path` for appending, creating it if needed
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, EmailParseError> {
+        let path = path.as_ref();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| audit_error("Failed to open audit log", format!("{}: {}", path.display(), e)))?;
+        Ok(AuditLog { file: Mutex::new(file) })
+    }
+
+    /// Appends `entry` as one line
+    pub fn record(&self, entry: &AuditEntry) -> Result<(), EmailParseError> {
+        let mut line = serde_json::to_vec(entry).map_err(|e| audit_error("Failed to serialize audit entry", e.to_string()))?;
+        line.push(b'\n');
+        let mut file = self.file.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        file.write_all(&line).map_err(|e| audit_error("Failed to write audit log", e.to_string()))
+    }
+}
+
+/// "AuditLogError" error with `details`
+fn audit_error(message: &str, details: String) -> EmailParseError {
+    EmailParseError {
+        error_type: "AuditLogError".to_string(),
+        message: message.to_string(),
+        details: Some(details),
+        position: None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_and_validate_email;
+
+    /// Tests that entries are appended as JSON lines without the plaintext address
+    #[test]
+    fn test_audit_log() {
+        let path = std::env::temp_dir().join(format!("audit-test-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let version = config_version(&"strict");
+        assert!(version.starts_with(&format!("{}+", crate::version())));
+        assert_eq!(version, config_version(&"strict"));
+        assert_ne!(version, config_version(&"lenient"));
+
+        let audit = AuditLog::open(&path).unwrap();
+        let invalid = parse_and_validate_email("jane.doe.example.com").unwrap();
+        audit.record(&AuditEntry::for_validation("jane.doe.example.com", &invalid, &version)).unwrap();
+        let valid = parse_and_validate_email("jane@example.com").unwrap();
+        audit.record(&AuditEntry::for_validation("jane@example.com", &valid, &version)).unwrap();
+        drop(audit);
+        AuditLog::open(&path).unwrap().record(&AuditEntry::for_validation("jane@example.com", &valid, &version)).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(!contents.contains("jane"));
+        let entries: Vec<AuditEntry> = contents.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].verdict, Verdict::Invalid);
+        assert_eq!(entries[0].reason.as_deref(), Some("Invalid email format"));
+        assert_eq!(entries[1].verdict, Verdict::Unknown);
+        assert_eq!(entries[1].email_hash, hash_email("jane@example.com"));
+        assert_eq!(entries[2].config_version, version);
+    }
+}
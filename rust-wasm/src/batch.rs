@@ -0,0 +1,170 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{dedupe, patterns, prefilter, EmailParseError, EmailParseResult, ValidationOptions};
+
+/// Validates a batch of email addresses with the given options
+///
+/// Besides validating each address, applies checks that need the whole
+/// batch: with `detect_suspicious_patterns` enabled, numbered series such as
+/// `user1@`, `user2@`, `user3@` are flagged in addition to per-address
+/// templates, and with `detect_duplicates` enabled, addresses that repeat an
+/// earlier one up to case, plus tags and Gmail dots get `duplicate_of`.
+///
+/// Inputs that are invalid at a glance (no `@`, illegal characters) are
+/// rejected by a prefilter without running the full parser.
+///
+/// # Arguments
+/// * `emails` - The email strings to validate
+/// * `options` - Validation options
+///
+/// # Returns
+/// * `Vec<Result<EmailParseResult, EmailParseError>>` - One entry per input, in input order
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::{parse_and_validate_emails, ValidationOptions};
+/// let options = ValidationOptions { detect_suspicious_patterns: true, ..Default::default() };
+/// let results = parse_and_validate_emails(&["a1@x.com", "a2@x.com", "a3@x.com"], &options);
+/// assert!(results.iter().all(|r| r.as_ref().unwrap().is_suspicious_pattern == Some(true)));
+/// ```
+pub fn parse_and_validate_emails<S: AsRef<str>>(emails: &[S], options: &ValidationOptions) -> Vec<Result<EmailParseResult, EmailParseError>> {
+    let mut results: Vec<_> = emails
+        .iter()
+        .map(|email| prefilter::validate_prefiltered(email.as_ref(), options))
+        .collect();
+
+    if options.detect_suspicious_patterns {
+        patterns::flag_suspicious_patterns(&mut results);
+    }
+    if options.detect_duplicates {
+        dedupe::flag_duplicates(&mut results);
+    }
+
+    results
+}
+
+/// Validates a slice of email addresses in parallel using rayon
+///
+/// Work is spread across the rayon global thread pool. The output is
+/// deterministic: the result at index `i` always corresponds to `emails[i]`,
+/// regardless of which thread processed it. Results match
+/// `parse_and_validate_emails`: the batch-wide pattern and duplicate checks
+/// run once every address is validated.
+///
+/// # Arguments
+/// * `emails` - The email strings to validate
+/// * `options` - Validation options
+///
+/// # Returns
+/// * `Vec<Result<EmailParseResult, EmailParseError>>` - One entry per input, in input order
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::{parse_and_validate_emails_par, ValidationOptions};
+/// let results = parse_and_validate_emails_par(&["user@example.com", "invalid-email"], &ValidationOptions::default());
+/// assert!(results[0].as_ref().unwrap().is_valid);
+/// assert!(!results[1].as_ref().unwrap().is_valid);
+/// ```
+#[cfg(feature = "parallel")]
+pub fn parse_and_validate_emails_par<S: AsRef<str> + Sync>(emails: &[S], options: &ValidationOptions) -> Vec<Result<EmailParseResult, EmailParseError>> {
+    let mut results: Vec<_> = emails
+        .par_iter()
+        .map(|email| prefilter::validate_prefiltered(email.as_ref(), options))
+        .collect();
+
+    if options.detect_suspicious_patterns {
+        patterns::flag_suspicious_patterns(&mut results);
+    }
+    if options.detect_duplicates {
+        dedupe::flag_duplicates(&mut results);
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that numbered series are flagged across a batch
+    #[test]
+    fn test_batch_suspicious_patterns() {
+        let options = ValidationOptions {
+            detect_suspicious_patterns: true,
+            ..Default::default()
+        };
+        let emails = [
+            "promo7@example.com",
+            "promo8@example.com",
+            "promo9@example.com",
+            "jdoe1987@example.com",
+            "test+0001@gmail.com",
+            "jane@example.com",
+            "invalid-email"
+        ];
+
+        let flags: Vec<Option<bool>> = parse_and_validate_emails(&emails, &options)
+            .into_iter()
+            .map(|result| result.unwrap().is_suspicious_pattern)
+            .collect();
+        assert_eq!(flags, vec![Some(true), Some(true), Some(true), Some(false), Some(true), Some(false), None]);
+
+        let results = parse_and_validate_emails(&emails, &ValidationOptions::default());
+        assert!(results.iter().all(|result| result.as_ref().unwrap().is_suspicious_pattern.is_none()));
+    }
+
+    /// Tests that functional duplicates point at their first occurrence
+    #[test]
+    fn test_batch_duplicates() {
+        let options = ValidationOptions {
+            detect_duplicates: true,
+            ..Default::default()
+        };
+        let emails = ["j.doe@gmail.com", "jane@example.com", "JDoe+promo@gmail.com", "invalid-email", "Jane@Example.com"];
+
+        let duplicates: Vec<Option<usize>> = parse_and_validate_emails(&emails, &options)
+            .into_iter()
+            .map(|result| result.unwrap().duplicate_of)
+            .collect();
+        assert_eq!(duplicates, vec![None, None, Some(0), None, Some(1)]);
+
+        let results = parse_and_validate_emails(&emails, &ValidationOptions::default());
+        assert!(results.iter().all(|result| result.as_ref().unwrap().duplicate_of.is_none()));
+    }
+
+    /// Tests that parallel results preserve input ordering
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_ordering() {
+        let emails: Vec<String> = (0..1000)
+            .map(|i| if i % 3 == 0 { format!("user{}", i) } else { format!("user{}@example.com", i) })
+            .collect();
+
+        let results = parse_and_validate_emails_par(&emails, &ValidationOptions::default());
+        assert_eq!(results.len(), emails.len());
+
+        for (i, result) in results.iter().enumerate() {
+            let result = result.as_ref().unwrap();
+            if i % 3 == 0 {
+                assert!(!result.is_valid);
+            } else {
+                assert!(result.is_valid);
+                assert_eq!(result.local_part, Some(format!("user{}", i)));
+            }
+        }
+
+        let mut emails = emails;
+        emails.extend(["promo7@example.com", "promo8@example.com", "promo9@example.com", "User1@Example.com", "j.doe@gmail.com", "JDoe+x@gmail.com"].map(String::from));
+        let options = ValidationOptions {
+            detect_suspicious_patterns: true,
+            detect_duplicates: true,
+            ..Default::default()
+        };
+        let serial = parse_and_validate_emails(&emails, &options);
+        let parallel = parse_and_validate_emails_par(&emails, &options);
+        assert_eq!(format!("{:?}", parallel), format!("{:?}", serial));
+        assert!(parallel.iter().any(|result| result.as_ref().unwrap().duplicate_of.is_some()));
+        assert!(parallel.iter().any(|result| result.as_ref().unwrap().is_suspicious_pattern == Some(true)));
+    }
+}
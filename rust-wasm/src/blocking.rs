@@ -0,0 +1,83 @@
+//! Blocking network work moved off the caller's thread
+//!
+//! The native resolver and the SMTP probe use blocking sockets. Running them
+//! on a worker thread keeps them from stalling the executor that awaits
+//! them, and lets `cancel::interruptible` give up on them at a stage timeout
+//! or cancellation; the worker then finishes on its own socket timeouts.
+
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+/// What the worker hands back, and the task waiting for it
+struct Shared<T> {
+    output: Option<thread::Result<T>>,
+    waker: Option<Waker>
+}
+
+/// Runs `work` on a new thread, completing with its output
+///
+/// A panic in `work` resumes in the awaiting task.
+pub(crate) fn run<T: Send + 'static>(work: impl FnOnce() -> T + Send + 'static) -> Blocking<T> {
+    let shared = Arc::new(Mutex::new(Shared { output: None, waker: None }));
+    let worker = shared.clone();
+    let spawned = thread::Builder::new().name("email-verifier-io".to_string()).spawn(move || {
+        let output = panic::catch_unwind(AssertUnwindSafe(work));
+        let waker = {
+            let mut shared = worker.lock().unwrap_or_else(|e| e.into_inner());
+            shared.output = Some(output);
+            shared.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    });
+    if let Err(e) = spawned {
+        shared.lock().unwrap_or_else(|e| e.into_inner()).output = Some(Err(Box::new(e)));
+    }
+    Blocking { shared }
+}
+
+/// Future returned by `run`
+pub(crate) struct Blocking<T> {
+    shared: Arc<Mutex<Shared<T>>>
+}
+
+impl<T> Future for Blocking<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut shared = self.shared.lock().unwrap_or_else(|e| e.into_inner());
+        match shared.output.take() {
+            Some(Ok(output)) => Poll::Ready(output),
+            Some(Err(payload)) => panic::resume_unwind(payload),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cancel::{interruptible, Interrupted};
+    use crate::test_util::block_on;
+    use std::time::{Duration, Instant};
+
+    /// Tests that work completes off-thread and that a timeout does not wait for it
+    #[test]
+    fn test_run() {
+        let caller = thread::current().id();
+        assert_ne!(block_on(run(|| thread::current().id())), caller);
+
+        let started = Instant::now();
+        let slow = run(|| thread::sleep(Duration::from_secs(2)));
+        assert_eq!(block_on(interruptible(slow, Some(Duration::from_millis(20)), None)), Err(Interrupted::TimedOut));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
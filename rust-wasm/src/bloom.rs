@@ -0,0 +1,209 @@
+//! Compact probabilistic membership for very large blocklists
+//!
+//! A million-entry blocklist at a 1% false-positive rate serializes to about
+//! 1.2 MB of bits (about 600 KB at 10%), small enough to ship to the browser, and
+//! each lookup probes a fixed number of bits regardless of list size. Lookups
+//! never miss a listed entry but may report an unlisted one as present.
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::EmailParseError;
+
+/// Magic bytes identifying a serialized filter
+const MAGIC: &[u8; 4] = b"EVBF";
+
+/// Serialization format version
+const FORMAT_VERSION: u8 = 1;
+
+/// Header length: magic, version, hash count (u32 LE), bit count (u64 LE)
+const HEADER_LENGTH: usize = 4 + 1 + 4 + 8;
+
+/// Largest hash count the builder produces, and the largest accepted from bytes
+const MAX_HASH_COUNT: u32 = 32;
+
+/// Collects entries and sizes a `BloomFilter` for a target false-positive rate
+///
+/// # Examples
+/// ```no_run
+/// # use email_validator_wasm::BloomFilterBuilder;
+/// # let blocklist = ["spam.example"];
+/// let mut builder = BloomFilterBuilder::new(1_000_000, 0.01);
+/// for domain in blocklist {
+///     builder.insert(domain);
+/// }
+/// std::fs::write("blocklist.bloom", builder.build().to_bytes())?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct BloomFilterBuilder {
+    filter: BloomFilter
+}
+
+impl BloomFilterBuilder {
+    /// Sizes the filter for `expected_items` entries at `false_positive_rate`
+    /// (clamped to 0.000001-0.5); inserting more entries raises the rate
+    pub fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        let items = expected_items.max(1) as f64;
+        let rate = false_positive_rate.clamp(1e-6, 0.5);
+        let ln2 = std::f64::consts::LN_2;
+
+        let bit_count = (-items * rate.ln() / (ln2 * ln2)).ceil().max(8.0) as u64;
+        let hash_count = ((bit_count as f64 / items) * ln2).round().clamp(1.0, MAX_HASH_COUNT as f64) as u32;
+
+        BloomFilterBuilder {
+            filter: BloomFilter {
+                hash_count,
+                bit_count,
+                bits: vec![0; bit_count.div_ceil(8) as usize]
+            }
+        }
+    }
+
+    /// Adds an entry (trimmed and lowercased, like lookups)
+    pub fn insert(&mut self, item: &str) {
+        let filter = &mut self.filter;
+        for bit in bit_positions(item, filter.hash_count, filter.bit_count) {
+            filter.bits[(bit / 8) as usize] |= 1 << (bit % 8);
+        }
+    }
+
+    /// Finishes the filter
+    pub fn build(self) -> BloomFilter {
+        self.filter
+    }
+}
+
+/// Bloom filter over normalized strings (domains or addresses)
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BloomFilter {
+    hash_count: u32,
+    bit_count: u64,
+    bits: Vec<u8>
+}
+
+impl BloomFilter {
+    /// Whether `item` (trimmed and lowercased) may be in the set; false
+    /// positives occur at the configured rate, false negatives never
+    pub fn contains(&self, item: &str) -> bool {
+        bit_positions(item, self.hash_count, self.bit_count)
+            .all(|bit| self.bits[(bit / 8) as usize] & (1 << (bit % 8)) != 0)
+    }
+
+    /// Serializes the filter for shipping (e.g. to the browser)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LENGTH + self.bits.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(FORMAT_VERSION);
+        bytes.extend_from_slice(&self.hash_count.to_le_bytes());
+        bytes.extend_from_slice(&self.bit_count.to_le_bytes());
+        bytes.extend_from_slice(&self.bits);
+        bytes
+    }
+
+    /// Restores a filter serialized with `to_bytes`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EmailParseError> {
+        let invalid = |message: &str| EmailParseError {
+            error_type: "InvalidBloomFilter".to_string(),
+            message: message.to_string(),
+            details: None,
+            position: None
+        };
+
+        if bytes.len() < HEADER_LENGTH || &bytes[..4] != MAGIC {
+            return Err(invalid("Not a serialized bloom filter"));
+        }
+        if bytes[4] != FORMAT_VERSION {
+            return Err(invalid("Unsupported bloom filter version"));
+        }
+
+        let hash_count = u32::from_le_bytes(bytes[5..9].try_into().unwrap_or_default());
+        let bit_count = u64::from_le_bytes(bytes[9..17].try_into().unwrap_or_default());
+        let bits = &bytes[HEADER_LENGTH..];
+        if !(1..=MAX_HASH_COUNT).contains(&hash_count) {
+            return Err(invalid("Bloom filter hash count must be between 1 and 32"));
+        }
+        if bit_count == 0 || bits.len() as u64 != bit_count.div_ceil(8) {
+            return Err(invalid("Bloom filter header does not match its contents"));
+        }
+
+        Ok(BloomFilter { hash_count, bit_count, bits: bits.to_vec() })
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl BloomFilter {
+    /// Restores a filter from bytes produced by `BloomFilter::to_bytes`
+    ///
+    /// # Examples
+    /// ```javascript
+    /// const bytes = new Uint8Array(await (await fetch("/blocklist.bloom")).arrayBuffer());
+    /// const blocklist = BloomFilter.from_bytes(bytes);
+    /// if (blocklist.contains(domain)) reject();
+    /// ```
+    #[wasm_bindgen(js_name = from_bytes)]
+    pub fn from_bytes_wasm(bytes: &[u8]) -> Result<BloomFilter, JsValue> {
+        BloomFilter::from_bytes(bytes).map_err(|e| crate::wasm::to_js(&e))
+    }
+
+    /// Whether `item` may be in the set
+    #[wasm_bindgen(js_name = contains)]
+    pub fn contains_wasm(&self, item: &str) -> bool {
+        self.contains(item)
+    }
+}
+
+/// Bit positions for an item via double hashing (Kirsch-Mitzenmacher)
+fn bit_positions(item: &str, hash_count: u32, bit_count: u64) -> impl Iterator<Item = u64> {
+    let normalized = item.trim().to_lowercase();
+    let h1 = fnv1a(normalized.as_bytes());
+    let h2 = mix(h1) | 1;
+    (0..hash_count as u64).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % bit_count)
+}
+
+/// 64-bit FNV-1a, stable across platforms so filters built natively work in wasm
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3))
+}
+
+/// SplitMix64 finalizer, deriving the second hash from the first
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests membership, the false-positive rate and a serialization round trip
+    #[test]
+    fn test_bloom_filter() {
+        let mut builder = BloomFilterBuilder::new(10_000, 0.01);
+        for i in 0..10_000 {
+            builder.insert(&format!("blocked-{}.example", i));
+        }
+        let filter = builder.build();
+
+        assert!((0..10_000).all(|i| filter.contains(&format!("blocked-{}.example", i))));
+        assert!(filter.contains(" BLOCKED-42.example "));
+
+        let false_positives = (0..10_000).filter(|i| filter.contains(&format!("allowed-{}.example", i))).count();
+        assert!(false_positives < 200, "{} false positives", false_positives);
+
+        let restored = BloomFilter::from_bytes(&filter.to_bytes()).unwrap();
+        assert_eq!(restored, filter);
+
+        assert_eq!(BloomFilter::from_bytes(b"nope").unwrap_err().error_type, "InvalidBloomFilter");
+        let mut truncated = filter.to_bytes();
+        truncated.pop();
+        assert!(BloomFilter::from_bytes(&truncated).is_err());
+
+        let mut crafted = filter.to_bytes();
+        crafted[5..9].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(BloomFilter::from_bytes(&crafted).unwrap_err().error_type, "InvalidBloomFilter");
+    }
+}
@@ -0,0 +1,251 @@
+//! k-anonymity breach lookups against a Have-I-Been-Pwned style range API
+//!
+//! Only the first five hex characters of the address hash leave the process;
+//! the range response lists every known hash suffix under that prefix and the
+//! match is made locally.
+
+use serde::{Serialize, Deserialize};
+
+use crate::hash::hash_email;
+
+/// Number of hash characters sent to the range endpoint
+pub const RANGE_PREFIX_LENGTH: usize = 5;
+
+/// Bytes of a range response read; a full range is a few tens of kilobytes
+#[cfg(all(feature = "tls", not(target_arch = "wasm32")))]
+const MAX_RANGE_SIZE: usize = 1024 * 1024;
+
+/// How long `HttpsBreachSource` waits on the network unless told otherwise
+#[cfg(all(feature = "tls", not(target_arch = "wasm32")))]
+const DEFAULT_RANGE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Error structure for breach lookup failures
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BreachError {
+    /// Type of error that occurred (e.g., "IoError", "RateLimited")
+    pub error_type: String,
+    /// Human-readable error message
+    pub message: String,
+    /// Additional error details if available
+    pub details: Option<String>
+}
+
+impl BreachError {
+    pub fn new(error_type: &str, message: &str, details: Option<String>) -> Self {
+        BreachError {
+            error_type: error_type.to_string(),
+            message: message.to_string(),
+            details
+        }
+    }
+}
+
+impl std::fmt::Display for BreachError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}", self.error_type, self.message)
+    }
+}
+
+/// Source of range responses for the breach stage
+///
+/// `fetch_range` receives the first `RANGE_PREFIX_LENGTH` characters of the
+/// address's `hash_email` digest and returns the response body: one hash
+/// suffix per line, optionally followed by `:count` (the Pwned Passwords
+/// range format).
+// The futures are intentionally not `Send`: the wasm source holds JS values.
+#[allow(async_fn_in_trait)]
+pub trait BreachRangeSource {
+    /// Fetches the range response for a hash prefix
+    async fn fetch_range(&self, prefix: &str) -> Result<String, BreachError>;
+}
+
+impl<T: BreachRangeSource + ?Sized> BreachRangeSource for &T {
+    async fn fetch_range(&self, prefix: &str) -> Result<String, BreachError> {
+        (**self).fetch_range(prefix).await
+    }
+}
+
+/// Placeholder source used while the breach stage is disabled; never queried
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoBreachCheck;
+
+impl BreachRangeSource for NoBreachCheck {
+    async fn fetch_range(&self, _prefix: &str) -> Result<String, BreachError> {
+        Ok(String::new())
+    }
+}
+
+/// Source that queries an HIBP-compatible range endpoint through the host's `fetch`
+///
+/// The prefix replaces `{prefix}` in the endpoint URL, or is appended to it
+/// when the URL has no placeholder.
+///
+/// # Examples
+/// ```
+/// let source = FetchBreachSource::new("https://breach.internal.example/range/{prefix}");
+/// ```
+#[cfg(target_arch = "wasm32")]
+#[derive(Debug, Clone)]
+pub struct FetchBreachSource {
+    endpoint: String
+}
+
+#[cfg(target_arch = "wasm32")]
+impl FetchBreachSource {
+    pub fn new(endpoint: &str) -> Self {
+        FetchBreachSource {
+            endpoint: endpoint.to_string()
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl BreachRangeSource for FetchBreachSource {
+    async fn fetch_range(&self, prefix: &str) -> Result<String, BreachError> {
+        crate::fetch::fetch_text(&range_url(&self.endpoint, prefix), "text/plain").await.map_err(|e| {
+            BreachError::new("IoError", "Breach range request failed", e.as_string().or_else(|| js_sys::JSON::stringify(&e).ok().map(String::from)))
+        })
+    }
+}
+
+/// Source that queries an HIBP-compatible range endpoint over HTTPS
+///
+/// The prefix replaces `{prefix}` in the endpoint URL, or is appended to it
+/// when the URL has no placeholder. The request blocks the calling thread
+/// for at most the timeout (10 seconds unless set with `with_timeout`).
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::breach::HttpsBreachSource;
+/// let source = HttpsBreachSource::new("https://breach.internal.example/range/{prefix}");
+/// ```
+#[cfg(all(feature = "tls", not(target_arch = "wasm32")))]
+#[derive(Debug, Clone)]
+pub struct HttpsBreachSource {
+    endpoint: String,
+    timeout: std::time::Duration
+}
+
+#[cfg(all(feature = "tls", not(target_arch = "wasm32")))]
+impl HttpsBreachSource {
+    pub fn new(endpoint: &str) -> Self {
+        HttpsBreachSource {
+            endpoint: endpoint.to_string(),
+            timeout: DEFAULT_RANGE_TIMEOUT
+        }
+    }
+
+    /// Bounds connecting, and each read and write, to `timeout`
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+#[cfg(all(feature = "tls", not(target_arch = "wasm32")))]
+impl BreachRangeSource for HttpsBreachSource {
+    async fn fetch_range(&self, prefix: &str) -> Result<String, BreachError> {
+        let url = range_url(&self.endpoint, prefix);
+        let (host, path) = crate::http::split_https_url(&url)
+            .ok_or_else(|| BreachError::new("InvalidEndpoint", "Breach endpoint must be an https:// URL", Some(self.endpoint.clone())))?;
+        let (status, body) = crate::http::https_get(host, path, self.timeout, MAX_RANGE_SIZE)
+            .await
+            .map_err(|e| BreachError::new("IoError", "Breach range request failed", Some(e)))?;
+        match status {
+            200 => Ok(body),
+            // Some range APIs answer 404 when no hash has the prefix
+            404 => Ok(String::new()),
+            429 => Err(BreachError::new("RateLimited", "Breach range API rate limit exceeded", None)),
+            status => Err(BreachError::new("HttpError", "Breach range request failed", Some(format!("HTTP status {}", status))))
+        }
+    }
+}
+
+/// Range URL for `prefix`: the prefix replaces `{prefix}` in `endpoint`, or
+/// is appended to it when the URL has no placeholder
+#[cfg(any(target_arch = "wasm32", feature = "tls", test))]
+fn range_url(endpoint: &str, prefix: &str) -> String {
+    if endpoint.contains("{prefix}") {
+        endpoint.replace("{prefix}", prefix)
+    } else {
+        format!("{}{}", endpoint, prefix)
+    }
+}
+
+/// Checks whether an address appears in the breach corpus behind `source`
+///
+/// # Arguments
+/// * `source` - Range source to query
+/// * `email` - The address to check (normalized as in `hash_email`)
+///
+/// # Returns
+/// * `Result<bool, BreachError>` - Whether the address's hash is listed, or error
+pub async fn check_breach<S: BreachRangeSource>(source: &S, email: &str) -> Result<bool, BreachError> {
+    let hash = hash_email(email);
+    let (prefix, suffix) = hash.split_at(RANGE_PREFIX_LENGTH);
+    let body = source.fetch_range(prefix).await?;
+    Ok(range_contains(&body, suffix))
+}
+
+/// Whether a range response lists `suffix` (case-insensitive, ignoring counts)
+fn range_contains(body: &str, suffix: &str) -> bool {
+    body.lines()
+        .map(|line| line.split(':').next().unwrap_or_default().trim())
+        .any(|listed| listed.eq_ignore_ascii_case(suffix))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::test_util::block_on;
+    use std::cell::RefCell;
+
+    /// Range source backed by a fixed list of breached addresses, recording
+    /// the prefixes it was asked for
+    #[derive(Default)]
+    pub(crate) struct MockBreachSource {
+        breached: Vec<String>,
+        pub(crate) queried: RefCell<Vec<String>>
+    }
+
+    impl MockBreachSource {
+        pub(crate) fn new(breached: &[&str]) -> Self {
+            MockBreachSource {
+                breached: breached.iter().map(|email| hash_email(email)).collect(),
+                queried: RefCell::new(Vec::new())
+            }
+        }
+    }
+
+    impl BreachRangeSource for MockBreachSource {
+        async fn fetch_range(&self, prefix: &str) -> Result<String, BreachError> {
+            self.queried.borrow_mut().push(prefix.to_string());
+            Ok(self.breached
+                .iter()
+                .filter(|hash| hash.starts_with(prefix))
+                .map(|hash| format!("{}:3\r\n", hash[RANGE_PREFIX_LENGTH..].to_uppercase()))
+                .collect())
+        }
+    }
+
+    /// Tests that only the prefix is sent and the suffix is matched locally
+    #[test]
+    fn test_check_breach() {
+        let source = MockBreachSource::new(&["leaked@example.com"]);
+
+        assert!(block_on(check_breach(&source, "Leaked@Example.com")).unwrap());
+        assert!(!block_on(check_breach(&source, "safe@example.com")).unwrap());
+
+        let queried = source.queried.borrow();
+        assert_eq!(queried[0], hash_email("leaked@example.com")[..RANGE_PREFIX_LENGTH]);
+        assert!(queried.iter().all(|prefix| prefix.len() == RANGE_PREFIX_LENGTH));
+    }
+
+    /// Tests prefix templating of the range endpoint
+    #[test]
+    fn test_range_url() {
+        assert_eq!(range_url("https://range.example/range/{prefix}?v=1", "abcde"), "https://range.example/range/abcde?v=1");
+        assert_eq!(range_url("https://range.example/range/", "abcde"), "https://range.example/range/abcde");
+    }
+}
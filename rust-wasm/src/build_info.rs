@@ -0,0 +1,132 @@
+//! Identification of the build and ruleset behind a verification decision
+//!
+//! The built-in lists carry no release numbers of their own, so each is
+//! identified by a digest of its entries: two builds with the same digest
+//! score domains against the same data.
+
+use serde::{Serialize, Deserialize};
+
+#[cfg(feature = "academic-domains")]
+use crate::academic;
+use crate::{category, domain_lists, hash, tld_risk, DISPOSABLE_DOMAINS, TRUSTED_DOMAINS};
+
+/// Cargo features that change behavior, with whether each is enabled
+const FEATURES: [(&str, bool); 19] = [
+    ("std", cfg!(feature = "std")),
+    ("wasm", cfg!(feature = "wasm")),
+    ("net", cfg!(feature = "net")),
+    ("parallel", cfg!(feature = "parallel")),
+    ("lettre", cfg!(feature = "lettre")),
+    ("email_address", cfg!(feature = "email_address")),
+    ("python", cfg!(feature = "python")),
+    ("node", cfg!(feature = "node")),
+    ("server", cfg!(feature = "server")),
+    ("schema", cfg!(feature = "schema")),
+    ("metrics", cfg!(feature = "metrics")),
+    ("tracing", cfg!(feature = "tracing")),
+    ("tls", cfg!(feature = "tls")),
+    ("storage", cfg!(feature = "storage")),
+    ("arrow", cfg!(feature = "arrow")),
+    ("csv", cfg!(feature = "csv")),
+    ("toml", cfg!(feature = "toml")),
+    ("regex-compat", cfg!(feature = "regex-compat")),
+    ("academic-domains", cfg!(feature = "academic-domains"))
+];
+
+/// Version of one domain list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ListVersion {
+    /// List name ("trusted", "disposable", "consumer", "suspicious_tlds",
+    /// "academic" with the `academic-domains` feature, or "loaded" for lists
+    /// added with `load_domain_lists`)
+    pub name: String,
+    /// Number of entries
+    pub entries: usize,
+    /// First 16 hex digits of the SHA-256 digest of the sorted entries
+    pub digest: String
+}
+
+impl ListVersion {
+    fn new(name: &str, mut entries: Vec<String>) -> Self {
+        entries.sort_unstable();
+        ListVersion {
+            name: name.to_string(),
+            entries: entries.len(),
+            digest: hash::sha256_hex(entries.join("\n").as_bytes())[..16].to_string()
+        }
+    }
+}
+
+/// Crate version, enabled features and domain-list versions of this build
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BuildInfo {
+    /// Crate version
+    pub version: String,
+    /// Enabled Cargo features
+    pub features: Vec<String>,
+    /// Built-in lists, followed by the loaded lists when there are any
+    pub domain_lists: Vec<ListVersion>
+}
+
+/// Crate version, e.g. "0.1.0"
+pub fn version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+/// Describes this build and the domain lists in effect
+///
+/// Logged alongside a verification result, it identifies the ruleset that
+/// produced the decision. The "loaded" entry changes with every
+/// `load_domain_lists` call that changes the lists.
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::build_info;
+/// let info = build_info();
+/// println!("{} {:?}", info.version, info.features);
+/// ```
+pub fn build_info() -> BuildInfo {
+    let to_strings = |domains: &[&str]| domains.iter().map(|domain| domain.to_string()).collect();
+    let mut lists = vec![
+        ListVersion::new("trusted", to_strings(&TRUSTED_DOMAINS)),
+        ListVersion::new("disposable", to_strings(&DISPOSABLE_DOMAINS)),
+        ListVersion::new("consumer", to_strings(&category::CONSUMER_DOMAINS)),
+        ListVersion::new(
+            "suspicious_tlds",
+            tld_risk::SUSPICIOUS_TLD_SCORES.iter().map(|(tld, score)| format!("{}={}", tld, score)).collect()
+        )
+    ];
+    #[cfg(feature = "academic-domains")]
+    lists.push(ListVersion::new("academic", academic::institution_domains().iter().map(|domain| domain.to_string()).collect()));
+    if let Some(loaded) = domain_lists::loaded_entries() {
+        lists.push(ListVersion::new("loaded", loaded));
+    }
+
+    BuildInfo {
+        version: version().to_string(),
+        features: FEATURES.iter().filter(|(_, enabled)| *enabled).map(|(name, _)| name.to_string()).collect(),
+        domain_lists: lists
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that the build info reports the version, features and built-in lists
+    #[test]
+    fn test_build_info() {
+        let info = build_info();
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert!(info.features.contains(&"std".to_string()));
+
+        let trusted = &info.domain_lists[0];
+        assert_eq!(trusted.name, "trusted");
+        assert_eq!(trusted.entries, TRUSTED_DOMAINS.len());
+        assert_eq!(trusted.digest.len(), 16);
+        assert_eq!(trusted, &build_info().domain_lists[0]);
+        assert_ne!(trusted.digest, info.domain_lists[1].digest);
+    }
+}
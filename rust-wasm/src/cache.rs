@@ -0,0 +1,176 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::time::unix_time_ms;
+
+/// Storage for verification results that are expensive to recompute
+/// 
+/// Keys are normalized addresses or domains. Methods take `&self` so
+/// implementations backed by external stores (Redis, sled) can be shared;
+/// in-process implementations use interior mutability.
+pub trait VerificationCache<V> {
+    /// Returns the cached value for `key` if present and not expired
+    fn get(&self, key: &str) -> Option<V>;
+
+    /// Stores `value` under `key`, expiring after `ttl`
+    fn insert(&self, key: &str, value: V, ttl: Duration);
+}
+
+impl<V, T: VerificationCache<V> + ?Sized> VerificationCache<V> for Arc<T> {
+    fn get(&self, key: &str) -> Option<V> {
+        (**self).get(key)
+    }
+
+    fn insert(&self, key: &str, value: V, ttl: Duration) {
+        (**self).insert(key, value, ttl)
+    }
+}
+
+/// In-memory least-recently-used cache with per-entry TTL
+/// 
+/// Holds at most `capacity` entries; inserting beyond that evicts the entry
+/// that was used least recently. Expired entries are dropped on access.
+/// 
+/// # Examples
+/// ```
+/// # use email_validator_wasm::{DomainChecks, LruCache, VerificationCache};
+/// # use std::time::Duration;
+/// # let checks = DomainChecks::default();
+/// let cache = LruCache::new(10_000);
+/// cache.insert("example.com", checks, Duration::from_secs(300));
+/// assert!(cache.get("example.com").is_some());
+/// ```
+#[derive(Debug)]
+pub struct LruCache<V> {
+    capacity: usize,
+    state: Mutex<LruState<V>>
+}
+
+#[derive(Debug)]
+struct LruState<V> {
+    entries: HashMap<String, LruEntry<V>>,
+    /// Recency order: access tick -> key, oldest first
+    recency: BTreeMap<u64, String>,
+    tick: u64
+}
+
+#[derive(Debug)]
+struct LruEntry<V> {
+    value: V,
+    expires_at_ms: u64,
+    tick: u64
+}
+
+impl<V> LruState<V> {
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.recency.remove(&entry.tick);
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+}
+
+impl<V> LruCache<V> {
+    pub fn new(capacity: usize) -> Self {
+        LruCache {
+            capacity: capacity.max(1),
+            state: Mutex::new(LruState {
+                entries: HashMap::new(),
+                recency: BTreeMap::new(),
+                tick: 0
+            })
+        }
+    }
+
+    /// Number of entries currently stored, including expired ones not yet evicted
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes every entry
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.entries.clear();
+        state.recency.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruState<V>> {
+        // A panic while holding the lock cannot leave the maps inconsistent
+        // in a way that matters for a cache, so recover from poisoning
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<V: Clone> VerificationCache<V> for LruCache<V> {
+    fn get(&self, key: &str) -> Option<V> {
+        let mut state = self.lock();
+        let expired = state.entries.get(key)?.expires_at_ms <= unix_time_ms();
+        if expired {
+            state.remove(key);
+            return None;
+        }
+
+        let tick = state.next_tick();
+        let entry = state.entries.get_mut(key)?;
+        let old_tick = std::mem::replace(&mut entry.tick, tick);
+        let value = entry.value.clone();
+        state.recency.remove(&old_tick);
+        state.recency.insert(tick, key.to_string());
+        Some(value)
+    }
+
+    fn insert(&self, key: &str, value: V, ttl: Duration) {
+        let mut state = self.lock();
+        state.remove(key);
+
+        while state.entries.len() >= self.capacity {
+            match state.recency.pop_first() {
+                Some((_, oldest)) => { state.entries.remove(&oldest); }
+                None => break
+            }
+        }
+
+        let tick = state.next_tick();
+        let expires_at_ms = unix_time_ms().saturating_add(ttl.as_millis() as u64);
+        state.entries.insert(key.to_string(), LruEntry { value, expires_at_ms, tick });
+        state.recency.insert(tick, key.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests LRU eviction order and TTL expiry
+    #[test]
+    fn test_lru_cache() {
+        let cache = LruCache::new(2);
+        let ttl = Duration::from_secs(60);
+
+        cache.insert("a.com", 1, ttl);
+        cache.insert("b.com", 2, ttl);
+        assert_eq!(cache.get("a.com"), Some(1)); // a.com is now most recent
+        cache.insert("c.com", 3, ttl);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("b.com"), None);
+        assert_eq!(cache.get("a.com"), Some(1));
+        assert_eq!(cache.get("c.com"), Some(3));
+
+        cache.insert("a.com", 10, Duration::ZERO);
+        assert_eq!(cache.get("a.com"), None);
+        assert_eq!(cache.len(), 1);
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
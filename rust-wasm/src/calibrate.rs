@@ -0,0 +1,343 @@
+//! Calibration of the domain score against labeled datasets
+//!
+//! `calibrate` scores a set of addresses known to be good or bad, measures
+//! how well accepting those at or above a threshold separates them, and
+//! searches for signal weights and a threshold that separate them better.
+//! The search is a coordinate descent over a fixed grid of weights, so the
+//! suggestions stay close to the current scoring and are easy to review.
+
+use serde::{Serialize, Deserialize};
+
+use crate::{parse_and_validate_email_with_options, ScoreBreakdown, ValidationOptions};
+
+/// Weights tried for each signal during the search
+const WEIGHT_GRID: [f64; 9] = [0.0, 0.25, 0.5, 0.75, 1.0, 1.25, 1.5, 1.75, 2.0];
+
+/// Step between the thresholds tried during the search
+const THRESHOLD_STEP: f64 = 5.0;
+
+/// Passes of the coordinate descent over all signals
+const SEARCH_ROUNDS: usize = 3;
+
+/// Whether a labeled address is one to accept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DatasetLabel {
+    Good,
+    Bad
+}
+
+/// One address of a labeled dataset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabeledEmail {
+    pub email: String,
+    pub label: DatasetLabel
+}
+
+/// Multiplier applied to each `ScoreBreakdown` contribution
+///
+/// The default weights (all 1) reproduce the unweighted `domain_score`;
+/// set `ValidationOptions::score_weights` to score with others.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct ScoreWeights {
+    pub base: f64,
+    pub country_risk: f64,
+    pub confusable_spoof: f64,
+    pub typosquat: f64,
+    pub access_policy: f64,
+    pub mail_auth: f64,
+    pub bimi: f64,
+    pub blocklist: f64,
+    pub domain_age: f64,
+    pub ct_log: f64,
+    pub custom_scorer: f64
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        ScoreWeights::from_array([1.0; 11])
+    }
+}
+
+impl ScoreWeights {
+    /// Score of `breakdown` under these weights, clamped to 0-100
+    pub fn score(&self, breakdown: &ScoreBreakdown) -> f64 {
+        self.to_array()
+            .iter()
+            .zip(contributions(breakdown))
+            .map(|(weight, contribution)| weight * contribution)
+            .sum::<f64>()
+            .clamp(0.0, 100.0)
+    }
+
+    fn to_array(self) -> [f64; 11] {
+        [
+            self.base,
+            self.country_risk,
+            self.confusable_spoof,
+            self.typosquat,
+            self.access_policy,
+            self.mail_auth,
+            self.bimi,
+            self.blocklist,
+            self.domain_age,
+            self.ct_log,
+            self.custom_scorer
+        ]
+    }
+
+    fn from_array(weights: [f64; 11]) -> Self {
+        let [base, country_risk, confusable_spoof, typosquat, access_policy, mail_auth, bimi, blocklist, domain_age, ct_log, custom_scorer] = weights;
+        ScoreWeights { base, country_risk, confusable_spoof, typosquat, access_policy, mail_auth, bimi, blocklist, domain_age, ct_log, custom_scorer }
+    }
+}
+
+/// `ScoreBreakdown` contributions in `ScoreWeights` field order
+fn contributions(breakdown: &ScoreBreakdown) -> [f64; 11] {
+    [
+        breakdown.base,
+        breakdown.country_risk,
+        breakdown.confusable_spoof,
+        breakdown.typosquat,
+        breakdown.access_policy,
+        breakdown.mail_auth,
+        breakdown.bimi,
+        breakdown.blocklist,
+        breakdown.domain_age,
+        breakdown.ct_log,
+        breakdown.custom_scorer
+    ]
+}
+
+/// How well a threshold separates the labeled addresses
+///
+/// Good addresses are the positive class: precision is the share of accepted
+/// addresses that are good, recall the share of good addresses accepted.
+/// Invalid addresses are never accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationMetrics {
+    /// Lowest accepted score
+    pub threshold: f64,
+    /// Good addresses accepted
+    pub true_positives: usize,
+    /// Bad addresses accepted
+    pub false_positives: usize,
+    /// Good addresses rejected
+    pub false_negatives: usize,
+    /// Bad addresses rejected
+    pub true_negatives: usize,
+    pub precision: f64,
+    pub recall: f64,
+    /// Harmonic mean of precision and recall
+    pub f1: f64
+}
+
+impl CalibrationMetrics {
+    fn measure(samples: &[(Option<ScoreBreakdown>, DatasetLabel)], weights: &ScoreWeights, threshold: f64) -> Self {
+        let mut metrics = CalibrationMetrics {
+            threshold,
+            true_positives: 0,
+            false_positives: 0,
+            false_negatives: 0,
+            true_negatives: 0,
+            precision: 0.0,
+            recall: 0.0,
+            f1: 0.0
+        };
+        for (breakdown, label) in samples {
+            let accepted = breakdown.as_ref().is_some_and(|breakdown| weights.score(breakdown) >= threshold);
+            match (accepted, label) {
+                (true, DatasetLabel::Good) => metrics.true_positives += 1,
+                (true, DatasetLabel::Bad) => metrics.false_positives += 1,
+                (false, DatasetLabel::Good) => metrics.false_negatives += 1,
+                (false, DatasetLabel::Bad) => metrics.true_negatives += 1
+            }
+        }
+        let ratio = |part: usize, whole: usize| if whole == 0 { 0.0 } else { part as f64 / whole as f64 };
+        metrics.precision = ratio(metrics.true_positives, metrics.true_positives + metrics.false_positives);
+        metrics.recall = ratio(metrics.true_positives, metrics.true_positives + metrics.false_negatives);
+        if metrics.precision + metrics.recall > 0.0 {
+            metrics.f1 = 2.0 * metrics.precision * metrics.recall / (metrics.precision + metrics.recall);
+        }
+        metrics
+    }
+}
+
+/// Result of `calibrate`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationReport {
+    /// Labeled addresses evaluated
+    pub samples: usize,
+    /// Current scoring at the requested threshold
+    pub current: CalibrationMetrics,
+    /// Weights the search found; equal to the starting weights when nothing
+    /// beat them
+    pub suggested_weights: ScoreWeights,
+    /// Suggested weights at the best threshold found for them
+    pub suggested: CalibrationMetrics
+}
+
+/// Evaluates the scoring under `options` against `dataset`, accepting
+/// addresses that score at least `threshold`, and suggests weights
+///
+/// The search starts from `options.score_weights`, so a report's
+/// `suggested_weights` can be applied and calibrated again. Scoring is
+/// syntax-level; to calibrate the network stages, verify the dataset and
+/// pass the breakdowns to `calibrate_breakdowns`.
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::{calibrate, DatasetLabel, LabeledEmail, VerificationProfile};
+/// # let profile = VerificationProfile::default();
+/// # let dataset = [LabeledEmail { email: "jane@example.com".to_string(), label: DatasetLabel::Good }];
+/// let report = calibrate(&dataset, &profile.validation, 50.0);
+/// println!("F1 {:.2} -> {:.2}", report.current.f1, report.suggested.f1);
+/// ```
+pub fn calibrate(dataset: &[LabeledEmail], options: &ValidationOptions, threshold: f64) -> CalibrationReport {
+    let samples: Vec<_> = dataset
+        .iter()
+        .map(|sample| {
+            let breakdown = parse_and_validate_email_with_options(&sample.email, options).ok().and_then(|result| result.score_breakdown);
+            (breakdown, sample.label)
+        })
+        .collect();
+    search(&samples, options.score_weights.unwrap_or_default(), threshold)
+}
+
+/// `calibrate` over already scored addresses; None stands for an invalid one
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::{calibrate_breakdowns, DatasetLabel, VerificationResult};
+/// # let (verified, labels): (Vec<VerificationResult>, Vec<DatasetLabel>) = (Vec::new(), Vec::new());
+/// let samples: Vec<_> = verified.iter().zip(&labels).map(|(result, label)| (result.email.score_breakdown.clone(), *label)).collect();
+/// let report = calibrate_breakdowns(&samples, 50.0);
+/// ```
+pub fn calibrate_breakdowns(samples: &[(Option<ScoreBreakdown>, DatasetLabel)], threshold: f64) -> CalibrationReport {
+    search(samples, ScoreWeights::default(), threshold)
+}
+
+/// Coordinate descent over `WEIGHT_GRID` starting from `start`
+fn search(samples: &[(Option<ScoreBreakdown>, DatasetLabel)], start: ScoreWeights, threshold: f64) -> CalibrationReport {
+    let current = CalibrationMetrics::measure(samples, &start, threshold);
+    let mut weights = start.to_array();
+    let mut best = best_threshold(samples, &start, current);
+    for _ in 0..SEARCH_ROUNDS {
+        let mut improved = false;
+        for signal in 0..weights.len() {
+            for weight in WEIGHT_GRID {
+                let mut candidate = weights;
+                candidate[signal] = weight;
+                let metrics = best_threshold(samples, &ScoreWeights::from_array(candidate), best);
+                // Only a strict improvement moves a weight, so ties keep the current scoring
+                if metrics.f1 > best.f1 {
+                    weights = candidate;
+                    best = metrics;
+                    improved = true;
+                }
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+    CalibrationReport {
+        samples: samples.len(),
+        current,
+        suggested_weights: ScoreWeights::from_array(weights),
+        suggested: best
+    }
+}
+
+/// Best metrics for `weights` over the threshold grid, or `baseline` when no
+/// threshold beats it
+fn best_threshold(samples: &[(Option<ScoreBreakdown>, DatasetLabel)], weights: &ScoreWeights, baseline: CalibrationMetrics) -> CalibrationMetrics {
+    let mut best = CalibrationMetrics::measure(samples, weights, baseline.threshold);
+    let mut threshold = 0.0;
+    while threshold <= 100.0 {
+        let metrics = CalibrationMetrics::measure(samples, weights, threshold);
+        if metrics.f1 > best.f1 {
+            best = metrics;
+        }
+        threshold += THRESHOLD_STEP;
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labeled(email: &str, label: DatasetLabel) -> LabeledEmail {
+        LabeledEmail { email: email.to_string(), label }
+    }
+
+    /// Tests metrics at the current weights and that the search improves on them
+    #[test]
+    fn test_calibrate() {
+        let dataset = [
+            labeled("jane@gmail.com", DatasetLabel::Good),
+            labeled("john@example.com", DatasetLabel::Good),
+            labeled("temp@mailinator.com", DatasetLabel::Bad),
+            labeled("not-an-email", DatasetLabel::Bad),
+            labeled("jane@gmial.com", DatasetLabel::Bad)
+        ];
+        let report = calibrate(&dataset, &ValidationOptions::default(), 90.0);
+        assert_eq!(report.samples, 5);
+        assert_eq!(report.current.threshold, 90.0);
+        assert_eq!(report.current.true_negatives + report.current.false_positives, 3);
+        assert!(report.suggested.f1 >= report.current.f1);
+        assert_eq!(report.suggested.true_positives, 2);
+
+        let invalid_only = calibrate(&[labeled("not-an-email", DatasetLabel::Good)], &ValidationOptions::default(), 0.0);
+        assert_eq!(invalid_only.current.false_negatives, 1);
+        assert_eq!(invalid_only.suggested_weights, ScoreWeights::default());
+    }
+
+    /// Tests that applying the suggested weights reproduces the suggested metrics
+    #[test]
+    fn test_apply_suggested_weights() {
+        let dataset = [
+            labeled("jane@gmail.com", DatasetLabel::Good),
+            labeled("john@example.com", DatasetLabel::Good),
+            labeled("temp@mailinator.com", DatasetLabel::Bad),
+            labeled("promo@example.ru", DatasetLabel::Bad)
+        ];
+        // A country boost that lifts a bad domain above the good ones
+        let options = ValidationOptions {
+            tld_scores: [("ru".to_string(), 10.0)].into(),
+            country_risk: [("RU".to_string(), 85.0)].into(),
+            ..Default::default()
+        };
+        let report = calibrate(&dataset, &options, 90.0);
+        assert_ne!(report.suggested_weights, ScoreWeights::default());
+        assert!(report.suggested.f1 > report.current.f1);
+
+        let weighted = ValidationOptions { score_weights: Some(report.suggested_weights), ..options };
+        let accepted: Vec<bool> = dataset
+            .iter()
+            .map(|sample| {
+                let result = parse_and_validate_email_with_options(&sample.email, &weighted).unwrap();
+                result.domain_score.is_some_and(|score| score >= report.suggested.threshold)
+            })
+            .collect();
+        let count = |label: DatasetLabel, accept: bool| dataset.iter().zip(&accepted).filter(|(sample, accepted)| sample.label == label && **accepted == accept).count();
+        assert_eq!(count(DatasetLabel::Good, true), report.suggested.true_positives);
+        assert_eq!(count(DatasetLabel::Bad, true), report.suggested.false_positives);
+        assert_eq!(count(DatasetLabel::Good, false), report.suggested.false_negatives);
+        assert_eq!(count(DatasetLabel::Bad, false), report.suggested.true_negatives);
+
+        assert_eq!(calibrate(&dataset, &weighted, report.suggested.threshold).current, report.suggested);
+    }
+
+    /// Tests that weights reweight each contribution
+    #[test]
+    fn test_score_weights() {
+        let breakdown = ScoreBreakdown { base: 50.0, blocklist: -60.0, ct_log: 5.0, ..Default::default() };
+        assert_eq!(ScoreWeights::default().score(&breakdown), 0.0);
+        let weights = ScoreWeights { blocklist: 0.5, ..Default::default() };
+        assert_eq!(weights.score(&breakdown), 25.0);
+    }
+}
@@ -0,0 +1,305 @@
+//! Cancellation and per-stage timeouts for the network stages
+//!
+//! A `CancellationToken` is shared by every verification that should stop
+//! together (typically a batch); cancelling it makes pending and later
+//! verifications fail with a `Cancelled` error. Stage timeouts are enforced
+//! by racing the stage against a timer. Native builds run their blocking
+//! socket work (DNS, SMTP and HTTPS) on worker threads, so the race stops
+//! waiting at the deadline; the abandoned worker finishes on its own socket
+//! timeouts.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::JsCast;
+
+/// Shared flag that stops verifications once set
+///
+/// Clones share state, so one clone can be handed to a `Verifier` while
+/// another is kept to cancel it.
+///
+/// # Examples
+/// ```no_run
+/// # use email_validator_wasm::{CancellationToken, Verifier, SandboxResolver};
+/// # async fn example(resolver: SandboxResolver) {
+/// let token = CancellationToken::new();
+/// let verifier = Verifier::new(resolver).with_cancellation(token.clone());
+/// // from another task or thread
+/// token.cancel();
+/// assert_eq!(verifier.verify("user@example.com").await.unwrap_err().error_type, "Cancelled");
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    state: Mutex<State>,
+    #[cfg(not(target_arch = "wasm32"))]
+    condvar: std::sync::Condvar
+}
+
+#[derive(Debug, Default)]
+struct State {
+    cancelled: bool,
+    /// Tasks to wake on cancellation, keyed by registration id
+    wakers: Vec<(u64, Waker)>
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        CancellationToken::default()
+    }
+
+    /// Cancels every verification sharing this token
+    pub fn cancel(&self) {
+        let wakers = {
+            let mut state = self.state();
+            state.cancelled = true;
+            std::mem::take(&mut state.wakers)
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        self.inner.condvar.notify_all();
+        for (_, waker) in wakers {
+            waker.wake();
+        }
+    }
+
+    /// Whether `cancel` has been called
+    pub fn is_cancelled(&self) -> bool {
+        self.state().cancelled
+    }
+
+    /// Returns a guard that cancels the token when dropped, e.g. when the
+    /// request that owns it is abandoned
+    pub fn drop_guard(self) -> DropGuard {
+        DropGuard { token: self }
+    }
+
+    /// Creates a token cancelled when the JS `AbortSignal` aborts
+    ///
+    /// # Arguments
+    /// * `signal` - An `AbortSignal` (already aborted signals yield a cancelled token)
+    #[cfg(target_arch = "wasm32")]
+    pub fn from_abort_signal(signal: &JsValue) -> Result<Self, JsValue> {
+        let token = CancellationToken::new();
+        if js_sys::Reflect::get(signal, &"aborted".into())?.is_truthy() {
+            token.cancel();
+            return Ok(token);
+        }
+
+        let handle = token.clone();
+        let listener = Closure::once_into_js(move || handle.cancel());
+        let add_event_listener = js_sys::Reflect::get(signal, &"addEventListener".into())?.dyn_into::<js_sys::Function>()?;
+        add_event_listener.call2(signal, &"abort".into(), &listener)?;
+        Ok(token)
+    }
+
+    /// Creates a token that cancels itself after `duration`
+    pub(crate) fn after(duration: Duration) -> Self {
+        let token = CancellationToken::new();
+        timer::schedule(token.clone(), duration);
+        token
+    }
+
+    /// Blocks until the token is cancelled or `duration` elapses, returning
+    /// whether it was cancelled
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn wait_timeout(&self, duration: Duration) -> bool {
+        let state = self.state();
+        let (state, _) = self.inner.condvar
+            .wait_timeout_while(state, duration, |state| !state.cancelled)
+            .unwrap_or_else(|e| e.into_inner());
+        state.cancelled
+    }
+
+    /// Registers `waker` under `id` unless already cancelled, returning
+    /// whether the token is cancelled
+    fn register(&self, id: u64, waker: &Waker) -> bool {
+        let mut state = self.state();
+        if state.cancelled {
+            return true;
+        }
+        match state.wakers.iter_mut().find(|(registered, _)| *registered == id) {
+            Some((_, registered)) => registered.clone_from(waker),
+            None => state.wakers.push((id, waker.clone()))
+        }
+        false
+    }
+
+    fn unregister(&self, id: u64) {
+        self.state().wakers.retain(|(registered, _)| *registered != id);
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.inner.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Cancels its token when dropped
+#[derive(Debug)]
+pub struct DropGuard {
+    token: CancellationToken
+}
+
+impl Drop for DropGuard {
+    fn drop(&mut self) {
+        self.token.cancel();
+    }
+}
+
+/// Why a stage stopped before completing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Interrupted {
+    TimedOut,
+    Cancelled
+}
+
+/// Runs `future` until it completes, `timeout` elapses or `cancel` is cancelled
+pub(crate) async fn interruptible<F: Future>(
+    future: F,
+    timeout: Option<Duration>,
+    cancel: Option<&CancellationToken>
+) -> Result<F::Output, Interrupted> {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+    Interruptible {
+        future: Box::pin(future),
+        deadline: timeout.map(CancellationToken::after),
+        cancel,
+        id: NEXT_ID.fetch_add(1, Ordering::Relaxed)
+    }
+    .await
+}
+
+struct Interruptible<'a, F> {
+    future: Pin<Box<F>>,
+    deadline: Option<CancellationToken>,
+    cancel: Option<&'a CancellationToken>,
+    id: u64
+}
+
+impl<F: Future> Future for Interruptible<'_, F> {
+    type Output = Result<F::Output, Interrupted>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let id = self.id;
+        if self.cancel.is_some_and(|cancel| cancel.register(id, cx.waker())) {
+            return Poll::Ready(Err(Interrupted::Cancelled));
+        }
+        if self.deadline.as_ref().is_some_and(|deadline| deadline.register(id, cx.waker())) {
+            return Poll::Ready(Err(Interrupted::TimedOut));
+        }
+        self.future.as_mut().poll(cx).map(Ok)
+    }
+}
+
+impl<F> Drop for Interruptible<'_, F> {
+    fn drop(&mut self) {
+        if let Some(cancel) = self.cancel {
+            cancel.unregister(self.id);
+        }
+    }
+}
+
+/// Single background thread firing every native deadline, so timeouts do not
+/// cost a thread each
+#[cfg(not(target_arch = "wasm32"))]
+mod timer {
+    use std::sync::{Condvar, Mutex, OnceLock};
+    use std::time::{Duration, Instant};
+
+    use super::CancellationToken;
+
+    type Queue = (Mutex<Vec<(Instant, CancellationToken)>>, Condvar);
+
+    pub(super) fn schedule(token: CancellationToken, duration: Duration) {
+        let (pending, condvar) = queue();
+        let Some(deadline) = Instant::now().checked_add(duration) else {
+            return;
+        };
+        pending.lock().unwrap_or_else(|e| e.into_inner()).push((deadline, token));
+        condvar.notify_one();
+    }
+
+    fn queue() -> &'static Queue {
+        static QUEUE: OnceLock<Queue> = OnceLock::new();
+        QUEUE.get_or_init(|| {
+            std::thread::Builder::new()
+                .name("email-verifier-timer".to_string())
+                .spawn(run)
+                .expect("failed to spawn timer thread");
+            (Mutex::new(Vec::new()), Condvar::new())
+        })
+    }
+
+    fn run() {
+        let (pending, condvar) = queue();
+        let mut pending = pending.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            let now = Instant::now();
+            // Deadlines whose stage already finished hold the only reference
+            pending.retain(|(deadline, token)| {
+                if *deadline <= now {
+                    token.cancel();
+                    return false;
+                }
+                std::sync::Arc::strong_count(&token.inner) > 1
+            });
+
+            pending = match pending.iter().map(|(deadline, _)| *deadline).min() {
+                Some(next) => condvar.wait_timeout(pending, next - now).unwrap_or_else(|e| e.into_inner()).0,
+                None => condvar.wait(pending).unwrap_or_else(|e| e.into_inner())
+            };
+        }
+    }
+}
+
+/// Deadlines on wasm are JS timers
+#[cfg(target_arch = "wasm32")]
+mod timer {
+    use std::time::Duration;
+
+    use wasm_bindgen::prelude::*;
+    use wasm_bindgen::JsCast;
+
+    use super::CancellationToken;
+
+    pub(super) fn schedule(token: CancellationToken, duration: Duration) {
+        let listener = Closure::once_into_js(move || token.cancel());
+        crate::wasm::set_timeout(listener.unchecked_ref(), duration.as_millis().min(i32::MAX as u128) as i32);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::block_on;
+
+    /// Tests that a cancelled token interrupts a pending stage and that a
+    /// deadline times it out
+    #[test]
+    fn test_interruptible() {
+        assert_eq!(block_on(interruptible(async { 1 }, Some(Duration::from_secs(60)), None)), Ok(1));
+
+        let pending = std::future::pending::<()>();
+        assert_eq!(block_on(interruptible(pending, Some(Duration::from_millis(10)), None)), Err(Interrupted::TimedOut));
+
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        std::thread::spawn(move || canceller.cancel());
+        let pending = std::future::pending::<()>();
+        assert_eq!(block_on(interruptible(pending, None, Some(&token))), Err(Interrupted::Cancelled));
+        assert!(token.wait_timeout(Duration::ZERO));
+        assert!(!CancellationToken::new().wait_timeout(Duration::from_millis(1)));
+    }
+}
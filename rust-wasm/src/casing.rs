@@ -0,0 +1,762 @@
+//! Field naming shared by the JavaScript bindings (wasm and the Node.js addon)
+//!
+//! Results use camelCase field names (`isValid`, `localPart`) unless
+//! `set_snake_case_output(true)` restores the Rust names, for both backends
+//! at once. Options objects are read in either convention.
+//!
+//! Renaming happens while serializing: `Cased` rewrites struct field names as
+//! they are written, so map keys (domains, URI parameters, error messages)
+//! and caller-supplied JSON keep theirs without a second pass over the
+//! output. `AcceptCamelCase` does the reverse for options objects.
+
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Mutex;
+
+use serde::de::{self, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor};
+use serde::ser::{self, Serialize, Serializer};
+
+thread_local! {
+    /// Whether results keep the snake_case field names of the Rust types
+    ///
+    /// Per thread, so each JS context (the main thread, every worker)
+    /// keeps its own setting.
+    static SNAKE_CASE_OUTPUT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Switches between camelCase (the default) and snake_case field names
+pub(crate) fn set_snake_case_output(enabled: bool) {
+    SNAKE_CASE_OUTPUT.with(|snake_case| snake_case.set(enabled));
+}
+
+/// Whether results keep their snake_case field names
+pub(crate) fn snake_case_output() -> bool {
+    SNAKE_CASE_OUTPUT.with(Cell::get)
+}
+
+/// `snake_case` to `camelCase`
+pub(crate) fn camel_case(key: &str) -> String {
+    let mut camel = String::with_capacity(key.len());
+    let mut upper = false;
+    for c in key.chars() {
+        if c == '_' && !camel.is_empty() {
+            upper = true;
+        } else if upper {
+            camel.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            camel.push(c);
+        }
+    }
+    camel
+}
+
+/// camelCase form of a field name
+///
+/// Serializers and deserializers take field names as `&'static str`; the
+/// set of field names is fixed, so each converted name is leaked once.
+fn camel_name(name: &'static str) -> &'static str {
+    static NAMES: Mutex<BTreeMap<&'static str, &'static str>> = Mutex::new(BTreeMap::new());
+    if !name.contains('_') {
+        return name;
+    }
+    let mut names = NAMES.lock().unwrap_or_else(|e| e.into_inner());
+    names.entry(name).or_insert_with(|| Box::leak(camel_case(name).into_boxed_str()))
+}
+
+/// `fields` followed by the camelCase names that differ from them
+fn with_camel_names(fields: &'static [&'static str]) -> &'static [&'static str] {
+    static LISTS: Mutex<BTreeMap<(usize, usize), &'static [&'static str]>> = Mutex::new(BTreeMap::new());
+    let mut lists = LISTS.lock().unwrap_or_else(|e| e.into_inner());
+    lists.entry((fields.as_ptr() as usize, fields.len())).or_insert_with(|| {
+        let camel = fields.iter().map(|field| camel_name(field)).filter(|name| !fields.contains(name));
+        Box::leak(fields.iter().copied().chain(camel).collect())
+    })
+}
+
+/// The field of `fields` a key names, in either convention
+fn rust_name<'a>(key: &'a str, fields: &'static [&'static str]) -> &'a str {
+    fields.iter().copied().find(|field| camel_name(field) == key).unwrap_or(key)
+}
+
+/// Serializes a value with the configured field naming
+pub(crate) struct Cased<'a, T: ?Sized>(pub(crate) &'a T);
+
+impl<T: Serialize + ?Sized> Serialize for Cased<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if snake_case_output() {
+            self.0.serialize(serializer)
+        } else {
+            self.0.serialize(CamelCase(serializer))
+        }
+    }
+}
+
+/// Serializes a value with camelCase field names
+struct Camel<'a, T: ?Sized>(&'a T);
+
+impl<T: Serialize + ?Sized> Serialize for Camel<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(CamelCase(serializer))
+    }
+}
+
+/// Forwards serializer methods that write a single value to the wrapped
+/// serializer
+macro_rules! forward_scalars {
+    () => {
+        forward_scalars!(
+            serialize_bool(v: bool), serialize_i8(v: i8), serialize_i16(v: i16), serialize_i32(v: i32),
+            serialize_i64(v: i64), serialize_i128(v: i128), serialize_u8(v: u8), serialize_u16(v: u16),
+            serialize_u32(v: u32), serialize_u64(v: u64), serialize_u128(v: u128), serialize_f32(v: f32),
+            serialize_f64(v: f64), serialize_char(v: char), serialize_bytes(v: &[u8]), serialize_none(),
+            serialize_unit(), serialize_unit_struct(name: &'static str),
+            serialize_unit_variant(name: &'static str, index: u32, variant: &'static str)
+        );
+    };
+    ($($method:ident($($arg:ident: $ty:ty),*)),*) => {
+        $(fn $method(self, $($arg: $ty),*) -> Result<Self::Ok, Self::Error> {
+            self.0.$method($($arg),*)
+        })*
+    };
+}
+
+/// Serializer writing struct fields under their camelCase names
+///
+/// Maps keep their keys, except those of flattened structs, which serde
+/// writes as maps of unknown length.
+struct CamelCase<S>(S);
+
+impl<S: Serializer> Serializer for CamelCase<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = CamelCase<S::SerializeSeq>;
+    type SerializeTuple = CamelCase<S::SerializeTuple>;
+    type SerializeTupleStruct = CamelCase<S::SerializeTupleStruct>;
+    type SerializeTupleVariant = CamelCase<S::SerializeTupleVariant>;
+    type SerializeMap = CamelCaseMap<S::SerializeMap>;
+    type SerializeStruct = CamelCase<S::SerializeStruct>;
+    type SerializeStructVariant = CamelCase<S::SerializeStructVariant>;
+
+    forward_scalars!();
+
+    fn serialize_str(self, v: &str) -> Result<S::Ok, S::Error> {
+        self.0.serialize_str(v)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<S::Ok, S::Error> {
+        self.0.serialize_some(&Camel(value))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, name: &'static str, value: &T) -> Result<S::Ok, S::Error> {
+        self.0.serialize_newtype_struct(name, &Camel(value))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        value: &T
+    ) -> Result<S::Ok, S::Error> {
+        self.0.serialize_newtype_variant(name, index, variant, &Camel(value))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, S::Error> {
+        self.0.serialize_seq(len).map(CamelCase)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, S::Error> {
+        self.0.serialize_tuple(len).map(CamelCase)
+    }
+
+    fn serialize_tuple_struct(self, name: &'static str, len: usize) -> Result<Self::SerializeTupleStruct, S::Error> {
+        self.0.serialize_tuple_struct(name, len).map(CamelCase)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        self.0.serialize_tuple_variant(name, index, variant, len).map(CamelCase)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+        self.0.serialize_map(len).map(|map| CamelCaseMap { map, flattened: len.is_none() })
+    }
+
+    fn serialize_struct(self, name: &'static str, len: usize) -> Result<Self::SerializeStruct, S::Error> {
+        self.0.serialize_struct(name, len).map(CamelCase)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        self.0.serialize_struct_variant(name, index, variant, len).map(CamelCase)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.0.is_human_readable()
+    }
+}
+
+/// Implements a sequence-like serializer trait for `CamelCase`, renaming
+/// within each element
+macro_rules! camel_case_elements {
+    ($($trait:ident::$method:ident),*) => {
+        $(impl<S: ser::$trait> ser::$trait for CamelCase<S> {
+            type Ok = S::Ok;
+            type Error = S::Error;
+
+            fn $method<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), S::Error> {
+                self.0.$method(&Camel(value))
+            }
+
+            fn end(self) -> Result<S::Ok, S::Error> {
+                self.0.end()
+            }
+        })*
+    };
+}
+
+camel_case_elements!(
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field
+);
+
+/// Implements a struct serializer trait for `CamelCase`, renaming each field
+macro_rules! camel_case_fields {
+    ($($trait:ident),*) => {
+        $(impl<S: ser::$trait> ser::$trait for CamelCase<S> {
+            type Ok = S::Ok;
+            type Error = S::Error;
+
+            fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), S::Error> {
+                self.0.serialize_field(camel_name(key), &Camel(value))
+            }
+
+            fn skip_field(&mut self, key: &'static str) -> Result<(), S::Error> {
+                self.0.skip_field(camel_name(key))
+            }
+
+            fn end(self) -> Result<S::Ok, S::Error> {
+                self.0.end()
+            }
+        })*
+    };
+}
+
+camel_case_fields!(SerializeStruct, SerializeStructVariant);
+
+/// Map serializer for `CamelCase`; the keys of a flattened struct are field
+/// names and are renamed, other keys are data and are kept
+struct CamelCaseMap<M> {
+    map: M,
+    flattened: bool
+}
+
+impl<M: ser::SerializeMap> ser::SerializeMap for CamelCaseMap<M> {
+    type Ok = M::Ok;
+    type Error = M::Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), M::Error> {
+        if self.flattened {
+            self.map.serialize_key(&FieldName(key))
+        } else {
+            self.map.serialize_key(key)
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), M::Error> {
+        self.map.serialize_value(&Camel(value))
+    }
+
+    fn serialize_entry<K: Serialize + ?Sized, V: Serialize + ?Sized>(&mut self, key: &K, value: &V) -> Result<(), M::Error> {
+        if self.flattened {
+            self.map.serialize_entry(&FieldName(key), &Camel(value))
+        } else {
+            self.map.serialize_entry(key, &Camel(value))
+        }
+    }
+
+    fn end(self) -> Result<M::Ok, M::Error> {
+        self.map.end()
+    }
+}
+
+/// Serializes a map key that is a field name in camelCase
+struct FieldName<'a, T: ?Sized>(&'a T);
+
+impl<T: Serialize + ?Sized> Serialize for FieldName<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(FieldNameSerializer(serializer))
+    }
+}
+
+/// Serializer camel-casing the string it is given
+struct FieldNameSerializer<S>(S);
+
+impl<S: Serializer> Serializer for FieldNameSerializer<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = S::SerializeSeq;
+    type SerializeTuple = S::SerializeTuple;
+    type SerializeTupleStruct = S::SerializeTupleStruct;
+    type SerializeTupleVariant = S::SerializeTupleVariant;
+    type SerializeMap = S::SerializeMap;
+    type SerializeStruct = S::SerializeStruct;
+    type SerializeStructVariant = S::SerializeStructVariant;
+
+    forward_scalars!();
+
+    fn serialize_str(self, v: &str) -> Result<S::Ok, S::Error> {
+        self.0.serialize_str(&camel_case(v))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<S::Ok, S::Error> {
+        self.0.serialize_some(value)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, name: &'static str, value: &T) -> Result<S::Ok, S::Error> {
+        self.0.serialize_newtype_struct(name, value)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        value: &T
+    ) -> Result<S::Ok, S::Error> {
+        self.0.serialize_newtype_variant(name, index, variant, value)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<S::SerializeSeq, S::Error> {
+        self.0.serialize_seq(len)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<S::SerializeTuple, S::Error> {
+        self.0.serialize_tuple(len)
+    }
+
+    fn serialize_tuple_struct(self, name: &'static str, len: usize) -> Result<S::SerializeTupleStruct, S::Error> {
+        self.0.serialize_tuple_struct(name, len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize
+    ) -> Result<S::SerializeTupleVariant, S::Error> {
+        self.0.serialize_tuple_variant(name, index, variant, len)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<S::SerializeMap, S::Error> {
+        self.0.serialize_map(len)
+    }
+
+    fn serialize_struct(self, name: &'static str, len: usize) -> Result<S::SerializeStruct, S::Error> {
+        self.0.serialize_struct(name, len)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize
+    ) -> Result<S::SerializeStructVariant, S::Error> {
+        self.0.serialize_struct_variant(name, index, variant, len)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.0.is_human_readable()
+    }
+}
+
+/// Deserializer accepting camelCase field names alongside the Rust ones
+///
+/// Struct fields are requested under both names, which suits deserializers
+/// that look fields up by name (serde-wasm-bindgen) as well as those that
+/// hand over every key (serde_json).
+pub(crate) struct AcceptCamelCase<D>(pub(crate) D);
+
+/// Forwards `deserialize_*` methods that take only a visitor
+macro_rules! forward_deserialize {
+    ($($method:ident),*) => {
+        $(fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+            self.0.$method(AcceptVisitor::new(visitor))
+        })*
+    };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for AcceptCamelCase<D> {
+    type Error = D::Error;
+
+    forward_deserialize!(
+        deserialize_any, deserialize_bool, deserialize_i8, deserialize_i16, deserialize_i32, deserialize_i64,
+        deserialize_i128, deserialize_u8, deserialize_u16, deserialize_u32, deserialize_u64, deserialize_u128,
+        deserialize_f32, deserialize_f64, deserialize_char, deserialize_str, deserialize_string, deserialize_bytes,
+        deserialize_byte_buf, deserialize_option, deserialize_unit, deserialize_seq, deserialize_map,
+        deserialize_identifier, deserialize_ignored_any
+    );
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, name: &'static str, visitor: V) -> Result<V::Value, D::Error> {
+        self.0.deserialize_unit_struct(name, AcceptVisitor::new(visitor))
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, name: &'static str, visitor: V) -> Result<V::Value, D::Error> {
+        self.0.deserialize_newtype_struct(name, AcceptVisitor::new(visitor))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, D::Error> {
+        self.0.deserialize_tuple(len, AcceptVisitor::new(visitor))
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(self, name: &'static str, len: usize, visitor: V) -> Result<V::Value, D::Error> {
+        self.0.deserialize_tuple_struct(name, len, AcceptVisitor::new(visitor))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V
+    ) -> Result<V::Value, D::Error> {
+        self.0.deserialize_struct(name, with_camel_names(fields), AcceptVisitor { visitor, fields: Some(fields) })
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V
+    ) -> Result<V::Value, D::Error> {
+        self.0.deserialize_enum(name, variants, AcceptVisitor::new(visitor))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.0.is_human_readable()
+    }
+}
+
+/// Visitor passing nested deserializers on wrapped in `AcceptCamelCase`;
+/// `fields` are the field names of the struct being visited
+struct AcceptVisitor<V> {
+    visitor: V,
+    fields: Option<&'static [&'static str]>
+}
+
+impl<V> AcceptVisitor<V> {
+    fn new(visitor: V) -> Self {
+        AcceptVisitor { visitor, fields: None }
+    }
+}
+
+/// Forwards `visit_*` methods for single values to the wrapped visitor
+macro_rules! forward_visit {
+    ($($method:ident($ty:ty)),*) => {
+        $(fn $method<E: de::Error>(self, v: $ty) -> Result<V::Value, E> {
+            self.visitor.$method(v)
+        })*
+    };
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for AcceptVisitor<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.visitor.expecting(formatter)
+    }
+
+    forward_visit!(
+        visit_bool(bool), visit_i8(i8), visit_i16(i16), visit_i32(i32), visit_i64(i64), visit_i128(i128),
+        visit_u8(u8), visit_u16(u16), visit_u32(u32), visit_u64(u64), visit_u128(u128), visit_f32(f32),
+        visit_f64(f64), visit_char(char), visit_str(&str), visit_borrowed_str(&'de str), visit_string(String),
+        visit_bytes(&[u8]), visit_borrowed_bytes(&'de [u8]), visit_byte_buf(Vec<u8>)
+    );
+
+    fn visit_none<E: de::Error>(self) -> Result<V::Value, E> {
+        self.visitor.visit_none()
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<V::Value, E> {
+        self.visitor.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<V::Value, D::Error> {
+        self.visitor.visit_some(AcceptCamelCase(deserializer))
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<V::Value, D::Error> {
+        self.visitor.visit_newtype_struct(AcceptCamelCase(deserializer))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<V::Value, A::Error> {
+        self.visitor.visit_seq(AcceptSeq(seq))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<V::Value, A::Error> {
+        self.visitor.visit_map(AcceptMap { map, fields: self.fields })
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<V::Value, A::Error> {
+        self.visitor.visit_enum(AcceptEnum(data))
+    }
+}
+
+/// Seed deserializing through `AcceptCamelCase`
+struct AcceptSeed<T>(T);
+
+impl<'de, T: DeserializeSeed<'de>> DeserializeSeed<'de> for AcceptSeed<T> {
+    type Value = T::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<T::Value, D::Error> {
+        self.0.deserialize(AcceptCamelCase(deserializer))
+    }
+}
+
+/// Sequence access wrapping each element in `AcceptCamelCase`
+struct AcceptSeq<A>(A);
+
+impl<'de, A: SeqAccess<'de>> SeqAccess<'de> for AcceptSeq<A> {
+    type Error = A::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, A::Error> {
+        self.0.next_element_seed(AcceptSeed(seed))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.0.size_hint()
+    }
+}
+
+/// Map access translating a struct's camelCase keys back to its field names
+struct AcceptMap<A> {
+    map: A,
+    fields: Option<&'static [&'static str]>
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for AcceptMap<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, A::Error> {
+        match self.fields {
+            Some(fields) => self.map.next_key_seed(KeySeed { seed, fields }),
+            None => self.map.next_key_seed(seed)
+        }
+    }
+
+    fn next_value_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<T::Value, A::Error> {
+        self.map.next_value_seed(AcceptSeed(seed))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.map.size_hint()
+    }
+}
+
+/// Seed reading a struct key as the field it names
+struct KeySeed<K> {
+    seed: K,
+    fields: &'static [&'static str]
+}
+
+impl<'de, K: DeserializeSeed<'de>> DeserializeSeed<'de> for KeySeed<K> {
+    type Value = K::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<K::Value, D::Error> {
+        self.seed.deserialize(KeyDeserializer { deserializer, fields: self.fields })
+    }
+}
+
+/// Deserializer for a struct key, handing the field name to the visitor
+struct KeyDeserializer<D> {
+    deserializer: D,
+    fields: &'static [&'static str]
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for KeyDeserializer<D> {
+    type Error = D::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+        self.deserializer.deserialize_any(KeyVisitor { visitor, fields: self.fields })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option unit
+        unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+/// Visitor mapping a camelCase key to the field name
+struct KeyVisitor<V> {
+    visitor: V,
+    fields: &'static [&'static str]
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for KeyVisitor<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.visitor.expecting(formatter)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<V::Value, E> {
+        self.visitor.visit_str(rust_name(v, self.fields))
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<V::Value, E> {
+        self.visitor.visit_borrowed_str(rust_name(v, self.fields))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<V::Value, E> {
+        self.visitor.visit_str(rust_name(&v, self.fields))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<V::Value, E> {
+        self.visitor.visit_u64(v)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<V::Value, E> {
+        self.visitor.visit_bytes(v)
+    }
+}
+
+/// Enum access wrapping variant contents in `AcceptCamelCase`
+struct AcceptEnum<A>(A);
+
+impl<'de, A: EnumAccess<'de>> EnumAccess<'de> for AcceptEnum<A> {
+    type Error = A::Error;
+    type Variant = AcceptEnum<A::Variant>;
+
+    fn variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<(T::Value, Self::Variant), A::Error> {
+        self.0.variant_seed(seed).map(|(value, variant)| (value, AcceptEnum(variant)))
+    }
+}
+
+impl<'de, A: VariantAccess<'de>> VariantAccess<'de> for AcceptEnum<A> {
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), A::Error> {
+        self.0.unit_variant()
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, A::Error> {
+        self.0.newtype_variant_seed(AcceptSeed(seed))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, A::Error> {
+        self.0.tuple_variant(len, AcceptVisitor::new(visitor))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value, A::Error> {
+        self.0.struct_variant(with_camel_names(fields), AcceptVisitor { visitor, fields: Some(fields) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ValidationOptions;
+    use serde::{Deserialize, Serialize};
+
+    /// Tests conversion of field names to camelCase
+    #[test]
+    fn test_camel_case() {
+        assert_eq!(camel_case("is_valid"), "isValid");
+        assert_eq!(camel_case("email_hash"), "emailHash");
+        assert_eq!(camel_case("domain"), "domain");
+        assert_eq!(camel_case("mx_records_2"), "mxRecords2");
+        assert_eq!(camel_case("_private"), "_private");
+    }
+
+    #[derive(Serialize)]
+    struct Report {
+        invalid_count: usize
+    }
+
+    #[derive(Serialize)]
+    struct Warning {
+        warning_type: &'static str
+    }
+
+    #[derive(Serialize)]
+    struct Outcome {
+        error_message: &'static str
+    }
+
+    #[derive(Serialize)]
+    struct Record {
+        is_valid: bool,
+        params: BTreeMap<&'static str, &'static str>,
+        metadata: serde_json::Value,
+        domains: BTreeMap<&'static str, Report>,
+        warnings: Vec<Warning>,
+        #[serde(flatten)]
+        outcome: Outcome
+    }
+
+    /// Tests that field names, including flattened ones, are renamed while map
+    /// keys and caller-supplied JSON keep their keys
+    #[test]
+    fn test_cased() {
+        let record = Record {
+            is_valid: true,
+            params: BTreeMap::from([("reply_to", "jane@example.com")]),
+            metadata: serde_json::json!({"crm_id": {"source_system": "sf"}}),
+            domains: BTreeMap::from([("example.com", Report { invalid_count: 1 })]),
+            warnings: vec![Warning { warning_type: "UnusualTld" }],
+            outcome: Outcome { error_message: "Invalid email format" }
+        };
+        assert_eq!(serde_json::to_value(Cased(&record)).unwrap(), serde_json::json!({
+            "isValid": true,
+            "params": {"reply_to": "jane@example.com"},
+            "metadata": {"crm_id": {"source_system": "sf"}},
+            "domains": {"example.com": {"invalidCount": 1}},
+            "warnings": [{"warningType": "UnusualTld"}],
+            "errorMessage": "Invalid email format"
+        }));
+
+        set_snake_case_output(true);
+        let snake_case = serde_json::to_value(Cased(&record)).unwrap();
+        set_snake_case_output(false);
+        assert_eq!(snake_case, serde_json::to_value(&record).unwrap());
+    }
+
+    /// Tests that options are read in either convention
+    #[test]
+    fn test_accept_camel_case() {
+        let camel_case = serde_json::json!({
+            "maxLength": 100,
+            "normalizeUnicode": true,
+            "domainLists": {"blocked": ["spam.example"]},
+            "localPartThresholds": {"keyboardWalk": 4}
+        });
+        let snake_case = serde_json::json!({
+            "max_length": 100,
+            "normalize_unicode": true,
+            "domain_lists": {"blocked": ["spam.example"]},
+            "local_part_thresholds": {"keyboard_walk": 4}
+        });
+        for options in [camel_case, snake_case] {
+            let options = ValidationOptions::deserialize(AcceptCamelCase(options)).unwrap();
+            assert_eq!(options.max_length, Some(100));
+            assert!(options.normalize_unicode);
+            assert_eq!(options.domain_lists.blocked, ["spam.example"]);
+            assert_eq!(options.local_part_thresholds.keyboard_walk, 4);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Domain category classification for segmentation
+
+use serde::{Serialize, Deserialize};
+
+use crate::{academic, domain_lists, government, DomainLists};
+
+/// Kind of organization behind a mail domain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum DomainCategory {
+    /// Free or paid mailbox provider for individuals (Gmail, Outlook, ...)
+    Consumer,
+    /// Any other organization's own domain
+    Corporate,
+    /// Universities and schools (`.edu`, `ac.uk`, `edu.au`, `uni-*.de`, and
+    /// the embedded institution list with the `academic-domains` feature)
+    Educational,
+    /// Government and military bodies (`.gov`, `gov.uk`, `go.jp`, `bund.de`,
+    /// and the `government` domain list)
+    Government,
+    /// Throwaway inbox services
+    Disposable
+}
+
+impl DomainCategory {
+    /// Name used in serialized results
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DomainCategory::Consumer => "consumer",
+            DomainCategory::Corporate => "corporate",
+            DomainCategory::Educational => "educational",
+            DomainCategory::Government => "government",
+            DomainCategory::Disposable => "disposable"
+        }
+    }
+}
+
+/// Consumer mailbox providers beyond the trusted domains
+pub(crate) const CONSUMER_DOMAINS: [&str; 20] = [
+    "gmail.com", "googlemail.com", "hotmail.com", "live.com", "msn.com",
+    "icloud.com", "me.com", "mac.com", "aol.com", "yahoo.co.jp",
+    "yahoo.co.uk", "proton.me", "protonmail.com", "gmx.de", "gmx.net",
+    "web.de", "yandex.ru", "mail.ru", "zoho.com", "fastmail.com"
+];
+
+/// Classifies a domain from its TLD structure and known provider lists
+///
+/// `lists` are the per-call domain lists, consulted for government domains.
+pub(crate) fn classify_domain(domain: &str, lists: &DomainLists) -> DomainCategory {
+    let domain = domain.to_lowercase();
+
+    if domain_lists::is_disposable(&domain) {
+        return DomainCategory::Disposable;
+    }
+    if domain_lists::is_trusted(&domain) || CONSUMER_DOMAINS.contains(&domain.as_str()) {
+        return DomainCategory::Consumer;
+    }
+    if academic::is_academic(&domain) {
+        return DomainCategory::Educational;
+    }
+    if government::is_government(&domain, lists) {
+        return DomainCategory::Government;
+    }
+
+    DomainCategory::Corporate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests TLD rules and provider lists
+    #[test]
+    fn test_classify_domain() {
+        let classify = |domain| classify_domain(domain, &DomainLists::default());
+        assert_eq!(classify("gmail.com"), DomainCategory::Consumer);
+        assert_eq!(classify("Outlook.com"), DomainCategory::Consumer);
+        assert_eq!(classify("mailinator.com"), DomainCategory::Disposable);
+        assert_eq!(classify("cs.stanford.edu"), DomainCategory::Educational);
+        assert_eq!(classify("ox.ac.uk"), DomainCategory::Educational);
+        assert_eq!(classify("irs.gov"), DomainCategory::Government);
+        assert_eq!(classify("digital.cabinet-office.gov.uk"), DomainCategory::Government);
+        assert_eq!(classify("mof.go.jp"), DomainCategory::Government);
+        assert_eq!(classify("bmi.bund.de"), DomainCategory::Government);
+        assert_eq!(classify("acme.co.uk"), DomainCategory::Corporate);
+        assert_eq!(classify("gov.example.com"), DomainCategory::Corporate);
+    }
+}
@@ -0,0 +1,140 @@
+//! RFC 5322 comments (CFWS) in strict mode
+//!
+//! RFC 5322 lets an address carry parenthesized comments, with optional
+//! folding whitespace, around the local part and the domain:
+//! `john.smith(work)@example.com (primary)`. Mail systems ignore them, so
+//! strict validation removes them and validates what is left instead of
+//! rejecting the address.
+
+use crate::message::Message;
+
+/// Removes the comments from `email`
+///
+/// Returns the bare address and the text of each comment in input order,
+/// with nested comments kept and quoted pairs unescaped. Whitespace next to
+/// a comment is removed with it. Parentheses inside a quoted local part or
+/// a domain literal are not comments.
+///
+/// A comment next to a dot (`john(x).smith@example.com`) is replaced by a
+/// space, for `obsolete::rewrite_obsolete` to flag. Fails with
+/// `Message::InvalidFormat` for an unbalanced comment, and with
+/// `Message::MisplacedComment` for a comment that would split an atom or label (`jo(x)hn@example.com`).
+///
+/// # Examples
+/// ```ignore
+/// let (address, comments) = strip_comments("john.smith(work)@example.com (primary)").unwrap();
+/// assert_eq!(address, "john.smith@example.com");
+/// assert_eq!(comments, ["work", "primary"]);
+/// ```
+pub(crate) fn strip_comments(email: &str) -> Result<(String, Vec<String>), Message> {
+    let mut address = String::with_capacity(email.len());
+    let mut comments = Vec::new();
+    // Offset in `address` at which each comment was removed
+    let mut boundaries = Vec::new();
+    let mut comment = String::new();
+    let mut comment_depth = 0usize;
+    let mut in_quotes = false;
+    let mut in_literal = false;
+    let mut escaped = false;
+
+    for c in email.chars() {
+        if comment_depth > 0 {
+            if escaped {
+                escaped = false;
+                comment.push(c);
+                continue;
+            }
+            match c {
+                '\\' => escaped = true,
+                '(' => {
+                    comment_depth += 1;
+                    comment.push(c);
+                }
+                ')' => {
+                    comment_depth -= 1;
+                    if comment_depth > 0 {
+                        comment.push(c);
+                        continue;
+                    }
+                    comments.push(comment.trim().to_string());
+                    comment.clear();
+                    boundaries.push(address.len());
+                }
+                c => comment.push(c)
+            }
+            continue;
+        }
+
+        if escaped {
+            escaped = false;
+        } else if in_quotes {
+            match c {
+                '\\' => escaped = true,
+                '"' => in_quotes = false,
+                _ => {}
+            }
+        } else if in_literal {
+            in_literal = c != ']';
+        } else {
+            match c {
+                '(' => {
+                    let trimmed = address.trim_end_matches([' ', '\t']).len();
+                    address.truncate(trimmed);
+                    comment_depth = 1;
+                    continue;
+                }
+                ')' => return Err(Message::InvalidFormat),
+                ' ' | '\t' if boundaries.last() == Some(&address.len()) => continue,
+                '"' => in_quotes = true,
+                '[' => in_literal = true,
+                _ => {}
+            }
+        }
+        address.push(c);
+    }
+
+    if comment_depth > 0 {
+        return Err(Message::InvalidFormat);
+    }
+
+    // A comment next to a dot is obsolete syntax (see `obsolete`); it stands
+    // for whitespace there, so the address keeps a space in its place
+    for offset in boundaries.into_iter().rev() {
+        let (before, after) = address.split_at(offset);
+        if before.is_empty() || after.is_empty() || before.ends_with('@') || after.starts_with('@') {
+            continue;
+        }
+        if !before.ends_with('.') && !after.starts_with('.') {
+            return Err(Message::MisplacedComment);
+        }
+        address.insert(offset, ' ');
+    }
+    Ok((address, comments))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that comments around the local part and domain are removed and recorded
+    #[test]
+    fn test_strip_comments() {
+        let (address, comments) = strip_comments("john.smith(comment)@example.com(comment)").unwrap();
+        assert_eq!(address, "john.smith@example.com");
+        assert_eq!(comments, ["comment", "comment"]);
+
+        let (address, comments) = strip_comments(" (lead) jane@ (a (nested) \\) one) example.com").unwrap();
+        assert_eq!(address, "jane@example.com");
+        assert_eq!(comments, ["lead", "a (nested) ) one"]);
+
+        let (address, comments) = strip_comments("\"jane (not a comment)\"@example.com").unwrap();
+        assert_eq!(address, "\"jane (not a comment)\"@example.com");
+        assert!(comments.is_empty());
+
+        assert_eq!(strip_comments("jane(@example.com"), Err(Message::InvalidFormat));
+        assert_eq!(strip_comments("jane)@example.com"), Err(Message::InvalidFormat));
+        assert_eq!(strip_comments("jo(x)hn@example.com"), Err(Message::MisplacedComment));
+        assert_eq!(strip_comments("john@exa(x)mple.com"), Err(Message::MisplacedComment));
+        assert_eq!(strip_comments("john(x).smith@example.(y)com").unwrap().0, "john .smith@example. com");
+    }
+}
@@ -0,0 +1,188 @@
+//! Arrow and Parquet output for batch results (`arrow` feature)
+//!
+//! One row per input with the result flattened into typed columns, so batch
+//! output loads straight into Spark, DuckDB or pandas.
+
+use std::io::Write;
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, BooleanArray, Float64Array, RecordBatch, StringArray, UInt64Array};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+use parquet::errors::ParquetError;
+
+use crate::{DomainCategory, EmailParseError, EmailParseResult, MailboxProvider, SpamtrapRisk};
+
+type Row<'a> = (&'a str, &'a Result<EmailParseResult, EmailParseError>);
+
+/// Schema of the record batches built by `to_record_batch`
+pub fn validation_schema() -> SchemaRef {
+    let string = |name: &str| Field::new(name, DataType::Utf8, true);
+    let boolean = |name: &str| Field::new(name, DataType::Boolean, true);
+    Arc::new(Schema::new(vec![
+        Field::new("email", DataType::Utf8, false),
+        Field::new("is_valid", DataType::Boolean, false),
+        string("local_part"),
+        string("domain"),
+        Field::new("domain_score", DataType::Float64, true),
+        string("error_type"),
+        string("error_message"),
+        boolean("is_confusable_spoof"),
+        boolean("is_typosquat"),
+        string("typosquat_target"),
+        boolean("is_suspicious_pattern"),
+        Field::new("duplicate_of", DataType::UInt64, true),
+        string("subaddress"),
+        string("canonical_local_part"),
+        string("canonical_email"),
+        string("mailbox_provider"),
+        boolean("is_disposable_alias"),
+        boolean("is_relay_address"),
+        string("spamtrap_risk"),
+        boolean("is_test_address"),
+        boolean("is_academic"),
+        boolean("is_government"),
+        string("email_hash"),
+        string("country"),
+        string("domain_category")
+    ]))
+}
+
+/// Builds a record batch with one row per input
+///
+/// `emails` and `results` are zipped, as returned by
+/// `parse_and_validate_emails`. Inputs the parser rejected outright have
+/// `is_valid` false and their error in `error_type` and `error_message`.
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::{parse_and_validate_emails, to_record_batch, ValidationOptions};
+/// # let (emails, options) = (["jane@example.com", "invalid-email"], ValidationOptions::default());
+/// let results = parse_and_validate_emails(&emails, &options);
+/// let batch = to_record_batch(&emails, &results)?;
+/// assert_eq!(batch.num_rows(), emails.len());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn to_record_batch<S: AsRef<str>>(emails: &[S], results: &[Result<EmailParseResult, EmailParseError>]) -> Result<RecordBatch, ArrowError> {
+    if emails.len() != results.len() {
+        return Err(ArrowError::InvalidArgumentError(format!("{} emails but {} results", emails.len(), results.len())));
+    }
+    let rows: Vec<Row> = emails.iter().map(AsRef::as_ref).zip(results).collect();
+
+    let strings = |value: fn(&EmailParseResult) -> Option<&str>| -> ArrayRef {
+        Arc::new(rows.iter().map(|(_, result)| result.as_ref().ok().and_then(value)).collect::<StringArray>())
+    };
+    let booleans = |value: fn(&EmailParseResult) -> Option<bool>| -> ArrayRef {
+        Arc::new(rows.iter().map(|(_, result)| result.as_ref().ok().and_then(value)).collect::<BooleanArray>())
+    };
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(rows.iter().map(|(email, _)| Some(*email)).collect::<StringArray>()),
+        Arc::new(rows.iter().map(|(_, result)| Some(result.as_ref().is_ok_and(|result| result.is_valid))).collect::<BooleanArray>()),
+        strings(|result| result.local_part.as_deref()),
+        strings(|result| result.domain.as_deref()),
+        Arc::new(rows.iter().map(|(_, result)| result.as_ref().ok().and_then(|result| result.domain_score)).collect::<Float64Array>()),
+        Arc::new(rows.iter().map(|(_, result)| result.as_ref().err().map(|e| e.error_type.as_str())).collect::<StringArray>()),
+        Arc::new(
+            rows.iter()
+                .map(|(_, result)| match result {
+                    Ok(result) => result.error_message.as_deref(),
+                    Err(e) => Some(e.message.as_str())
+                })
+                .collect::<StringArray>()
+        ),
+        booleans(|result| result.is_confusable_spoof),
+        booleans(|result| result.is_typosquat),
+        strings(|result| result.typosquat_target.as_deref()),
+        booleans(|result| result.is_suspicious_pattern),
+        Arc::new(rows.iter().map(|(_, result)| result.as_ref().ok().and_then(|result| result.duplicate_of).map(|index| index as u64)).collect::<UInt64Array>()),
+        strings(|result| result.subaddress.as_deref()),
+        strings(|result| result.canonical_local_part.as_deref()),
+        strings(|result| result.canonical_email.as_deref()),
+        strings(|result| result.mailbox_provider.as_ref().map(MailboxProvider::as_str)),
+        booleans(|result| result.is_disposable_alias),
+        booleans(|result| result.is_relay_address),
+        strings(|result| result.spamtrap_risk.as_ref().map(SpamtrapRisk::as_str)),
+        booleans(|result| result.is_test_address),
+        booleans(|result| result.is_academic),
+        booleans(|result| result.is_government),
+        strings(|result| result.email_hash.as_deref()),
+        strings(|result| result.country.as_deref()),
+        strings(|result| result.domain_category.as_ref().map(DomainCategory::as_str))
+    ];
+
+    RecordBatch::try_new(validation_schema(), columns)
+}
+
+/// Writes batch results to `writer` as a Parquet file
+///
+/// # Examples
+/// ```no_run
+/// # use email_validator_wasm::{parse_and_validate_emails, write_parquet, ValidationOptions};
+/// # use std::fs::File;
+/// # let (emails, options) = (["jane@example.com", "invalid-email"], ValidationOptions::default());
+/// let results = parse_and_validate_emails(&emails, &options);
+/// write_parquet(File::create("results.parquet")?, &emails, &results)?;
+/// // duckdb: SELECT domain, count(*) FROM 'results.parquet' WHERE NOT is_valid GROUP BY domain
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn write_parquet<S: AsRef<str>, W: Write + Send>(writer: W, emails: &[S], results: &[Result<EmailParseResult, EmailParseError>]) -> Result<(), ParquetError> {
+    let batch = to_record_batch(emails, results)?;
+    let mut writer = ArrowWriter::try_new(writer, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::Array;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    use crate::{parse_and_validate_emails, ValidationOptions};
+
+    fn results(emails: &[&str]) -> Vec<Result<EmailParseResult, EmailParseError>> {
+        parse_and_validate_emails(emails, &ValidationOptions::default())
+    }
+
+    /// Tests column values for valid, invalid and rejected inputs
+    #[test]
+    fn test_to_record_batch() {
+        let emails = ["user+tag@gmail.com", "invalid", "bad\r@example.com"];
+        let batch = to_record_batch(&emails, &results(&emails)).unwrap();
+        assert_eq!(batch.num_rows(), 3);
+        assert_eq!(batch.schema(), validation_schema());
+
+        let column = |name: &str| batch.column_by_name(name).unwrap().clone();
+        let is_valid = column("is_valid");
+        let is_valid = is_valid.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert_eq!((is_valid.value(0), is_valid.value(1), is_valid.value(2)), (true, false, false));
+
+        let subaddress = column("subaddress");
+        assert_eq!(subaddress.as_any().downcast_ref::<StringArray>().unwrap().value(0), "tag");
+        let error_type = column("error_type");
+        let error_type = error_type.as_any().downcast_ref::<StringArray>().unwrap();
+        assert!(error_type.is_null(1));
+        assert_eq!(error_type.value(2), "ControlCharacter");
+        let category = column("domain_category");
+        assert_eq!(category.as_any().downcast_ref::<StringArray>().unwrap().value(0), "consumer");
+
+        assert!(to_record_batch(&["a@example.com"], &[]).is_err());
+    }
+
+    /// Tests that a written Parquet file reads back with the same rows
+    #[test]
+    fn test_write_parquet() {
+        let emails = ["a@example.com", "b@example.com", "invalid"];
+        let path = std::env::temp_dir().join(format!("results-{}.parquet", std::process::id()));
+        write_parquet(std::fs::File::create(&path).unwrap(), &emails, &results(&emails)).unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap()).unwrap().build().unwrap();
+        let batches: Vec<RecordBatch> = reader.collect::<Result<_, _>>().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(batches.iter().map(RecordBatch::num_rows).sum::<usize>(), 3);
+        let email = batches[0].column(0).as_any().downcast_ref::<StringArray>().unwrap().value(2).to_string();
+        assert_eq!(email, "invalid");
+    }
+}
@@ -0,0 +1,75 @@
+//! Homograph detection based on the UTS #39 confusable skeleton
+
+use crate::punycode;
+
+/// Characters that render (nearly) identically to an ASCII prototype
+///
+/// A subset of the Unicode confusables data covering the Cyrillic, Greek,
+/// Latin-extended and digit lookalikes used in practice to spoof domain names.
+const CONFUSABLES: &[(char, &str)] = &[
+    // Cyrillic
+    ('а', "a"), ('в', "b"), ('с', "c"), ('ԁ', "d"), ('е', "e"), ('ё', "e"), ('һ', "h"),
+    ('і', "i"), ('ї', "i"), ('ј', "j"), ('к', "k"), ('ӏ', "l"), ('м', "m"), ('п', "n"),
+    ('о', "o"), ('р', "p"), ('ԛ', "q"), ('ѕ', "s"), ('т', "t"), ('ц', "u"), ('ѵ', "v"),
+    ('ԝ', "w"), ('х', "x"), ('у', "y"), ('ү', "y"), ('ӡ', "3"),
+    // Greek
+    ('α', "a"), ('β', "b"), ('ε', "e"), ('η', "n"), ('ι', "i"), ('κ', "k"), ('ν', "v"),
+    ('ο', "o"), ('ρ', "p"), ('τ', "t"), ('υ', "u"), ('χ', "x"), ('γ', "y"), ('ω', "w"),
+    // Latin extended and IPA
+    ('ɑ', "a"), ('ƅ', "b"), ('ɡ', "g"), ('ı', "i"), ('ɩ', "i"), ('ȷ', "j"), ('ɫ', "l"),
+    ('ɴ', "n"), ('ɵ', "o"), ('ʀ', "r"), ('ʂ', "s"), ('ᴜ', "u"), ('ᴠ', "v"),
+    ('ᴡ', "w"), ('ʏ', "y"), ('ᴢ', "z"),
+    // Digits and case-sensitive lookalikes
+    ('0', "o"), ('1', "l"), ('I', "l"), ('ǀ', "l"), ('ⅼ', "l")
+];
+
+/// Sequences that read as a single letter once mapped
+const SEQUENCE_CONFUSABLES: &[(&str, &str)] = &[("rn", "m"), ("vv", "w"), ("cl", "d")];
+
+/// Computes the confusable skeleton of a domain: punycode labels are decoded,
+/// each lookalike character is replaced by its ASCII prototype (checking the
+/// character as written, then lowercased) and lookalike sequences are collapsed
+///
+/// Two domains with the same skeleton are visually confusable.
+pub(crate) fn skeleton(domain: &str) -> String {
+    let unicode = punycode::domain_to_unicode(domain).unwrap_or_else(|| domain.to_string());
+
+    let prototype = |c: char| CONFUSABLES.iter().find(|(confusable, _)| *confusable == c).map(|(_, p)| *p);
+
+    let mut mapped = String::with_capacity(unicode.len());
+    for c in unicode.chars() {
+        match prototype(c) {
+            Some(p) => mapped.push_str(p),
+            None => {
+                for lower in c.to_lowercase() {
+                    match prototype(lower) {
+                        Some(p) => mapped.push_str(p),
+                        None => mapped.push(lower)
+                    }
+                }
+            }
+        }
+    }
+
+    SEQUENCE_CONFUSABLES
+        .iter()
+        .fold(mapped, |text, (sequence, prototype)| text.replace(sequence, prototype))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that homographs share a skeleton with the domain they imitate
+    #[test]
+    fn test_skeleton() {
+        assert_eq!(skeleton("xn--ggle-55da.com"), "google.com"); // Cyrillic о
+        assert_eq!(skeleton("gооgle.com"), "google.com");
+        assert_eq!(skeleton("YAH00.COM"), skeleton("yahoo.com"));
+        assert_eq!(skeleton("outIook.com"), skeleton("outlook.com")); // Uppercase i
+        assert_eq!(skeleton("rnail.example"), skeleton("mail.example"));
+
+        assert_ne!(skeleton("example.com"), skeleton("google.com"));
+        assert_ne!(skeleton("googles.com"), skeleton("google.com"));
+    }
+}
@@ -0,0 +1,41 @@
+//! Country detection from country-code top-level domains
+
+/// ccTLDs whose ISO 3166-1 alpha-2 code differs from the TLD
+const CCTLD_EXCEPTIONS: [(&str, &str); 2] = [
+    ("uk", "GB"),
+    ("ac", "SH")
+];
+
+/// Two-letter TLDs that do not denote a single country
+const NON_COUNTRY_CCTLDS: [&str; 2] = ["eu", "su"];
+
+/// ISO 3166-1 alpha-2 code of the country owning the domain's ccTLD
+///
+/// Returns `None` for generic TLDs (`.com`) and for two-letter TLDs that are
+/// not tied to a country (`.eu`).
+pub(crate) fn country_from_domain(domain: &str) -> Option<String> {
+    let tld = domain.rsplit('.').next()?.to_lowercase();
+    if tld.len() != 2 || !tld.bytes().all(|b| b.is_ascii_alphabetic()) || NON_COUNTRY_CCTLDS.contains(&tld.as_str()) {
+        return None;
+    }
+
+    match CCTLD_EXCEPTIONS.iter().find(|(cctld, _)| *cctld == tld) {
+        Some((_, code)) => Some(code.to_string()),
+        None => Some(tld.to_uppercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests ccTLD mapping, ISO exceptions and non-country TLDs
+    #[test]
+    fn test_country_from_domain() {
+        assert_eq!(country_from_domain("example.de"), Some("DE".to_string()));
+        assert_eq!(country_from_domain("mail.example.CO.JP"), Some("JP".to_string()));
+        assert_eq!(country_from_domain("example.co.uk"), Some("GB".to_string()));
+        assert_eq!(country_from_domain("example.com"), None);
+        assert_eq!(country_from_domain("europa.eu"), None);
+    }
+}
@@ -0,0 +1,181 @@
+//! CSV list cleaning (`csv` feature)
+//!
+//! Reads a CSV, validates the address in one column of each row and writes
+//! the row back unchanged with validation columns appended.
+
+use std::io::{Read, Write};
+
+use serde::{Serialize, Deserialize};
+
+use crate::{parse_and_validate_email_with_options, EmailParseError, ValidationOptions};
+
+/// Columns appended to every row, in order
+pub const CSV_RESULT_COLUMNS: [&str; 4] = ["is_valid", "domain_score", "domain_category", "validation_error"];
+
+/// Column holding the address
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EmailColumn {
+    /// Header name, matched case-insensitively
+    Name(String),
+    /// Zero-based column index
+    Index(usize)
+}
+
+impl Default for EmailColumn {
+    fn default() -> Self {
+        EmailColumn::Name("email".to_string())
+    }
+}
+
+/// Options for `validate_csv`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CsvOptions {
+    /// Column holding the address (default: the `email` header)
+    pub column: EmailColumn,
+    /// Whether the first row is a header; it is written back with the result
+    /// columns appended (default true)
+    pub has_headers: bool,
+    /// Field delimiter (default `,`)
+    pub delimiter: u8,
+    /// Options applied to each address
+    pub validation: ValidationOptions
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            column: EmailColumn::default(),
+            has_headers: true,
+            delimiter: b',',
+            validation: ValidationOptions::default()
+        }
+    }
+}
+
+/// Validates the address column of each CSV row from `reader`, writing each
+/// row with `CSV_RESULT_COLUMNS` appended to `writer`
+///
+/// Rows are processed one at a time, so memory stays bounded. Rows too short
+/// to have the column are written with the error "MissingColumn".
+///
+/// # Returns
+/// * `Result<usize, EmailParseError>` - Number of data rows processed
+///
+/// # Examples
+/// ```no_run
+/// # use email_validator_wasm::{validate_csv, CsvOptions, EmailColumn};
+/// # use std::fs::File;
+/// let options = CsvOptions { column: EmailColumn::Name("Contact Email".to_string()), ..Default::default() };
+/// let rows = validate_csv(File::open("contacts.csv")?, File::create("contacts.checked.csv")?, &options).map_err(|e| e.message)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn validate_csv<R: Read, W: Write>(reader: R, writer: W, options: &CsvOptions) -> Result<usize, EmailParseError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(options.has_headers)
+        .delimiter(options.delimiter)
+        .flexible(true)
+        .from_reader(reader);
+    let mut writer = csv::WriterBuilder::new().delimiter(options.delimiter).flexible(true).from_writer(writer);
+
+    let index = match &options.column {
+        EmailColumn::Index(index) => *index,
+        EmailColumn::Name(name) => {
+            if !options.has_headers {
+                return Err(csv_error("ColumnNotFound", "Columns can only be selected by name when the CSV has headers", None));
+            }
+            let headers = reader.headers().map_err(|e| csv_error("CsvError", "Failed to read CSV headers", Some(e.to_string())))?;
+            headers
+                .iter()
+                .position(|header| header.trim().eq_ignore_ascii_case(name))
+                .ok_or_else(|| csv_error("ColumnNotFound", &format!("No column named \"{}\"", name), None))?
+        }
+    };
+
+    if options.has_headers {
+        let mut headers = reader.headers().map_err(|e| csv_error("CsvError", "Failed to read CSV headers", Some(e.to_string())))?.clone();
+        headers.extend(CSV_RESULT_COLUMNS);
+        writer.write_record(&headers).map_err(write_error)?;
+    }
+
+    let mut processed = 0;
+    let mut record = csv::StringRecord::new();
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => return Err(csv_error("CsvError", "Failed to read CSV row", Some(e.to_string())))
+        }
+
+        let columns = match record.get(index) {
+            None => ["false".to_string(), String::new(), String::new(), "MissingColumn".to_string()],
+            Some(email) => match parse_and_validate_email_with_options(email, &options.validation) {
+                Ok(result) => [
+                    result.is_valid.to_string(),
+                    result.domain_score.map(|score| score.to_string()).unwrap_or_default(),
+                    result.domain_category.map_or_else(String::new, |category| category.as_str().to_string()),
+                    result.error_message.unwrap_or_default()
+                ],
+                Err(e) => ["false".to_string(), String::new(), String::new(), e.message]
+            }
+        };
+        record.extend(columns.iter());
+        writer.write_record(&record).map_err(write_error)?;
+        processed += 1;
+    }
+
+    writer.flush().map_err(|e| csv_error("CsvError", "Failed to write CSV", Some(e.to_string())))?;
+    Ok(processed)
+}
+
+fn csv_error(error_type: &str, message: &str, details: Option<String>) -> EmailParseError {
+    EmailParseError {
+        error_type: error_type.to_string(),
+        message: message.to_string(),
+        details,
+        position: None
+    }
+}
+
+fn write_error(e: csv::Error) -> EmailParseError {
+    csv_error("CsvError", "Failed to write CSV row", Some(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(input: &str, options: &CsvOptions) -> Result<String, EmailParseError> {
+        let mut output = Vec::new();
+        validate_csv(input.as_bytes(), &mut output, options)?;
+        Ok(String::from_utf8(output).unwrap())
+    }
+
+    /// Tests that rows are kept intact with result columns appended
+    #[test]
+    fn test_validate_csv() {
+        let input = "name,Email,plan\n\"Doe, Jane\",jane@gmail.com,pro\nBob,not-an-email,free\nShort\n";
+        let output = run(input, &CsvOptions::default()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "name,Email,plan,is_valid,domain_score,domain_category,validation_error");
+        assert!(lines[1].starts_with("\"Doe, Jane\",jane@gmail.com,pro,true,"), "{}", lines[1]);
+        assert!(lines[1].contains(",consumer,"), "{}", lines[1]);
+        assert!(lines[2].starts_with("Bob,not-an-email,free,false,,,"), "{}", lines[2]);
+        assert_eq!(lines[3], "Short,false,,,MissingColumn");
+    }
+
+    /// Tests column selection by index, headerless input and delimiters
+    #[test]
+    fn test_validate_csv_options() {
+        let options = CsvOptions { column: EmailColumn::Index(1), has_headers: false, delimiter: b';', ..Default::default() };
+        let output = run("1;user@example.com\n2;bad\n", &options).unwrap();
+        assert!(output.lines().next().unwrap().starts_with("1;user@example.com;true;"));
+        assert_eq!(output.lines().count(), 2);
+
+        let missing = CsvOptions { column: EmailColumn::Name("mail".to_string()), ..Default::default() };
+        assert_eq!(run("email\na@example.com\n", &missing).unwrap_err().error_type, "ColumnNotFound");
+        let headerless = CsvOptions { has_headers: false, ..Default::default() };
+        assert_eq!(run("a@example.com\n", &headerless).unwrap_err().error_type, "ColumnNotFound");
+    }
+}
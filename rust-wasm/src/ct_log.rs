@@ -0,0 +1,83 @@
+//! Certificate Transparency presence check for a domain
+//!
+//! A domain with certificates in the public CT logs has served HTTPS at some
+//! point, a sign that it runs real services rather than existing only to
+//! send or receive mail. The check goes through a configurable search API
+//! that returns matching log entries as a JSON array, such as crt.sh's
+//! `output=json`.
+
+/// crt.sh search for certificates issued to the domain
+pub const DEFAULT_CT_LOG_ENDPOINT: &str = "https://crt.sh/?q={domain}&output=json";
+
+/// Bytes of the response read; presence only needs the start of the array
+#[cfg(all(feature = "tls", not(target_arch = "wasm32")))]
+const MAX_RESPONSE_SIZE: usize = 4096;
+
+/// Search URL for `domain`: the domain replaces `{domain}` in `endpoint`, or
+/// is appended to it when the URL has no placeholder
+#[cfg(any(target_arch = "wasm32", feature = "tls", test))]
+fn search_url(endpoint: &str, domain: &str) -> String {
+    let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+    if endpoint.contains("{domain}") {
+        endpoint.replace("{domain}", &domain)
+    } else {
+        format!("{}{}", endpoint, domain)
+    }
+}
+
+/// Whether a search response lists at least one entry
+///
+/// Only the opening of the array is inspected, so a response truncated at
+/// `MAX_RESPONSE_SIZE` still answers.
+#[cfg(any(target_arch = "wasm32", feature = "tls", test))]
+fn lists_entries(body: &str) -> Result<bool, String> {
+    let body = body.trim_start();
+    match body.strip_prefix('[') {
+        Some(rest) => Ok(!rest.trim_start().starts_with(']')),
+        None => Err("CT log response is not a JSON array".to_string())
+    }
+}
+
+/// Checks whether `domain` has certificates in the CT logs through the
+/// host's `fetch`
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn check_ct_logs(endpoint: &str, domain: &str) -> Result<bool, String> {
+    let body = crate::fetch::fetch_text(&search_url(endpoint, domain), "application/json").await.map_err(|e| e.as_string().unwrap_or_default())?;
+    lists_entries(&body)
+}
+
+/// Checks whether `domain` has certificates in the CT logs over HTTPS
+#[cfg(all(feature = "tls", not(target_arch = "wasm32")))]
+pub(crate) async fn check_ct_logs(endpoint: &str, domain: &str) -> Result<bool, String> {
+    let url = search_url(endpoint, domain);
+    let (host, path) = crate::http::split_https_url(&url).ok_or("CT log endpoint must be an https:// URL")?;
+    let (status, body) = crate::http::https_get(host, path, std::time::Duration::from_secs(10), MAX_RESPONSE_SIZE).await?;
+    if status != 200 {
+        return Err(format!("HTTP status {}", status));
+    }
+    lists_entries(&body)
+}
+
+/// CT logs cannot be searched without TLS support
+#[cfg(all(not(feature = "tls"), not(target_arch = "wasm32")))]
+pub(crate) async fn check_ct_logs(_endpoint: &str, _domain: &str) -> Result<bool, String> {
+    Err("Checking CT logs requires the `tls` feature".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests URL templating and presence detection on full and truncated responses
+    #[test]
+    fn test_ct_log_search() {
+        assert_eq!(search_url(DEFAULT_CT_LOG_ENDPOINT, "Example.com."), "https://crt.sh/?q=example.com&output=json");
+        assert_eq!(search_url("https://ct.internal.example/domains/", "example.com"), "https://ct.internal.example/domains/example.com");
+
+        assert_eq!(lists_entries(r#"[{"issuer_ca_id":16418,"common_name":"example.com"}]"#), Ok(true));
+        assert_eq!(lists_entries(r#" [ {"issuer_ca_id":16418,"common_na"#), Ok(true));
+        assert_eq!(lists_entries("[]"), Ok(false));
+        assert_eq!(lists_entries("[ \n]"), Ok(false));
+        assert!(lists_entries("<html>Too many requests</html>").is_err());
+    }
+}
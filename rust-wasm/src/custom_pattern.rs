@@ -0,0 +1,137 @@
+//! Caller-supplied address patterns
+//!
+//! Some organizations mandate their own address regex. `CustomPattern`
+//! replaces the built-in syntax rules with it; the crate still rejects empty
+//! input, control characters and addresses over 320 characters, and still
+//! splits the address and scores the domain.
+
+use std::fmt;
+use std::sync::Mutex;
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::message::Message;
+use crate::EmailParseError;
+
+/// Most distinct patterns kept compiled
+const CACHE_SIZE: usize = 16;
+
+/// Compiled program size above which a pattern is refused
+const SIZE_LIMIT: usize = 1 << 20;
+
+/// Recently compiled patterns, most recent last, so options deserialized on
+/// every call (as from JS) do not recompile the same pattern
+static COMPILED: Mutex<Vec<(String, Regex)>> = Mutex::new(Vec::new());
+
+/// A validated, compiled address pattern that must match the whole address
+///
+/// Serialized as the pattern string; deserializing compiles it, so an
+/// invalid pattern is reported when options are loaded, not per address.
+#[derive(Clone)]
+pub struct CustomPattern {
+    source: String,
+    regex: Regex
+}
+
+impl CustomPattern {
+    /// Compiles `pattern`, anchored to match the whole address
+    ///
+    /// # Examples
+    /// ```
+    /// # use email_validator_wasm::CustomPattern;
+    /// let pattern = CustomPattern::new(r"[a-z.]+@corp\.example")?;
+    /// assert!(pattern.is_match("jane.doe@corp.example"));
+    /// # Ok::<(), email_validator_wasm::EmailParseError>(())
+    /// ```
+    pub fn new(pattern: &str) -> Result<Self, EmailParseError> {
+        let mut compiled = COMPILED.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(index) = compiled.iter().position(|(source, _)| source == pattern) {
+            let entry = compiled.remove(index);
+            let custom = CustomPattern { source: entry.0.clone(), regex: entry.1.clone() };
+            compiled.push(entry);
+            return Ok(custom);
+        }
+
+        let regex = RegexBuilder::new(&format!("^(?:{})$", pattern))
+            .size_limit(SIZE_LIMIT)
+            .build()
+            .map_err(|e| EmailParseError {
+                error_type: "InvalidPattern".to_string(),
+                message: "Failed to compile the custom email pattern".to_string(),
+                details: Some(e.to_string()),
+                position: None
+            })?;
+        if compiled.len() == CACHE_SIZE {
+            compiled.remove(0);
+        }
+        compiled.push((pattern.to_string(), regex.clone()));
+        Ok(CustomPattern { source: pattern.to_string(), regex })
+    }
+
+    /// The pattern as supplied
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether `email` matches the pattern in full
+    pub fn is_match(&self, email: &str) -> bool {
+        self.regex.is_match(email)
+    }
+
+    /// Splits a matching address at its last `@`, or returns the message
+    /// explaining why it is invalid
+    pub(crate) fn split<'a>(&self, email: &'a str) -> Result<Result<[&'a str; 2], Message>, EmailParseError> {
+        if !self.is_match(email) {
+            return Ok(Err(Message::PatternMismatch));
+        }
+        match email.rsplit_once('@') {
+            Some((local_part, domain)) if !local_part.is_empty() && !domain.is_empty() => Ok(Ok([local_part, domain])),
+            _ => Ok(Err(Message::InvalidFormat))
+        }
+    }
+}
+
+impl fmt::Debug for CustomPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CustomPattern").field(&self.source).finish()
+    }
+}
+
+impl Serialize for CustomPattern {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+impl<'de> Deserialize<'de> for CustomPattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        CustomPattern::new(&pattern).map_err(|e| serde::de::Error::custom(format!("{}: {}", e.message, e.details.unwrap_or_default())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests anchoring, splitting, caching and rejection of bad patterns
+    #[test]
+    fn test_custom_pattern() {
+        let pattern = CustomPattern::new(r"[a-z]+\.[a-z]+@corp\.example").unwrap();
+        assert_eq!(pattern.split("jane.doe@corp.example").unwrap(), Ok(["jane.doe", "corp.example"]));
+        assert_eq!(pattern.split("x jane.doe@corp.example").unwrap(), Err(Message::PatternMismatch));
+        assert_eq!(pattern.split("jane.doe@corp.example.evil").unwrap(), Err(Message::PatternMismatch));
+
+        let no_at = CustomPattern::new(r"[a-z]+").unwrap();
+        assert_eq!(no_at.split("jane").unwrap(), Err(Message::InvalidFormat));
+
+        let again = CustomPattern::new(r"[a-z]+\.[a-z]+@corp\.example").unwrap();
+        assert_eq!(again.as_str(), pattern.as_str());
+
+        let error = CustomPattern::new("[unclosed").unwrap_err();
+        assert_eq!(error.error_type, "InvalidPattern");
+        assert!(serde_json::from_str::<CustomPattern>(r#""(""#).is_err());
+        assert_eq!(serde_json::to_string(&pattern).unwrap(), r#""[a-z]+\\.[a-z]+@corp\\.example""#);
+    }
+}
@@ -0,0 +1,116 @@
+//! Canonical forms for detecting functional duplicates in lists
+//!
+//! Two addresses are functional duplicates when they reach the same mailbox:
+//! case differs, a plus tag was added, or the provider otherwise folds the
+//! spelling (see `providers`).
+
+use std::collections::HashMap;
+
+use serde::{Serialize, Deserialize};
+
+use crate::{providers, EmailParseError, EmailParseResult};
+
+/// Inputs that collapse to the same canonical address
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DuplicateGroup {
+    /// The shared canonical address
+    pub canonical: String,
+    /// Indices of the inputs, in input order; the first is the one to keep
+    pub indices: Vec<usize>
+}
+
+/// Canonical form of an address: lowercased, without its plus tag, and
+/// folded by its provider's rules (for Gmail, without dots and with
+/// `googlemail.com` folded into `gmail.com`)
+///
+/// Returns None when the input has no `@` or an empty side. Syntax is not
+/// otherwise checked.
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::canonical_email;
+/// assert_eq!(canonical_email("J.Doe+news@GoogleMail.com").as_deref(), Some("jdoe@gmail.com"));
+/// assert_eq!(canonical_email("j.doe+news@example.com").as_deref(), Some("j.doe@example.com"));
+/// ```
+pub fn canonical_email(email: &str) -> Option<String> {
+    let (local_part, domain) = email.trim().rsplit_once('@')?;
+    providers::canonicalize(local_part, domain)
+}
+
+/// Groups inputs that are functional duplicates of one another
+///
+/// Only groups with more than one input are returned, ordered by their
+/// first index. Inputs without a canonical form are never grouped.
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::find_duplicates;
+/// let groups = find_duplicates(&["a.b@gmail.com", "other@example.com", "AB+x@gmail.com"]);
+/// assert_eq!(groups[0].indices, vec![0, 2]);
+/// ```
+pub fn find_duplicates<S: AsRef<str>>(emails: &[S]) -> Vec<DuplicateGroup> {
+    group(emails.iter().map(|email| canonical_email(email.as_ref())))
+}
+
+fn group(canonical: impl Iterator<Item = Option<String>>) -> Vec<DuplicateGroup> {
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut groups: Vec<DuplicateGroup> = Vec::new();
+    for (index, canonical) in canonical.enumerate() {
+        let Some(canonical) = canonical else {
+            continue;
+        };
+        match positions.get(&canonical) {
+            Some(&position) => groups[position].indices.push(index),
+            None => {
+                positions.insert(canonical.clone(), groups.len());
+                groups.push(DuplicateGroup { canonical, indices: vec![index] });
+            }
+        }
+    }
+    groups.retain(|group| group.indices.len() > 1);
+    groups
+}
+
+/// Sets `duplicate_of` on valid results that repeat an earlier address in the batch
+pub(crate) fn flag_duplicates(results: &mut [Result<EmailParseResult, EmailParseError>]) {
+    let canonical = results.iter().map(|result| match result {
+        Ok(EmailParseResult { is_valid: true, canonical_email, .. }) => canonical_email.clone(),
+        _ => None
+    });
+
+    for group in group(canonical) {
+        let first = group.indices[0];
+        for &index in &group.indices[1..] {
+            if let Ok(result) = &mut results[index] {
+                result.duplicate_of = Some(first);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests case, plus-tag and Gmail dot folding
+    #[test]
+    fn test_canonical_email() {
+        assert_eq!(canonical_email("J.Doe+news@GoogleMail.com").as_deref(), Some("jdoe@gmail.com"));
+        assert_eq!(canonical_email("jdoe@gmail.com.").as_deref(), Some("jdoe@gmail.com"));
+        assert_eq!(canonical_email(" John.Smith+tag@Example.COM ").as_deref(), Some("john.smith@example.com"));
+        assert_eq!(canonical_email("+tag@example.com"), None);
+        assert_eq!(canonical_email("no-at-sign"), None);
+    }
+
+    /// Tests grouping order and that unparseable inputs are never grouped
+    #[test]
+    fn test_find_duplicates() {
+        let groups = find_duplicates(&["a.b@gmail.com", "x", "c@example.com", "AB+1@googlemail.com", "x", "C@example.com", "ab@gmail.com"]);
+        assert_eq!(groups, vec![
+            DuplicateGroup { canonical: "ab@gmail.com".to_string(), indices: vec![0, 3, 6] },
+            DuplicateGroup { canonical: "c@example.com".to_string(), indices: vec![2, 5] }
+        ]);
+        assert!(find_duplicates(&["a@example.com", "b@example.com"]).is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+//! Differential validation of one input set under two configurations
+//!
+//! Before rolling out a rules change (new domain lists, stricter mode, an
+//! access policy), run the addresses it will see through the current and the
+//! proposed `ValidationOptions` and review where they disagree.
+
+use serde::{Serialize, Deserialize};
+
+use crate::{batch, EmailParseError, EmailParseResult, ValidationOptions, ValidationWarning};
+
+/// What one configuration decided for an address
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationOutcome {
+    pub is_valid: bool,
+    /// Validation error message, or the parse error for unparseable input
+    pub error: Option<String>,
+    pub domain_score: Option<f64>,
+    /// Flagged by the batch-wide numbered-series check
+    pub is_suspicious_pattern: Option<bool>,
+    /// Index of the earlier address this one duplicates
+    pub duplicate_of: Option<usize>,
+    pub warnings: Vec<ValidationWarning>
+}
+
+impl ValidationOutcome {
+    fn from_result(result: Result<EmailParseResult, EmailParseError>) -> Self {
+        match result {
+            Ok(result) => ValidationOutcome {
+                is_valid: result.is_valid,
+                error: result.error_message,
+                domain_score: result.domain_score,
+                is_suspicious_pattern: result.is_suspicious_pattern,
+                duplicate_of: result.duplicate_of,
+                warnings: result.warnings
+            },
+            Err(e) => ValidationOutcome {
+                is_valid: false,
+                error: Some(e.message),
+                domain_score: None,
+                is_suspicious_pattern: None,
+                duplicate_of: None,
+                warnings: Vec::new()
+            }
+        }
+    }
+}
+
+/// An address the two configurations decided differently
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Disagreement {
+    /// Position of the address in the input
+    pub index: usize,
+    pub email: String,
+    pub baseline: ValidationOutcome,
+    pub candidate: ValidationOutcome
+}
+
+/// Result of `compare_configurations`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DifferentialReport {
+    /// Addresses compared
+    pub total: usize,
+    /// Valid under the baseline but not the candidate
+    pub newly_rejected: usize,
+    /// Valid under the candidate but not the baseline
+    pub newly_accepted: usize,
+    /// Same validity, but a different error, domain score, batch-level flag
+    /// or warnings
+    pub changed: usize,
+    /// Every address with a different outcome, in input order
+    pub disagreements: Vec<Disagreement>
+}
+
+/// Validates `emails` under `baseline` and `candidate` and reports the
+/// addresses whose outcome differs
+///
+/// Both runs go through `parse_and_validate_emails`, so batch-level checks
+/// (numbered series, duplicates) are compared too.
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::{compare_configurations, ValidationMode, ValidationOptions};
+/// # let (emails, current) = (["jane@example.com"], ValidationOptions::default());
+/// let proposed = ValidationOptions { mode: ValidationMode::Strict, ..current.clone() };
+/// let report = compare_configurations(&emails, &current, &proposed);
+/// println!("{} newly rejected, {} newly accepted", report.newly_rejected, report.newly_accepted);
+/// ```
+pub fn compare_configurations<S: AsRef<str>>(emails: &[S], baseline: &ValidationOptions, candidate: &ValidationOptions) -> DifferentialReport {
+    let baseline_results = batch::parse_and_validate_emails(emails, baseline);
+    let candidate_results = batch::parse_and_validate_emails(emails, candidate);
+
+    let mut report = DifferentialReport { total: emails.len(), ..Default::default() };
+    for (index, (email, (baseline, candidate))) in emails.iter().zip(baseline_results.into_iter().zip(candidate_results)).enumerate() {
+        let baseline = ValidationOutcome::from_result(baseline);
+        let candidate = ValidationOutcome::from_result(candidate);
+        if baseline == candidate {
+            continue;
+        }
+        match (baseline.is_valid, candidate.is_valid) {
+            (true, false) => report.newly_rejected += 1,
+            (false, true) => report.newly_accepted += 1,
+            _ => report.changed += 1
+        }
+        report.disagreements.push(Disagreement { index, email: email.as_ref().to_string(), baseline, candidate });
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AccessPolicy, DomainLists};
+
+    /// Tests that only addresses decided differently are reported, and counted by direction
+    #[test]
+    fn test_compare_configurations() {
+        let emails = ["user@example.com", "user@example.org", "user@example.net", "not-an-email"];
+        let proposed = ValidationOptions {
+            domain_lists: DomainLists { trusted: vec!["example.com".to_string()], ..Default::default() },
+            access_policy: AccessPolicy::new().deny("example.org").unwrap(),
+            ..Default::default()
+        };
+        let report = compare_configurations(&emails, &ValidationOptions::default(), &proposed);
+        assert_eq!(report.total, 4);
+        assert_eq!((report.newly_rejected, report.newly_accepted, report.changed), (1, 0, 1));
+        let indices: Vec<usize> = report.disagreements.iter().map(|disagreement| disagreement.index).collect();
+        assert_eq!(indices, [0, 1]);
+        assert_eq!(report.disagreements[0].candidate.domain_score, Some(80.0));
+        assert!(report.disagreements[1].baseline.is_valid);
+        assert!(!report.disagreements[1].candidate.is_valid);
+
+        let report = compare_configurations(&emails, &proposed, &ValidationOptions::default());
+        assert_eq!(report.newly_accepted, 1);
+        assert!(compare_configurations(&emails, &proposed, &proposed).disagreements.is_empty());
+    }
+
+    /// Tests that a change to a batch-level check alone is reported
+    #[test]
+    fn test_compare_batch_checks() {
+        let emails = ["jane@example.com", "Jane@Example.com"];
+        let proposed = ValidationOptions { detect_duplicates: true, ..Default::default() };
+        let report = compare_configurations(&emails, &ValidationOptions::default(), &proposed);
+        assert_eq!((report.newly_rejected, report.newly_accepted, report.changed), (0, 0, 1));
+        assert_eq!(report.disagreements[0].index, 1);
+        assert_eq!(report.disagreements[0].baseline.duplicate_of, None);
+        assert_eq!(report.disagreements[0].candidate.duplicate_of, Some(0));
+    }
+}
@@ -0,0 +1,274 @@
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::{normalize_name, DnsError, DnsResolver, MxRecord, TlsaRecord};
+use crate::cache::{LruCache, VerificationCache};
+
+/// Shared store of DNS answers, keyed by record type and name
+///
+/// Answers live for their advertised TTL, capped at `max_ttl`; answers
+/// without a TTL (from resolvers that don't report one) use `default_ttl`.
+/// `NxDomain` answers are cached for `negative_ttl`, other errors are not
+/// cached. Clones share entries, so one cache can back every resolver in a
+/// batch or server.
+///
+/// # Examples
+/// ```no_run
+/// # use email_validator_wasm::dns::{CachingResolver, DnsCache, SystemResolver};
+/// # use email_validator_wasm::Verifier;
+/// # use std::time::Duration;
+/// let cache = DnsCache::new(10_000).with_max_ttl(Duration::from_secs(600));
+/// let verifier = Verifier::new(CachingResolver::new(SystemResolver::from_system_config()?, cache.clone()));
+/// # Ok::<(), email_validator_wasm::dns::DnsError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct DnsCache {
+    entries: Arc<LruCache<CachedAnswer>>,
+    default_ttl: Duration,
+    max_ttl: Duration,
+    negative_ttl: Duration
+}
+
+#[derive(Debug, Clone)]
+enum CachedAnswer {
+    Mx(Result<Vec<MxRecord>, DnsError>),
+    Txt(Result<Vec<String>, DnsError>),
+    Tlsa(Result<Vec<TlsaRecord>, DnsError>),
+    A(Result<Vec<Ipv4Addr>, DnsError>)
+}
+
+impl DnsCache {
+    /// Creates a cache holding at most `capacity` answers
+    pub fn new(capacity: usize) -> Self {
+        DnsCache {
+            entries: Arc::new(LruCache::new(capacity)),
+            default_ttl: Duration::from_secs(300),
+            max_ttl: Duration::from_secs(86_400),
+            negative_ttl: Duration::from_secs(60)
+        }
+    }
+
+    /// Sets the lifetime of answers that carry no TTL (default 5 minutes)
+    pub fn with_default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = ttl;
+        self
+    }
+
+    /// Caps how long any answer is kept, whatever its TTL (default 1 day)
+    pub fn with_max_ttl(mut self, ttl: Duration) -> Self {
+        self.max_ttl = ttl;
+        self
+    }
+
+    /// Sets how long `NxDomain` answers are kept (default 1 minute)
+    pub fn with_negative_ttl(mut self, ttl: Duration) -> Self {
+        self.negative_ttl = ttl;
+        self
+    }
+
+    /// Number of answers currently stored, including expired ones not yet evicted
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes every answer
+    pub fn clear(&self) {
+        self.entries.clear()
+    }
+
+    fn get(&self, key: &str) -> Option<CachedAnswer> {
+        self.entries.get(key)
+    }
+
+    fn store<T>(&self, key: &str, answer: &Result<(Vec<T>, Option<Duration>), DnsError>, wrap: fn(Result<Vec<T>, DnsError>) -> CachedAnswer)
+    where
+        T: Clone
+    {
+        let (value, ttl) = match answer {
+            Ok((records, ttl)) => (Ok(records.clone()), ttl.unwrap_or(self.default_ttl)),
+            Err(error) if error.is_nx_domain() => (Err(error.clone()), self.negative_ttl),
+            Err(_) => return
+        };
+
+        let ttl = ttl.min(self.max_ttl);
+        if !ttl.is_zero() {
+            self.entries.insert(key, wrap(value), ttl);
+        }
+    }
+}
+
+impl Default for DnsCache {
+    fn default() -> Self {
+        DnsCache::new(10_000)
+    }
+}
+
+/// Resolver that answers repeated lookups from a `DnsCache`
+///
+/// Verifying many addresses at the same domain then costs one lookup per
+/// record type instead of one per address.
+#[derive(Debug, Clone)]
+pub struct CachingResolver<R> {
+    inner: R,
+    cache: DnsCache
+}
+
+impl<R: DnsResolver> CachingResolver<R> {
+    pub fn new(inner: R, cache: DnsCache) -> Self {
+        CachingResolver { inner, cache }
+    }
+
+    /// Returns the cache backing this resolver
+    pub fn cache(&self) -> &DnsCache {
+        &self.cache
+    }
+}
+
+impl<R: DnsResolver> DnsResolver for CachingResolver<R> {
+    async fn lookup_mx(&self, domain: &str) -> Result<Vec<MxRecord>, DnsError> {
+        Ok(self.lookup_mx_with_ttl(domain).await?.0)
+    }
+
+    async fn lookup_txt(&self, domain: &str) -> Result<Vec<String>, DnsError> {
+        Ok(self.lookup_txt_with_ttl(domain).await?.0)
+    }
+
+    /// A and TLSA answers carry no TTL through the trait, so they keep the default lifetime
+    async fn lookup_a(&self, name: &str) -> Result<Vec<Ipv4Addr>, DnsError> {
+        let key = format!("a:{}", normalize_name(name));
+        if let Some(CachedAnswer::A(answer)) = self.cache.get(&key) {
+            return answer;
+        }
+
+        let answer = self.inner.lookup_a(name).await.map(|records| (records, None));
+        self.cache.store(&key, &answer, CachedAnswer::A);
+        answer.map(|(records, _)| records)
+    }
+
+    async fn lookup_tlsa(&self, name: &str) -> Result<Vec<TlsaRecord>, DnsError> {
+        let key = format!("tlsa:{}", normalize_name(name));
+        if let Some(CachedAnswer::Tlsa(answer)) = self.cache.get(&key) {
+            return answer;
+        }
+
+        let answer = self.inner.lookup_tlsa(name).await.map(|records| (records, None));
+        self.cache.store(&key, &answer, CachedAnswer::Tlsa);
+        answer.map(|(records, _)| records)
+    }
+
+    /// Cached answers report no TTL since their remaining lifetime is not tracked
+    async fn lookup_mx_with_ttl(&self, domain: &str) -> Result<(Vec<MxRecord>, Option<Duration>), DnsError> {
+        let key = format!("mx:{}", normalize_name(domain));
+        if let Some(CachedAnswer::Mx(answer)) = self.cache.get(&key) {
+            return answer.map(|records| (records, None));
+        }
+
+        let answer = self.inner.lookup_mx_with_ttl(domain).await;
+        self.cache.store(&key, &answer, CachedAnswer::Mx);
+        answer
+    }
+
+    async fn lookup_txt_with_ttl(&self, domain: &str) -> Result<(Vec<String>, Option<Duration>), DnsError> {
+        let key = format!("txt:{}", normalize_name(domain));
+        if let Some(CachedAnswer::Txt(answer)) = self.cache.get(&key) {
+            return answer.map(|records| (records, None));
+        }
+
+        let answer = self.inner.lookup_txt_with_ttl(domain).await;
+        self.cache.store(&key, &answer, CachedAnswer::Txt);
+        answer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+    use crate::dns::MockResolver;
+    use crate::test_util::block_on;
+
+    /// Counts lookups and reports a fixed TTL
+    struct CountingResolver {
+        inner: MockResolver,
+        ttl: Option<Duration>,
+        lookups: RefCell<usize>
+    }
+
+    impl DnsResolver for CountingResolver {
+        async fn lookup_mx(&self, domain: &str) -> Result<Vec<MxRecord>, DnsError> {
+            Ok(self.lookup_mx_with_ttl(domain).await?.0)
+        }
+
+        async fn lookup_txt(&self, domain: &str) -> Result<Vec<String>, DnsError> {
+            Ok(self.lookup_txt_with_ttl(domain).await?.0)
+        }
+
+        async fn lookup_mx_with_ttl(&self, domain: &str) -> Result<(Vec<MxRecord>, Option<Duration>), DnsError> {
+            *self.lookups.borrow_mut() += 1;
+            Ok((self.inner.lookup_mx(domain).await?, self.ttl))
+        }
+
+        async fn lookup_txt_with_ttl(&self, domain: &str) -> Result<(Vec<String>, Option<Duration>), DnsError> {
+            *self.lookups.borrow_mut() += 1;
+            Ok((self.inner.lookup_txt(domain).await?, self.ttl))
+        }
+    }
+
+    fn counting(ttl: Option<Duration>) -> CountingResolver {
+        let inner = MockResolver::new()
+            .with_mx("example.com", vec![MxRecord { preference: 10, exchange: "mx.example.com".to_string() }])
+            .with_txt("example.com", vec!["v=spf1 -all".to_string()])
+            .with_error("broken.com", DnsError::new("Timeout", "DNS query timed out", None));
+        CountingResolver { inner, ttl, lookups: RefCell::new(0) }
+    }
+
+    /// Tests that repeated lookups hit the cache, including NXDOMAIN, while
+    /// failures and zero-TTL answers are looked up again
+    #[test]
+    fn test_caching_resolver() {
+        let cache = DnsCache::new(100);
+        let resolver = CachingResolver::new(counting(None), cache.clone());
+
+        for _ in 0..1_000 {
+            assert_eq!(block_on(resolver.lookup_mx("Example.com.")).unwrap().len(), 1);
+            assert_eq!(block_on(resolver.lookup_txt("example.com")).unwrap(), vec!["v=spf1 -all"]);
+            assert!(block_on(resolver.lookup_mx("missing.com")).unwrap_err().is_nx_domain());
+        }
+        assert_eq!(*resolver.inner.lookups.borrow(), 3);
+        assert_eq!(cache.len(), 3);
+
+        block_on(resolver.lookup_txt("broken.com")).unwrap_err();
+        block_on(resolver.lookup_txt("broken.com")).unwrap_err();
+        assert_eq!(*resolver.inner.lookups.borrow(), 5);
+
+        // Another resolver sharing the cache reuses its answers
+        let shared = CachingResolver::new(counting(None), cache);
+        block_on(shared.lookup_mx("example.com")).unwrap();
+        assert_eq!(*shared.inner.lookups.borrow(), 0);
+
+        let uncacheable = CachingResolver::new(counting(Some(Duration::ZERO)), DnsCache::default());
+        block_on(uncacheable.lookup_mx("example.com")).unwrap();
+        block_on(uncacheable.lookup_mx("example.com")).unwrap();
+        assert_eq!(*uncacheable.inner.lookups.borrow(), 2);
+    }
+
+    /// Tests that answers expire after their TTL
+    #[test]
+    fn test_caching_resolver_expiry() {
+        let resolver = CachingResolver::new(counting(Some(Duration::from_secs(3600))), DnsCache::new(10).with_max_ttl(Duration::from_millis(20)));
+
+        block_on(resolver.lookup_mx("example.com")).unwrap();
+        block_on(resolver.lookup_mx("example.com")).unwrap();
+        assert_eq!(*resolver.inner.lookups.borrow(), 1);
+
+        std::thread::sleep(Duration::from_millis(30));
+        block_on(resolver.lookup_mx("example.com")).unwrap();
+        assert_eq!(*resolver.inner.lookups.borrow(), 2);
+    }
+}
@@ -0,0 +1,177 @@
+use std::net::Ipv4Addr;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use super::{normalize_name, DnsError, DnsResolver, MxRecord, TlsaRecord};
+use crate::fetch::fetch_text;
+
+const TYPE_A: u16 = 1;
+const TYPE_MX: u16 = 15;
+const TYPE_TXT: u16 = 16;
+const TYPE_TLSA: u16 = 52;
+
+const STATUS_NO_ERROR: u8 = 0;
+const STATUS_NX_DOMAIN: u8 = 3;
+
+/// Resolver that queries a DNS-over-HTTPS endpoint using the JSON API
+/// (`application/dns-json`) through the host's `fetch`
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::dns::DohResolver;
+/// let resolver = DohResolver::new("https://dns.internal.example/dns-query");
+/// ```
+#[derive(Debug, Clone)]
+pub struct DohResolver {
+    endpoint: String
+}
+
+impl DohResolver {
+    pub fn new(endpoint: &str) -> Self {
+        DohResolver {
+            endpoint: endpoint.to_string()
+        }
+    }
+
+    async fn query(&self, name: &str, record_type: u16) -> Result<Vec<DohAnswer>, DnsError> {
+        let url = format!(
+            "{}?name={}&type={}",
+            self.endpoint,
+            js_sys::encode_uri_component(&normalize_name(name)),
+            record_type
+        );
+
+        let body = fetch_text(&url, "application/dns-json").await.map_err(|e| {
+            DnsError::new("IoError", "DNS-over-HTTPS request failed", e.as_string().or_else(|| js_sys::JSON::stringify(&e).ok().map(String::from)))
+        })?;
+
+        let response: DohResponse = serde_json::from_str(&body).map_err(|e| {
+            DnsError::new("MalformedResponse", "DNS server returned a malformed response", Some(e.to_string()))
+        })?;
+
+        match response.status {
+            STATUS_NO_ERROR => Ok(response.answer.into_iter().filter(|a| a.record_type == record_type).collect()),
+            STATUS_NX_DOMAIN => Err(DnsError::nx_domain(name)),
+            status => Err(DnsError::new("ServerFailure", "DNS server returned an error", Some(format!("rcode {}", status))))
+        }
+    }
+}
+
+impl Default for DohResolver {
+    fn default() -> Self {
+        DohResolver::new("https://cloudflare-dns.com/dns-query")
+    }
+}
+
+impl DnsResolver for DohResolver {
+    async fn lookup_mx(&self, domain: &str) -> Result<Vec<MxRecord>, DnsError> {
+        Ok(self.lookup_mx_with_ttl(domain).await?.0)
+    }
+
+    async fn lookup_txt(&self, domain: &str) -> Result<Vec<String>, DnsError> {
+        Ok(self.lookup_txt_with_ttl(domain).await?.0)
+    }
+
+    async fn lookup_a(&self, name: &str) -> Result<Vec<Ipv4Addr>, DnsError> {
+        self.query(name, TYPE_A).await?
+            .iter()
+            .map(|answer| answer.data.trim().parse().map_err(|_| {
+                DnsError::new("MalformedResponse", "DNS server returned a malformed response", Some(answer.data.clone()))
+            }))
+            .collect()
+    }
+
+    async fn lookup_tlsa(&self, name: &str) -> Result<Vec<TlsaRecord>, DnsError> {
+        self.query(name, TYPE_TLSA).await?
+            .iter()
+            .map(|answer| parse_tlsa(&answer.data).ok_or_else(|| {
+                DnsError::new("MalformedResponse", "DNS server returned a malformed response", Some(answer.data.clone()))
+            }))
+            .collect()
+    }
+
+    async fn lookup_mx_with_ttl(&self, domain: &str) -> Result<(Vec<MxRecord>, Option<Duration>), DnsError> {
+        let answers = self.query(domain, TYPE_MX).await?;
+        let records = answers
+            .iter()
+            .map(|answer| {
+                let (preference, exchange) = answer.data.trim().split_once(' ')
+                    .ok_or_else(|| DnsError::new("MalformedResponse", "DNS server returned a malformed response", Some(answer.data.clone())))?;
+                Ok(MxRecord {
+                    preference: preference.parse().map_err(|_| {
+                        DnsError::new("MalformedResponse", "DNS server returned a malformed response", Some(answer.data.clone()))
+                    })?,
+                    exchange: exchange.trim().trim_end_matches('.').to_string()
+                })
+            })
+            .collect::<Result<_, DnsError>>()?;
+        Ok((records, min_ttl(&answers)))
+    }
+
+    async fn lookup_txt_with_ttl(&self, domain: &str) -> Result<(Vec<String>, Option<Duration>), DnsError> {
+        let answers = self.query(domain, TYPE_TXT).await?;
+        let records = answers
+            .iter()
+            .map(|answer| unquote_txt(&answer.data))
+            .collect();
+        Ok((records, min_ttl(&answers)))
+    }
+}
+
+/// Smallest TTL among `answers`, or `None` for an empty answer
+fn min_ttl(answers: &[DohAnswer]) -> Option<Duration> {
+    answers.iter().map(|answer| answer.ttl).min().map(|ttl| Duration::from_secs(ttl.into()))
+}
+
+/// JSON response body of a DoH JSON API query
+#[derive(Debug, Deserialize)]
+struct DohResponse {
+    #[serde(rename = "Status")]
+    status: u8,
+    #[serde(rename = "Answer", default)]
+    answer: Vec<DohAnswer>
+}
+
+#[derive(Debug, Deserialize)]
+struct DohAnswer {
+    #[serde(rename = "type")]
+    record_type: u16,
+    #[serde(rename = "TTL", default)]
+    ttl: u32,
+    data: String
+}
+
+/// Parses TLSA presentation data, either `3 1 1 ab12...` or the generic
+/// `\# 35 03 01 01 ab12...` form some DoH servers return
+fn parse_tlsa(data: &str) -> Option<TlsaRecord> {
+    let mut fields = data.split_whitespace();
+    let (usage, selector, matching_type, data) = if data.starts_with("\\#") {
+        let hex: String = fields.skip(2).collect();
+        let byte = |i: usize| hex.get(i * 2..i * 2 + 2).and_then(|b| u8::from_str_radix(b, 16).ok());
+        (byte(0)?, byte(1)?, byte(2)?, hex.get(6..)?.to_string())
+    } else {
+        (fields.next()?.parse().ok()?, fields.next()?.parse().ok()?, fields.next()?.parse().ok()?, fields.collect())
+    };
+    Some(TlsaRecord { usage, selector, matching_type, data: data.to_lowercase() })
+}
+
+/// Joins the quoted character strings of a TXT answer (`"v=spf1 " "-all"`)
+fn unquote_txt(data: &str) -> String {
+    if !data.starts_with('"') {
+        return data.to_string();
+    }
+
+    let mut text = String::with_capacity(data.len());
+    let mut in_quotes = false;
+    let mut chars = data.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '\\' if in_quotes => text.extend(chars.next()),
+            c if in_quotes => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+use std::time::Duration;
+
+use serde::{Serialize, Deserialize};
+
+mod cache;
+#[cfg(target_arch = "wasm32")]
+mod doh;
+#[cfg(not(target_arch = "wasm32"))]
+mod system;
+
+pub use cache::{CachingResolver, DnsCache};
+#[cfg(target_arch = "wasm32")]
+pub use doh::DohResolver;
+#[cfg(not(target_arch = "wasm32"))]
+pub use system::SystemResolver;
+
+/// A mail exchanger (MX) record
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MxRecord {
+    /// Preference value, lower values are tried first
+    pub preference: u16,
+    /// Host name of the mail exchanger, without the trailing dot
+    pub exchange: String
+}
+
+/// A TLSA record (RFC 6698) binding a TLS certificate to a service, used by DANE
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TlsaRecord {
+    /// Certificate usage (e.g. 3 for DANE-EE)
+    pub usage: u8,
+    /// Which part of the certificate is matched (0 full, 1 public key)
+    pub selector: u8,
+    /// How the data is matched (0 exact, 1 SHA-256, 2 SHA-512)
+    pub matching_type: u8,
+    /// Certificate association data as lowercase hex
+    pub data: String
+}
+
+/// Error structure for DNS lookup failures
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DnsError {
+    /// Type of error that occurred (e.g., "NxDomain", "Timeout", "IoError")
+    pub error_type: String,
+    /// Human-readable error message
+    pub message: String,
+    /// Additional error details if available
+    pub details: Option<String>
+}
+
+impl DnsError {
+    pub(crate) fn new(error_type: &str, message: &str, details: Option<String>) -> Self {
+        DnsError {
+            error_type: error_type.to_string(),
+            message: message.to_string(),
+            details
+        }
+    }
+
+    /// Error returned when the queried name does not exist
+    pub fn nx_domain(domain: &str) -> Self {
+        DnsError::new("NxDomain", "Domain does not exist", Some(domain.to_string()))
+    }
+
+    /// Whether the queried name does not exist (as opposed to a lookup failure)
+    pub fn is_nx_domain(&self) -> bool {
+        self.error_type == "NxDomain"
+    }
+}
+
+impl std::fmt::Display for DnsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}", self.error_type, self.message)
+    }
+}
+
+/// Source of DNS answers for the verification pipeline
+/// 
+/// Implementations must return `DnsError::nx_domain` when the queried name does
+/// not exist and an empty list when it exists but has no records of the type.
+// The futures are intentionally not `Send`: the wasm resolver holds JS values.
+#[allow(async_fn_in_trait)]
+pub trait DnsResolver {
+    /// Looks up the MX records of `domain`
+    async fn lookup_mx(&self, domain: &str) -> Result<Vec<MxRecord>, DnsError>;
+
+    /// Looks up the TXT records of `domain`, with multi-string records joined
+    async fn lookup_txt(&self, domain: &str) -> Result<Vec<String>, DnsError>;
+
+    /// Looks up the IPv4 addresses (A records) of `name`, as used by DNS blocklists
+    /// 
+    /// The default fails with "Unsupported".
+    async fn lookup_a(&self, name: &str) -> Result<Vec<Ipv4Addr>, DnsError> {
+        Err(DnsError::new("Unsupported", "Resolver does not support A lookups", Some(name.to_string())))
+    }
+
+    /// Looks up the TLSA records at `name` (e.g. `_25._tcp.mx.example.com`)
+    /// 
+    /// The default fails with "Unsupported", for resolvers that only answer
+    /// MX and TXT queries.
+    async fn lookup_tlsa(&self, name: &str) -> Result<Vec<TlsaRecord>, DnsError> {
+        Err(DnsError::new("Unsupported", "Resolver does not support TLSA lookups", Some(name.to_string())))
+    }
+
+    /// Looks up the MX records of `domain` along with the answer's TTL
+    /// 
+    /// Resolvers that see the wire answer report the smallest record TTL; the
+    /// default reports `None`, leaving the caching lifetime to the caller.
+    async fn lookup_mx_with_ttl(&self, domain: &str) -> Result<(Vec<MxRecord>, Option<Duration>), DnsError> {
+        Ok((self.lookup_mx(domain).await?, None))
+    }
+
+    /// Looks up the TXT records of `domain` along with the answer's TTL
+    async fn lookup_txt_with_ttl(&self, domain: &str) -> Result<(Vec<String>, Option<Duration>), DnsError> {
+        Ok((self.lookup_txt(domain).await?, None))
+    }
+}
+
+impl<T: DnsResolver + ?Sized> DnsResolver for &T {
+    async fn lookup_mx(&self, domain: &str) -> Result<Vec<MxRecord>, DnsError> {
+        (**self).lookup_mx(domain).await
+    }
+
+    async fn lookup_txt(&self, domain: &str) -> Result<Vec<String>, DnsError> {
+        (**self).lookup_txt(domain).await
+    }
+
+    async fn lookup_a(&self, name: &str) -> Result<Vec<Ipv4Addr>, DnsError> {
+        (**self).lookup_a(name).await
+    }
+
+    async fn lookup_tlsa(&self, name: &str) -> Result<Vec<TlsaRecord>, DnsError> {
+        (**self).lookup_tlsa(name).await
+    }
+
+    async fn lookup_mx_with_ttl(&self, domain: &str) -> Result<(Vec<MxRecord>, Option<Duration>), DnsError> {
+        (**self).lookup_mx_with_ttl(domain).await
+    }
+
+    async fn lookup_txt_with_ttl(&self, domain: &str) -> Result<(Vec<String>, Option<Duration>), DnsError> {
+        (**self).lookup_txt_with_ttl(domain).await
+    }
+}
+
+/// In-memory resolver returning canned answers, for tests
+/// 
+/// Names without any configured answer resolve to `NxDomain`; names with only
+/// some record types configured return empty lists for the others.
+/// 
+/// # Examples
+/// ```
+/// # use email_validator_wasm::dns::{MockResolver, MxRecord};
+/// let resolver = MockResolver::new()
+///     .with_mx("example.com", vec![MxRecord { preference: 10, exchange: "mx.example.com".to_string() }])
+///     .with_txt("example.com", vec!["v=spf1 -all".to_string()]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockResolver {
+    mx: HashMap<String, Vec<MxRecord>>,
+    txt: HashMap<String, Vec<String>>,
+    tlsa: HashMap<String, Vec<TlsaRecord>>,
+    a: HashMap<String, Vec<Ipv4Addr>>,
+    errors: HashMap<String, DnsError>
+}
+
+impl MockResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the MX records returned for `domain`
+    pub fn with_mx(mut self, domain: &str, records: Vec<MxRecord>) -> Self {
+        self.mx.insert(normalize_name(domain), records);
+        self
+    }
+
+    /// Sets the TXT records returned for `domain`
+    pub fn with_txt(mut self, domain: &str, records: Vec<String>) -> Self {
+        self.txt.insert(normalize_name(domain), records);
+        self
+    }
+
+    /// Sets the A records returned for `name`
+    pub fn with_a(mut self, name: &str, records: Vec<Ipv4Addr>) -> Self {
+        self.a.insert(normalize_name(name), records);
+        self
+    }
+
+    /// Sets the TLSA records returned for `name`
+    pub fn with_tlsa(mut self, name: &str, records: Vec<TlsaRecord>) -> Self {
+        self.tlsa.insert(normalize_name(name), records);
+        self
+    }
+
+    /// Makes every lookup for `domain` fail with `error`
+    pub fn with_error(mut self, domain: &str, error: DnsError) -> Self {
+        self.errors.insert(normalize_name(domain), error);
+        self
+    }
+
+    fn lookup<T: Clone>(&self, records: &HashMap<String, Vec<T>>, domain: &str) -> Result<Vec<T>, DnsError> {
+        let name = normalize_name(domain);
+        if let Some(error) = self.errors.get(&name) {
+            return Err(error.clone());
+        }
+
+        if !self.mx.contains_key(&name) && !self.txt.contains_key(&name) && !self.tlsa.contains_key(&name) && !self.a.contains_key(&name) {
+            return Err(DnsError::nx_domain(domain));
+        }
+
+        Ok(records.get(&name).cloned().unwrap_or_default())
+    }
+}
+
+impl DnsResolver for MockResolver {
+    async fn lookup_mx(&self, domain: &str) -> Result<Vec<MxRecord>, DnsError> {
+        self.lookup(&self.mx, domain)
+    }
+
+    async fn lookup_txt(&self, domain: &str) -> Result<Vec<String>, DnsError> {
+        self.lookup(&self.txt, domain)
+    }
+
+    async fn lookup_a(&self, name: &str) -> Result<Vec<Ipv4Addr>, DnsError> {
+        self.lookup(&self.a, name)
+    }
+
+    async fn lookup_tlsa(&self, name: &str) -> Result<Vec<TlsaRecord>, DnsError> {
+        self.lookup(&self.tlsa, name)
+    }
+}
+
+/// Lowercases a DNS name and strips the trailing root dot
+pub(crate) fn normalize_name(name: &str) -> String {
+    name.trim_end_matches('.').to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::block_on;
+
+    /// Tests canned answers, missing record types and NXDOMAIN in the mock resolver
+    #[test]
+    fn test_mock_resolver() {
+        let mx = MxRecord { preference: 10, exchange: "mx.example.com".to_string() };
+        let resolver = MockResolver::new()
+            .with_mx("Example.com.", vec![mx.clone()])
+            .with_error("broken.com", DnsError::new("Timeout", "DNS query timed out", None));
+
+        assert_eq!(block_on(resolver.lookup_mx("example.com")), Ok(vec![mx]));
+        assert_eq!(block_on(resolver.lookup_txt("example.com")), Ok(vec![]));
+        assert!(block_on(resolver.lookup_mx("missing.com")).unwrap_err().is_nx_domain());
+        assert_eq!(block_on(resolver.lookup_txt("broken.com")).unwrap_err().error_type, "Timeout");
+    }
+}
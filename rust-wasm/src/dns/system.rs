@@ -0,0 +1,444 @@
+use std::io::{Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpStream, UdpSocket};
+use std::time::Duration;
+
+use super::{normalize_name, DnsError, DnsResolver, MxRecord, TlsaRecord};
+use crate::blocking;
+use crate::proxy::Proxy;
+
+const TYPE_A: u16 = 1;
+const TYPE_MX: u16 = 15;
+const TYPE_TXT: u16 = 16;
+const TYPE_TLSA: u16 = 52;
+const CLASS_IN: u16 = 1;
+
+const RCODE_NO_ERROR: u8 = 0;
+const RCODE_NX_DOMAIN: u8 = 3;
+
+/// Resolver that queries a DNS server directly over UDP (with TCP fallback
+/// for truncated answers)
+///
+/// Each lookup runs its blocking socket exchange on a worker thread, so
+/// awaiting it does not stall the executor; the worker gives up when the
+/// server answers or the per-query timeout expires.
+///
+/// # Examples
+/// ```no_run
+/// # use email_validator_wasm::dns::SystemResolver;
+/// # use std::time::Duration;
+/// // Use the nameserver configured in /etc/resolv.conf
+/// let resolver = SystemResolver::from_system_config()?;
+///
+/// // Or point lookups at a specific resolver
+/// let resolver = SystemResolver::new("10.0.0.53:53".parse().unwrap())
+///     .with_timeout(Duration::from_secs(2));
+/// # Ok::<(), email_validator_wasm::dns::DnsError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct SystemResolver {
+    nameserver: SocketAddr,
+    timeout: Duration,
+    proxy: Option<Proxy>
+}
+
+impl SystemResolver {
+    pub fn new(nameserver: SocketAddr) -> Self {
+        SystemResolver {
+            nameserver,
+            timeout: Duration::from_secs(5),
+            proxy: None
+        }
+    }
+
+    /// Creates a resolver using the first nameserver listed in `/etc/resolv.conf`
+    pub fn from_system_config() -> Result<Self, DnsError> {
+        let config = std::fs::read_to_string("/etc/resolv.conf").map_err(|e| {
+            DnsError::new("ConfigError", "Failed to read /etc/resolv.conf", Some(e.to_string()))
+        })?;
+
+        config
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("nameserver"))
+            .filter_map(|address| address.trim().parse().ok())
+            .map(|ip| SystemResolver::new(SocketAddr::new(ip, 53)))
+            .next()
+            .ok_or_else(|| DnsError::new("ConfigError", "No nameserver found in /etc/resolv.conf", None))
+    }
+
+    /// Sets the per-query timeout (default 5 seconds)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sends queries over TCP through `proxy` (UDP cannot be tunnelled), so
+    /// lookups work where all egress must go through a proxy
+    pub fn with_proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Runs `query_blocking` on a worker thread
+    async fn query(&self, name: &str, record_type: u16) -> Result<Vec<Record>, DnsError> {
+        let resolver = self.clone();
+        let name = name.to_string();
+        blocking::run(move || resolver.query_blocking(&name, record_type)).await
+    }
+
+    fn query_blocking(&self, name: &str, record_type: u16) -> Result<Vec<Record>, DnsError> {
+        let id = query_id();
+        let query = build_query(id, name, record_type)?;
+
+        let mut response = match self.proxy {
+            Some(_) => self.exchange_tcp(&query, id)?,
+            None => self.exchange_udp(&query, id)?
+        };
+        if response.truncated && self.proxy.is_none() {
+            response = self.exchange_tcp(&query, id)?;
+        }
+
+        match response.rcode {
+            RCODE_NO_ERROR => Ok(response.answers.into_iter().filter(|r| r.record_type == record_type).collect()),
+            RCODE_NX_DOMAIN => Err(DnsError::nx_domain(name)),
+            rcode => Err(DnsError::new("ServerFailure", "DNS server returned an error", Some(format!("rcode {}", rcode))))
+        }
+    }
+
+    fn exchange_udp(&self, query: &[u8], id: u16) -> Result<Response, DnsError> {
+        let bind_address = if self.nameserver.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+        let socket = UdpSocket::bind(bind_address).map_err(io_error)?;
+        socket.set_read_timeout(Some(self.timeout)).map_err(io_error)?;
+        socket.connect(self.nameserver).map_err(io_error)?;
+        socket.send(query).map_err(io_error)?;
+
+        let mut buffer = [0u8; 4096];
+        loop {
+            let len = socket.recv(&mut buffer).map_err(io_error)?;
+            // Ignore stray datagrams that do not answer our query
+            if let Some(response) = parse_response(&buffer[..len], id)? {
+                return Ok(response);
+            }
+        }
+    }
+
+    fn exchange_tcp(&self, query: &[u8], id: u16) -> Result<Response, DnsError> {
+        let mut stream = match &self.proxy {
+            Some(proxy) => proxy.connect(&self.nameserver.ip().to_string(), self.nameserver.port(), self.timeout).map_err(io_error)?,
+            None => {
+                let stream = TcpStream::connect_timeout(&self.nameserver, self.timeout).map_err(io_error)?;
+                stream.set_read_timeout(Some(self.timeout)).map_err(io_error)?;
+                stream.set_write_timeout(Some(self.timeout)).map_err(io_error)?;
+                stream
+            }
+        };
+
+        stream.write_all(&(query.len() as u16).to_be_bytes()).map_err(io_error)?;
+        stream.write_all(query).map_err(io_error)?;
+
+        let mut len = [0u8; 2];
+        stream.read_exact(&mut len).map_err(io_error)?;
+        let mut buffer = vec![0u8; u16::from_be_bytes(len) as usize];
+        stream.read_exact(&mut buffer).map_err(io_error)?;
+
+        parse_response(&buffer, id)?.ok_or_else(|| malformed("Response ID does not match query"))
+    }
+}
+
+impl DnsResolver for SystemResolver {
+    async fn lookup_mx(&self, domain: &str) -> Result<Vec<MxRecord>, DnsError> {
+        Ok(self.lookup_mx_with_ttl(domain).await?.0)
+    }
+
+    async fn lookup_txt(&self, domain: &str) -> Result<Vec<String>, DnsError> {
+        Ok(self.lookup_txt_with_ttl(domain).await?.0)
+    }
+
+    async fn lookup_a(&self, name: &str) -> Result<Vec<Ipv4Addr>, DnsError> {
+        Ok(self.query(name, TYPE_A).await?
+            .into_iter()
+            .filter_map(|record| match record.data {
+                RecordData::A(address) => Some(address),
+                _ => None
+            })
+            .collect())
+    }
+
+    async fn lookup_tlsa(&self, name: &str) -> Result<Vec<TlsaRecord>, DnsError> {
+        Ok(self.query(name, TYPE_TLSA).await?
+            .into_iter()
+            .filter_map(|record| match record.data {
+                RecordData::Tlsa(tlsa) => Some(tlsa),
+                _ => None
+            })
+            .collect())
+    }
+
+    async fn lookup_mx_with_ttl(&self, domain: &str) -> Result<(Vec<MxRecord>, Option<Duration>), DnsError> {
+        let records = self.query(domain, TYPE_MX).await?;
+        let ttl = min_ttl(&records);
+        Ok((records
+            .into_iter()
+            .filter_map(|record| match record.data {
+                RecordData::Mx(mx) => Some(mx),
+                _ => None
+            })
+            .collect(), ttl))
+    }
+
+    async fn lookup_txt_with_ttl(&self, domain: &str) -> Result<(Vec<String>, Option<Duration>), DnsError> {
+        let records = self.query(domain, TYPE_TXT).await?;
+        let ttl = min_ttl(&records);
+        Ok((records
+            .into_iter()
+            .filter_map(|record| match record.data {
+                RecordData::Txt(txt) => Some(txt),
+                _ => None
+            })
+            .collect(), ttl))
+    }
+}
+
+/// Smallest TTL among `records`, or `None` for an empty answer
+fn min_ttl(records: &[Record]) -> Option<Duration> {
+    records.iter().map(|record| record.ttl).min().map(|ttl| Duration::from_secs(ttl.into()))
+}
+
+/// A parsed DNS response
+#[derive(Debug)]
+struct Response {
+    truncated: bool,
+    rcode: u8,
+    answers: Vec<Record>
+}
+
+/// A single resource record from the answer section
+#[derive(Debug)]
+struct Record {
+    record_type: u16,
+    /// Seconds the record may be cached
+    ttl: u32,
+    data: RecordData
+}
+
+#[derive(Debug)]
+enum RecordData {
+    A(Ipv4Addr),
+    Mx(MxRecord),
+    Txt(String),
+    Tlsa(TlsaRecord),
+    Other
+}
+
+fn io_error(e: std::io::Error) -> DnsError {
+    if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) {
+        DnsError::new("Timeout", "DNS query timed out", Some(e.to_string()))
+    } else {
+        DnsError::new("IoError", "DNS query failed", Some(e.to_string()))
+    }
+}
+
+fn malformed(details: &str) -> DnsError {
+    DnsError::new("MalformedResponse", "DNS server returned a malformed response", Some(details.to_string()))
+}
+
+/// Generates a query ID that is hard for off-path attackers to guess
+fn query_id() -> u16 {
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default());
+    hasher.finish() as u16
+}
+
+/// Builds a recursive query for `name` with a single question
+fn build_query(id: u16, name: &str, record_type: u16) -> Result<Vec<u8>, DnsError> {
+    let mut packet = Vec::with_capacity(512);
+    packet.extend_from_slice(&id.to_be_bytes());
+    packet.extend_from_slice(&0x0100u16.to_be_bytes()); // Recursion desired
+    packet.extend_from_slice(&1u16.to_be_bytes()); // QDCOUNT
+    packet.extend_from_slice(&[0; 6]); // ANCOUNT, NSCOUNT, ARCOUNT
+
+    for label in normalize_name(name).split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(DnsError::new("InvalidName", "Invalid DNS name", Some(name.to_string())));
+        }
+        packet.push(label.len() as u8);
+        packet.extend_from_slice(label.as_bytes());
+    }
+    packet.push(0);
+
+    packet.extend_from_slice(&record_type.to_be_bytes());
+    packet.extend_from_slice(&CLASS_IN.to_be_bytes());
+    Ok(packet)
+}
+
+/// Parses a response packet, returning `None` if it does not answer query `id`
+fn parse_response(packet: &[u8], id: u16) -> Result<Option<Response>, DnsError> {
+    if packet.len() < 12 {
+        return Err(malformed("Packet shorter than DNS header"));
+    }
+    if read_u16(packet, 0)? != id {
+        return Ok(None);
+    }
+
+    let flags = read_u16(packet, 2)?;
+    let question_count = read_u16(packet, 4)?;
+    let answer_count = read_u16(packet, 6)?;
+
+    let mut offset = 12;
+    for _ in 0..question_count {
+        let (_, next) = read_name(packet, offset)?;
+        offset = next + 4; // QTYPE, QCLASS
+    }
+
+    let mut answers = Vec::with_capacity(answer_count as usize);
+    for _ in 0..answer_count {
+        let (_, next) = read_name(packet, offset)?;
+        let record_type = read_u16(packet, next)?;
+        let ttl = read_u32(packet, next + 4)?;
+        let data_len = read_u16(packet, next + 8)? as usize;
+        let data_start = next + 10;
+        let data_end = data_start + data_len;
+        if data_end > packet.len() {
+            return Err(malformed("Record data exceeds packet length"));
+        }
+
+        let data = match record_type {
+            TYPE_A if data_len == 4 => RecordData::A(Ipv4Addr::new(packet[data_start], packet[data_start + 1], packet[data_start + 2], packet[data_start + 3])),
+            TYPE_MX => RecordData::Mx(MxRecord {
+                preference: read_u16(packet, data_start)?,
+                exchange: read_name(packet, data_start + 2)?.0
+            }),
+            TYPE_TXT => RecordData::Txt(read_character_strings(&packet[data_start..data_end])?),
+            TYPE_TLSA if data_len >= 3 => RecordData::Tlsa(TlsaRecord {
+                usage: packet[data_start],
+                selector: packet[data_start + 1],
+                matching_type: packet[data_start + 2],
+                data: packet[data_start + 3..data_end].iter().map(|byte| format!("{:02x}", byte)).collect()
+            }),
+            _ => RecordData::Other
+        };
+
+        answers.push(Record { record_type, ttl, data });
+        offset = data_end;
+    }
+
+    Ok(Some(Response {
+        truncated: flags & 0x0200 != 0,
+        rcode: (flags & 0x000f) as u8,
+        answers
+    }))
+}
+
+fn read_u16(packet: &[u8], offset: usize) -> Result<u16, DnsError> {
+    packet
+        .get(offset..offset + 2)
+        .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+        .ok_or_else(|| malformed("Unexpected end of packet"))
+}
+
+fn read_u32(packet: &[u8], offset: usize) -> Result<u32, DnsError> {
+    packet
+        .get(offset..offset + 4)
+        .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .ok_or_else(|| malformed("Unexpected end of packet"))
+}
+
+/// Reads a possibly compressed name, returning it and the offset just past it
+fn read_name(packet: &[u8], offset: usize) -> Result<(String, usize), DnsError> {
+    let mut labels = Vec::new();
+    let mut position = offset;
+    let mut end = None;
+    let mut jumps = 0;
+
+    loop {
+        let len = *packet.get(position).ok_or_else(|| malformed("Unexpected end of packet"))? as usize;
+        match len {
+            0 => {
+                end.get_or_insert(position + 1);
+                break;
+            }
+            len if len & 0xc0 == 0xc0 => {
+                jumps += 1;
+                if jumps > 16 {
+                    return Err(malformed("Too many compression pointers"));
+                }
+                end.get_or_insert(position + 2);
+                position = (read_u16(packet, position)? & 0x3fff) as usize;
+            }
+            len => {
+                let label = packet
+                    .get(position + 1..position + 1 + len)
+                    .ok_or_else(|| malformed("Label exceeds packet length"))?;
+                labels.push(String::from_utf8_lossy(label).into_owned());
+                position += 1 + len;
+            }
+        }
+    }
+
+    Ok((labels.join("."), end.unwrap_or(position)))
+}
+
+/// Joins the length-prefixed character strings of a TXT record
+fn read_character_strings(data: &[u8]) -> Result<String, DnsError> {
+    let mut text = Vec::with_capacity(data.len());
+    let mut position = 0;
+    while position < data.len() {
+        let len = data[position] as usize;
+        let chunk = data
+            .get(position + 1..position + 1 + len)
+            .ok_or_else(|| malformed("TXT string exceeds record length"))?;
+        text.extend_from_slice(chunk);
+        position += 1 + len;
+    }
+    Ok(String::from_utf8_lossy(&text).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests query encoding and parsing of a compressed MX/TXT response
+    #[test]
+    fn test_wire_format() {
+        let query = build_query(0x1234, "Example.com.", TYPE_MX).unwrap();
+        assert_eq!(&query[..2], &[0x12, 0x34]);
+        assert_eq!(&query[12..25], b"\x07example\x03com\x00");
+        assert_eq!(&query[25..], &[0, 15, 0, 1]);
+
+        let mut response = query.clone();
+        response[2] = 0x81; // QR, RD
+        response[3] = 0x80; // RA, rcode 0
+        response[7] = 2; // ANCOUNT
+        // MX 10 mx.example.com, with the owner and exchange suffix compressed
+        response.extend_from_slice(&[0xc0, 12, 0, 15, 0, 1, 0, 0, 0x0e, 0x10, 0, 7, 0, 10, 2, b'm', b'x', 0xc0, 12]);
+        // TXT split across two character strings
+        response.extend_from_slice(&[0xc0, 12, 0, 16, 0, 1, 0, 0, 0x0e, 0x10, 0, 10]);
+        response.extend_from_slice(b"\x04v=sp\x04f1 -");
+
+        assert!(parse_response(&response, 0x4321).unwrap().is_none());
+
+        let parsed = parse_response(&response, 0x1234).unwrap().unwrap();
+        assert!(!parsed.truncated);
+        assert_eq!(parsed.rcode, RCODE_NO_ERROR);
+        assert_eq!(parsed.answers.len(), 2);
+        assert_eq!(min_ttl(&parsed.answers), Some(Duration::from_secs(3600)));
+        match &parsed.answers[0].data {
+            RecordData::Mx(mx) => {
+                assert_eq!(mx.preference, 10);
+                assert_eq!(mx.exchange, "mx.example.com");
+            }
+            other => panic!("expected MX record, got {:?}", other)
+        }
+        match &parsed.answers[1].data {
+            RecordData::Txt(txt) => assert_eq!(txt, "v=spf1 -"),
+            other => panic!("expected TXT record, got {:?}", other)
+        }
+
+        assert!(build_query(1, "bad..name", TYPE_MX).is_err());
+        assert!(parse_response(&response[..20], 0x1234).is_err());
+    }
+}
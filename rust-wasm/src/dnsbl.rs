@@ -0,0 +1,110 @@
+//! Domain reputation from DNS blocklists (DBL-style)
+//!
+//! A domain is checked against a list by resolving `<domain>.<zone>`: an A
+//! record in `127.0.0.0/8` means it is listed, `NxDomain` means it is not.
+
+use std::net::Ipv4Addr;
+
+use serde::{Serialize, Deserialize};
+
+use crate::dns::DnsResolver;
+
+/// A domain blocklist queried over DNS
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::{Blocklist, SandboxResolver, Verifier};
+/// # let resolver = SandboxResolver::new();
+/// let lists = vec![Blocklist::spamhaus_dbl(), Blocklist::new("Internal", "dbl.internal.example")];
+/// let verifier = Verifier::new(resolver).with_blocklists(lists);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Blocklist {
+    /// Name reported in `blocklist` when the domain is listed
+    pub name: String,
+    /// DNS zone the domain is prepended to (e.g. "dbl.spamhaus.org")
+    pub zone: String
+}
+
+impl Blocklist {
+    pub fn new(name: &str, zone: &str) -> Self {
+        Blocklist {
+            name: name.to_string(),
+            zone: zone.trim_end_matches('.').to_string()
+        }
+    }
+
+    /// Spamhaus Domain Block List
+    ///
+    /// Spamhaus refuses queries relayed through large public resolvers; use
+    /// your own recursive resolver or a Data Query Service zone.
+    pub fn spamhaus_dbl() -> Self {
+        Blocklist::new("Spamhaus DBL", "dbl.spamhaus.org")
+    }
+
+    /// SURBL multi list
+    pub fn surbl() -> Self {
+        Blocklist::new("SURBL", "multi.surbl.org")
+    }
+
+    /// URIBL multi list
+    pub fn uribl() -> Self {
+        Blocklist::new("URIBL", "multi.uribl.com")
+    }
+}
+
+/// Whether a blocklist answer means "listed" rather than a refusal or error
+///
+/// Lists answer in `127.0.0.0/8`; `127.0.0.1` (URIBL) and `127.255.255.0/24`
+/// (Spamhaus) report refused or rate-limited queries instead.
+fn is_listing(address: &Ipv4Addr) -> bool {
+    let [a, b, c, d] = address.octets();
+    a == 127 && !(b == 255 && c == 255) && (b, c, d) != (0, 0, 1)
+}
+
+/// Checks `domain` against each list in order
+///
+/// Returns whether the domain is listed and the name of the first list that
+/// lists it. The result is `None` when no list lists the domain but at least
+/// one could not be queried.
+pub(crate) async fn check_blocklists<R: DnsResolver>(resolver: &R, domain: &str, lists: &[Blocklist]) -> (Option<bool>, Option<String>) {
+    let mut all_answered = true;
+    for list in lists {
+        match resolver.lookup_a(&format!("{}.{}", domain.trim_end_matches('.'), list.zone)).await {
+            Ok(addresses) if addresses.iter().any(is_listing) => return (Some(true), Some(list.name.clone())),
+            Ok(addresses) if addresses.is_empty() => {}
+            Ok(_) => all_answered = false,
+            Err(e) if e.is_nx_domain() => {}
+            Err(_) => all_answered = false
+        }
+    }
+    (all_answered.then_some(false), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::{DnsError, MockResolver};
+    use crate::test_util::block_on;
+
+    /// Tests listings, refusals and unreachable lists
+    #[test]
+    fn test_check_blocklists() {
+        let resolver = MockResolver::new()
+            .with_a("spammy.com.dbl.spamhaus.org", vec![Ipv4Addr::new(127, 0, 1, 2)])
+            .with_a("refused.com.dbl.spamhaus.org", vec![Ipv4Addr::new(127, 255, 255, 254)])
+            .with_a("spammy.com.multi.uribl.com", vec![Ipv4Addr::new(127, 0, 0, 2)])
+            .with_a("ratelimited.com.multi.uribl.com", vec![Ipv4Addr::new(127, 0, 0, 1)])
+            .with_error("broken.com.dbl.spamhaus.org", DnsError::new("Timeout", "DNS query timed out", None));
+        let lists = [Blocklist::spamhaus_dbl(), Blocklist::uribl()];
+        let check = |domain: &str| block_on(check_blocklists(&resolver, domain, &lists));
+
+        assert_eq!(check("spammy.com"), (Some(true), Some("Spamhaus DBL".to_string())));
+        assert_eq!(check("clean.com"), (Some(false), None));
+        assert_eq!(check("refused.com"), (None, None));
+        assert_eq!(check("ratelimited.com"), (None, None));
+        assert_eq!(check("broken.com"), (None, None));
+        assert_eq!(block_on(check_blocklists(&resolver, "spammy.com", &[Blocklist::uribl()])), (Some(true), Some("URIBL".to_string())));
+    }
+}
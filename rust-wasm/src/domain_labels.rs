@@ -0,0 +1,178 @@
+//! Label-by-label domain checks
+//!
+//! The address parser rejects a malformed domain as a whole. These checks
+//! look at each dot-separated label on its own, so the error can say which
+//! label is broken and why, and add the DNS length limits and `xn--`
+//! decodability the parser does not check.
+
+use crate::{i18n, punycode, EmailParseError, Locale};
+
+/// Longest domain, in octets
+const MAX_DOMAIN_LENGTH: usize = 255;
+
+/// Longest label, in octets
+const MAX_LABEL_LENGTH: usize = 63;
+
+/// What is wrong with a single label
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LabelProblem {
+    /// Nothing between two dots, or a leading or trailing dot
+    Empty,
+    /// Longer than `MAX_LABEL_LENGTH` octets
+    TooLong,
+    LeadingHyphen,
+    TrailingHyphen,
+    /// An `xn--` label that does not decode to an internationalized label
+    InvalidPunycode
+}
+
+/// Why a domain failed `check_domain`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DomainError {
+    /// The whole domain is longer than `MAX_DOMAIN_LENGTH` octets
+    TooLong { length: usize },
+    /// The label at `index` (0-based, leftmost first) is invalid
+    Label { index: usize, label: String, problem: LabelProblem }
+}
+
+/// Whether an `xn--` label decodes to a label with non-ASCII characters;
+/// one that decodes to plain ASCII should have been written as such
+fn is_valid_a_label(encoded: &str) -> bool {
+    punycode::decode(encoded).is_some_and(|decoded| !decoded.is_empty() && !decoded.is_ascii())
+}
+
+/// The first problem with `label`, if any
+fn label_problem(label: &str) -> Option<LabelProblem> {
+    if label.is_empty() {
+        return Some(LabelProblem::Empty);
+    }
+    if label.len() > MAX_LABEL_LENGTH {
+        return Some(LabelProblem::TooLong);
+    }
+    if label.starts_with('-') {
+        return Some(LabelProblem::LeadingHyphen);
+    }
+    if label.ends_with('-') {
+        return Some(LabelProblem::TrailingHyphen);
+    }
+    match label.get(..punycode::ACE_PREFIX.len()) {
+        Some(prefix) if prefix.eq_ignore_ascii_case(punycode::ACE_PREFIX) && !is_valid_a_label(&label[prefix.len()..]) => {
+            Some(LabelProblem::InvalidPunycode)
+        }
+        _ => None
+    }
+}
+
+/// Checks the overall length of `domain`, then each label in turn
+///
+/// Only length, hyphen placement and punycode are checked; which characters
+/// a label may contain is left to the parser.
+pub(crate) fn check_domain(domain: &str) -> Result<(), DomainError> {
+    if domain.len() > MAX_DOMAIN_LENGTH {
+        return Err(DomainError::TooLong { length: domain.len() });
+    }
+    for (index, label) in domain.split('.').enumerate() {
+        if let Some(problem) = label_problem(label) {
+            return Err(DomainError::Label { index, label: label.to_string(), problem });
+        }
+    }
+    Ok(())
+}
+
+/// The localized message for the first label problem in `domain`
+///
+/// Domain literals and an empty domain are left to the parser's "Invalid
+/// email format".
+pub(crate) fn domain_error_message(domain: &str, locale: Locale) -> Option<String> {
+    if domain.is_empty() || domain.starts_with('[') {
+        return None;
+    }
+    check_domain(domain).err().map(|error| i18n::domain_error(locale, &error))
+}
+
+/// Validates each label of `domain` separately
+///
+/// A domain may be at most 255 octets. Each label must be 1 to 63 octets,
+/// must not start or end with a hyphen, and, if it starts with `xn--`, must
+/// be valid punycode for an internationalized label. The error names the
+/// first offending label by its 0-based index.
+///
+/// # Arguments
+/// * `domain` - The domain to check, without the local part or `@`
+///
+/// # Returns
+/// * `Result<(), EmailParseError>` - Ok, or an "InvalidDomainLabel" or
+///   "DomainTooLong" error; for a label, `details` gives its index and
+///   `position` the character offset where it starts
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::validate_domain_labels;
+/// assert!(validate_domain_labels("mail.example.com").is_ok());
+/// let error = validate_domain_labels("mail.-example.com").unwrap_err();
+/// assert_eq!(error.message, "Domain label 1 (\"-example\") starts with a hyphen");
+/// assert_eq!(error.position, Some(5));
+/// ```
+pub fn validate_domain_labels(domain: &str) -> Result<(), EmailParseError> {
+    check_domain(domain).map_err(|error| {
+        let message = i18n::domain_error(Locale::En, &error);
+        match error {
+            DomainError::TooLong { length } => EmailParseError {
+                error_type: "DomainTooLong".to_string(),
+                message,
+                details: Some(format!("{} octets", length)),
+                position: None
+            },
+            DomainError::Label { index, .. } => {
+                let start: usize = domain.split('.').take(index).map(|label| label.chars().count() + 1).sum();
+                EmailParseError {
+                    error_type: "InvalidDomainLabel".to_string(),
+                    message,
+                    details: Some(format!("Label index {}", index)),
+                    position: Some(start)
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that each label problem is reported with the index of the first bad label
+    #[test]
+    fn test_check_domain() {
+        for domain in ["example.com", "mail.example.co.uk", "xn--ggle-55da.com", "XN--GGLE-55DA.com", "a-b.c-d"] {
+            assert_eq!(check_domain(domain), Ok(()), "{}", domain);
+        }
+        assert_eq!(check_domain(&format!("{}.com", "a".repeat(63))), Ok(()));
+
+        let problem = |domain: &str| match check_domain(domain) {
+            Err(DomainError::Label { index, problem, .. }) => Some((index, problem)),
+            _ => None
+        };
+        assert_eq!(problem("example..com"), Some((1, LabelProblem::Empty)));
+        assert_eq!(problem(".example.com"), Some((0, LabelProblem::Empty)));
+        assert_eq!(problem(&format!("mail.{}.com", "a".repeat(64))), Some((1, LabelProblem::TooLong)));
+        assert_eq!(problem("mail.-example.com"), Some((1, LabelProblem::LeadingHyphen)));
+        assert_eq!(problem("mail.example-.com"), Some((1, LabelProblem::TrailingHyphen)));
+        assert_eq!(problem("xn--ab-9999999999.com"), Some((0, LabelProblem::InvalidPunycode)));
+        assert_eq!(problem("example.xn--99"), Some((1, LabelProblem::InvalidPunycode)));
+
+        let long = vec!["a".repeat(63); 4].join(".");
+        assert_eq!(check_domain(&long), Ok(()));
+        assert_eq!(check_domain(&format!("{}.com", long)), Err(DomainError::TooLong { length: 259 }));
+    }
+
+    /// Tests the public error shape
+    #[test]
+    fn test_validate_domain_labels() {
+        let error = validate_domain_labels("mail.example-.com").unwrap_err();
+        assert_eq!(error.error_type, "InvalidDomainLabel");
+        assert_eq!(error.message, "Domain label 1 (\"example-\") ends with a hyphen");
+        assert_eq!(error.details.as_deref(), Some("Label index 1"));
+        assert_eq!(error.position, Some(5));
+        assert_eq!(validate_domain_labels(&"a.".repeat(130)).unwrap_err().error_type, "DomainTooLong");
+    }
+}
@@ -0,0 +1,223 @@
+//! Runtime-loadable disposable/trusted/blocked domain lists
+//!
+//! The built-in lists are compiled in; lists loaded with `load_domain_lists`
+//! supplement them until the next load replaces them, so a fetched blob can
+//! track disposable providers that rotate domains without a rebuild.
+
+use std::collections::HashSet;
+use std::sync::{OnceLock, RwLock};
+
+use serde::{Serialize, Deserialize};
+
+use crate::{confusables, EmailParseError, DISPOSABLE_DOMAINS, TRUSTED_DOMAINS};
+
+/// Domain lists as loaded from JSON; omitted lists are treated as empty
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct DomainLists {
+    /// Disposable/temporary inbox domains (scored 20)
+    pub disposable: Vec<String>,
+    /// Trusted mail providers (scored 80 and protected against homographs)
+    pub trusted: Vec<String>,
+    /// Domains that must never be accepted (scored 0)
+    pub blocked: Vec<String>,
+    /// Domains known to host spamtraps (spamtrap risk high)
+    pub spamtrap: Vec<String>,
+    /// Domains that lapsed and may have been re-registered to catch mail
+    /// for old addresses (spamtrap risk high)
+    pub expired: Vec<String>,
+    /// Government domains beyond the built-in names (subdomains match too)
+    pub government: Vec<String>
+}
+
+/// Loaded lists, lowercased for lookup
+#[derive(Debug, Default)]
+struct LoadedLists {
+    disposable: HashSet<String>,
+    trusted: HashSet<String>,
+    /// `confusables::skeleton` of each trusted domain, computed once per load
+    trusted_skeletons: HashSet<String>,
+    blocked: HashSet<String>,
+    spamtrap: HashSet<String>,
+    expired: HashSet<String>,
+    government: HashSet<String>
+}
+
+static LOADED: RwLock<Option<LoadedLists>> = RwLock::new(None);
+
+/// Replaces the runtime domain lists with those in a JSON blob
+///
+/// The blob is an object with optional `disposable`, `trusted` and `blocked`
+/// arrays of domains. The lists supplement the built-in ones and replace any
+/// previously loaded lists; the swap is atomic, so concurrent validations
+/// see either the old or the new lists.
+///
+/// # Arguments
+/// * `json` - The domain lists as JSON
+///
+/// # Returns
+/// * `Result<(), EmailParseError>` - Ok once loaded, or an "InvalidDomainLists" error
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::load_domain_lists;
+/// load_domain_lists(r#"{"disposable": ["throwaway.example"], "blocked": ["spam.example"]}"#)?;
+/// # Ok::<(), email_validator_wasm::EmailParseError>(())
+/// ```
+pub fn load_domain_lists(json: &str) -> Result<(), EmailParseError> {
+    let lists: DomainLists = serde_json::from_str(json).map_err(|e| EmailParseError {
+        error_type: "InvalidDomainLists".to_string(),
+        message: "Failed to parse domain lists".to_string(),
+        details: Some(e.to_string()),
+        position: None
+    })?;
+    install(lists);
+    Ok(())
+}
+
+/// Makes `lists` the loaded lists, replacing any loaded before
+pub(crate) fn install(lists: DomainLists) {
+    let to_set = |domains: Vec<String>| -> HashSet<String> {
+        domains.iter().map(|domain| domain.trim().to_lowercase()).filter(|domain| !domain.is_empty()).collect()
+    };
+    let trusted = to_set(lists.trusted);
+    let loaded = LoadedLists {
+        disposable: to_set(lists.disposable),
+        trusted_skeletons: trusted.iter().map(|domain| confusables::skeleton(domain)).collect(),
+        trusted,
+        blocked: to_set(lists.blocked),
+        spamtrap: to_set(lists.spamtrap),
+        expired: to_set(lists.expired),
+        government: to_set(lists.government)
+    };
+
+    *LOADED.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(loaded);
+}
+
+/// Runs `f` against the loaded lists, if any
+fn with_loaded<T>(f: impl FnOnce(&LoadedLists) -> T) -> Option<T> {
+    LOADED.read().unwrap_or_else(|poisoned| poisoned.into_inner()).as_ref().map(f)
+}
+
+/// Whether a lowercased domain is a disposable inbox service
+pub(crate) fn is_disposable(domain: &str) -> bool {
+    DISPOSABLE_DOMAINS.contains(&domain) || with_loaded(|lists| lists.disposable.contains(domain)).unwrap_or(false)
+}
+
+/// Whether a lowercased domain is a trusted mail provider
+pub(crate) fn is_trusted(domain: &str) -> bool {
+    TRUSTED_DOMAINS.contains(&domain) || with_loaded(|lists| lists.trusted.contains(domain)).unwrap_or(false)
+}
+
+/// Whether `domain` is not a trusted provider but shares a confusable
+/// skeleton with one, such as `gооgle.com` with a Cyrillic `о`
+///
+/// The trusted domains' skeletons are computed once, so the cost does not
+/// grow with the size of the loaded trusted list.
+pub(crate) fn is_trusted_spoof(domain: &str) -> bool {
+    static BUILT_IN_SKELETONS: OnceLock<HashSet<String>> = OnceLock::new();
+
+    if is_trusted(&domain.to_lowercase()) {
+        return false;
+    }
+    let skeleton = confusables::skeleton(domain);
+    BUILT_IN_SKELETONS
+        .get_or_init(|| TRUSTED_DOMAINS.iter().map(|domain| confusables::skeleton(domain)).collect())
+        .contains(&skeleton)
+        || with_loaded(|lists| lists.trusted_skeletons.contains(&skeleton)).unwrap_or(false)
+}
+
+/// Whether a lowercased domain is blocked
+pub(crate) fn is_blocked(domain: &str) -> bool {
+    with_loaded(|lists| lists.blocked.contains(domain)).unwrap_or(false)
+}
+
+/// Whether a lowercased domain is a loaded spamtrap or expired domain, or
+/// on either of the per-call `lists`
+pub(crate) fn is_trap_domain(lists: &DomainLists, domain: &str) -> bool {
+    let listed = |domains: &[String]| domains.iter().any(|listed| listed.trim().eq_ignore_ascii_case(domain));
+    listed(&lists.spamtrap)
+        || listed(&lists.expired)
+        || with_loaded(|lists| lists.spamtrap.contains(domain) || lists.expired.contains(domain)).unwrap_or(false)
+}
+
+/// Whether a lowercased domain is on the loaded or per-call `government` list
+pub(crate) fn is_government(lists: &DomainLists, domain: &str) -> bool {
+    lists.government.iter().any(|listed| listed.trim().eq_ignore_ascii_case(domain))
+        || with_loaded(|lists| lists.government.contains(domain)).unwrap_or(false)
+}
+
+/// Score from per-call lists (blocked 0, trusted 80, disposable 20), or
+/// None when `domain` is on none of them
+pub(crate) fn custom_list_score(lists: &DomainLists, domain: &str) -> Option<f64> {
+    let listed = |domains: &[String]| domains.iter().any(|listed| listed.trim().eq_ignore_ascii_case(domain));
+    if listed(&lists.blocked) {
+        Some(0.0)
+    } else if listed(&lists.trusted) {
+        Some(80.0)
+    } else if listed(&lists.disposable) {
+        Some(20.0)
+    } else {
+        None
+    }
+}
+
+/// Every loaded domain, prefixed with the list it belongs to and sorted,
+/// or None when no lists are loaded
+pub(crate) fn loaded_entries() -> Option<Vec<String>> {
+    with_loaded(|lists| {
+        let named = [
+            ("disposable", &lists.disposable),
+            ("trusted", &lists.trusted),
+            ("blocked", &lists.blocked),
+            ("spamtrap", &lists.spamtrap),
+            ("expired", &lists.expired),
+            ("government", &lists.government)
+        ];
+        let mut entries: Vec<String> = named
+            .into_iter()
+            .flat_map(|(list, domains)| domains.iter().map(move |domain| format!("{}:{}", list, domain)))
+            .collect();
+        entries.sort_unstable();
+        entries
+    })
+}
+
+/// Built-in and loaded trusted domains
+pub(crate) fn trusted_domains() -> Vec<String> {
+    let mut trusted: Vec<String> = TRUSTED_DOMAINS.iter().map(|domain| domain.to_string()).collect();
+    if let Some(loaded) = with_loaded(|lists| lists.trusted.iter().cloned().collect::<Vec<_>>()) {
+        trusted.extend(loaded);
+    }
+    trusted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that loaded lists supplement the built-ins and replace earlier loads
+    #[test]
+    fn test_load_domain_lists() {
+        load_domain_lists(r#"{"disposable": ["Rotating-1.example"], "trusted": ["bigmail.example"], "blocked": ["spam.example"]}"#).unwrap();
+        assert!(is_disposable("rotating-1.example"));
+        assert!(is_disposable("mailinator.com"));
+        assert!(is_trusted("bigmail.example"));
+        assert!(is_blocked("spam.example"));
+        assert!(trusted_domains().contains(&"bigmail.example".to_string()));
+        assert!(is_trusted_spoof("bigrnail.example"));
+        assert!(is_trusted_spoof("xn--ggle-55da.com"));
+        assert!(!is_trusted_spoof("BigMail.example"));
+
+        load_domain_lists(r#"{"disposable": ["rotating-2.example"]}"#).unwrap();
+        assert!(!is_disposable("rotating-1.example"));
+        assert!(is_disposable("rotating-2.example"));
+        assert!(!is_blocked("spam.example"));
+        assert!(!is_trusted_spoof("bigrnail.example"));
+
+        let error = load_domain_lists(r#"{"disposable": "not-a-list"}"#).unwrap_err();
+        assert_eq!(error.error_type, "InvalidDomainLists");
+        assert!(is_disposable("rotating-2.example"));
+    }
+}
@@ -0,0 +1,148 @@
+//! Enrichment hooks that attach caller-defined metadata to results
+//!
+//! Company names, industries or internal CRM ids come from services this
+//! crate knows nothing about. An `Enricher` runs after validation and
+//! returns key/value pairs that are merged into
+//! `EmailParseResult::metadata`, so such providers can be plugged in without
+//! new result fields.
+
+use std::collections::BTreeMap;
+
+use crate::{parse_and_validate_email_with_options, EmailParseError, EmailParseResult, ValidationOptions};
+
+/// Key/value metadata attached to a result by enrichers
+pub type Metadata = BTreeMap<String, serde_json::Value>;
+
+/// Source of metadata for validated addresses
+///
+/// `enrich` is async so providers can call out over the network; an enricher
+/// with nothing to await simply returns. It is only invoked for valid
+/// results. A lookup that fails should leave its keys out (or record the
+/// failure under a key of its own) rather than fail validation.
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::{EmailParseResult, Enricher, Metadata};
+/// # struct Account { id: u64, name: String }
+/// # struct CrmClient;
+/// # impl CrmClient {
+/// #     async fn account_for(&self, _domain: &str) -> Result<Account, ()> { Err(()) }
+/// # }
+/// struct CrmLookup { client: CrmClient }
+///
+/// impl Enricher for CrmLookup {
+///     async fn enrich(&self, result: &EmailParseResult) -> Metadata {
+///         let mut metadata = Metadata::new();
+///         if let Ok(account) = self.client.account_for(result.domain.as_deref().unwrap_or_default()).await {
+///             metadata.insert("crm_id".to_string(), account.id.into());
+///             metadata.insert("company".to_string(), account.name.into());
+///         }
+///         metadata
+///     }
+/// }
+/// ```
+#[allow(async_fn_in_trait)]
+pub trait Enricher {
+    /// Metadata for a valid result
+    async fn enrich(&self, result: &EmailParseResult) -> Metadata;
+}
+
+impl<T: Enricher + ?Sized> Enricher for &T {
+    async fn enrich(&self, result: &EmailParseResult) -> Metadata {
+        (**self).enrich(result).await
+    }
+}
+
+/// Runs both enrichers in order; on a key both set, the second wins
+impl<A: Enricher, B: Enricher> Enricher for (A, B) {
+    async fn enrich(&self, result: &EmailParseResult) -> Metadata {
+        let mut metadata = self.0.enrich(result).await;
+        metadata.extend(self.1.enrich(result).await);
+        metadata
+    }
+}
+
+/// Merges the enricher's metadata into a valid `result`; invalid results are
+/// left untouched
+pub async fn enrich_result<E: Enricher>(result: &mut EmailParseResult, enricher: &E) {
+    if result.is_valid {
+        let metadata = enricher.enrich(result).await;
+        result.metadata.extend(metadata);
+    }
+}
+
+/// Validates `email` with `options`, then enriches the result
+///
+/// # Examples
+/// ```no_run
+/// # use email_validator_wasm::{validate_and_enrich, EmailParseResult, Enricher, Metadata, ValidationOptions};
+/// # struct CrmClient;
+/// # struct CrmLookup;
+/// # impl CrmLookup { fn new(_client: CrmClient) -> Self { CrmLookup } }
+/// # impl Enricher for CrmLookup {
+/// #     async fn enrich(&self, _result: &EmailParseResult) -> Metadata { Metadata::new() }
+/// # }
+/// # async fn example(client: CrmClient) -> Result<(), email_validator_wasm::EmailParseError> {
+/// let result = validate_and_enrich("jane@acme.example", &ValidationOptions::default(), &CrmLookup::new(client)).await?;
+/// println!("{:?}", result.metadata.get("crm_id"));
+/// # Ok(())
+/// # }
+/// ```
+pub async fn validate_and_enrich<E: Enricher>(email: &str, options: &ValidationOptions, enricher: &E) -> Result<EmailParseResult, EmailParseError> {
+    let mut result = parse_and_validate_email_with_options(email, options)?;
+    enrich_result(&mut result, enricher).await;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::block_on;
+
+    /// Attaches a fixed company name for one domain
+    struct Directory;
+
+    impl Enricher for Directory {
+        async fn enrich(&self, result: &EmailParseResult) -> Metadata {
+            let mut metadata = Metadata::new();
+            if result.domain.as_deref() == Some("acme.example") {
+                metadata.insert("company".to_string(), "Acme".into());
+            }
+            metadata
+        }
+    }
+
+    /// Attaches a value computed after yielding once, like a network lookup
+    struct Crm;
+
+    impl Enricher for Crm {
+        async fn enrich(&self, _result: &EmailParseResult) -> Metadata {
+            let mut yielded = false;
+            std::future::poll_fn(|context| {
+                if yielded {
+                    return std::task::Poll::Ready(());
+                }
+                yielded = true;
+                context.waker().wake_by_ref();
+                std::task::Poll::Pending
+            }).await;
+            Metadata::from([("crm_id".to_string(), 42.into()), ("company".to_string(), "Acme Corp".into())])
+        }
+    }
+
+    /// Tests merging, ordering and that invalid results are not enriched
+    #[test]
+    fn test_enrich() {
+        let options = ValidationOptions::default();
+        let result = block_on(validate_and_enrich("jane@acme.example", &options, &Directory)).unwrap();
+        assert_eq!(result.metadata["company"], "Acme");
+
+        let result = block_on(validate_and_enrich("jane@acme.example", &options, &(Directory, Crm))).unwrap();
+        assert_eq!(result.metadata["company"], "Acme Corp");
+        assert_eq!(result.metadata["crm_id"], 42);
+
+        let result = block_on(validate_and_enrich("not an email", &options, &Directory)).unwrap();
+        assert!(result.metadata.is_empty());
+        assert!(!serde_json::to_value(&result).unwrap().as_object().unwrap().contains_key("metadata"));
+    }
+}
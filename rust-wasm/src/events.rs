@@ -0,0 +1,73 @@
+//! Structured events for each verification stage
+//!
+//! An observer attached with `Verifier::with_observer` is told when every
+//! stage starts and how it finished, so host apps can drive progress UIs and
+//! audit logs without patching the crate. The events carry the same stage
+//! names and outcomes as the `tracing` spans, and addresses in them are
+//! redacted by the verifier's `LogRedaction`.
+
+use serde::Serialize;
+
+/// A stage of the verification pipeline starting or finishing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum PipelineEvent {
+    /// A stage began for `domain`
+    StageStarted {
+        /// Stage name (`verify`, `mx`, `mail_auth`, `smtp`, ...)
+        stage: &'static str,
+        domain: String,
+        /// Redacted address, for stages that work on the whole address
+        /// (`verify`, `smtp`, `breach`, `gravatar`)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        email: Option<String>
+    },
+    /// A stage ended for `domain` with `outcome` (`found`, `timeout`,
+    /// `cancelled`, ...)
+    StageFinished {
+        stage: &'static str,
+        domain: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        email: Option<String>,
+        outcome: String,
+        duration_ms: u64
+    }
+}
+
+impl PipelineEvent {
+    /// Name of the stage the event is about
+    pub fn stage(&self) -> &'static str {
+        match self {
+            PipelineEvent::StageStarted { stage, .. } | PipelineEvent::StageFinished { stage, .. } => stage
+        }
+    }
+}
+
+/// Receiver of pipeline events
+///
+/// Called synchronously from the pipeline, so implementations should hand
+/// slow work (network writes, disk I/O) off rather than block. Closures taking
+/// `&PipelineEvent` implement it.
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::{PipelineEvent, SandboxResolver, Verifier};
+/// # struct Progress;
+/// # impl Progress { fn update(&self, _stage: &str, _outcome: &str, _duration_ms: u64) {} }
+/// # let (resolver, progress) = (SandboxResolver::new(), Progress);
+/// let verifier = Verifier::new(resolver).with_observer(move |event: &PipelineEvent| {
+///     if let PipelineEvent::StageFinished { stage, outcome, duration_ms, .. } = event {
+///         progress.update(stage, outcome, *duration_ms);
+///     }
+/// });
+/// ```
+pub trait EventObserver {
+    /// Handles one event
+    fn on_event(&self, event: &PipelineEvent);
+}
+
+impl<F: Fn(&PipelineEvent)> EventObserver for F {
+    fn on_event(&self, event: &PipelineEvent) {
+        self(event)
+    }
+}
@@ -0,0 +1,94 @@
+//! Extraction of email addresses from free text (support tickets, pasted notes)
+
+use regex::Regex;
+use serde::{Serialize, Deserialize};
+
+use crate::{parse_and_validate_email, EmailParseError, EmailParseResult};
+
+/// A candidate address found in free text, with its validation result
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExtractedEmail {
+    /// The candidate address as it appears in the text
+    pub email: String,
+    /// Byte offset of the first character of the address in the text
+    pub start: usize,
+    /// Byte offset just past the last character of the address in the text
+    pub end: usize,
+    /// Validation result for the address
+    pub result: EmailParseResult
+}
+
+/// Scans free text for candidate email addresses and validates each one
+///
+/// Candidates are runs of address characters around an `@` with a dotted
+/// domain; leading dots and trailing sentence punctuation are not included.
+/// Every candidate is returned, valid or not, so callers can surface
+/// near-misses (`jane..doe@example.com`).
+///
+/// # Arguments
+/// * `text` - The text to scan
+///
+/// # Returns
+/// * `Result<Vec<ExtractedEmail>, EmailParseError>` - Candidates in order of appearance, or error
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::extract_emails;
+/// let found = extract_emails("Please reply to jane@example.com.").unwrap();
+/// assert_eq!(found[0].email, "jane@example.com");
+/// assert_eq!((found[0].start, found[0].end), (16, 32));
+/// ```
+pub fn extract_emails(text: &str) -> Result<Vec<ExtractedEmail>, EmailParseError> {
+    let candidate_regex = match Regex::new(r"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9-]+(?:\.[a-zA-Z0-9-]+)+") {
+        Ok(regex) => regex,
+        Err(e) => return Err(EmailParseError {
+            error_type: "RegexError".to_string(),
+            message: "Failed to compile email extraction regex".to_string(),
+            details: Some(e.to_string()),
+            position: None
+        })
+    };
+
+    candidate_regex
+        .find_iter(text)
+        .map(|candidate| {
+            let matched = candidate.as_str();
+            let email = matched.trim_start_matches('.').trim_end_matches('-');
+            let start = candidate.start() + (matched.len() - matched.trim_start_matches('.').len());
+
+            Ok(ExtractedEmail {
+                email: email.to_string(),
+                start,
+                end: start + email.len(),
+                result: parse_and_validate_email(email)?
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that addresses are found with byte offsets into the original text
+    #[test]
+    fn test_extract_emails() {
+        let text = "Hi — ticket from <jane.doe@example.com>, cc ops@google.com. Old: ...bob..x@example.org";
+        let found = extract_emails(text).unwrap();
+        assert_eq!(found.len(), 3);
+
+        for extracted in &found {
+            assert_eq!(&text[extracted.start..extracted.end], extracted.email);
+        }
+
+        assert_eq!(found[0].email, "jane.doe@example.com");
+        assert!(found[0].result.is_valid);
+        assert_eq!(found[1].email, "ops@google.com");
+        assert_eq!(found[1].result.domain_score, Some(80.0));
+        assert_eq!(found[2].email, "bob..x@example.org");
+        assert!(!found[2].result.is_valid);
+
+        assert!(extract_emails("no addresses here, just @mentions").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,36 @@
+//! Minimal HTTP GET over the host's `fetch`, shared by wasm network stages
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = fetch)]
+    fn fetch_with_init(input: &str, init: &JsValue) -> js_sys::Promise;
+}
+
+/// Performs a GET request with the given `accept` header and returns the body text
+pub(crate) async fn fetch_text(url: &str, accept: &str) -> Result<String, JsValue> {
+    let headers = js_sys::Object::new();
+    js_sys::Reflect::set(&headers, &"accept".into(), &accept.into())?;
+    let init = js_sys::Object::new();
+    js_sys::Reflect::set(&init, &"headers".into(), &headers)?;
+
+    let response = JsFuture::from(fetch_with_init(url, &init)).await?;
+    if !js_sys::Reflect::get(&response, &"ok".into())?.is_truthy() {
+        let status = js_sys::Reflect::get(&response, &"status".into())?;
+        return Err(JsValue::from_str(&format!("HTTP status {}", status.as_f64().unwrap_or_default())));
+    }
+
+    let text = js_sys::Reflect::get(&response, &"text".into())?.dyn_into::<js_sys::Function>()?;
+    let body = JsFuture::from(text.call0(&response)?.dyn_into::<js_sys::Promise>()?).await?;
+    body.as_string().ok_or_else(|| JsValue::from_str("Response body is not text"))
+}
+
+/// Performs a GET request and returns only the HTTP status, for existence checks
+pub(crate) async fn fetch_status(url: &str) -> Result<u16, JsValue> {
+    let response = JsFuture::from(fetch_with_init(url, &js_sys::Object::new())).await?;
+    let status = js_sys::Reflect::get(&response, &"status".into())?;
+    Ok(status.as_f64().unwrap_or_default() as u16)
+}
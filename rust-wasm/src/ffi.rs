@@ -0,0 +1,140 @@
+//! C ABI for native hosts (iOS, Android NDK, C++ backends)
+//!
+//! Results cross the boundary as NUL-terminated JSON with the same shape the
+//! wasm exports produce: a serialized `EmailParseResult` on success or a
+//! serialized `EmailParseError` otherwise. Every string returned here must be
+//! released with `ts_email_string_free`. The header is generated with
+//! `cbindgen --config cbindgen.toml --output include/ts_email.h`.
+
+use std::ffi::{c_char, CStr, CString};
+
+use serde::Serialize;
+
+use crate::{parse_and_validate_email_with_options, EmailParseError, ValidationOptions};
+
+/// Validates a NUL-terminated UTF-8 email address
+///
+/// # Returns
+/// A JSON `EmailParseResult`, or a JSON `EmailParseError` (error_type
+/// "InvalidArgument" for a null or non-UTF-8 argument). Never null.
+///
+/// # Safety
+/// `email` must be null or point to a NUL-terminated string that stays valid
+/// for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn ts_email_validate(email: *const c_char) -> *mut c_char {
+    ts_email_validate_with_options(email, std::ptr::null())
+}
+
+/// Validates an email address with a JSON `ValidationOptions` object
+///
+/// `options_json` may be null, which uses the default options.
+///
+/// # Returns
+/// A JSON `EmailParseResult`, or a JSON `EmailParseError` (error_type
+/// "InvalidOptions" for options that fail to parse). Never null.
+///
+/// # Safety
+/// Both arguments must be null or point to NUL-terminated strings that stay
+/// valid for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn ts_email_validate_with_options(email: *const c_char, options_json: *const c_char) -> *mut c_char {
+    let email = match read_str(email, "email") {
+        Ok(email) => email,
+        Err(e) => return to_json(&e)
+    };
+
+    let options: ValidationOptions = if options_json.is_null() {
+        ValidationOptions::default()
+    } else {
+        let parsed = read_str(options_json, "options_json").and_then(|json| {
+            serde_json::from_str(json).map_err(|e| EmailParseError {
+                error_type: "InvalidOptions".to_string(),
+                message: "Failed to parse validation options".to_string(),
+                details: Some(e.to_string()),
+                position: None
+            })
+        });
+        match parsed {
+            Ok(options) => options,
+            Err(e) => return to_json(&e)
+        }
+    };
+
+    match parse_and_validate_email_with_options(email, &options) {
+        Ok(result) => to_json(&result),
+        Err(e) => to_json(&e)
+    }
+}
+
+/// Releases a string returned by this library
+///
+/// # Safety
+/// `s` must be null or a pointer returned by a `ts_email_*` function that has
+/// not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn ts_email_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Borrows a C string argument as UTF-8
+unsafe fn read_str<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, EmailParseError> {
+    let invalid = |message: String| EmailParseError {
+        error_type: "InvalidArgument".to_string(),
+        message,
+        details: None,
+        position: None
+    };
+
+    if ptr.is_null() {
+        return Err(invalid(format!("`{}` is null", name)));
+    }
+    CStr::from_ptr(ptr).to_str().map_err(|_| invalid(format!("`{}` is not valid UTF-8", name)))
+}
+
+/// Serializes a value into an owned C string for the caller to free
+fn to_json<T: Serialize>(value: &T) -> *mut c_char {
+    // serde_json escapes control characters, so the output never contains NUL
+    let json = serde_json::to_string(value).unwrap_or_else(|e| {
+        format!(r#"{{"error_type":"SerializationError","message":"Failed to serialize result","details":{:?}}}"#, e.to_string())
+    });
+    CString::new(json).unwrap_or_default().into_raw()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Calls an FFI function and takes ownership of the returned JSON
+    fn call(f: impl FnOnce() -> *mut c_char) -> serde_json::Value {
+        let ptr = f();
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_string();
+        unsafe { ts_email_string_free(ptr) };
+        serde_json::from_str(&json).unwrap()
+    }
+
+    /// Tests validation, options and argument errors through the C ABI
+    #[test]
+    fn test_ffi_validate() {
+        let email = CString::new("user@example.com").unwrap();
+        let result = call(|| unsafe { ts_email_validate(email.as_ptr()) });
+        assert_eq!(result["is_valid"], true);
+        assert_eq!(result["domain"], "example.com");
+
+        let options = CString::new(r#"{"access_policy": [{"action": "deny", "pattern": "example.com"}]}"#).unwrap();
+        let result = call(|| unsafe { ts_email_validate_with_options(email.as_ptr(), options.as_ptr()) });
+        assert_eq!(result["is_valid"], false);
+
+        let options = CString::new("{not json").unwrap();
+        let error = call(|| unsafe { ts_email_validate_with_options(email.as_ptr(), options.as_ptr()) });
+        assert_eq!(error["error_type"], "InvalidOptions");
+
+        let error = call(|| unsafe { ts_email_validate(std::ptr::null()) });
+        assert_eq!(error["error_type"], "InvalidArgument");
+
+        unsafe { ts_email_string_free(std::ptr::null_mut()) };
+    }
+}
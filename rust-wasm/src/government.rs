@@ -0,0 +1,65 @@
+//! Government domain detection
+//!
+//! Most governments publish mail under a reserved name: the `.gov` and
+//! `.mil` TLDs, or a second-level label under their ccTLD (`gov.uk`,
+//! `gc.ca`, `gouv.fr`, `go.jp`). Others use ordinary domains (`bund.de`,
+//! `admin.ch`), listed in `GOVERNMENT_DOMAINS`; further domains can be added
+//! through the `government` domain list.
+
+use crate::{domain_lists, DomainLists};
+
+/// Second-level labels under a ccTLD reserved for government bodies
+const GOVERNMENT_SLDS: [&str; 9] = ["gov", "go", "gouv", "gob", "govt", "gc", "gv", "mil", "gub"];
+
+/// Government domains outside the reserved names, by country
+const GOVERNMENT_DOMAINS: [&str; 12] = [
+    "bund.de",
+    "admin.ch",
+    "overheid.nl",
+    "rijksoverheid.nl",
+    "belgium.be",
+    "fgov.be",
+    "regeringskansliet.se",
+    "valtioneuvosto.fi",
+    "regjeringen.no",
+    "europa.eu",
+    "parliament.uk",
+    "fed.us"
+];
+
+/// Whether a lowercased domain belongs to a government body: a reserved
+/// name, a built-in government domain or a subdomain of one, or a domain on
+/// the loaded or per-call `government` list
+pub(crate) fn is_government(domain: &str, lists: &DomainLists) -> bool {
+    let domain = domain.trim_end_matches('.');
+    let mut labels = domain.rsplit('.');
+    let tld = labels.next().unwrap_or_default();
+    let second_level = labels.next().unwrap_or_default();
+
+    if matches!(tld, "gov" | "mil") || (tld.len() == 2 && GOVERNMENT_SLDS.contains(&second_level)) {
+        return true;
+    }
+    let mut parents = std::iter::once(domain).chain(domain.match_indices('.').map(|(index, _)| &domain[index + 1..]));
+    parents.any(|parent| GOVERNMENT_DOMAINS.contains(&parent) || domain_lists::is_government(lists, parent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests reserved names, built-in domains and the extensible list
+    #[test]
+    fn test_is_government() {
+        let none = DomainLists::default();
+        for domain in ["irs.gov", "army.mil", "digital.cabinet-office.gov.uk", "tbs-sct.gc.ca", "interieur.gouv.fr", "mof.go.jp", "bmi.bund.de", "admin.ch"] {
+            assert!(is_government(domain, &none), "{}", domain);
+        }
+        for domain in ["gov.example.com", "example.com", "notbund.de"] {
+            assert!(!is_government(domain, &none), "{}", domain);
+        }
+
+        let lists = DomainLists { government: vec!["agency.example".to_string()], ..Default::default() };
+        assert!(is_government("mail.agency.example", &lists));
+        assert!(!is_government("agency.example", &none));
+    }
+}
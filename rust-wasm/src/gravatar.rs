@@ -0,0 +1,63 @@
+//! Gravatar existence check for an address
+//!
+//! Having a Gravatar is a strong sign that a consumer address belongs to a
+//! real person. Only the address's SHA-256 hash is sent.
+
+#[cfg(any(target_arch = "wasm32", feature = "tls", test))]
+use crate::hash::hash_email;
+
+/// Host serving Gravatar avatars
+#[cfg(any(target_arch = "wasm32", feature = "tls"))]
+const GRAVATAR_HOST: &str = "gravatar.com";
+
+/// Avatar path for `email`; `d=404` makes missing avatars return 404
+/// instead of a placeholder image
+#[cfg(any(target_arch = "wasm32", feature = "tls", test))]
+fn avatar_path(email: &str) -> String {
+    format!("/avatar/{}?d=404", hash_email(email))
+}
+
+/// Maps the avatar response status to whether a Gravatar exists
+#[cfg(any(target_arch = "wasm32", feature = "tls", test))]
+fn status_to_presence(status: u16) -> Result<bool, String> {
+    match status {
+        200 => Ok(true),
+        404 => Ok(false),
+        status => Err(format!("HTTP status {}", status))
+    }
+}
+
+/// Checks whether `email` has a Gravatar through the host's `fetch`
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn check_gravatar(email: &str) -> Result<bool, String> {
+    let url = format!("https://{}{}", GRAVATAR_HOST, avatar_path(email));
+    let status = crate::fetch::fetch_status(&url).await.map_err(|e| e.as_string().unwrap_or_default())?;
+    status_to_presence(status)
+}
+
+/// Checks whether `email` has a Gravatar over HTTPS
+#[cfg(all(feature = "tls", not(target_arch = "wasm32")))]
+pub(crate) async fn check_gravatar(email: &str) -> Result<bool, String> {
+    let (status, _) = crate::http::https_get(GRAVATAR_HOST, &avatar_path(email), std::time::Duration::from_secs(10), 0).await?;
+    status_to_presence(status)
+}
+
+/// Gravatar cannot be queried without TLS support
+#[cfg(all(not(feature = "tls"), not(target_arch = "wasm32")))]
+pub(crate) async fn check_gravatar(_email: &str) -> Result<bool, String> {
+    Err("Checking Gravatar requires the `tls` feature".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the avatar path and status mapping
+    #[test]
+    fn test_gravatar_request() {
+        assert_eq!(avatar_path(" User@Example.com"), format!("/avatar/{}?d=404", hash_email("user@example.com")));
+        assert_eq!(status_to_presence(200), Ok(true));
+        assert_eq!(status_to_presence(404), Ok(false));
+        assert!(status_to_presence(429).is_err());
+    }
+}
@@ -0,0 +1,56 @@
+//! Privacy-safe hashing of addresses for analytics joins
+
+use std::fmt::Write;
+
+use sha2::{Digest, Sha256};
+
+/// Normalizes an address for hashing: surrounding whitespace trimmed and
+/// the whole address lowercased, so case variants share a hash
+fn normalize_for_hash(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+/// Computes the SHA-256 hash of a normalized email address
+///
+/// The address is trimmed and lowercased before hashing, matching the
+/// normalization used by common audience-matching pipelines. The address
+/// is not validated.
+///
+/// # Arguments
+/// * `email` - The address to hash
+///
+/// # Returns
+/// * `String` - Lowercase hex-encoded SHA-256 digest (64 characters)
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::hash_email;
+/// assert_eq!(hash_email(" User@Example.com "), hash_email("user@example.com"));
+/// ```
+pub fn hash_email(email: &str) -> String {
+    sha256_hex(normalize_for_hash(email).as_bytes())
+}
+
+/// Lowercase hex-encoded SHA-256 digest of `data`
+pub(crate) fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data).iter().fold(String::with_capacity(64), |mut hex, byte| {
+        let _ = write!(hex, "{:02x}", byte);
+        hex
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the digest against a known vector and normalization of case/whitespace
+    #[test]
+    fn test_hash_email() {
+        assert_eq!(
+            hash_email("user@example.com"),
+            "b4c9a289323b21a01c3e940f150eb9b8c542587f1abfd8f0e1cc1ffc5e475514"
+        );
+        assert_eq!(hash_email("  USER@Example.COM\n"), hash_email("user@example.com"));
+        assert_ne!(hash_email("user1@example.com"), hash_email("user@example.com"));
+    }
+}
@@ -0,0 +1,105 @@
+//! Guard against SMTP/MIME header injection via address fields
+
+/// Raw characters that can terminate or split a header line
+const UNSAFE_CHARS: [char; 6] = ['\r', '\n', '\0', '\u{0085}', '\u{2028}', '\u{2029}'];
+
+/// Encoded forms of CR, LF and NUL (percent, backslash-escape and HTML
+/// entity), matched case-insensitively
+const ENCODED_NEWLINES: [&str; 14] = [
+    "%0a", "%0d", "%00",
+    "\\n", "\\r", "\\0",
+    "&#10;", "&#13;", "&#0;", "&#x0a;", "&#x0d;", "&#xa;", "&#xd;", "&#x0;"
+];
+
+/// Quoted-printable CRLF, unsafe wherever it appears
+const QUOTED_PRINTABLE_CRLF: &str = "=0d=0a";
+
+/// Quoted-printable CR, LF and NUL; `=` is valid in a local part, so these
+/// are only unsafe inside an RFC 2047 Q-encoded word
+const QUOTED_PRINTABLE_NEWLINES: [&str; 3] = ["=0a", "=0d", "=00"];
+
+/// Whether a Q-encoded word (`=?charset?Q?text?=`) in `lower` encodes CR, LF
+/// or NUL
+fn has_encoded_word_newline(lower: &str) -> bool {
+    let mut rest = lower;
+    while let Some(start) = rest.find("=?") {
+        let mut parts = rest[start + 2..].splitn(3, '?');
+        let (Some(_charset), Some(encoding), Some(tail)) = (parts.next(), parts.next(), parts.next()) else {
+            return false;
+        };
+        let Some(end) = tail.find("?=") else {
+            return false;
+        };
+        let text = &tail[..end];
+        if encoding == "q" && QUOTED_PRINTABLE_NEWLINES.iter().any(|encoded| text.contains(encoded)) {
+            return true;
+        }
+        rest = &tail[end + 2..];
+    }
+    false
+}
+
+/// Checks whether an address can be placed in an SMTP or MIME header
+///
+/// Rejects raw CR, LF and NUL (plus Unicode line separators) and their
+/// common encodings (`%0A`, `\n`, `&#13;`, and `=0D=0A` or `=0A` inside a
+/// `=?utf-8?Q?...?=` encoded-word), which an attacker may rely on being
+/// decoded later in the pipeline. This is an explicit
+/// injection guard to run before building headers; it does not check that
+/// the address is well-formed.
+///
+/// # Arguments
+/// * `email` - The address to check
+///
+/// # Returns
+/// * `bool` - `true` if no line-breaking sequence was found
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::is_header_safe;
+/// assert!(is_header_safe("user@example.com"));
+/// assert!(!is_header_safe("user@example.com\r\nBcc: victim@example.com"));
+/// assert!(!is_header_safe("user@example.com%0ABcc:victim@example.com"));
+/// ```
+pub fn is_header_safe(email: &str) -> bool {
+    if email.contains(UNSAFE_CHARS) {
+        return false;
+    }
+
+    let lower = email.to_ascii_lowercase();
+    !ENCODED_NEWLINES.iter().any(|encoded| lower.contains(encoded))
+        && !lower.contains(QUOTED_PRINTABLE_CRLF)
+        && !has_encoded_word_newline(&lower)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests detection of raw and encoded line breaks
+    #[test]
+    fn test_is_header_safe() {
+        assert!(is_header_safe("user@example.com"));
+        assert!(is_header_safe("first.last+tag@mail.example.co.uk"));
+
+        for email in [
+            "user@example.com\nBcc: victim@example.com",
+            "user@example.com\r",
+            "user\0@example.com",
+            "user@example.com%0D%0ABcc:victim@example.com",
+            "user@example.com\\r\\nBcc:victim@example.com",
+            "user@example.com&#x0A;Bcc:victim@example.com",
+            "user=0D=0A@example.com",
+            "=?utf-8?Q?user=0ABcc:victim@example.com?=",
+            "=?utf-8?b?dXNlcg==?= =?UTF-8?q?x=0D?=@example.com",
+            "user@example.com\u{2028}"
+        ] {
+            assert!(!is_header_safe(email), "{:?} should be unsafe", email);
+        }
+
+        // `=` is atext, so hex-looking pairs in a plain local part are fine
+        for email in ["user=0dd@example.com", "a=00b@example.com", "x=0a@example.com", "=?utf-8?Q?jane?=@example.com"] {
+            assert!(is_header_safe(email), "{:?} should be safe", email);
+        }
+    }
+}
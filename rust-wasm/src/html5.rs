@@ -0,0 +1,98 @@
+//! The WHATWG `input[type=email]` validity check
+//!
+//! Browsers accept an address in an email field when it matches the HTML
+//! standard's "valid email address" production, which is deliberately looser
+//! than RFC 5322 in some places (single-label domains, dots anywhere in the
+//! local part) and stricter in others (no quoted strings or comments). This
+//! is that production, so a server-side check can agree with the browser's
+//! constraint validation character for character.
+
+/// Characters allowed in the local part: `atext` plus `.`
+fn is_local_char(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b".!#$%&'*+/=?^_`{|}~-".contains(&byte)
+}
+
+/// Whether `label` matches `[a-zA-Z0-9](?:[a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?`
+fn is_valid_label(label: &[u8]) -> bool {
+    match label {
+        [] => false,
+        [first, .., last] if !first.is_ascii_alphanumeric() || !last.is_ascii_alphanumeric() => false,
+        [only] => only.is_ascii_alphanumeric(),
+        _ => label.len() <= 63 && label.iter().all(|byte| byte.is_ascii_alphanumeric() || *byte == b'-')
+    }
+}
+
+/// Whether `value` is a valid email address as defined by the HTML standard
+/// for `<input type="email">`
+///
+/// Equivalent to the standard's regular expression:
+/// `^[a-zA-Z0-9.!#$%&'*+/=?^_`{|}~-]+@[a-zA-Z0-9](?:[a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?(?:\.[a-zA-Z0-9](?:[a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?)*$`
+///
+/// The browser validates the field's sanitized value, with newlines removed
+/// and leading and trailing whitespace trimmed, so apply the same clean-up to
+/// raw input first. Internationalized domains must already be in punycode.
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::is_valid_html5_email;
+/// assert!(is_valid_html5_email("jane@localhost"));
+/// assert!(is_valid_html5_email("jane..doe.@example.com"));
+/// assert!(!is_valid_html5_email("\"jane doe\"@example.com"));
+/// ```
+pub fn is_valid_html5_email(value: &str) -> bool {
+    let Some((local_part, domain)) = value.split_once('@') else {
+        return false;
+    };
+    !local_part.is_empty()
+        && local_part.bytes().all(is_local_char)
+        && domain.split('.').all(|label| is_valid_label(label.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests addresses the browser accepts that RFC-style validation may not, and vice versa
+    #[test]
+    fn test_is_valid_html5_email() {
+        for valid in [
+            "jane@example.com",
+            "jane@localhost",
+            "j@x",
+            ".jane..doe.@example.com",
+            "!#$%&'*+/=?^_`{|}~-@example.com",
+            "jane@123.example",
+            "jane@a-b.c-d",
+            "jane@EXAMPLE.COM"
+        ] {
+            assert!(is_valid_html5_email(valid), "{}", valid);
+        }
+        let label = "a".repeat(63);
+        assert!(is_valid_html5_email(&format!("jane@{}.com", label)));
+
+        for invalid in [
+            "",
+            "jane",
+            "@example.com",
+            "jane@",
+            "jane@@example.com",
+            "jane@ex@ample.com",
+            "\"jane doe\"@example.com",
+            "jane(comment)@example.com",
+            "jane@[192.0.2.1]",
+            "jane@-example.com",
+            "jane@example-.com",
+            "jane@example..com",
+            "jane@.example.com",
+            "jane@example.com.",
+            "jane@exa_mple.com",
+            "jané@example.com",
+            "jane@exämple.com",
+            " jane@example.com",
+            "jane@example.com\n"
+        ] {
+            assert!(!is_valid_html5_email(invalid), "{:?}", invalid);
+        }
+        assert!(!is_valid_html5_email(&format!("jane@{}a.com", label)));
+    }
+}
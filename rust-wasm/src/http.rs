@@ -0,0 +1,91 @@
+//! Minimal HTTPS GET over rustls, shared by native enrichment stages
+
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::time::Duration;
+
+use rustls::pki_types::ServerName;
+use rustls::{ClientConfig, ClientConnection, StreamOwned};
+
+use crate::blocking;
+use crate::smtp::tls::{provider, webpki_verifier};
+
+/// Performs a GET request for `path` on `host`, returning the status code and
+/// at most `max_body` bytes of body
+///
+/// The certificate must validate against the public roots. The request uses
+/// HTTP/1.0 so the body is never chunked. It runs on a worker thread, so a
+/// stage timeout can stop waiting for it.
+pub(crate) async fn https_get(host: &str, path: &str, timeout: Duration, max_body: usize) -> Result<(u16, String), String> {
+    let (host, path) = (host.to_string(), path.to_string());
+    blocking::run(move || https_get_blocking(&host, &path, timeout, max_body)).await
+}
+
+fn https_get_blocking(host: &str, path: &str, timeout: Duration, max_body: usize) -> Result<(u16, String), String> {
+    let address = (host, 443).to_socket_addrs().map_err(|e| e.to_string())?.next().ok_or("Host has no address")?;
+    let stream = TcpStream::connect_timeout(&address, timeout).map_err(|e| e.to_string())?;
+    stream.set_read_timeout(Some(timeout)).map_err(|e| e.to_string())?;
+    stream.set_write_timeout(Some(timeout)).map_err(|e| e.to_string())?;
+
+    let config = ClientConfig::builder_with_provider(provider())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| e.to_string())?
+        .with_webpki_verifier(webpki_verifier().map_err(|e| e.message)?)
+        .with_no_client_auth();
+    let server_name = ServerName::try_from(host.to_string()).map_err(|e| e.to_string())?;
+    let connection = ClientConnection::new(Arc::new(config), server_name).map_err(|e| e.to_string())?;
+    let mut stream = StreamOwned::new(connection, stream);
+
+    write!(stream, "GET {} HTTP/1.0\r\nHost: {}\r\nUser-Agent: email-validator\r\n\r\n", path, host).map_err(|e| e.to_string())?;
+    let mut response = Vec::new();
+    match Read::by_ref(&mut stream).take(max_body as u64 + 8192).read_to_end(&mut response) {
+        Ok(_) => {}
+        // Many servers close without a TLS close_notify
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof && !response.is_empty() => {}
+        Err(e) => return Err(e.to_string())
+    }
+
+    parse_response(&response)
+}
+
+/// Splits an `https://` URL into host and path, or None for another scheme
+pub(crate) fn split_https_url(url: &str) -> Option<(&str, &str)> {
+    let rest = url.strip_prefix("https://")?;
+    let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    Some((host, if path.is_empty() { "/" } else { path }))
+}
+
+/// Splits a raw HTTP/1.x response into status code and body
+fn parse_response(response: &[u8]) -> Result<(u16, String), String> {
+    let response = String::from_utf8_lossy(response);
+    let (head, body) = response.split_once("\r\n\r\n").ok_or("Malformed HTTP response")?;
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .ok_or("Malformed HTTP status line")?;
+    Ok((status, body.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests status and body extraction
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(parse_response(b"HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\n\r\nmode: enforce\r\n"), Ok((200, "mode: enforce\r\n".to_string())));
+        assert_eq!(parse_response(b"HTTP/1.0 404 Not Found\r\n\r\n"), Ok((404, String::new())));
+        assert!(parse_response(b"HTTP/1.1 200 OK\r\n").is_err());
+        assert!(parse_response(b"garbage\r\n\r\n").is_err());
+    }
+
+    /// Tests host and path extraction
+    #[test]
+    fn test_split_https_url() {
+        assert_eq!(split_https_url("https://crt.sh/?q=example.com"), Some(("crt.sh", "/?q=example.com")));
+        assert_eq!(split_https_url("https://range.example"), Some(("range.example", "/")));
+        assert_eq!(split_https_url("http://range.example/"), None);
+    }
+}
@@ -0,0 +1,276 @@
+//! Bundled translations of validation messages
+//!
+//! Checks name the message they report with `message::Message`; its text
+//! comes from here in the language `ValidationOptions::locale` asks for. Error
+//! and warning types are never translated, so code can keep matching on them.
+
+use serde::{Serialize, Deserialize};
+
+use crate::domain_labels::{DomainError, LabelProblem};
+use crate::message::Message;
+
+/// Language of validation messages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    Pt,
+    De,
+    Fr
+}
+
+impl Locale {
+    /// ISO 639-1 code
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+            Locale::Pt => "pt",
+            Locale::De => "de",
+            Locale::Fr => "fr"
+        }
+    }
+}
+
+impl std::str::FromStr for Locale {
+    type Err = String;
+
+    /// Parses a language code or tag, ignoring case and region (`es-MX`, `pt_BR`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s.split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase();
+        match language.as_str() {
+            "en" => Ok(Locale::En),
+            "es" => Ok(Locale::Es),
+            "pt" => Ok(Locale::Pt),
+            "de" => Ok(Locale::De),
+            "fr" => Ok(Locale::Fr),
+            _ => Err(format!("Unsupported locale: {}", s))
+        }
+    }
+}
+
+/// Messages `translate_message` can look up by their English text
+const CATALOG: [Message; 11] = [
+    Message::Empty,
+    Message::TooLong,
+    Message::InvalidFormat,
+    Message::QuotedLocalPart,
+    Message::DomainLiteral,
+    Message::AccessDenied,
+    Message::ControlCharacter,
+    Message::PatternMismatch,
+    Message::MisplacedComment,
+    Message::MixedLocalPart,
+    Message::SpaceAroundDot
+];
+
+/// Translations of a message in `Locale` order after English: es, pt, de, fr
+fn translations(message: Message) -> [&'static str; 4] {
+    match message {
+        Message::Empty => [
+            "El correo electrónico no puede estar vacío",
+            "O e-mail não pode estar vazio",
+            "Die E-Mail-Adresse darf nicht leer sein",
+            "L'adresse e-mail ne peut pas être vide"
+        ],
+        Message::TooLong => [
+            "El correo electrónico supera la longitud máxima de 320 caracteres",
+            "O e-mail excede o comprimento máximo de 320 caracteres",
+            "Die E-Mail-Adresse überschreitet die maximale Länge von 320 Zeichen",
+            "L'adresse e-mail dépasse la longueur maximale de 320 caractères"
+        ],
+        Message::InvalidFormat => [
+            "Formato de correo electrónico no válido",
+            "Formato de e-mail inválido",
+            "Ungültiges E-Mail-Format",
+            "Format d'adresse e-mail invalide"
+        ],
+        Message::QuotedLocalPart => [
+            "No se aceptan partes locales entre comillas",
+            "Partes locais entre aspas não são aceitas",
+            "Lokale Teile in Anführungszeichen werden nicht akzeptiert",
+            "Les parties locales entre guillemets ne sont pas acceptées"
+        ],
+        Message::DomainLiteral => [
+            "No se aceptan literales de dominio",
+            "Literais de domínio não são aceitos",
+            "Domain-Literale werden nicht akzeptiert",
+            "Les littéraux de domaine ne sont pas acceptés"
+        ],
+        Message::AccessDenied => [
+            "Dirección denegada por la política de acceso",
+            "Endereço negado pela política de acesso",
+            "Adresse durch die Zugriffsrichtlinie abgelehnt",
+            "Adresse refusée par la politique d'accès"
+        ],
+        Message::ControlCharacter => [
+            "El correo electrónico contiene un carácter de control",
+            "O e-mail contém um caractere de controle",
+            "Die E-Mail-Adresse enthält ein Steuerzeichen",
+            "L'adresse e-mail contient un caractère de contrôle"
+        ],
+        Message::PatternMismatch => [
+            "El correo electrónico no coincide con el patrón requerido",
+            "O e-mail não corresponde ao padrão exigido",
+            "Die E-Mail-Adresse entspricht nicht dem vorgegebenen Muster",
+            "L'adresse e-mail ne correspond pas au format requis"
+        ],
+        Message::MisplacedComment => [
+            "Los comentarios solo se permiten antes o después de la parte local y del dominio",
+            "Comentários só são permitidos antes ou depois da parte local e do domínio",
+            "Kommentare sind nur vor oder nach dem lokalen Teil und der Domain erlaubt",
+            "Les commentaires ne sont autorisés qu'avant ou après la partie locale et le domaine"
+        ],
+        Message::MixedLocalPart => [
+            "La parte local mezcla palabras entre comillas y sin comillas, una sintaxis obsoleta de RFC 5322",
+            "A parte local mistura palavras entre aspas e sem aspas, uma sintaxe obsoleta da RFC 5322",
+            "Der lokale Teil mischt Wörter mit und ohne Anführungszeichen, eine veraltete Syntax nach RFC 5322",
+            "La partie locale mélange des mots entre guillemets et sans guillemets, une syntaxe obsolète de la RFC 5322"
+        ],
+        Message::SpaceAroundDot => [
+            "Los espacios o comentarios junto a un punto son sintaxis obsoleta de RFC 5322",
+            "Espaços ou comentários ao redor de um ponto são sintaxe obsoleta da RFC 5322",
+            "Leerraum oder ein Kommentar um einen Punkt ist veraltete Syntax nach RFC 5322",
+            "Les espaces ou commentaires autour d'un point sont une syntaxe obsolète de la RFC 5322"
+        ]
+    }
+}
+
+impl Message {
+    /// Text in `locale`
+    pub(crate) fn text(self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::En => self.english(),
+            locale => translations(self)[locale as usize - 1]
+        }
+    }
+}
+
+/// Translation of an English validation message, or None when the message
+/// is not in the catalog
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::{translate_message, Locale};
+/// assert_eq!(translate_message("Invalid email format", Locale::Es), Some("Formato de correo electrónico no válido"));
+/// ```
+pub fn translate_message(message: &str, locale: Locale) -> Option<&'static str> {
+    CATALOG.iter().find(|known| known.english() == message).map(|known| known.text(locale))
+}
+
+/// Error message for an address longer than a configured `max_length`
+pub(crate) fn too_long(locale: Locale, max_length: usize) -> String {
+    match locale {
+        Locale::En => format!("Email exceeds maximum length of {} characters", max_length),
+        Locale::Es => format!("El correo electrónico supera la longitud máxima de {} caracteres", max_length),
+        Locale::Pt => format!("O e-mail excede o comprimento máximo de {} caracteres", max_length),
+        Locale::De => format!("Die E-Mail-Adresse überschreitet die maximale Länge von {} Zeichen", max_length),
+        Locale::Fr => format!("L'adresse e-mail dépasse la longueur maximale de {} caractères", max_length)
+    }
+}
+
+/// Text of the "LocalPartTooLong" warning
+pub(crate) fn local_part_too_long(locale: Locale, length: usize, limit: usize) -> String {
+    match locale {
+        Locale::En => format!("Local part is {} characters, exceeding the RFC 5321 limit of {}", length, limit),
+        Locale::Es => format!("La parte local tiene {} caracteres y supera el límite de {} de RFC 5321", length, limit),
+        Locale::Pt => format!("A parte local tem {} caracteres, excedendo o limite de {} da RFC 5321", length, limit),
+        Locale::De => format!("Der lokale Teil hat {} Zeichen und überschreitet das RFC-5321-Limit von {}", length, limit),
+        Locale::Fr => format!("La partie locale compte {} caractères, au-delà de la limite de {} de la RFC 5321", length, limit)
+    }
+}
+
+/// Text of the "UnusualTld" warning
+pub(crate) fn unusual_tld(locale: Locale, tld: &str) -> String {
+    match locale {
+        Locale::En => format!("Top-level domain \".{}\" is uncommon", tld),
+        Locale::Es => format!("El dominio de nivel superior \".{}\" es poco común", tld),
+        Locale::Pt => format!("O domínio de nível superior \".{}\" é incomum", tld),
+        Locale::De => format!("Die Top-Level-Domain \".{}\" ist ungewöhnlich", tld),
+        Locale::Fr => format!("Le domaine de premier niveau \".{}\" est inhabituel", tld)
+    }
+}
+
+/// Text of a `domain_labels::DomainError`
+pub(crate) fn domain_error(locale: Locale, error: &DomainError) -> String {
+    let (index, label, problem) = match error {
+        DomainError::TooLong { length } => return match locale {
+            Locale::En => format!("Domain is {} octets, exceeding the limit of 255", length),
+            Locale::Es => format!("El dominio tiene {} octetos y supera el límite de 255", length),
+            Locale::Pt => format!("O domínio tem {} octetos, excedendo o limite de 255", length),
+            Locale::De => format!("Die Domain hat {} Oktette und überschreitet das Limit von 255", length),
+            Locale::Fr => format!("Le domaine compte {} octets, au-delà de la limite de 255", length)
+        },
+        DomainError::Label { index, label, problem } => (index, label, problem)
+    };
+    let problem = match (locale, problem) {
+        (Locale::En, LabelProblem::Empty) => "is empty",
+        (Locale::En, LabelProblem::TooLong) => "exceeds 63 octets",
+        (Locale::En, LabelProblem::LeadingHyphen) => "starts with a hyphen",
+        (Locale::En, LabelProblem::TrailingHyphen) => "ends with a hyphen",
+        (Locale::En, LabelProblem::InvalidPunycode) => "is not valid punycode",
+        (Locale::Es, LabelProblem::Empty) => "está vacía",
+        (Locale::Es, LabelProblem::TooLong) => "supera los 63 octetos",
+        (Locale::Es, LabelProblem::LeadingHyphen) => "empieza con un guion",
+        (Locale::Es, LabelProblem::TrailingHyphen) => "termina con un guion",
+        (Locale::Es, LabelProblem::InvalidPunycode) => "no es punycode válido",
+        (Locale::Pt, LabelProblem::Empty) => "está vazio",
+        (Locale::Pt, LabelProblem::TooLong) => "excede 63 octetos",
+        (Locale::Pt, LabelProblem::LeadingHyphen) => "começa com hífen",
+        (Locale::Pt, LabelProblem::TrailingHyphen) => "termina com hífen",
+        (Locale::Pt, LabelProblem::InvalidPunycode) => "não é punycode válido",
+        (Locale::De, LabelProblem::Empty) => "ist leer",
+        (Locale::De, LabelProblem::TooLong) => "überschreitet 63 Oktette",
+        (Locale::De, LabelProblem::LeadingHyphen) => "beginnt mit einem Bindestrich",
+        (Locale::De, LabelProblem::TrailingHyphen) => "endet mit einem Bindestrich",
+        (Locale::De, LabelProblem::InvalidPunycode) => "ist kein gültiges Punycode",
+        (Locale::Fr, LabelProblem::Empty) => "est vide",
+        (Locale::Fr, LabelProblem::TooLong) => "dépasse 63 octets",
+        (Locale::Fr, LabelProblem::LeadingHyphen) => "commence par un tiret",
+        (Locale::Fr, LabelProblem::TrailingHyphen) => "se termine par un tiret",
+        (Locale::Fr, LabelProblem::InvalidPunycode) => "n'est pas un punycode valide"
+    };
+    match locale {
+        Locale::En => format!("Domain label {} (\"{}\") {}", index, label, problem),
+        Locale::Es => format!("La etiqueta de dominio {} (\"{}\") {}", index, label, problem),
+        Locale::Pt => format!("O rótulo de domínio {} (\"{}\") {}", index, label, problem),
+        Locale::De => format!("Das Domain-Label {} (\"{}\") {}", index, label, problem),
+        Locale::Fr => format!("Le libellé de domaine {} (\"{}\") {}", index, label, problem)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests catalog lookups and locale parsing
+    #[test]
+    fn test_translate_message() {
+        assert_eq!(translate_message("Invalid email format", Locale::Pt), Some("Formato de e-mail inválido"));
+        assert_eq!(translate_message("Invalid email format", Locale::En), Some("Invalid email format"));
+        assert_eq!(translate_message("Something else", Locale::De), None);
+
+        assert_eq!("es-MX".parse::<Locale>(), Ok(Locale::Es));
+        assert_eq!("PT_br".parse::<Locale>(), Ok(Locale::Pt));
+        assert!("it".parse::<Locale>().is_err());
+    }
+
+    /// Tests that every message has text in all five locales, translated
+    /// rather than copied from English
+    #[test]
+    fn test_catalog_complete() {
+        let locales = [Locale::En, Locale::Es, Locale::Pt, Locale::De, Locale::Fr];
+        for (index, message) in CATALOG.iter().enumerate() {
+            assert!(!CATALOG[..index].contains(message), "{:?}", message);
+            for locale in locales {
+                let text = message.text(locale);
+                assert!(!text.is_empty(), "{:?} {:?}", message, locale);
+                assert!(locale == Locale::En || text != message.english(), "{:?} {:?}", message, locale);
+            }
+            assert_eq!(translate_message(message.english(), Locale::Fr), Some(message.text(Locale::Fr)));
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! Conversions into address types of other crates (`lettre`, `email_address`)
+
+use crate::{EmailAddress, EmailParseError};
+
+/// Error for a validated address the target crate refuses to represent
+fn conversion_error(target: &str, details: impl ToString) -> EmailParseError {
+    EmailParseError {
+        error_type: "ConversionError".to_string(),
+        message: format!("Address is not representable as {}", target),
+        details: Some(details.to_string()),
+        position: None
+    }
+}
+
+#[cfg(feature = "lettre")]
+impl TryFrom<&EmailAddress> for lettre::Address {
+    type Error = EmailParseError;
+
+    fn try_from(address: &EmailAddress) -> Result<Self, Self::Error> {
+        lettre::Address::new(address.local_part(), address.domain())
+            .map_err(|e| conversion_error("lettre::Address", e))
+    }
+}
+
+#[cfg(feature = "lettre")]
+impl TryFrom<&EmailAddress> for lettre::message::Mailbox {
+    type Error = EmailParseError;
+
+    fn try_from(address: &EmailAddress) -> Result<Self, Self::Error> {
+        Ok(lettre::message::Mailbox::new(None, address.try_into()?))
+    }
+}
+
+#[cfg(feature = "lettre")]
+impl TryFrom<&crate::Mailbox> for lettre::message::Mailbox {
+    type Error = EmailParseError;
+
+    /// Converts a parsed mailbox, keeping its display name; fails if the
+    /// address did not pass validation
+    fn try_from(mailbox: &crate::Mailbox) -> Result<Self, Self::Error> {
+        let (true, Some(local_part), Some(domain)) = (mailbox.address.is_valid, &mailbox.address.local_part, &mailbox.address.domain) else {
+            return Err(conversion_error("lettre::message::Mailbox", "address is invalid"));
+        };
+        let address = lettre::Address::new(local_part, domain)
+            .map_err(|e| conversion_error("lettre::message::Mailbox", e))?;
+        Ok(lettre::message::Mailbox::new(mailbox.display_name.clone(), address))
+    }
+}
+
+#[cfg(feature = "email_address")]
+impl TryFrom<&EmailAddress> for email_address::EmailAddress {
+    type Error = EmailParseError;
+
+    fn try_from(address: &EmailAddress) -> Result<Self, Self::Error> {
+        address.as_str()
+            .parse::<email_address::EmailAddress>()
+            .map_err(|e| conversion_error("email_address::EmailAddress", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests conversion of validated addresses and mailboxes into lettre types
+    #[cfg(feature = "lettre")]
+    #[test]
+    fn test_lettre_conversions() {
+        let address: EmailAddress = "jane@Example.com".parse().unwrap();
+        let mailbox = lettre::message::Mailbox::try_from(&address).unwrap();
+        assert_eq!(mailbox.to_string(), "jane@example.com");
+
+        let parsed = crate::parse_mailbox("Jane Doe <jane@example.com>").unwrap();
+        let mailbox = lettre::message::Mailbox::try_from(&parsed).unwrap();
+        assert_eq!(mailbox.name, Some("Jane Doe".to_string()));
+        assert_eq!(mailbox.email.domain(), "example.com");
+
+        let invalid = crate::parse_mailbox("Jane <not-an-email>").unwrap();
+        assert!(lettre::message::Mailbox::try_from(&invalid).is_err());
+    }
+
+    /// Tests conversion into email_address::EmailAddress
+    #[cfg(feature = "email_address")]
+    #[test]
+    fn test_email_address_conversion() {
+        let address: EmailAddress = "jane+news@example.com".parse().unwrap();
+        let converted = email_address::EmailAddress::try_from(&address).unwrap();
+        assert_eq!(converted.as_str(), "jane+news@example.com");
+    }
+}
@@ -0,0 +1,267 @@
+//! Resumable verification jobs
+//!
+//! A job writes one JSONL record per address, in the same shape as
+//! `validate_stream`, and flushes every `checkpoint_interval` records. The
+//! output doubles as the checkpoint: resuming reads it back and skips every
+//! address it already holds, so a crashed multi-hour run loses at most one
+//! interval of work.
+
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use serde::{Serialize, Deserialize};
+
+use crate::breach::BreachRangeSource;
+use crate::dns::DnsResolver;
+use crate::{EmailParseError, VerificationResult, Verifier};
+
+/// Outcome of verifying one address
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum JobOutcome {
+    Result(Box<VerificationResult>),
+    Error(EmailParseError)
+}
+
+/// One line of job output
+#[derive(Debug, Serialize)]
+struct JobRecord<'a> {
+    email: &'a str,
+    #[serde(flatten)]
+    outcome: JobOutcome
+}
+
+/// The part of a job record needed to resume
+#[derive(Debug, Deserialize)]
+struct CompletedRecord {
+    email: String
+}
+
+/// Counts reported when a job run ends
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobSummary {
+    /// Addresses verified and written by this run
+    pub processed: usize,
+    /// Addresses skipped because an earlier run already verified them
+    pub skipped: usize,
+    /// Addresses written as errors (syntax failures the parser rejects outright)
+    pub failed: usize,
+    /// Whether the run stopped early because the verifier was cancelled
+    pub cancelled: bool
+}
+
+/// A batch verification that can be resumed after a crash
+///
+/// # Examples
+/// ```no_run
+/// # use email_validator_wasm::dns::SystemResolver;
+/// # use email_validator_wasm::smtp::SmtpVerifier;
+/// # use email_validator_wasm::{VerificationJob, Verifier};
+/// # async fn example(resolver: SystemResolver, smtp: SmtpVerifier, emails: Vec<String>) -> std::io::Result<()> {
+/// let verifier = Verifier::new(resolver).with_smtp(smtp);
+/// let mut job = VerificationJob::new(&verifier).with_checkpoint_interval(50);
+/// // Re-running after a crash picks up where results.jsonl left off
+/// let summary = job.run_to_file(emails, "results.jsonl").await?;
+/// println!("{} verified, {} already done", summary.processed, summary.skipped);
+/// # Ok(())
+/// # }
+/// ```
+pub struct VerificationJob<'a, R, B> {
+    verifier: &'a Verifier<R, B>,
+    checkpoint_interval: usize,
+    completed: HashSet<String>
+}
+
+impl<'a, R: DnsResolver, B: BreachRangeSource> VerificationJob<'a, R, B> {
+    pub fn new(verifier: &'a Verifier<R, B>) -> Self {
+        VerificationJob {
+            verifier,
+            checkpoint_interval: 100,
+            completed: HashSet::new()
+        }
+    }
+
+    /// Flushes output every `records` addresses (default 100, minimum 1)
+    pub fn with_checkpoint_interval(mut self, records: usize) -> Self {
+        self.checkpoint_interval = records.max(1);
+        self
+    }
+
+    /// Marks every address recorded in the output of an earlier run as done
+    ///
+    /// Lines that don't parse, such as one cut short by a crash, are ignored
+    /// and their addresses verified again.
+    pub fn resume_from<Rd: BufRead>(mut self, reader: Rd) -> io::Result<Self> {
+        for line in reader.lines() {
+            if let Ok(record) = serde_json::from_str::<CompletedRecord>(&line?) {
+                self.completed.insert(record.email);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Number of addresses already done
+    pub fn completed(&self) -> usize {
+        self.completed.len()
+    }
+
+    /// Verifies each address not yet done, writing JSONL records to `writer`
+    ///
+    /// Duplicates within `emails` are verified once. Cancelling the verifier
+    /// ends the run after flushing; the cancelled address is not written.
+    pub async fn run<I, S, W>(&mut self, emails: I, writer: W) -> io::Result<JobSummary>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        W: Write
+    {
+        self.run_with_checkpoint(emails, writer, |writer| writer.flush()).await
+    }
+
+    /// Runs the job with `path` as both output and checkpoint
+    ///
+    /// An existing file is resumed: its addresses are skipped, a record cut
+    /// short by a crash is dropped, and new records are appended. Checkpoints
+    /// are synced to disk.
+    pub async fn run_to_file<I, S>(&mut self, emails: I, path: impl AsRef<Path>) -> io::Result<JobSummary>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>
+    {
+        let mut file = OpenOptions::new().read(true).append(true).create(true).open(path)?;
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents)?;
+        let complete = contents.iter().rposition(|&byte| byte == b'\n').map_or(0, |end| end + 1);
+        if complete < contents.len() {
+            // Append mode writes after the new end
+            file.set_len(complete as u64)?;
+        }
+
+        self.completed.extend(
+            BufReader::new(&contents[..complete])
+                .lines()
+                .map_while(Result::ok)
+                .filter_map(|line| serde_json::from_str::<CompletedRecord>(&line).ok())
+                .map(|record| record.email)
+        );
+
+        self.run_with_checkpoint(emails, BufWriter::new(file), |writer: &mut BufWriter<File>| {
+            writer.flush()?;
+            writer.get_ref().sync_data()
+        })
+        .await
+    }
+
+    async fn run_with_checkpoint<I, S, W, C>(&mut self, emails: I, mut writer: W, mut checkpoint: C) -> io::Result<JobSummary>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        W: Write,
+        C: FnMut(&mut W) -> io::Result<()>
+    {
+        let mut summary = JobSummary::default();
+        let mut since_checkpoint = 0;
+
+        for email in emails {
+            let email = email.as_ref().trim();
+            if email.is_empty() {
+                continue;
+            }
+            if self.completed.contains(email) {
+                summary.skipped += 1;
+                continue;
+            }
+
+            let outcome = match self.verifier.verify(email).await {
+                Ok(result) => JobOutcome::Result(Box::new(result)),
+                Err(e) if e.error_type == "Cancelled" => {
+                    summary.cancelled = true;
+                    break;
+                }
+                Err(e) => {
+                    summary.failed += 1;
+                    JobOutcome::Error(e)
+                }
+            };
+            serde_json::to_writer(&mut writer, &JobRecord { email, outcome })?;
+            writer.write_all(b"\n")?;
+            self.completed.insert(email.to_string());
+            summary.processed += 1;
+
+            since_checkpoint += 1;
+            if since_checkpoint == self.checkpoint_interval {
+                checkpoint(&mut writer)?;
+                since_checkpoint = 0;
+            }
+        }
+
+        checkpoint(&mut writer)?;
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::{MockResolver, MxRecord};
+    use crate::test_util::block_on;
+    use crate::CancellationToken;
+
+    fn verifier() -> Verifier<MockResolver> {
+        Verifier::new(MockResolver::new().with_mx("example.com", vec![MxRecord { preference: 10, exchange: "mx.example.com".to_string() }]))
+    }
+
+    /// Tests that a resumed job skips recorded addresses and re-verifies a truncated record
+    #[test]
+    fn test_job_resume() {
+        let verifier = verifier();
+        let mut output = Vec::new();
+        let summary = block_on(VerificationJob::new(&verifier).run(["a@example.com", "a@example.com", "b\r@example.com", "c@missing.com"], &mut output)).unwrap();
+        assert_eq!(summary, JobSummary { processed: 3, skipped: 1, failed: 1, cancelled: false });
+
+        let lines: Vec<serde_json::Value> = output.split(|&b| b == b'\n').filter(|line| !line.is_empty()).map(|line| serde_json::from_slice(line).unwrap()).collect();
+        assert_eq!(lines[0]["email"], "a@example.com");
+        assert_eq!(lines[0]["has_mx"], true);
+        assert_eq!(lines[1]["error_type"], "ControlCharacter");
+        assert_eq!(lines[2]["domain_exists"], false);
+
+        // Simulate a crash partway through the last record
+        output.truncate(output.len() - 10);
+        let mut job = VerificationJob::new(&verifier).resume_from(&output[..]).unwrap();
+        assert_eq!(job.completed(), 2);
+        let mut resumed = Vec::new();
+        let summary = block_on(job.run(["a@example.com", "b\r@example.com", "c@missing.com", "d@example.com"], &mut resumed)).unwrap();
+        assert_eq!(summary, JobSummary { processed: 2, skipped: 2, failed: 0, cancelled: false });
+        assert_eq!(String::from_utf8(resumed).unwrap().lines().count(), 2);
+    }
+
+    /// Tests resuming from a file with a torn final line
+    #[test]
+    fn test_job_run_to_file() {
+        let path = std::env::temp_dir().join(format!("job-{}.jsonl", std::process::id()));
+        std::fs::write(&path, "{\"email\":\"a@example.com\",\"is_valid\":true}\n{\"email\":\"b@exa").unwrap();
+
+        let verifier = verifier();
+        let summary = block_on(VerificationJob::new(&verifier).with_checkpoint_interval(1).run_to_file(["a@example.com", "b@example.com"], &path)).unwrap();
+        assert_eq!((summary.processed, summary.skipped), (1, 1));
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let emails: Vec<String> = contents.lines().map(|line| serde_json::from_str::<CompletedRecord>(line).unwrap().email).collect();
+        assert_eq!(emails, ["a@example.com", "b@example.com"]);
+    }
+
+    /// Tests that cancellation stops the run without recording the address
+    #[test]
+    fn test_job_cancelled() {
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let verifier = verifier().with_cancellation(cancel);
+        let mut output = Vec::new();
+        let summary = block_on(VerificationJob::new(&verifier).run(["a@example.com"], &mut output)).unwrap();
+        assert!(summary.cancelled);
+        assert!(output.is_empty());
+    }
+}
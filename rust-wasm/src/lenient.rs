@@ -0,0 +1,136 @@
+//! Clean-up of loosely formatted input (`  <user@example.com>, `,
+//! `ｕｓｅｒ＠ｅｘａｍｐｌｅ．ｃｏｍ`)
+
+use unicode_normalization::UnicodeNormalization;
+
+/// Characters stripped from the end of input in lenient mode
+const TRAILING_PUNCTUATION: [char; 7] = ['.', ',', ';', ':', '!', '?', '\''];
+
+/// Strips surrounding whitespace, angle brackets and trailing punctuation
+///
+/// Returns the cleaned address and a description of each correction made,
+/// in the order applied.
+pub(crate) fn clean_input(email: &str) -> (&str, Vec<String>) {
+    let mut corrections = Vec::new();
+    let mut cleaned = email;
+    let mut trimmed_whitespace = false;
+
+    loop {
+        let before = cleaned;
+
+        if cleaned.trim() != cleaned {
+            cleaned = cleaned.trim();
+            trimmed_whitespace = true;
+        }
+
+        let without_punctuation = cleaned.trim_end_matches(TRAILING_PUNCTUATION);
+        if without_punctuation.len() != cleaned.len() {
+            corrections.push(format!("Removed trailing punctuation \"{}\"", &cleaned[without_punctuation.len()..]));
+            cleaned = without_punctuation;
+        }
+
+        if let Some(inner) = cleaned.strip_prefix('<').and_then(|rest| rest.strip_suffix('>')) {
+            corrections.push("Removed surrounding angle brackets".to_string());
+            cleaned = inner;
+        }
+
+        if cleaned == before {
+            break;
+        }
+    }
+
+    if trimmed_whitespace {
+        corrections.insert(0, "Trimmed surrounding whitespace".to_string());
+    }
+
+    (cleaned, corrections)
+}
+
+/// Normalizes input to NFC and folds full-width forms and smart quotes to ASCII
+///
+/// Returns the normalized address and a description of each transformation
+/// that changed the input.
+pub(crate) fn normalize_unicode(email: &str) -> (String, Vec<String>) {
+    let mut corrections = Vec::new();
+
+    let composed: String = email.nfc().collect();
+    if composed != email {
+        corrections.push("Normalized to Unicode NFC".to_string());
+    }
+
+    let mut folded_width = false;
+    let mut folded_quotes = false;
+    let folded = composed
+        .chars()
+        .map(|c| match c {
+            // Full-width ASCII variants (U+FF01-U+FF5E) sit at a fixed offset
+            '\u{FF01}'..='\u{FF5E}' => {
+                folded_width = true;
+                char::from_u32(c as u32 - 0xFEE0).unwrap_or(c)
+            }
+            '\u{3000}' => {
+                folded_width = true;
+                ' '
+            }
+            '\u{3002}' | '\u{FF61}' => {
+                folded_width = true;
+                '.'
+            }
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => {
+                folded_quotes = true;
+                '\''
+            }
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => {
+                folded_quotes = true;
+                '"'
+            }
+            c => c
+        })
+        .collect();
+
+    if folded_width {
+        corrections.push("Folded full-width characters to ASCII".to_string());
+    }
+    if folded_quotes {
+        corrections.push("Replaced smart quotes with ASCII quotes".to_string());
+    }
+
+    (folded, corrections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests stripping of whitespace, brackets and punctuation in combination
+    #[test]
+    fn test_clean_input() {
+        let (cleaned, corrections) = clean_input("  < user@example.com >, ");
+        assert_eq!(cleaned, "user@example.com");
+        assert_eq!(corrections, vec![
+            "Trimmed surrounding whitespace",
+            "Removed trailing punctuation \",\"",
+            "Removed surrounding angle brackets"
+        ]);
+
+        let (cleaned, corrections) = clean_input("user@example.com");
+        assert_eq!(cleaned, "user@example.com");
+        assert!(corrections.is_empty());
+    }
+
+    /// Tests NFC composition and full-width/smart-quote folding
+    #[test]
+    fn test_normalize_unicode() {
+        let (normalized, corrections) = normalize_unicode("ｕｓｅｒ＠ｅｘａｍｐｌｅ．ｃｏｍ");
+        assert_eq!(normalized, "user@example.com");
+        assert_eq!(corrections, vec!["Folded full-width characters to ASCII"]);
+
+        let (normalized, corrections) = normalize_unicode("\u{2018}jose\u{0301}\u{2019}");
+        assert_eq!(normalized, "'jos\u{00E9}'");
+        assert_eq!(corrections, vec!["Normalized to Unicode NFC", "Replaced smart quotes with ASCII quotes"]);
+
+        let (normalized, corrections) = normalize_unicode("user@example.com");
+        assert_eq!(normalized, "user@example.com");
+        assert!(corrections.is_empty());
+    }
+}
@@ -1,10 +1,278 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+// Timers, fetch and AbortSignal support on wasm32 go through js-sys
+#[cfg(all(feature = "std", target_arch = "wasm32", not(feature = "wasm")))]
+compile_error!("the `wasm` feature is required when building with `std` for wasm32");
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::{String, ToString}};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
 use serde::{Serialize, Deserialize};
+#[cfg(feature = "regex-compat")]
 use regex::Regex;
-use wasm_bindgen::prelude::*;
+
+#[cfg(feature = "std")]
+mod academic;
+#[cfg(feature = "std")]
+mod access;
+#[cfg(feature = "std")]
+mod address;
+#[cfg(all(feature = "net", not(target_arch = "wasm32")))]
+mod audit;
+#[cfg(feature = "std")]
+mod batch;
+#[cfg(all(feature = "net", not(target_arch = "wasm32")))]
+mod blocking;
+#[cfg(feature = "std")]
+mod bloom;
+#[cfg(feature = "net")]
+pub mod breach;
+#[cfg(feature = "std")]
+mod build_info;
+#[cfg(feature = "std")]
+mod cache;
+#[cfg(feature = "std")]
+mod calibrate;
+#[cfg(feature = "net")]
+mod cancel;
+#[cfg(any(feature = "wasm", feature = "node"))]
+mod casing;
+#[cfg(feature = "std")]
+mod category;
+#[cfg(feature = "std")]
+mod cfws;
+#[cfg(all(feature = "arrow", not(target_arch = "wasm32")))]
+mod columnar;
+#[cfg(feature = "std")]
+mod confusables;
+#[cfg(feature = "std")]
+mod country;
+#[cfg(feature = "net")]
+mod ct_log;
+#[cfg(feature = "csv")]
+mod csv_input;
+#[cfg(feature = "std")]
+mod custom_pattern;
+#[cfg(feature = "std")]
+mod dedupe;
+#[cfg(feature = "std")]
+mod differential;
+#[cfg(feature = "std")]
+mod domain_labels;
+#[cfg(feature = "std")]
+mod domain_lists;
+#[cfg(feature = "net")]
+pub mod dns;
+#[cfg(feature = "net")]
+mod dnsbl;
+#[cfg(feature = "std")]
+mod enrich;
+#[cfg(feature = "net")]
+mod events;
+#[cfg(feature = "std")]
+mod extract;
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+mod ffi;
+#[cfg(all(feature = "net", target_arch = "wasm32"))]
+mod fetch;
+#[cfg(feature = "std")]
+mod government;
+#[cfg(feature = "net")]
+mod gravatar;
+#[cfg(feature = "std")]
+mod hash;
+#[cfg(feature = "std")]
+mod header;
+mod html5;
+#[cfg(all(feature = "tls", not(target_arch = "wasm32")))]
+mod http;
+#[cfg(any(feature = "lettre", feature = "email_address"))]
+mod interop;
+#[cfg(all(feature = "net", not(target_arch = "wasm32")))]
+mod job;
+#[cfg(feature = "std")]
+mod i18n;
+#[cfg(feature = "std")]
+mod lenient;
+#[cfg(feature = "std")]
+mod local_part;
+#[cfg(feature = "std")]
+mod mailbox;
+#[cfg(feature = "std")]
+mod mailto;
+mod message;
+#[cfg(feature = "net")]
+mod mta_sts;
+#[cfg(feature = "net")]
+mod mx_provider;
+#[cfg(feature = "node")]
+mod node;
+#[cfg(feature = "std")]
+mod obsolete;
+mod parser;
+#[cfg(feature = "std")]
+mod partial;
+#[cfg(feature = "std")]
+mod patterns;
+#[cfg(feature = "std")]
+mod placeholder;
+mod prefilter;
+#[cfg(feature = "net")]
+mod profile;
+#[cfg(all(feature = "net", not(target_arch = "wasm32")))]
+mod proxy;
+mod providers;
+#[cfg(feature = "std")]
+mod punycode;
+#[cfg(feature = "net")]
+mod rdap;
+#[cfg(feature = "std")]
+mod redact;
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "std")]
+mod relay;
+#[cfg(feature = "net")]
+mod report;
+#[cfg(feature = "net")]
+mod sandbox;
+#[cfg(all(feature = "server", not(target_arch = "wasm32")))]
+pub mod server;
+#[cfg(feature = "net")]
+pub mod smtp;
+#[cfg(feature = "net")]
+mod stage;
+#[cfg(all(feature = "storage", not(target_arch = "wasm32")))]
+mod storage;
+#[cfg(all(feature = "toml", not(target_arch = "wasm32")))]
+mod settings;
+#[cfg(feature = "std")]
+mod spamtrap;
+#[cfg(feature = "std")]
+mod suggest;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "metrics")]
+pub mod telemetry;
+#[cfg(all(feature = "std", test))]
+mod test_util;
+#[cfg(feature = "std")]
+mod time;
+mod tld_risk;
+#[cfg(feature = "std")]
+mod typosquat;
+#[cfg(feature = "net")]
+mod verify;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "std")]
+pub use access::{AccessAction, AccessPolicy, AccessRule};
+#[cfg(feature = "std")]
+pub use address::EmailAddress;
+#[cfg(all(feature = "net", not(target_arch = "wasm32")))]
+pub use audit::{config_version, AuditEntry, AuditLog, AuditStage};
+#[cfg(feature = "std")]
+pub use batch::parse_and_validate_emails;
+#[cfg(feature = "parallel")]
+pub use batch::parse_and_validate_emails_par;
+#[cfg(feature = "std")]
+pub use bloom::{BloomFilter, BloomFilterBuilder};
+#[cfg(feature = "std")]
+pub use build_info::{build_info, version, BuildInfo, ListVersion};
+#[cfg(feature = "std")]
+pub use cache::{LruCache, VerificationCache};
+#[cfg(feature = "std")]
+pub use calibrate::{calibrate, calibrate_breakdowns, CalibrationMetrics, CalibrationReport, DatasetLabel, LabeledEmail, ScoreWeights};
+#[cfg(feature = "net")]
+pub use cancel::{CancellationToken, DropGuard};
+#[cfg(feature = "std")]
+pub use category::DomainCategory;
+#[cfg(all(feature = "arrow", not(target_arch = "wasm32")))]
+pub use columnar::{to_record_batch, validation_schema, write_parquet};
+#[cfg(feature = "csv")]
+pub use csv_input::{validate_csv, CsvOptions, EmailColumn, CSV_RESULT_COLUMNS};
+#[cfg(feature = "net")]
+pub use ct_log::DEFAULT_CT_LOG_ENDPOINT;
+#[cfg(feature = "std")]
+pub use custom_pattern::CustomPattern;
+#[cfg(feature = "std")]
+pub use dedupe::{canonical_email, find_duplicates, DuplicateGroup};
+#[cfg(feature = "std")]
+pub use differential::{compare_configurations, DifferentialReport, Disagreement, ValidationOutcome};
+#[cfg(feature = "std")]
+pub use domain_labels::validate_domain_labels;
+#[cfg(feature = "net")]
+pub use dnsbl::Blocklist;
+#[cfg(feature = "std")]
+pub use domain_lists::{load_domain_lists, DomainLists};
+#[cfg(feature = "std")]
+pub use enrich::{enrich_result, validate_and_enrich, Enricher, Metadata};
+#[cfg(feature = "net")]
+pub use events::{EventObserver, PipelineEvent};
+#[cfg(feature = "std")]
+pub use extract::{extract_emails, ExtractedEmail};
+#[cfg(feature = "std")]
+pub use hash::hash_email;
+#[cfg(feature = "std")]
+pub use header::is_header_safe;
+pub use html5::is_valid_html5_email;
+#[cfg(all(feature = "net", not(target_arch = "wasm32")))]
+pub use job::{JobSummary, VerificationJob};
+#[cfg(feature = "std")]
+pub use i18n::{translate_message, Locale};
+#[cfg(feature = "std")]
+pub use local_part::LocalPartThresholds;
+#[cfg(feature = "std")]
+pub use mailbox::{parse_address_list, parse_address_list_with_options, parse_mailbox, parse_mailbox_with_options, Mailbox};
+#[cfg(feature = "std")]
+pub use mailto::{parse_mailto, MailtoUri};
+#[cfg(feature = "net")]
+pub use mta_sts::PolicyMode;
+#[cfg(feature = "net")]
+pub use mx_provider::MxProvider;
+pub use parser::{parse_address, DomainKind, LocalPartKind, ParsedAddress, Span};
+#[cfg(feature = "std")]
+pub use partial::{validate_partial, PartialState, PartialValidation};
+pub use prefilter::quick_reject;
+#[cfg(feature = "net")]
+pub use profile::{Stages, VerificationProfile};
+#[cfg(all(feature = "net", not(target_arch = "wasm32")))]
+pub use proxy::{Proxy, ProxyKind};
+pub use providers::MailboxProvider;
+#[cfg(feature = "std")]
+pub use redact::{redact_email, LogRedaction};
+#[cfg(feature = "net")]
+pub use report::{BatchReport, DomainReport, VerdictCounts};
+#[cfg(all(feature = "net", not(target_arch = "wasm32")))]
+pub use sandbox::SandboxConnector;
+#[cfg(feature = "net")]
+pub use sandbox::{SandboxResolver, SANDBOX_DOMAIN};
+#[cfg(all(feature = "storage", not(target_arch = "wasm32")))]
+pub use storage::{HistoryEntry, HistoryQuery, StorageError, VerdictChange, VerificationStore};
+#[cfg(all(feature = "toml", not(target_arch = "wasm32")))]
+pub use settings::{DnsSettings, ListPaths, Settings, SmtpSettings, DEFAULT_SETTINGS_FILE};
+#[cfg(feature = "std")]
+pub use spamtrap::SpamtrapRisk;
+#[cfg(feature = "std")]
+pub use stream::validate_stream;
+#[cfg(feature = "std")]
+pub use suggest::{suggest_corrections, CorrectionCandidate, CorrectionKind};
+#[cfg(feature = "net")]
+pub use verify::{due_for_reverification, DomainChecks, StageTimeouts, VerificationResult, Verdict, Verifier};
+
+use message::Message;
 
 /// Result of email parsing and validation
 /// Contains validation status, parsed components, and domain risk scoring
-#[derive(Debug, Serialize, Deserialize)]
+#[cfg(feature = "std")]
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EmailParseResult {
     /// Whether the email is valid according to RFC standards
     pub is_valid: bool,
@@ -15,75 +283,503 @@ pub struct EmailParseResult {
     /// Risk score for the domain (0-100, higher is more trusted)
     pub domain_score: Option<f64>,
     /// Error message if validation failed
-    pub error_message: Option<String>
+    pub error_message: Option<String>,
+    /// Whether the domain is a homograph of a trusted provider (e.g., Cyrillic
+    /// "о" in place of Latin "o"); such domains receive a heavy score penalty
+    pub is_confusable_spoof: Option<bool>,
+    /// Whether the domain is a near-miss lookalike of a protected domain
+    pub is_typosquat: Option<bool>,
+    /// The protected domain being imitated, if `is_typosquat` is true
+    pub typosquat_target: Option<String>,
+    /// How human-chosen the local part looks (0-100, higher is more human);
+    /// only computed when `ValidationOptions::score_local_part` is enabled
+    pub local_part_quality: Option<f64>,
+    /// Whether the address looks auto-generated (`user12@`, `test+0001@`, or
+    /// part of a numbered series in the same batch); only computed when
+    /// `ValidationOptions::detect_suspicious_patterns` is enabled
+    pub is_suspicious_pattern: Option<bool>,
+    /// Index of the earlier input in the same batch that this address
+    /// duplicates once canonicalized (see `canonical_email`); only computed by
+    /// batch APIs when `ValidationOptions::detect_duplicates` is enabled, and
+    /// None for the first occurrence
+    pub duplicate_of: Option<usize>,
+    /// Subaddress tag after the first `+` in the local part (`newsletter` in
+    /// `user+newsletter@gmail.com`), or after the first `-` at Yahoo
+    pub subaddress: Option<String>,
+    /// The local part without its subaddress tag (`user` in `user+newsletter`)
+    pub canonical_local_part: Option<String>,
+    /// The mailbox the address reaches under its provider's rules (see
+    /// `canonical_email`): `J.Doe+news@googlemail.com` is `jdoe@gmail.com`
+    pub canonical_email: Option<String>,
+    /// Consumer mailbox provider serving the domain, with its country
+    /// variants folded together (`hotmail.co.uk` and `live.fr` are both
+    /// `microsoft`)
+    pub mailbox_provider: Option<MailboxProvider>,
+    /// Whether the address is a provider's disposable alias
+    /// (`base-keyword@yahoo.com`); `canonical_email` gives the base address
+    pub is_disposable_alias: Option<bool>,
+    /// Whether the domain is a privacy relay (Apple Hide My Email, Firefox
+    /// Relay, DuckDuckGo, SimpleLogin, addy.io); relays forward to a real
+    /// inbox, so they are not disposable, but the user can turn them off
+    pub is_relay_address: Option<bool>,
+    /// How likely the address is a spamtrap: a trap-named or long-dormant
+    /// role mailbox, or a domain on a loaded or per-call `spamtrap` or
+    /// `expired` list
+    pub spamtrap_risk: Option<SpamtrapRisk>,
+    /// Whether the address is an obvious test input (`test@test.com`,
+    /// `foo@bar.com`, `a@a.com`, or an RFC 2606 domain such as
+    /// `example.com`), for excluding from analytics
+    pub is_test_address: Option<bool>,
+    /// Whether the domain belongs to a university or school, by naming
+    /// pattern (`.edu`, `ac.uk`, `edu.au`, `ac.jp`, `uni-*.de`) or, with the
+    /// `academic-domains` feature, the embedded institution list; for
+    /// student-discount verification
+    pub is_academic: Option<bool>,
+    /// Whether the domain belongs to a government body: `.gov` and `.mil`,
+    /// reserved ccTLD names (`gov.uk`, `gc.ca`, `gouv.fr`, `go.jp`), built-in
+    /// domains such as `bund.de`, or the loaded or per-call `government` list
+    pub is_government: Option<bool>,
+    /// What was stripped or transformed in the input before validation; only
+    /// present when `ValidationOptions::lenient_input` or
+    /// `ValidationOptions::normalize_unicode` is enabled
+    pub corrections: Option<Vec<String>>,
+    /// Text of the RFC 5322 comments removed before validation
+    /// (`john(work)@example.com` records `work`); only present in
+    /// `ValidationMode::Strict`, which accepts comments around the local part
+    /// and domain
+    pub comments: Option<Vec<String>>,
+    /// The address most likely meant, when the domain is a known provider's
+    /// behind a classic TLD typo (`jane@gmail.con` suggests `jane@gmail.com`)
+    /// or the `@` was written out or replaced by a space (`jane(at)example.com`,
+    /// `jane example.com`); set whether or not the input itself is valid
+    pub suggestion: Option<String>,
+    /// Non-fatal concerns about an address that is otherwise accepted
+    #[serde(default)]
+    pub warnings: Vec<ValidationWarning>,
+    /// Hex SHA-256 of the lowercased address (see `hash_email`), for
+    /// deduplicating and joining without storing plaintext
+    pub email_hash: Option<String>,
+    /// ISO 3166-1 alpha-2 code of the country owning the domain's ccTLD
+    /// (`GB` for `.co.uk`); None for generic TLDs
+    pub country: Option<String>,
+    /// Kind of organization behind the domain, for segmentation
+    pub domain_category: Option<DomainCategory>,
+    /// The `ValidationOptions::access_policy` rule that decided the address;
+    /// denied addresses are invalid, allowed ones get a domain score of 100
+    pub matched_access_rule: Option<AccessRule>,
+    /// Contribution of each scoring signal to `domain_score`
+    pub score_breakdown: Option<ScoreBreakdown>,
+    /// Key/value pairs attached by an `Enricher` (company name, CRM id, ...);
+    /// empty unless the result went through `validate_and_enrich` or
+    /// `Verifier::verify_enriched`
+    #[serde(default, skip_serializing_if = "Metadata::is_empty")]
+    pub metadata: Metadata
+}
+
+/// Borrowed view of a parsed address, returned by `parse_email_ref`
+/// Holds the syntax-level fields of `EmailParseResult` as slices of the
+/// input, so parsing allocates nothing; no domain scoring is done
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct EmailParts<'a> {
+    /// Whether the address is syntactically valid
+    pub is_valid: bool,
+    /// The local part of the email (before the @ symbol)
+    pub local_part: Option<&'a str>,
+    /// The domain part of the email (after the @ symbol)
+    pub domain: Option<&'a str>,
+    /// Subaddress tag after the first `+` in the local part
+    pub subaddress: Option<&'a str>,
+    /// The local part without its subaddress tag
+    pub canonical_local_part: Option<&'a str>,
+    /// Error message if validation failed
+    pub error_message: Option<&'static str>
+}
+
+/// Contribution of each scoring signal to `domain_score`
+/// The contributions sum to the final score; signals that did not apply are 0
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct ScoreBreakdown {
+    /// Score from the domain lists and TLD table (see `score_domain`)
+    pub base: f64,
+    /// Adjustment from `ValidationOptions::country_risk`
+    pub country_risk: f64,
+    /// Penalty for a homograph of a trusted domain
+    pub confusable_spoof: f64,
+    /// Reduction capping the score of a typosquat of a protected domain
+    pub typosquat: f64,
+    /// Raise to full trust for addresses allowed by the access policy
+    pub access_policy: f64,
+    /// Adjustment from the verifier's SPF/DMARC stage
+    pub mail_auth: f64,
+    /// Boost for a published BIMI record, from the same stage
+    pub bimi: f64,
+    /// Penalty from the verifier's blocklist stage
+    pub blocklist: f64,
+    /// Penalty for a newly registered domain, from the domain age stage
+    pub domain_age: f64,
+    /// Boost for certificates in the CT logs, from the CT log stage
+    pub ct_log: f64,
+    /// Change made by a host-provided `domain_scorer` callback (wasm)
+    pub custom_scorer: f64
+}
+
+#[cfg(feature = "std")]
+impl ScoreBreakdown {
+    /// Applies `adjust` to `score`, recording the change in `contribution`
+    pub(crate) fn record(score: &mut f64, contribution: &mut f64, adjust: impl FnOnce(f64) -> f64) {
+        let new_score = adjust(*score);
+        *contribution += new_score - *score;
+        *score = new_score;
+    }
+
+    /// Sum of all contributions, equal to the final `domain_score` unless
+    /// `ValidationOptions::score_weights` is set
+    pub fn total(&self) -> f64 {
+        self.base + self.country_risk + self.confusable_spoof + self.typosquat + self.access_policy + self.mail_auth + self.bimi + self.blocklist + self.domain_age + self.ct_log + self.custom_scorer
+    }
+}
+
+/// How much of the RFC 5322 address syntax is accepted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ValidationMode {
+    /// Dot-atom addresses only, as accepted by most mail forms
+    #[default]
+    Standard,
+    /// Also accepts well-formed quoted local parts (`"john doe"@example.com`)
+    /// and domain literals (`user@[192.0.2.1]`)
+    Strict
+}
+
+impl ValidationMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ValidationMode::Standard => "standard",
+            ValidationMode::Strict => "strict"
+        }
+    }
+}
+
+impl core::str::FromStr for ValidationMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "standard" => Ok(ValidationMode::Standard),
+            "strict" => Ok(ValidationMode::Strict),
+            _ => Err(format!("Unknown validation mode: {}", s))
+        }
+    }
+}
+
+/// What happens to an otherwise valid address longer than `max_length`
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum LengthPolicy {
+    /// The address is invalid
+    #[default]
+    Reject,
+    /// The address stays valid with an "AddressTooLong" warning; the
+    /// 320-character limit still rejects
+    Warn
+}
+
+#[cfg(feature = "std")]
+impl LengthPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LengthPolicy::Reject => "reject",
+            LengthPolicy::Warn => "warn"
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::str::FromStr for LengthPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "reject" => Ok(LengthPolicy::Reject),
+            "warn" => Ok(LengthPolicy::Warn),
+            _ => Err(format!("Unknown length policy: {}", s))
+        }
+    }
+}
+
+/// Options tuning email validation
+/// Deserializable from the JS options object; omitted fields use their defaults
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct ValidationOptions {
+    /// The caller's own brand domains; near-miss lookalikes of these (edit
+    /// distance ≤ 2, added hyphens, swapped TLDs) are flagged as typosquats
+    pub protected_domains: Vec<String>,
+    /// Accepted address syntax; `Strict` admits quoted local parts and
+    /// domain literals
+    pub mode: ValidationMode,
+    /// Organization-mandated pattern the whole address must match, replacing
+    /// the built-in syntax rules (and `mode`); control characters and the
+    /// 320-character limit are still enforced
+    #[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+    pub custom_pattern: Option<CustomPattern>,
+    /// Maximum address length in bytes, for stores with a tighter limit than
+    /// the 320 always enforced (RFC 5321 paths allow 254)
+    pub max_length: Option<usize>,
+    /// Whether addresses over `max_length` are rejected or only flagged with
+    /// an "AddressTooLong" warning
+    pub length_policy: LengthPolicy,
+    /// Disposable, trusted and blocked domains for this call only; they take
+    /// precedence over the built-in and loaded lists
+    pub domain_lists: DomainLists,
+    /// Whether to score the local part for randomness (entropy, consonant
+    /// runs, digit ratio) to catch bot-generated sign-ups
+    pub score_local_part: bool,
+    /// When keyboard walks and repeated characters count against
+    /// `local_part_quality`
+    pub local_part_thresholds: LocalPartThresholds,
+    /// Whether to flag auto-generated address patterns; batch APIs also flag
+    /// numbered series of addresses within the batch
+    pub detect_suspicious_patterns: bool,
+    /// Whether batch APIs mark functional duplicates (same address up to case,
+    /// plus tags and Gmail dots) in `EmailParseResult::duplicate_of`
+    pub detect_duplicates: bool,
+    /// Whether to tolerate input copied from mail clients: surrounding
+    /// whitespace, angle brackets and trailing punctuation are stripped and
+    /// recorded in `EmailParseResult::corrections`
+    pub lenient_input: bool,
+    /// Whether to normalize input to NFC and fold full-width characters
+    /// (`ｕｓｅｒ＠ｅｘａｍｐｌｅ．ｃｏｍ`) and smart quotes to ASCII before
+    /// validation; transformations are recorded in `EmailParseResult::corrections`
+    pub normalize_unicode: bool,
+    /// Points added to the domain score by country (ISO 3166-1 alpha-2 code,
+    /// case-insensitive), derived from the domain's ccTLD; use negative
+    /// values for higher-risk countries
+    pub country_risk: HashMap<String, f64>,
+    /// Domain scores by TLD (`"xyz"` or `".xyz"`), overriding or extending
+    /// the built-in high-abuse TLD table
+    pub tld_scores: HashMap<String, f64>,
+    /// Customer allow/deny rules, evaluated before scoring (from JS, an
+    /// array of `{ action: "allow" | "deny", pattern }`)
+    pub access_policy: AccessPolicy,
+    /// Multipliers for each `ScoreBreakdown` contribution, such as the
+    /// `suggested_weights` of a `calibrate` report; None sums them unweighted
+    pub score_weights: Option<ScoreWeights>,
+    /// Language of `error_message` and warning text (`"en"`, `"es"`, `"pt"`,
+    /// `"de"` or `"fr"`); error and warning types stay in English
+    pub locale: Locale
+}
+
+/// Configuration accepted by `parse_and_validate_email_with_options_wasm`
+/// and the other JS entry points: strictness `mode`, the normalization flags,
+/// per-call `domain_lists` and `max_length` alongside the scoring options
+#[cfg(feature = "std")]
+pub type Config = ValidationOptions;
+
+#[cfg(feature = "std")]
+impl EmailParseResult {
+    /// Result for an input that failed validation
+    fn invalid(message: &str) -> Self {
+        EmailParseResult {
+            is_valid: false,
+            error_message: Some(message.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Result for an input rejected by the syntax checks with `message`,
+    /// naming the broken domain label when that is why the format is
+    /// invalid, and suggesting the address most likely meant
+    fn invalid_syntax(email: &str, message: Message, locale: Locale) -> Self {
+        let label_message = (message == Message::InvalidFormat)
+            .then(|| email.rsplit_once('@').and_then(|(_, domain)| domain_labels::domain_error_message(domain, locale)))
+            .flatten();
+        EmailParseResult {
+            suggestion: suggest::suggestion(email),
+            ..EmailParseResult::invalid(label_message.as_deref().unwrap_or(message.text(locale)))
+        }
+    }
 }
 
 /// Error structure for email parsing failures
 /// Provides detailed error information for debugging and user feedback
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EmailParseError {
     /// Type of error that occurred (e.g., "RegexError", "InvalidInput")
     pub error_type: String,
     /// Human-readable error message
     pub message: String,
     /// Additional error details if available
-    pub details: Option<String>
+    pub details: Option<String>,
+    /// Character offset (0-based) of the offending character, if applicable
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub position: Option<usize>
 }
 
-impl std::fmt::Display for EmailParseError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for EmailParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(f, "{}: {}", self.error_type, self.message)
     }
 }
 
+/// A non-fatal concern about an accepted address
+/// Unlike `EmailParseError`, warnings never change `is_valid`
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ValidationWarning {
+    /// Type of warning (e.g., "LocalPartTooLong", "UnusualTld")
+    pub warning_type: String,
+    /// Human-readable warning message
+    pub message: String
+}
+
+#[cfg(feature = "std")]
+impl ValidationWarning {
+    fn new(warning_type: &str, message: impl Into<String>) -> Self {
+        ValidationWarning {
+            warning_type: warning_type.to_string(),
+            message: message.into()
+        }
+    }
+}
+
+/// Longest local part allowed by RFC 5321; longer ones are often still delivered
+#[cfg(feature = "std")]
+const MAX_LOCAL_PART_LENGTH: usize = 64;
+
+/// Generic TLDs in common use; other TLDs longer than two letters (ccTLDs
+/// are always accepted) produce an "UnusualTld" warning
+#[cfg(feature = "std")]
+const COMMON_GENERIC_TLDS: [&str; 18] = [
+    "com", "net", "org", "edu", "gov", "mil", "int", "info", "biz",
+    "name", "pro", "mobi", "aero", "coop", "jobs", "travel", "app", "dev"
+];
+
+/// Collects non-fatal warnings for an address that passed validation
+#[cfg(feature = "std")]
+fn collect_warnings(local_part: &str, domain: &str, locale: Locale) -> Vec<ValidationWarning> {
+    let mut warnings = Vec::new();
+
+    if local_part.len() > MAX_LOCAL_PART_LENGTH {
+        warnings.push(ValidationWarning::new(
+            "LocalPartTooLong",
+            i18n::local_part_too_long(locale, local_part.len(), MAX_LOCAL_PART_LENGTH)
+        ));
+    }
+
+    let tld = domain.rsplit('.').next().unwrap_or_default().to_lowercase();
+    if tld.len() > 2 && !COMMON_GENERIC_TLDS.contains(&tld.as_str()) {
+        warnings.push(ValidationWarning::new("UnusualTld", i18n::unusual_tld(locale, &tld)));
+    }
+
+    warnings
+}
+
+/// Domains of trusted mail providers
+const TRUSTED_DOMAINS: [&str; 3] = [
+    "google.com",
+    "outlook.com",
+    "yahoo.com"
+];
+
+/// Domains of disposable/temporary inbox services
+const DISPOSABLE_DOMAINS: [&str; 2] = [
+    "mailinator.com",
+    "tempmail.com"
+];
+
+/// Points subtracted from the domain score of a homograph of a trusted domain
+#[cfg(feature = "std")]
+const CONFUSABLE_SPOOF_PENALTY: f64 = 60.0;
+
+/// Highest domain score a typosquat of a protected domain can receive
+#[cfg(feature = "std")]
+const TYPOSQUAT_MAX_SCORE: f64 = 10.0;
+
 /// Scores a domain based on its trustworthiness and reputation
 /// 
 /// Returns a risk score from 0-100 where:
 /// - 80+ : Trusted domains (Google, Outlook, Yahoo)
+/// - 0: Blocked domains (see `load_domain_lists`)
 /// - 20-30: Disposable/temporary email domains
+/// - 15-40: Domains under high-abuse TLDs (.tk, .top, .xyz, ...)
 /// - 50: Default score for regular domains
 /// 
 /// # Arguments
 /// * `domain` - The domain string to score (case-insensitive)
+/// * `tld_scores` - Scores by TLD overriding the built-in high-abuse TLD table
 /// 
 /// # Returns
 /// * `f64` - Risk score between 0 and 100
 /// 
 /// # Examples
+/// ```ignore
+/// assert_eq!(score_domain("google.com", &HashMap::new()), 80.0);
+/// assert_eq!(score_domain("mailinator.com", &HashMap::new()), 20.0);
+/// assert_eq!(score_domain("example.com", &HashMap::new()), 50.0);
 /// ```
-/// assert_eq!(score_domain("google.com"), 80.0);
-/// assert_eq!(score_domain("mailinator.com"), 20.0);
-/// assert_eq!(score_domain("example.com"), 50.0);
-/// ```
-fn score_domain(domain: &str) -> f64 {
+#[cfg(feature = "std")]
+fn score_domain(domain: &str, tld_scores: &HashMap<String, f64>) -> f64 {
     let domain_lower = domain.to_lowercase();
     
-    let trusted_domains = vec![
-        "google.com",
-        "outlook.com", 
-        "yahoo.com"
-    ];
-    
-    let disposable_domains = vec![
-        "mailinator.com",
-        "tempmail.com"
-    ];
+    if domain_lists::is_blocked(&domain_lower) {
+        return 0.0;
+    }
     
-    if trusted_domains.contains(&domain_lower.as_str()) {
+    if domain_lists::is_trusted(&domain_lower) {
         return 80.0;
     }
     
-    if disposable_domains.contains(&domain_lower.as_str()) {
+    if domain_lists::is_disposable(&domain_lower) {
         return 20.0;
     }
     
-    return 50.0;
+    tld_risk::tld_score(&domain_lower, tld_scores).unwrap_or(50.0)
+}
+
+/// Scores a domain with the built-in lists and TLD table only
+/// 
+/// Available without the `std` feature. Unlike the scoring done by
+/// `parse_and_validate_email`, lists loaded with `load_domain_lists` and TLD
+/// overrides are not consulted.
+/// 
+/// # Examples
+/// ```
+/// # use email_validator_wasm::score_domain_builtin;
+/// assert_eq!(score_domain_builtin("google.com"), 80.0);
+/// assert_eq!(score_domain_builtin("example.tk"), 15.0);
+/// ```
+pub fn score_domain_builtin(domain: &str) -> f64 {
+    let domain_lower = domain.to_lowercase();
+
+    if TRUSTED_DOMAINS.contains(&domain_lower.as_str()) {
+        return 80.0;
+    }
+
+    if DISPOSABLE_DOMAINS.contains(&domain_lower.as_str()) {
+        return 20.0;
+    }
+
+    tld_risk::builtin_tld_score(&domain_lower).unwrap_or(50.0)
 }
 
 /// Parses and validates an email address according to RFC standards
 /// 
 /// Performs comprehensive email validation including:
-/// - Format validation using RFC-compliant regex
+/// - Format validation with the hand-written address parser
 /// - Length validation (max 320 characters)
 /// - Local part and domain extraction
 /// - Domain risk scoring
@@ -97,6 +793,7 @@ fn score_domain(domain: &str) -> f64 {
 /// 
 /// # Examples
 /// ```
+/// # use email_validator_wasm::parse_and_validate_email;
 /// // Valid email
 /// let result = parse_and_validate_email("user@example.com").unwrap();
 /// assert!(result.is_valid);
@@ -108,108 +805,350 @@ fn score_domain(domain: &str) -> f64 {
 /// assert!(!result.is_valid);
 /// assert_eq!(result.error_message, Some("Invalid email format".to_string()));
 /// ```
+#[cfg(feature = "std")]
 pub fn parse_and_validate_email(email: &str) -> Result<EmailParseResult, EmailParseError> {
-    if email.is_empty() {
-        return Ok(EmailParseResult {
-            is_valid: false,
-            local_part: None,
-            domain: None,
-            domain_score: None,
-            error_message: Some("Email cannot be empty".to_string())
-        });
+    parse_and_validate_email_with_options(email, &ValidationOptions::default())
+}
+
+/// Parses an email address without allocating
+/// 
+/// Applies the same syntax checks as `parse_and_validate_email`, but returns
+/// slices of `email` instead of owned strings and skips domain scoring. Meant
+/// for high-throughput servers that only need the parts of an address.
+/// 
+/// # Arguments
+/// * `email` - The email string to parse
+/// 
+/// # Returns
+/// * `Result<EmailParts, EmailParseError>` - Borrowed parts or error
+/// 
+/// # Examples
+/// ```
+/// # use email_validator_wasm::parse_email_ref;
+/// let parts = parse_email_ref("user+news@example.com").unwrap();
+/// assert!(parts.is_valid);
+/// assert_eq!(parts.canonical_local_part, Some("user"));
+/// assert_eq!(parts.domain, Some("example.com"));
+/// ```
+pub fn parse_email_ref(email: &str) -> Result<EmailParts<'_>, EmailParseError> {
+    let [local_part, domain] = match check_syntax(email, ValidationMode::Standard)? {
+        Ok(parts) => parts,
+        Err(message) => return Ok(EmailParts { error_message: Some(message.english()), ..Default::default() })
+    };
+    let (canonical_local_part, subaddress) = providers::split_subaddress(local_part, domain);
+
+    Ok(EmailParts {
+        is_valid: true,
+        local_part: Some(local_part),
+        domain: Some(domain),
+        subaddress,
+        canonical_local_part: Some(canonical_local_part),
+        error_message: None
+    })
+}
+
+/// Parses and validates an email address with the given options
+/// 
+/// Behaves like `parse_and_validate_email`, additionally applying the checks
+/// enabled in `options`.
+/// 
+/// # Arguments
+/// * `email` - The email string to validate
+/// * `options` - Validation options
+/// 
+/// # Returns
+/// * `Result<EmailParseResult, EmailParseError>` - Validation result or error
+/// 
+/// # Examples
+/// ```
+/// # use email_validator_wasm::{parse_and_validate_email_with_options, ValidationOptions};
+/// let options = ValidationOptions {
+///     protected_domains: vec!["acmebank.com".to_string()],
+///     ..Default::default()
+/// };
+/// let result = parse_and_validate_email_with_options("user@acme-bank.com", &options).unwrap();
+/// assert_eq!(result.is_typosquat, Some(true));
+/// ```
+#[cfg(feature = "std")]
+pub fn parse_and_validate_email_with_options(email: &str, options: &ValidationOptions) -> Result<EmailParseResult, EmailParseError> {
+    let result = normalize_and_validate(email, options);
+    #[cfg(feature = "metrics")]
+    telemetry::record_validation(&result);
+    result
+}
+
+/// Applies the input clean-up enabled in `options`, then validates
+#[cfg(feature = "std")]
+fn normalize_and_validate(email: &str, options: &ValidationOptions) -> Result<EmailParseResult, EmailParseError> {
+    if !options.lenient_input && !options.normalize_unicode {
+        return normalize_strict_and_validate(email, options);
     }
 
-    if email.len() > 320 {
-        return Ok(EmailParseResult {
-            is_valid: false,
-            local_part: None,
-            domain: None,
-            domain_score: None,
-            error_message: Some("Email exceeds maximum length of 320 characters".to_string())
-        });
+    let mut corrections = Vec::new();
+    let normalized;
+    let mut email = email;
+
+    if options.normalize_unicode {
+        let (folded, folded_corrections) = lenient::normalize_unicode(email);
+        normalized = folded;
+        email = &normalized;
+        corrections.extend(folded_corrections);
     }
 
-    let email_regex = match Regex::new(r"^[a-zA-Z0-9_%+-](?:[a-zA-Z0-9._%+-]*[a-zA-Z0-9_%+-])?@[a-zA-Z0-9](?:[a-zA-Z0-9-]*[a-zA-Z0-9])?(?:\.[a-zA-Z0-9](?:[a-zA-Z0-9-]*[a-zA-Z0-9])?)*\.[a-zA-Z]{2,}$") {
-        Ok(regex) => regex,
-        Err(e) => return Err(EmailParseError {
-            error_type: "RegexError".to_string(),
-            message: "Failed to compile email regex".to_string(),
-            details: Some(e.to_string())
-        })
+    if options.lenient_input {
+        let (cleaned, cleaned_corrections) = lenient::clean_input(email);
+        email = cleaned;
+        corrections.extend(cleaned_corrections);
+    }
+
+    let mut result = normalize_strict_and_validate(email, options)?;
+    result.corrections = Some(corrections);
+    Ok(result)
+}
+
+/// Removes RFC 5322 comments and rewrites obsolete syntax in
+/// `ValidationMode::Strict`, then validates
+#[cfg(feature = "std")]
+fn normalize_strict_and_validate(email: &str, options: &ValidationOptions) -> Result<EmailParseResult, EmailParseError> {
+    if options.mode != ValidationMode::Strict {
+        return validate_email(email, options);
+    }
+
+    let (mut address, comments) = match cfws::strip_comments(email) {
+        Ok(stripped) => stripped,
+        Err(message) => {
+            // `jane(at)example.com` is a written-out `@`, not a misplaced comment
+            let message = match message {
+                Message::MisplacedComment if suggest::suggestion(email).is_some() => Message::InvalidFormat,
+                message => message
+            };
+            return Ok(EmailParseResult::invalid_syntax(email, message, options.locale));
+        }
     };
+    let mut obsolete_forms = Vec::new();
+    if let Some((rewritten, forms)) = obsolete::rewrite_obsolete(&address) {
+        address = rewritten;
+        obsolete_forms = forms;
+    }
 
-    if !email_regex.is_match(email) {
-        return Ok(EmailParseResult {
-            is_valid: false,
-            local_part: None,
-            domain: None,
-            domain_score: None,
-            error_message: Some("Invalid email format".to_string())
-        });
+    let mut result = validate_email(&address, options)?;
+    result.comments = Some(comments);
+    if result.is_valid {
+        result.warnings.extend(obsolete_forms.into_iter().map(|form| {
+            ValidationWarning::new("ObsoleteSyntax", form.message().text(options.locale))
+        }));
     }
+    Ok(result)
+}
 
-    let parts: Vec<&str> = email.split('@').collect();
-    if parts.len() != 2 {
-        return Ok(EmailParseResult {
-            is_valid: false,
-            local_part: None,
-            domain: None,
-            domain_score: None,
-            error_message: Some("Invalid email format".to_string())
-        });
+/// Validates an email address exactly as given, applying `options`
+#[cfg(feature = "std")]
+fn validate_email(email: &str, options: &ValidationOptions) -> Result<EmailParseResult, EmailParseError> {
+    let syntax = match &options.custom_pattern {
+        Some(pattern) => check_syntax_with(email, |email| pattern.split(email)),
+        None => check_syntax(email, options.mode)
+    };
+    let parts = match syntax {
+        Ok(Ok(parts)) => parts,
+        Ok(Err(message)) => return Ok(EmailParseResult::invalid_syntax(email, message, options.locale)),
+        Err(mut error) => {
+            if error.error_type == "ControlCharacter" {
+                error.message = Message::ControlCharacter.text(options.locale).to_string();
+            }
+            return Err(error);
+        }
+    };
+
+    let over_max_length = options.max_length.filter(|max_length| email.len() > *max_length);
+    if let (Some(max_length), LengthPolicy::Reject) = (over_max_length, options.length_policy) {
+        return Ok(EmailParseResult::invalid(&i18n::too_long(options.locale, max_length)));
     }
 
-    let local_part = parts[0];
-    if local_part.contains("..") {
-        return Ok(EmailParseResult {
-            is_valid: false,
-            local_part: None,
-            domain: None,
-            domain_score: None,
-            error_message: Some("Invalid email format".to_string())
-        });
+    if let Some(message) = domain_labels::domain_error_message(parts[1], options.locale) {
+        return Ok(EmailParseResult::invalid(&message));
     }
 
+    let local_part = parts[0];
+
+    let matched_access_rule = options.access_policy.evaluate(local_part, parts[1]).cloned();
+    let allowed_by_policy = match &matched_access_rule {
+        Some(rule) if rule.action == AccessAction::Deny => return Ok(EmailParseResult {
+            matched_access_rule,
+            ..EmailParseResult::invalid(Message::AccessDenied.text(options.locale))
+        }),
+        Some(_) => true,
+        None => false
+    };
+
+    let (canonical_local_part, subaddress) = providers::split_subaddress(local_part, parts[1]);
+    let subaddress = subaddress.map(str::to_string);
+    let canonical_local_part = canonical_local_part.to_string();
+    let is_disposable_alias = providers::is_disposable_alias(local_part, parts[1]);
+    let is_test_address = placeholder::is_test_address(&canonical_local_part, parts[1]);
+    let spamtrap_risk = spamtrap::spamtrap_risk(&canonical_local_part, &parts[1].to_lowercase(), &options.domain_lists);
+
+    let local_part_quality = options.score_local_part.then(|| local_part::local_part_quality(local_part, &options.local_part_thresholds));
+    let is_suspicious_pattern = options.detect_suspicious_patterns.then(|| patterns::matches_known_template(local_part));
+    let mut warnings = collect_warnings(local_part, parts[1], options.locale);
+    if let Some(max_length) = over_max_length {
+        warnings.push(ValidationWarning::new("AddressTooLong", i18n::too_long(options.locale, max_length)));
+    }
     let local_part = local_part.to_string();
     let domain = parts[1].to_string();
 
-    let domain_score = score_domain(&domain);
+    let mut domain_score = domain_lists::custom_list_score(&options.domain_lists, &domain)
+        .unwrap_or_else(|| score_domain(&domain, &options.tld_scores));
+    let mut breakdown = ScoreBreakdown { base: domain_score, ..Default::default() };
+
+    let country = country::country_from_domain(&domain);
+    if let Some(adjustment) = country.as_deref().and_then(|code| country_risk_adjustment(&options.country_risk, code)) {
+        ScoreBreakdown::record(&mut domain_score, &mut breakdown.country_risk, |score| (score + adjustment).clamp(0.0, 100.0));
+    }
+
+    let is_confusable_spoof = domain_lists::is_trusted_spoof(&domain);
+    if is_confusable_spoof {
+        ScoreBreakdown::record(&mut domain_score, &mut breakdown.confusable_spoof, |score| (score - CONFUSABLE_SPOOF_PENALTY).max(0.0));
+    }
+
+    let typosquat_target = typosquat::find_typosquat_target(&domain, &options.protected_domains);
+    if typosquat_target.is_some() {
+        ScoreBreakdown::record(&mut domain_score, &mut breakdown.typosquat, |score| score.min(TYPOSQUAT_MAX_SCORE));
+    }
+
+    if allowed_by_policy {
+        ScoreBreakdown::record(&mut domain_score, &mut breakdown.access_policy, |_| 100.0);
+    }
+    if let Some(weights) = &options.score_weights {
+        domain_score = weights.score(&breakdown);
+    }
 
+    let canonical_email = providers::canonicalize(&local_part, &domain);
+    let mailbox_provider = providers::mailbox_provider(&domain);
+    let is_relay_address = relay::is_relay_domain(&domain.to_lowercase());
     Ok(EmailParseResult {
         is_valid: true,
         local_part: Some(local_part),
         domain: Some(domain),
         domain_score: Some(domain_score),
-        error_message: None
+        error_message: None,
+        is_confusable_spoof: Some(is_confusable_spoof),
+        is_typosquat: Some(typosquat_target.is_some()),
+        typosquat_target: typosquat_target.map(str::to_string),
+        local_part_quality,
+        is_suspicious_pattern,
+        duplicate_of: None,
+        subaddress,
+        canonical_local_part: Some(canonical_local_part),
+        canonical_email,
+        mailbox_provider,
+        is_disposable_alias: Some(is_disposable_alias),
+        is_relay_address: Some(is_relay_address),
+        spamtrap_risk: Some(spamtrap_risk),
+        is_test_address: Some(is_test_address),
+        is_academic: Some(academic::is_academic(&parts[1].to_lowercase())),
+        is_government: Some(government::is_government(&parts[1].to_lowercase(), &options.domain_lists)),
+        corrections: None,
+        comments: None,
+        suggestion: suggest::suggestion(email),
+        warnings,
+        email_hash: Some(hash::hash_email(email)),
+        country,
+        domain_category: Some(category::classify_domain(parts[1], &options.domain_lists)),
+        matched_access_rule,
+        score_breakdown: Some(breakdown),
+        metadata: Metadata::new()
     })
 }
 
-/// WebAssembly entry point for email validation
-/// 
-/// This function is exposed to JavaScript via wasm-bindgen and provides
-/// the main interface for email validation from the TypeScript SDK.
-/// 
-/// # Arguments
-/// * `email` - The email string to validate
-/// 
-/// # Returns
-/// * `JsValue` - Serialized EmailParseResult or EmailParseError
-/// 
-/// # Examples
-/// ```javascript
-/// // From JavaScript/TypeScript
-/// const result = parse_and_validate_email_wasm("user@example.com");
-/// console.log(result.is_valid); // true
-/// ```
-#[wasm_bindgen]
-pub fn parse_and_validate_email_wasm(email: &str) -> JsValue {
-    match parse_and_validate_email(email) {
-        Ok(result) => serde_wasm_bindgen::to_value(&result).unwrap(),
-        Err(e) => serde_wasm_bindgen::to_value(&e).unwrap()
+/// Checks everything about `email` that does not depend on options, and
+/// splits it into local part and domain
+fn check_syntax(email: &str, mode: ValidationMode) -> Result<Result<[&str; 2], Message>, EmailParseError> {
+    check_syntax_with(email, |email| split_address(email, mode))
+}
+
+/// Runs the checks that apply to every address, then splits it with `split`
+fn check_syntax_with<'a>(
+    email: &'a str,
+    split: impl FnOnce(&'a str) -> Result<Result<[&'a str; 2], Message>, EmailParseError>
+) -> Result<Result<[&'a str; 2], Message>, EmailParseError> {
+    if email.is_empty() {
+        return Ok(Err(Message::Empty));
     }
+
+    // Reported separately from format errors: CR/LF in an address is a
+    // common header-injection vector
+    if let Some((position, c)) = email.chars().enumerate().find(|(_, c)| c.is_control()) {
+        return Err(EmailParseError {
+            error_type: "ControlCharacter".to_string(),
+            message: Message::ControlCharacter.english().to_string(),
+            details: Some(format!("U+{:04X} at position {}", c as u32, position)),
+            position: Some(position)
+        });
+    }
+
+    if email.len() > 320 {
+        return Ok(Err(Message::TooLong));
+    }
+
+    split(email)
+}
+
+/// Splits a syntactically valid address into local part and domain, or
+/// returns the message explaining why it is invalid
+///
+/// Quoted local parts and domain literals are well-formed but only accepted
+/// in `ValidationMode::Strict`.
+#[cfg(not(feature = "regex-compat"))]
+fn split_address(email: &str, mode: ValidationMode) -> Result<Result<[&str; 2], Message>, EmailParseError> {
+    let parsed = match parser::parse_address(email) {
+        Ok(parsed) => parsed,
+        Err(_) => return Ok(Err(Message::InvalidFormat))
+    };
+    if mode == ValidationMode::Standard && parsed.local_part_kind == parser::LocalPartKind::Quoted {
+        return Ok(Err(Message::QuotedLocalPart));
+    }
+    if mode == ValidationMode::Standard && parsed.domain_kind != parser::DomainKind::Name {
+        return Ok(Err(Message::DomainLiteral));
+    }
+    Ok(Ok([parsed.local_part.as_str(email), parsed.domain.as_str(email)]))
 }
 
-#[cfg(test)]
+/// Splits a syntactically valid address into local part and domain using the
+/// original validation regex (`regex-compat` feature)
+///
+/// The regex has no quoted or literal forms, so `mode` makes no difference.
+#[cfg(feature = "regex-compat")]
+fn split_address(email: &str, _mode: ValidationMode) -> Result<Result<[&str; 2], Message>, EmailParseError> {
+    let email_regex = match Regex::new(r"^[a-zA-Z0-9_%+-](?:[a-zA-Z0-9._%+-]*[a-zA-Z0-9_%+-])?@[a-zA-Z0-9](?:[a-zA-Z0-9-]*[a-zA-Z0-9])?(?:\.[a-zA-Z0-9](?:[a-zA-Z0-9-]*[a-zA-Z0-9])?)*\.[a-zA-Z]{2,}$") {
+        Ok(regex) => regex,
+        Err(e) => return Err(EmailParseError {
+            error_type: "RegexError".to_string(),
+            message: "Failed to compile email regex".to_string(),
+            details: Some(e.to_string()),
+            position: None
+        })
+    };
+
+    if !email_regex.is_match(email) {
+        return Ok(Err(Message::InvalidFormat));
+    }
+
+    match email.split_once('@') {
+        Some((local_part, domain)) if !local_part.contains("..") => Ok(Ok([local_part, domain])),
+        _ => Ok(Err(Message::InvalidFormat))
+    }
+}
+
+/// Looks up the configured score adjustment for a country code, ignoring case
+#[cfg(feature = "std")]
+fn country_risk_adjustment(country_risk: &HashMap<String, f64>, code: &str) -> Option<f64> {
+    country_risk
+        .iter()
+        .find(|(configured, _)| configured.eq_ignore_ascii_case(code))
+        .map(|(_, adjustment)| *adjustment)
+}
+
+#[cfg(all(feature = "std", test))]
 mod tests {
     use super::*;
 
@@ -222,7 +1161,7 @@ mod tests {
         assert!(result.is_valid);
         assert_eq!(result.local_part, Some("test".to_string()));
         assert_eq!(result.domain, Some("example.com".to_string()));
-        assert_eq!(result.domain_score, Some(80.0));
+        assert_eq!(result.domain_score, Some(50.0));
         assert_eq!(result.error_message, None);
     }
 
@@ -266,20 +1205,518 @@ mod tests {
     #[test]
     fn test_domain_scoring() {
         // Test trusted domains
-        assert_eq!(score_domain("google.com"), 80.0);
-        assert_eq!(score_domain("outlook.com"), 80.0);
-        assert_eq!(score_domain("yahoo.com"), 80.0);
-        assert_eq!(score_domain("GOOGLE.COM"), 80.0); // Case insensitive
+        assert_eq!(score_domain("google.com", &HashMap::new()), 80.0);
+        assert_eq!(score_domain("outlook.com", &HashMap::new()), 80.0);
+        assert_eq!(score_domain("yahoo.com", &HashMap::new()), 80.0);
+        assert_eq!(score_domain("GOOGLE.COM", &HashMap::new()), 80.0); // Case insensitive
         
         // Test disposable domains
-        assert_eq!(score_domain("mailinator.com"), 20.0);
-        assert_eq!(score_domain("tempmail.com"), 20.0);
-        assert_eq!(score_domain("MAILINATOR.COM"), 20.0); // Case insensitive
+        assert_eq!(score_domain("mailinator.com", &HashMap::new()), 20.0);
+        assert_eq!(score_domain("tempmail.com", &HashMap::new()), 20.0);
+        assert_eq!(score_domain("MAILINATOR.COM", &HashMap::new()), 20.0); // Case insensitive
         
         // Test regular domains (default score)
-        assert_eq!(score_domain("example.com"), 50.0);
-        assert_eq!(score_domain("test.org"), 50.0);
-        assert_eq!(score_domain("company.net"), 50.0);
+        assert_eq!(score_domain("example.com", &HashMap::new()), 50.0);
+        assert_eq!(score_domain("test.org", &HashMap::new()), 50.0);
+        assert_eq!(score_domain("company.net", &HashMap::new()), 50.0);
+        
+        // Test high-abuse TLDs
+        assert_eq!(score_domain("promo.xyz", &HashMap::new()), 30.0);
+        assert_eq!(score_domain("free.TK", &HashMap::new()), 15.0);
+        assert_eq!(score_domain("promo.xyz", &HashMap::from([("xyz".to_string(), 45.0)])), 45.0);
+    }
+
+    /// Tests that homographs of trusted domains are flagged and penalized
+    #[test]
+    fn test_confusable_spoof_domain() {
+        let result = parse_and_validate_email("user@xn--ggle-55da.com").unwrap(); // gооgle.com
+        assert!(result.is_valid);
+        assert_eq!(result.is_confusable_spoof, Some(true));
+        assert_eq!(result.domain_score, Some(0.0));
+
+        let result = parse_and_validate_email("user@google.com").unwrap();
+        assert_eq!(result.is_confusable_spoof, Some(false));
+        assert_eq!(result.domain_score, Some(80.0));
+    }
+
+    /// Tests that lookalikes of protected domains are flagged as high risk
+    #[test]
+    fn test_typosquat_domain() {
+        let options = ValidationOptions {
+            protected_domains: vec!["acmebank.com".to_string()],
+            ..Default::default()
+        };
+
+        let result = parse_and_validate_email_with_options("user@acme-bank.com", &options).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.is_typosquat, Some(true));
+        assert_eq!(result.typosquat_target, Some("acmebank.com".to_string()));
+        assert_eq!(result.domain_score, Some(10.0));
+
+        let result = parse_and_validate_email_with_options("user@acmebank.com", &options).unwrap();
+        assert_eq!(result.is_typosquat, Some(false));
+        assert_eq!(result.domain_score, Some(50.0));
+
+        let result = parse_and_validate_email("user@acme-bank.com").unwrap();
+        assert_eq!(result.is_typosquat, Some(false));
+    }
+
+    /// Tests that local part scoring is opt-in and separates generated addresses
+    #[test]
+    fn test_local_part_quality_option() {
+        let options = ValidationOptions {
+            score_local_part: true,
+            ..Default::default()
+        };
+
+        let human = parse_and_validate_email_with_options("jane.doe@gmail.com", &options).unwrap();
+        let generated = parse_and_validate_email_with_options("xk3j9qpl@gmail.com", &options).unwrap();
+        assert!(generated.is_valid);
+        assert!(human.local_part_quality.unwrap() > generated.local_part_quality.unwrap());
+
+        let mashed = parse_and_validate_email_with_options("asdfasdf@gmail.com", &options).unwrap();
+        assert!(mashed.local_part_quality.unwrap() < 50.0);
+
+        let result = parse_and_validate_email("xk3j9qpl@gmail.com").unwrap();
+        assert_eq!(result.local_part_quality, None);
+    }
+
+    /// Tests plus-tag and Yahoo alias extraction into subaddress and canonical local part
+    #[test]
+    fn test_subaddress_extraction() {
+        let result = parse_and_validate_email("user+newsletter@gmail.com").unwrap();
+        assert_eq!(result.is_disposable_alias, Some(false));
+        assert_eq!(result.local_part, Some("user+newsletter".to_string()));
+        assert_eq!(result.subaddress, Some("newsletter".to_string()));
+        assert_eq!(result.canonical_local_part, Some("user".to_string()));
+        assert_eq!(result.canonical_email, Some("user@gmail.com".to_string()));
+        assert_eq!(result.mailbox_provider, Some(MailboxProvider::Gmail));
+
+        let result = parse_and_validate_email("user+a+b@gmail.com").unwrap();
+        assert_eq!(result.subaddress, Some("a+b".to_string()));
+        assert_eq!(result.canonical_local_part, Some("user".to_string()));
+
+        let result = parse_and_validate_email("user@gmail.com").unwrap();
+        assert_eq!(result.subaddress, None);
+        assert_eq!(result.canonical_local_part, Some("user".to_string()));
+
+        let result = parse_and_validate_email("jane-shopping@yahoo.com").unwrap();
+        assert_eq!(result.subaddress, Some("shopping".to_string()));
+        assert_eq!(result.canonical_email, Some("jane@yahoo.com".to_string()));
+        assert_eq!(result.is_disposable_alias, Some(true));
+
+        let result = parse_and_validate_email("invalid-email").unwrap();
+        assert_eq!(result.canonical_local_part, None);
+    }
+
+    /// Tests that lenient input mode strips pasted decoration and records it
+    #[test]
+    fn test_lenient_input_option() {
+        let strict = parse_and_validate_email("  <user@example.com>. ").unwrap();
+        assert!(!strict.is_valid);
+        assert_eq!(strict.corrections, None);
+
+        let options = ValidationOptions {
+            lenient_input: true,
+            ..Default::default()
+        };
+        let result = parse_and_validate_email_with_options("  <user@example.com>. ", &options).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.local_part, Some("user".to_string()));
+        assert_eq!(result.corrections.map(|c| c.len()), Some(3));
+
+        let result = parse_and_validate_email_with_options("user@example.com", &options).unwrap();
+        assert_eq!(result.corrections, Some(Vec::new()));
+    }
+
+    /// Tests that questionable but accepted addresses carry warnings
+    #[test]
+    fn test_validation_warnings() {
+        let result = parse_and_validate_email("user@example.com").unwrap();
+        assert!(result.warnings.is_empty());
+
+        let long_local = format!("{}@example.com", "a".repeat(70));
+        let result = parse_and_validate_email(&long_local).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].warning_type, "LocalPartTooLong");
+
+        let result = parse_and_validate_email("user@example.zyx").unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.warnings[0].warning_type, "UnusualTld");
+
+        let result = parse_and_validate_email("user@example.de").unwrap();
+        assert!(result.warnings.is_empty());
+    }
+
+    /// Tests that full-width input from mobile keyboards validates once folded
+    #[test]
+    fn test_normalize_unicode_option() {
+        let input = "ｕｓｅｒ＠ｅｘａｍｐｌｅ．ｃｏｍ";
+        assert!(!parse_and_validate_email(input).unwrap().is_valid);
+
+        let options = ValidationOptions {
+            normalize_unicode: true,
+            lenient_input: true,
+            ..Default::default()
+        };
+        let result = parse_and_validate_email_with_options(&format!("＜{}＞", input), &options).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.domain, Some("example.com".to_string()));
+        assert_eq!(result.corrections, Some(vec![
+            "Folded full-width characters to ASCII".to_string(),
+            "Removed surrounding angle brackets".to_string()
+        ]));
+    }
+
+    /// Tests that control characters are rejected with their own error and position
+    #[test]
+    fn test_control_character_rejection() {
+        let error = parse_and_validate_email("user@exa\r\nmple.com").unwrap_err();
+        assert_eq!(error.error_type, "ControlCharacter");
+        assert_eq!(error.position, Some(8));
+        assert_eq!(error.details, Some("U+000D at position 8".to_string()));
+
+        let error = parse_and_validate_email("ü\tuser@example.com").unwrap_err();
+        assert_eq!(error.position, Some(1));
+    }
+
+    /// Tests that valid results carry a case-insensitive address hash
+    #[test]
+    fn test_email_hash() {
+        let result = parse_and_validate_email("User@Example.com").unwrap();
+        assert_eq!(result.email_hash, Some(hash_email("user@example.com")));
+
+        let result = parse_and_validate_email("invalid-email").unwrap();
+        assert_eq!(result.email_hash, None);
+    }
+
+    /// Tests ccTLD country detection and the configurable per-country adjustment
+    #[test]
+    fn test_country_risk_option() {
+        let result = parse_and_validate_email("user@example.co.uk").unwrap();
+        assert_eq!(result.country, Some("GB".to_string()));
+        assert_eq!(result.domain_score, Some(50.0));
+
+        let options = ValidationOptions {
+            country_risk: HashMap::from([("gb".to_string(), -30.0), ("DE".to_string(), 70.0)]),
+            ..Default::default()
+        };
+        let result = parse_and_validate_email_with_options("user@example.co.uk", &options).unwrap();
+        assert_eq!(result.domain_score, Some(20.0));
+        let result = parse_and_validate_email_with_options("user@example.de", &options).unwrap();
+        assert_eq!(result.domain_score, Some(100.0));
+        let result = parse_and_validate_email_with_options("user@example.com", &options).unwrap();
+        assert_eq!(result.country, None);
+        assert_eq!(result.domain_score, Some(50.0));
+    }
+
+    /// Tests that valid results carry a domain category, relay flag and spamtrap risk
+    #[test]
+    fn test_domain_category() {
+        let result = parse_and_validate_email("student@mit.edu").unwrap();
+        assert_eq!(result.domain_category, Some(DomainCategory::Educational));
+        assert_eq!(result.is_academic, Some(true));
+        assert_eq!(result.is_government, Some(false));
+
+        let agencies = ValidationOptions {
+            domain_lists: DomainLists { government: vec!["agency.example".to_string()], ..Default::default() },
+            ..Default::default()
+        };
+        let result = parse_and_validate_email_with_options("clerk@agency.example", &agencies).unwrap();
+        assert_eq!(result.is_government, Some(true));
+        assert_eq!(result.domain_category, Some(DomainCategory::Government));
+
+        let result = parse_and_validate_email("user@tempmail.com").unwrap();
+        assert_eq!(result.domain_category, Some(DomainCategory::Disposable));
+        assert_eq!(result.is_relay_address, Some(false));
+
+        assert_eq!(result.spamtrap_risk, Some(SpamtrapRisk::Low));
+        assert_eq!(result.is_test_address, Some(false));
+
+        let result = parse_and_validate_email("foo@bar.com").unwrap();
+        assert_eq!(result.is_test_address, Some(true));
+
+        let result = parse_and_validate_email("Webmaster+old@example.com").unwrap();
+        assert_eq!(result.spamtrap_risk, Some(SpamtrapRisk::Medium));
+
+        let result = parse_and_validate_email("x7k2m9@privaterelay.appleid.com").unwrap();
+        assert_eq!(result.is_relay_address, Some(true));
+        assert_ne!(result.domain_category, Some(DomainCategory::Disposable));
+
+        let result = parse_and_validate_email("invalid-email").unwrap();
+        assert_eq!(result.domain_category, None);
+    }
+
+    /// Tests that strict mode accepts quoted local parts and domain literals
+    #[test]
+    #[cfg(not(feature = "regex-compat"))]
+    fn test_strict_mode() {
+        let strict = Config { mode: ValidationMode::Strict, ..Default::default() };
+        for email in ["\"john doe\"@example.com", "user@[192.0.2.1]"] {
+            assert!(!parse_and_validate_email(email).unwrap().is_valid, "{}", email);
+            assert!(parse_and_validate_email_with_options(email, &strict).unwrap().is_valid, "{}", email);
+        }
+        assert!(!parse_and_validate_email_with_options("john..doe@example.com", &strict).unwrap().is_valid);
+        assert_eq!("strict".parse::<ValidationMode>(), Ok(ValidationMode::Strict));
+    }
+
+    /// Tests that strict mode removes comments and records them, and standard mode rejects them
+    #[test]
+    fn test_strict_mode_comments() {
+        let email = "john.smith(comment)@example.com(comment)";
+        let standard = parse_and_validate_email(email).unwrap();
+        assert!(!standard.is_valid);
+        assert_eq!(standard.comments, None);
+
+        let strict = Config { mode: ValidationMode::Strict, ..Default::default() };
+        let result = parse_and_validate_email_with_options(email, &strict).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.local_part.as_deref(), Some("john.smith"));
+        assert_eq!(result.domain.as_deref(), Some("example.com"));
+        assert_eq!(result.comments, Some(vec!["comment".to_string(), "comment".to_string()]));
+        assert_eq!(parse_and_validate_email_with_options("jane@example.com", &strict).unwrap().comments, Some(vec![]));
+
+        let result = parse_and_validate_email_with_options("jo(x)hn@example.com", &strict).unwrap();
+        assert_eq!(result.error_message.as_deref(), Some(Message::MisplacedComment.english()));
+        assert!(!parse_and_validate_email_with_options("john(@example.com", &strict).unwrap().is_valid);
+
+        let result = parse_and_validate_email_with_options("jane(at)example.com", &strict).unwrap();
+        assert_eq!(result.error_message.as_deref(), Some("Invalid email format"));
+        assert_eq!(result.suggestion.as_deref(), Some("jane@example.com"));
+
+        let results = parse_and_validate_emails(&[email, "jo(x)hn@example.com"], &strict);
+        let result = results[0].as_ref().unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.comments, Some(vec!["comment".to_string(), "comment".to_string()]));
+        assert_eq!(results[1].as_ref().unwrap().error_message.as_deref(), Some(Message::MisplacedComment.english()));
+    }
+
+    /// Tests that strict mode accepts obsolete syntax with an "ObsoleteSyntax" warning per form
+    #[test]
+    fn test_strict_mode_obsolete_syntax() {
+        let strict = Config { mode: ValidationMode::Strict, ..Default::default() };
+        let result = parse_and_validate_email_with_options("john.smith@example.com", &strict).unwrap();
+        assert!(result.warnings.iter().all(|warning| warning.warning_type != "ObsoleteSyntax"));
+
+        let email = "john.\"q\".public (work) . smith@example .com";
+        assert!(!parse_and_validate_email(email).unwrap().is_valid);
+        let result = parse_and_validate_email_with_options(email, &strict).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.local_part.as_deref(), Some("john.q.public.smith"));
+        assert_eq!(result.domain.as_deref(), Some("example.com"));
+        assert_eq!(result.comments, Some(vec!["work".to_string()]));
+        let obsolete: Vec<&str> = result.warnings.iter()
+            .filter(|warning| warning.warning_type == "ObsoleteSyntax")
+            .map(|warning| warning.message.as_str())
+            .collect();
+        assert_eq!(obsolete, [obsolete::ObsoleteForm::MixedLocalPart.message().english(), obsolete::ObsoleteForm::SpaceAroundDot.message().english()]);
+
+        let emails = [email, "john . smith@example.com"];
+        for (email, result) in emails.iter().zip(parse_and_validate_emails(&emails, &strict)) {
+            let result = result.unwrap();
+            assert!(result.is_valid, "{}", email);
+            assert!(result.warnings.iter().any(|warning| warning.warning_type == "ObsoleteSyntax"), "{}", email);
+        }
+    }
+
+    /// Tests that domain label problems name the offending label
+    #[test]
+    fn test_domain_label_errors() {
+        let email = format!("user@mail.{}.com", "a".repeat(64));
+        let result = parse_and_validate_email(&email).unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.error_message, Some(format!("Domain label 1 (\"{}\") exceeds 63 octets", "a".repeat(64))));
+
+        let result = parse_and_validate_email("user@mail.example-.com").unwrap();
+        assert_eq!(result.error_message.as_deref(), Some("Domain label 1 (\"example-\") ends with a hyphen"));
+        let result = parse_and_validate_email("user@xn--ab-9999999999.com").unwrap();
+        assert_eq!(result.error_message.as_deref(), Some("Domain label 0 (\"xn--ab-9999999999\") is not valid punycode"));
+        assert!(parse_and_validate_email("user@xn--ggle-55da.com").unwrap().is_valid);
+
+        let spanish = Config { locale: Locale::Es, ..Default::default() };
+        let result = parse_and_validate_email_with_options("user@-example.com", &spanish).unwrap();
+        assert_eq!(result.error_message.as_deref(), Some("La etiqueta de dominio 0 (\"-example\") empieza con un guion"));
+    }
+
+    /// Tests the maximum length and per-call domain lists
+    #[test]
+    fn test_config_options() {
+        let capped = Config { max_length: Some(20), ..Default::default() };
+        let result = parse_and_validate_email_with_options("someone@example.com", &capped).unwrap();
+        assert!(result.is_valid);
+        let result = parse_and_validate_email_with_options("someone.else@example.com", &capped).unwrap();
+        assert_eq!(result.error_message, Some("Email exceeds maximum length of 20 characters".to_string()));
+        let result = parse_and_validate_email_with_options("not an email at all", &capped).unwrap();
+        assert_eq!(result.error_message, Some("Invalid email format".to_string()));
+
+        let soft = Config { length_policy: LengthPolicy::Warn, ..capped };
+        let result = parse_and_validate_email_with_options("someone.else@example.com", &soft).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.warnings[0].warning_type, "AddressTooLong");
+        let result = parse_and_validate_email_with_options("someone@example.com", &soft).unwrap();
+        assert!(result.warnings.is_empty());
+        assert_eq!("warn".parse::<LengthPolicy>(), Ok(LengthPolicy::Warn));
+
+        let lists = Config {
+            domain_lists: DomainLists { disposable: vec!["Burner.example".to_string()], blocked: vec!["google.com".to_string()], ..Default::default() },
+            ..Default::default()
+        };
+        assert_eq!(parse_and_validate_email_with_options("user@burner.example", &lists).unwrap().domain_score, Some(20.0));
+        assert_eq!(parse_and_validate_email_with_options("user@google.com", &lists).unwrap().domain_score, Some(0.0));
+        assert_eq!(parse_and_validate_email_with_options("user@burner.example", &Config::default()).unwrap().domain_score, Some(50.0));
+    }
+
+    /// Tests that a custom pattern replaces the built-in syntax rules
+    #[test]
+    fn test_custom_pattern_option() {
+        let options: ValidationOptions = serde_json::from_str(r#"{"custom_pattern": "[a-z]+_[a-z]+@corp\\.example"}"#).unwrap();
+
+        let result = parse_and_validate_email_with_options("jane_doe@corp.example", &options).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.local_part, Some("jane_doe".to_string()));
+        assert_eq!(result.domain, Some("corp.example".to_string()));
+
+        let result = parse_and_validate_email_with_options("jane.doe@corp.example", &options).unwrap();
+        assert_eq!(result.error_message, Some("Email does not match the required pattern".to_string()));
+        let result = prefilter::validate_prefiltered("jane.doe@corp.example", &options).unwrap();
+        assert_eq!(result.error_message, Some("Email does not match the required pattern".to_string()));
+        assert!(parse_and_validate_email_with_options("jane_doe\n@corp.example", &options).is_err());
+
+        assert!(serde_json::from_str::<ValidationOptions>(r#"{"custom_pattern": "[a-z"}"#).is_err());
+    }
+
+    /// Tests that the locale option translates messages but not error types
+    #[test]
+    fn test_locale_option() {
+        let options = ValidationOptions { locale: Locale::Es, ..Default::default() };
+
+        let result = parse_and_validate_email_with_options("invalid-email", &options).unwrap();
+        assert_eq!(result.error_message, Some("Formato de correo electrónico no válido".to_string()));
+        let result = prefilter::validate_prefiltered("invalid-email", &options).unwrap();
+        assert_eq!(result.error_message, Some("Formato de correo electrónico no válido".to_string()));
+
+        let error = parse_and_validate_email_with_options("user\n@example.com", &options).unwrap_err();
+        assert_eq!(error.error_type, "ControlCharacter");
+        assert_eq!(error.message, "El correo electrónico contiene un carácter de control");
+
+        let options = ValidationOptions { locale: Locale::De, ..Default::default() };
+        let result = parse_and_validate_email_with_options("user@example.museum", &options).unwrap();
+        assert_eq!(result.warnings[0].warning_type, "UnusualTld");
+        assert_eq!(result.warnings[0].message, "Die Top-Level-Domain \".museum\" ist ungewöhnlich");
+    }
+
+    /// Tests that access policy rules deny or allow addresses before scoring
+    #[test]
+    fn test_access_policy_option() {
+        let options = ValidationOptions {
+            access_policy: AccessPolicy::new()
+                .allow("tempmail.com").unwrap()
+                .deny("local:noreply").unwrap(),
+            ..Default::default()
+        };
+
+        let result = parse_and_validate_email_with_options("noreply@example.com", &options).unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.error_message, Some("Address denied by access policy".to_string()));
+        assert_eq!(result.matched_access_rule.map(|rule| rule.pattern), Some("local:noreply".to_string()));
+
+        let result = parse_and_validate_email_with_options("qa@tempmail.com", &options).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.domain_score, Some(100.0));
+
+        let result = parse_and_validate_email_with_options("user@example.com", &options).unwrap();
+        assert_eq!(result.matched_access_rule, None);
+        assert_eq!(result.domain_score, Some(50.0));
+    }
+
+    /// Tests that TLD typos on known providers and a missing `@` populate the suggestion
+    #[test]
+    fn test_tld_typo_suggestion() {
+        let result = parse_and_validate_email("jane@gmail.con").unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.suggestion.as_deref(), Some("jane@gmail.com"));
+
+        let result = parse_and_validate_email("jane@gmail.co,").unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.suggestion.as_deref(), Some("jane@gmail.com"));
+
+        let result = parse_and_validate_email("jane[at]example.com").unwrap();
+        assert_eq!(result.error_message.as_deref(), Some("Invalid email format"));
+        assert_eq!(result.suggestion.as_deref(), Some("jane@example.com"));
+
+        assert_eq!(parse_and_validate_email("jane@gmail.com").unwrap().suggestion, None);
+        assert_eq!(parse_and_validate_email("jane@example.con").unwrap().suggestion, None);
+
+        // Batch validation prefilters trivially invalid input, which must not lose the suggestion
+        let emails = ["jane(at)example.com", "jane (at) example.com", "jane at example.com", "jane example.com", "jane[at]example.com"];
+        for (email, result) in emails.iter().zip(parse_and_validate_emails(&emails, &ValidationOptions::default())) {
+            let result = result.unwrap();
+            assert!(!result.is_valid, "{}", email);
+            assert_eq!(result.suggestion.as_deref(), Some("jane@example.com"), "{}", email);
+            assert_eq!(result.suggestion, parse_and_validate_email(email).unwrap().suggestion, "{}", email);
+        }
+    }
+
+    /// Tests that the score breakdown explains the composite domain score
+    #[test]
+    fn test_score_breakdown() {
+        let options = ValidationOptions {
+            protected_domains: vec!["acmebank.de".to_string()],
+            country_risk: HashMap::from([("DE".to_string(), 10.0)]),
+            ..Default::default()
+        };
+
+        let result = parse_and_validate_email_with_options("user@acme-bank.de", &options).unwrap();
+        let breakdown = result.score_breakdown.unwrap();
+        assert_eq!(breakdown.base, 50.0);
+        assert_eq!(breakdown.country_risk, 10.0);
+        assert_eq!(breakdown.typosquat, -50.0);
+        assert_eq!(breakdown.confusable_spoof, 0.0);
+        assert_eq!(Some(breakdown.total()), result.domain_score);
+
+        let result = parse_and_validate_email("invalid-email").unwrap();
+        assert_eq!(result.score_breakdown, None);
+    }
+
+    /// Tests that quoted local parts and domain literals get their own messages
+    #[cfg(not(feature = "regex-compat"))]
+    #[test]
+    fn test_quoted_and_literal_addresses() {
+        let result = parse_and_validate_email("\"john smith\"@example.com").unwrap();
+        assert_eq!(result.error_message.as_deref(), Some("Quoted local parts are not accepted"));
+        let result = parse_and_validate_email("user@[192.0.2.1]").unwrap();
+        assert_eq!(result.error_message.as_deref(), Some("Domain literals are not accepted"));
+        let result = parse_and_validate_email("user@[192.0.2]").unwrap();
+        assert_eq!(result.error_message.as_deref(), Some("Invalid email format"));
+    }
+
+    /// Tests that borrowed parts agree with the owned result
+    #[test]
+    fn test_parse_email_ref() {
+        for email in ["user+tag@example.com", "jane-shop@yahoo.com", "john.smith@example.co.uk", "invalid-email", "", "a@b@example.com"] {
+            let parts = parse_email_ref(email).unwrap();
+            let result = parse_and_validate_email(email).unwrap();
+            assert_eq!(parts.is_valid, result.is_valid, "{}", email);
+            assert_eq!(parts.local_part, result.local_part.as_deref());
+            assert_eq!(parts.domain, result.domain.as_deref());
+            assert_eq!(parts.subaddress, result.subaddress.as_deref());
+            assert_eq!(parts.canonical_local_part, result.canonical_local_part.as_deref());
+            assert_eq!(parts.error_message, result.error_message.as_deref());
+        }
+
+        let email = String::from("user@example.com");
+        let parts = parse_email_ref(&email).unwrap();
+        assert!(std::ptr::eq(parts.domain.unwrap().as_ptr(), email[5..].as_ptr()));
+        assert_eq!(parse_email_ref("user\n@example.com").unwrap_err().error_type, "ControlCharacter");
+    }
+
+    /// Tests that built-in scoring agrees with full scoring when no lists are loaded
+    #[test]
+    fn test_score_domain_builtin() {
+        for domain in ["Google.com", "mailinator.com", "example.tk", "example.XYZ", "example.com"] {
+            assert_eq!(score_domain_builtin(domain), score_domain(domain, &HashMap::new()), "{}", domain);
+        }
     }
 
     /// Tests various edge cases and boundary conditions
@@ -441,9 +1878,24 @@ mod tests {
         ];
 
         for email in malformed_emails {
+            if email.chars().any(char::is_control) {
+                let error = parse_and_validate_email(email).unwrap_err();
+                assert_eq!(error.error_type, "ControlCharacter", "Email '{}' should be rejected", email);
+                continue;
+            }
+
             let result = parse_and_validate_email(email).unwrap();
+            let empty_label = match email {
+                "test@domain." => Some("Domain label 1 (\"\") is empty"),
+                "test@.domain.com" => Some("Domain label 0 (\"\") is empty"),
+                "test@domain..com" => Some("Domain label 1 (\"\") is empty"),
+                _ => None
+            };
             if email.is_empty() {
                 assert_eq!(result.error_message, Some("Email cannot be empty".to_string()));
+            } else if let Some(message) = empty_label {
+                assert!(!result.is_valid, "Email '{}' should be invalid", email);
+                assert_eq!(result.error_message.as_deref(), Some(message));
             } else {
                 assert!(!result.is_valid, "Email '{}' should be invalid", email);
                 assert_eq!(result.error_message, Some("Invalid email format".to_string()));
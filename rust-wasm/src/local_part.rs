@@ -0,0 +1,211 @@
+//! Heuristics estimating whether a local part was chosen by a human or generated
+
+use serde::{Serialize, Deserialize};
+
+/// Keyboard rows, for detecting walks along adjacent keys
+const KEYBOARD_ROWS: [&str; 4] = ["1234567890", "qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// Shortest local part counted as one unit repeated (`abcabc`)
+const MIN_REPEATED_UNIT_LENGTH: usize = 6;
+
+/// Points subtracted when the whole local part is keyboard walks or repeats
+const KEYBOARD_PATTERN_PENALTY: f64 = 80.0;
+
+/// When runs of keys or characters count as mashed-keyboard patterns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct LocalPartThresholds {
+    /// Shortest run of adjacent keys on one keyboard row, either direction,
+    /// counted as a keyboard walk (`asdf`, `qwerty`, `7654`)
+    pub keyboard_walk: usize,
+    /// Shortest run of one repeated character counted (`aaaa`)
+    pub repeated_run: usize
+}
+
+impl Default for LocalPartThresholds {
+    fn default() -> Self {
+        LocalPartThresholds { keyboard_walk: 4, repeated_run: 4 }
+    }
+}
+
+/// Scores how human-chosen a local part looks, from 0 (random-looking) to 100
+///
+/// Only the part before any `+` tag is considered, lowercased and without
+/// `.`, `_` and `-` separators. The score starts at 100 and is reduced by:
+/// - long consonant runs (5 or more consonants in a row)
+/// - a very low share of vowels among letters
+/// - a high share of digits
+/// - frequent switching between letters and digits (`x3j9q`)
+/// - near-maximal character entropy, i.e. almost no repeated characters
+/// - keyboard walks, runs of one character and whole-string repeats
+///   (`qwerty123`, `aaaaaa`, `asdfasdf`), in proportion to how much of the
+///   local part they cover
+///
+/// # Examples
+/// ```ignore
+/// let thresholds = LocalPartThresholds::default();
+/// assert!(local_part_quality("jane.doe", &thresholds) > 70.0);
+/// assert!(local_part_quality("xk3j9qpl", &thresholds) < 50.0);
+/// assert!(local_part_quality("qwerty123", &thresholds) < 50.0);
+/// ```
+pub(crate) fn local_part_quality(local_part: &str, thresholds: &LocalPartThresholds) -> f64 {
+    let base = local_part.split('+').next().unwrap_or_default();
+    let chars: Vec<char> = base
+        .chars()
+        .filter(|c| !matches!(c, '.' | '_' | '-'))
+        .flat_map(char::to_lowercase)
+        .collect();
+
+    if chars.is_empty() {
+        return 0.0;
+    }
+
+    let mut penalty = 0.0;
+
+    let max_consonant_run = chars
+        .split(|c| !is_consonant(*c))
+        .map(<[char]>::len)
+        .max()
+        .unwrap_or_default();
+    if max_consonant_run >= 5 {
+        penalty += (max_consonant_run - 4) as f64 * 15.0;
+    }
+
+    let letters = chars.iter().filter(|c| c.is_alphabetic()).count();
+    let vowels = chars.iter().filter(|c| is_vowel(**c)).count();
+    if letters >= 4 && (vowels as f64 / letters as f64) < 0.15 {
+        penalty += 30.0;
+    }
+
+    let digits = chars.iter().filter(|c| c.is_ascii_digit()).count();
+    let digit_ratio = digits as f64 / chars.len() as f64;
+    if digit_ratio > 0.3 {
+        penalty += (digit_ratio - 0.3) * 100.0;
+    }
+
+    let transitions = chars
+        .windows(2)
+        .filter(|pair| pair[0].is_ascii_digit() != pair[1].is_ascii_digit())
+        .count();
+    if transitions > 2 {
+        penalty += (transitions - 2) as f64 * 15.0;
+    }
+
+    if chars.len() >= 6 {
+        let normalized_entropy = shannon_entropy(&chars) / (chars.len() as f64).log2();
+        if normalized_entropy > 0.85 {
+            penalty += (normalized_entropy - 0.85) / 0.15 * 20.0;
+        }
+    }
+
+    penalty += keyboard_pattern_coverage(&chars, thresholds) * KEYBOARD_PATTERN_PENALTY;
+
+    (100.0 - penalty).clamp(0.0, 100.0)
+}
+
+/// Share of `chars` inside keyboard walks, character runs or a whole-string
+/// repeat, from 0 to 1
+fn keyboard_pattern_coverage(chars: &[char], thresholds: &LocalPartThresholds) -> f64 {
+    let mut covered = vec![false; chars.len()];
+    let mut mark_runs = |min_length: usize, continues: &dyn Fn(usize) -> bool| {
+        let mut start = 0;
+        for end in 1..=chars.len() {
+            if end < chars.len() && continues(end) {
+                continue;
+            }
+            if end - start >= min_length.max(2) {
+                covered[start..end].fill(true);
+            }
+            start = end;
+        }
+    };
+
+    let key_step = |index: usize| -> Option<isize> {
+        let (row, column) = key_position(chars[index - 1])?;
+        let (next_row, next_column) = key_position(chars[index])?;
+        let step = next_column as isize - column as isize;
+        (row == next_row && step.abs() == 1).then_some(step)
+    };
+    mark_runs(thresholds.keyboard_walk, &|index| {
+        key_step(index).is_some_and(|step| index < 2 || key_step(index - 1).is_none_or(|previous| previous == step))
+    });
+    mark_runs(thresholds.repeated_run, &|index| chars[index] == chars[index - 1]);
+
+    // Units of one character are runs, governed by `repeated_run`
+    let repeats_unit = |unit: usize| {
+        chars.len().is_multiple_of(unit) && chars[..unit].iter().any(|c| *c != chars[0]) && chars.chunks(unit).all(|chunk| chunk == &chars[..unit])
+    };
+    if chars.len() >= MIN_REPEATED_UNIT_LENGTH && (2..=chars.len() / 2).any(repeats_unit) {
+        covered.fill(true);
+    }
+
+    covered.iter().filter(|covered| **covered).count() as f64 / chars.len() as f64
+}
+
+/// Row and column of a key on a QWERTY keyboard
+fn key_position(c: char) -> Option<(usize, usize)> {
+    KEYBOARD_ROWS.iter().enumerate().find_map(|(row, keys)| keys.find(c).map(|column| (row, column)))
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y')
+}
+
+fn is_consonant(c: char) -> bool {
+    c.is_ascii_alphabetic() && !is_vowel(c)
+}
+
+/// Shannon entropy of the character distribution, in bits per character
+fn shannon_entropy(chars: &[char]) -> f64 {
+    let mut counts = std::collections::HashMap::new();
+    for c in chars {
+        *counts.entry(c).or_insert(0usize) += 1;
+    }
+
+    let total = chars.len() as f64;
+    counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that human-looking local parts score high and generated ones low
+    #[test]
+    fn test_local_part_quality() {
+        let thresholds = LocalPartThresholds::default();
+        let local_part_quality = |local_part| local_part_quality(local_part, &thresholds);
+        for human in ["john.smith", "jane.doe+newsletter", "maria_garcia", "jdoe1987", "support"] {
+            assert!(local_part_quality(human) >= 60.0, "{} should look human: {}", human, local_part_quality(human));
+        }
+
+        for generated in ["xk3j9qpl", "qzxwvbnm", "a8f3k2m9z1", "7392018374"] {
+            assert!(local_part_quality(generated) < 50.0, "{} should look generated: {}", generated, local_part_quality(generated));
+        }
+
+        assert_eq!(local_part_quality("..."), 0.0);
+    }
+
+    /// Tests keyboard walks, character runs and repeats against their thresholds
+    #[test]
+    fn test_keyboard_patterns() {
+        let thresholds = LocalPartThresholds::default();
+        for mashed in ["asdfasdf", "qwerty123", "aaaaaa", "lkjhgf", "abcabcabc", "zxcvbnm"] {
+            assert!(local_part_quality(mashed, &thresholds) < 50.0, "{} should look mashed: {}", mashed, local_part_quality(mashed, &thresholds));
+        }
+        for human in ["anna", "coco", "jerry", "tyler.williams"] {
+            assert!(local_part_quality(human, &thresholds) >= 60.0, "{} should look human: {}", human, local_part_quality(human, &thresholds));
+        }
+
+        let lenient = LocalPartThresholds { keyboard_walk: 7, repeated_run: 7 };
+        assert!(local_part_quality("qwerty", &lenient) > local_part_quality("qwerty", &thresholds));
+        assert!(local_part_quality("aaaaaa", &lenient) > local_part_quality("aaaaaa", &thresholds));
+    }
+}
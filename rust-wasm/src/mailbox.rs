@@ -0,0 +1,350 @@
+//! RFC 5322 mailbox parsing (`Jane Doe <jane@example.com>`)
+
+use serde::{Serialize, Deserialize};
+
+use crate::{parse_and_validate_email_with_options, EmailParseError, EmailParseResult, ValidationOptions};
+
+/// A mailbox: an optional display name and its validated address
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Mailbox {
+    /// The display name, unquoted and unescaped (`Jane Doe`), if present
+    pub display_name: Option<String>,
+    /// Validation result for the address inside the angle brackets
+    pub address: EmailParseResult
+}
+
+impl Mailbox {
+    /// Mailbox for input whose quotes, comments or angle brackets are unbalanced
+    fn malformed() -> Self {
+        Mailbox {
+            display_name: None,
+            address: EmailParseResult::invalid("Invalid mailbox syntax")
+        }
+    }
+}
+
+/// Parses a mailbox in name-addr (`"Doe, Jane" <jane@example.com>`) or bare
+/// addr-spec (`jane@example.com`) form and validates its address
+///
+/// Quoted display names are unescaped; unquoted ones have runs of whitespace
+/// collapsed. Malformed mailbox syntax (unbalanced quotes or angle brackets)
+/// yields an invalid address result rather than an error.
+///
+/// # Arguments
+/// * `input` - The mailbox string to parse
+///
+/// # Returns
+/// * `Result<Mailbox, EmailParseError>` - Parsed mailbox or error
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::parse_mailbox;
+/// let mailbox = parse_mailbox("Jane Doe <jane@example.com>").unwrap();
+/// assert_eq!(mailbox.display_name, Some("Jane Doe".to_string()));
+/// assert!(mailbox.address.is_valid);
+/// ```
+pub fn parse_mailbox(input: &str) -> Result<Mailbox, EmailParseError> {
+    parse_mailbox_with_options(input, &ValidationOptions::default())
+}
+
+/// Parses a mailbox like `parse_mailbox`, validating its address with `options`
+///
+/// # Arguments
+/// * `input` - The mailbox string to parse
+/// * `options` - Validation options applied to the address
+///
+/// # Returns
+/// * `Result<Mailbox, EmailParseError>` - Parsed mailbox or error
+pub fn parse_mailbox_with_options(input: &str, options: &ValidationOptions) -> Result<Mailbox, EmailParseError> {
+    let (display_name, addr_spec) = match split_name_addr(input.trim()) {
+        Some(parts) => parts,
+        None => return Ok(Mailbox::malformed())
+    };
+
+    Ok(Mailbox {
+        display_name,
+        address: parse_and_validate_email_with_options(addr_spec, options)?
+    })
+}
+
+/// Parses an RFC 5322 address list (a pasted `To:` line) and validates each entry
+///
+/// Entries are separated by `,` or `;`; separators inside quoted strings,
+/// comments or angle brackets do not split. Comments are removed, and an
+/// entry without a display name takes its comment as one
+/// (`jane@example.com (Jane Doe)`). Empty entries are skipped.
+///
+/// # Arguments
+/// * `input` - The address list to parse
+///
+/// # Returns
+/// * `Result<Vec<Mailbox>, EmailParseError>` - One mailbox per entry, in input order
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::parse_address_list;
+/// let mailboxes = parse_address_list(r#""Doe, Jane" <jane@example.com>; bob@example.com"#).unwrap();
+/// assert_eq!(mailboxes.len(), 2);
+/// ```
+pub fn parse_address_list(input: &str) -> Result<Vec<Mailbox>, EmailParseError> {
+    parse_address_list_with_options(input, &ValidationOptions::default())
+}
+
+/// Parses an address list like `parse_address_list`, validating each address with `options`
+///
+/// # Arguments
+/// * `input` - The address list to parse
+/// * `options` - Validation options applied to each address
+///
+/// # Returns
+/// * `Result<Vec<Mailbox>, EmailParseError>` - One mailbox per entry, in input order
+pub fn parse_address_list_with_options(input: &str, options: &ValidationOptions) -> Result<Vec<Mailbox>, EmailParseError> {
+    split_address_list(input)
+        .into_iter()
+        .map(|entry| {
+            if entry.malformed {
+                return Ok(Mailbox::malformed());
+            }
+            let mut mailbox = parse_mailbox_with_options(&entry.text, options)?;
+            if mailbox.display_name.is_none() {
+                mailbox.display_name = entry.comment;
+            }
+            Ok(mailbox)
+        })
+        .collect()
+}
+
+/// One entry of an address list with its comments removed
+#[derive(Debug, Default)]
+struct ListEntry {
+    /// The entry text without comments
+    text: String,
+    /// Text of the entry's comments, joined by spaces
+    comment: Option<String>,
+    /// Whether a quoted string or comment was left unterminated
+    malformed: bool
+}
+
+impl ListEntry {
+    /// Whether the entry has no content at all (e.g. a trailing separator)
+    fn is_blank(&self) -> bool {
+        self.text.trim().is_empty() && self.comment.is_none() && !self.malformed
+    }
+}
+
+/// Splits an address list on `,` and `;` outside quotes, comments and angle brackets
+fn split_address_list(input: &str) -> Vec<ListEntry> {
+    let mut entries = Vec::new();
+    let mut entry = ListEntry::default();
+    let mut comment = String::new();
+    let mut comment_depth = 0usize;
+    let mut in_quotes = false;
+    let mut in_angle = false;
+    let mut escaped = false;
+
+    for c in input.chars() {
+        if escaped {
+            escaped = false;
+            if comment_depth > 0 {
+                comment.push(c);
+            } else {
+                entry.text.push(c);
+            }
+        } else if comment_depth > 0 {
+            match c {
+                '\\' => escaped = true,
+                '(' => {
+                    comment_depth += 1;
+                    comment.push(c);
+                }
+                ')' => {
+                    comment_depth -= 1;
+                    if comment_depth > 0 {
+                        comment.push(c);
+                        continue;
+                    }
+                    let text = comment.trim();
+                    if !text.is_empty() {
+                        entry.comment = Some(match entry.comment.take() {
+                            Some(previous) => format!("{previous} {text}"),
+                            None => text.to_string()
+                        });
+                    }
+                    comment.clear();
+                    entry.text.push(' ');
+                }
+                c => comment.push(c)
+            }
+        } else if in_quotes {
+            entry.text.push(c);
+            match c {
+                '\\' => escaped = true,
+                '"' => in_quotes = false,
+                _ => {}
+            }
+        } else {
+            match c {
+                '(' => comment_depth = 1,
+                ',' | ';' if !in_angle => {
+                    let finished = std::mem::take(&mut entry);
+                    if !finished.is_blank() {
+                        entries.push(finished);
+                    }
+                }
+                c => {
+                    match c {
+                        '"' => in_quotes = true,
+                        '<' => in_angle = true,
+                        '>' => in_angle = false,
+                        _ => {}
+                    }
+                    entry.text.push(c);
+                }
+            }
+        }
+    }
+
+    entry.malformed |= in_quotes || comment_depth > 0;
+    if !entry.is_blank() {
+        entries.push(entry);
+    }
+    entries
+}
+
+/// Splits a mailbox into its display name and addr-spec
+///
+/// Returns `None` if quotes or angle brackets are unbalanced.
+fn split_name_addr(input: &str) -> Option<(Option<String>, &str)> {
+    let Some(open) = find_unquoted(input, '<')? else {
+        return (!input.contains('>')).then_some((None, input));
+    };
+
+    let addr_spec = input[open + 1..].strip_suffix('>')?;
+    if addr_spec.contains(['<', '>']) {
+        return None;
+    }
+
+    Some((parse_display_name(&input[..open])?, addr_spec.trim()))
+}
+
+/// Byte offset of the first `target` outside a quoted string
+///
+/// Returns `None` if a quoted string is left unterminated.
+fn find_unquoted(input: &str, target: char) -> Option<Option<usize>> {
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (i, c) in input.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if in_quotes && c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            in_quotes = !in_quotes;
+        } else if c == target && !in_quotes {
+            return Some(Some(i));
+        }
+    }
+
+    (!in_quotes).then_some(None)
+}
+
+/// Decodes a display-name phrase, joining quoted and unquoted words
+///
+/// Returns `None` for an unterminated quoted string; an empty phrase
+/// decodes to `Some(None)`.
+fn parse_display_name(phrase: &str) -> Option<Option<String>> {
+    let mut name = String::new();
+    let mut chars = phrase.trim().chars();
+    let mut pending_space = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                if pending_space && !name.is_empty() {
+                    name.push(' ');
+                }
+                pending_space = false;
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => name.push(chars.next()?),
+                        c => name.push(c)
+                    }
+                }
+            }
+            c if c.is_whitespace() => pending_space = true,
+            c => {
+                if pending_space && !name.is_empty() {
+                    name.push(' ');
+                }
+                pending_space = false;
+                name.push(c);
+            }
+        }
+    }
+
+    Some((!name.is_empty()).then_some(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests name-addr parsing with plain, quoted and escaped display names
+    #[test]
+    fn test_parse_mailbox() {
+        let mailbox = parse_mailbox("Jane Doe <jane@example.com>").unwrap();
+        assert_eq!(mailbox.display_name, Some("Jane Doe".to_string()));
+        assert!(mailbox.address.is_valid);
+        assert_eq!(mailbox.address.local_part, Some("jane".to_string()));
+
+        let mailbox = parse_mailbox(r#"  "Doe, Jane \"JD\""   < jane@example.com > "#).unwrap();
+        assert_eq!(mailbox.display_name, Some(r#"Doe, Jane "JD""#.to_string()));
+        assert!(mailbox.address.is_valid);
+
+        let mailbox = parse_mailbox("<jane@example.com>").unwrap();
+        assert_eq!(mailbox.display_name, None);
+        assert!(mailbox.address.is_valid);
+
+        let mailbox = parse_mailbox("jane@example.com").unwrap();
+        assert_eq!(mailbox.display_name, None);
+        assert_eq!(mailbox.address.domain, Some("example.com".to_string()));
+    }
+
+    /// Tests that malformed mailbox syntax yields an invalid address
+    #[test]
+    fn test_parse_mailbox_malformed() {
+        for input in ["Jane <jane@example.com", "Jane jane@example.com>", "\"Jane <jane@example.com>", "Jane <<jane@example.com>>"] {
+            let mailbox = parse_mailbox(input).unwrap();
+            assert!(!mailbox.address.is_valid, "{input} should be invalid");
+            assert_eq!(mailbox.address.error_message, Some("Invalid mailbox syntax".to_string()));
+        }
+
+        let mailbox = parse_mailbox("Jane Doe <not-an-email>").unwrap();
+        assert_eq!(mailbox.display_name, Some("Jane Doe".to_string()));
+        assert!(!mailbox.address.is_valid);
+    }
+
+    /// Tests splitting a pasted To: line, respecting quotes, comments and brackets
+    #[test]
+    fn test_parse_address_list() {
+        let input = r#""Doe, Jane" <jane@example.com>; bob@example.com (Bob, Sales),, <carol@example.com>, not-an-email;"#;
+        let mailboxes = parse_address_list(input).unwrap();
+        assert_eq!(mailboxes.len(), 4);
+
+        assert_eq!(mailboxes[0].display_name, Some("Doe, Jane".to_string()));
+        assert_eq!(mailboxes[0].address.local_part, Some("jane".to_string()));
+        assert_eq!(mailboxes[1].display_name, Some("Bob, Sales".to_string()));
+        assert!(mailboxes[1].address.is_valid);
+        assert_eq!(mailboxes[2].display_name, None);
+        assert!(mailboxes[2].address.is_valid);
+        assert!(!mailboxes[3].address.is_valid);
+
+        let mailboxes = parse_address_list("jane@example.com, (unterminated bob@example.com").unwrap();
+        assert_eq!(mailboxes.len(), 2);
+        assert!(mailboxes[0].address.is_valid);
+        assert_eq!(mailboxes[1].address.error_message, Some("Invalid mailbox syntax".to_string()));
+    }
+}
@@ -0,0 +1,134 @@
+//! Parsing of `mailto:` URIs (RFC 6068) scraped from web pages
+
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+
+use crate::{parse_and_validate_email, EmailParseError, EmailParseResult};
+
+/// URI scheme prefix, matched case-insensitively
+const MAILTO_SCHEME: &str = "mailto:";
+
+/// A parsed `mailto:` URI
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MailtoUri {
+    /// The percent-decoded address from the URI path
+    pub email: String,
+    /// Validation result for `email`
+    pub address: EmailParseResult,
+    /// Percent-decoded query parameters (`subject`, `body`, `cc`, ...) keyed
+    /// by lowercased name; the first occurrence of a repeated name wins
+    pub params: BTreeMap<String, String>
+}
+
+/// Parses a `mailto:` URI, validating its percent-decoded address
+///
+/// The scheme is matched case-insensitively. As in RFC 6068, `+` is taken
+/// literally rather than as an encoded space. Input without the `mailto:`
+/// scheme or with malformed percent-encoding in the address yields an
+/// invalid address result rather than an error.
+///
+/// # Arguments
+/// * `uri` - The URI to parse, e.g. `mailto:jane%2Bnews@example.com?subject=Hi`
+///
+/// # Returns
+/// * `Result<MailtoUri, EmailParseError>` - Parsed URI or error
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::parse_mailto;
+/// let mailto = parse_mailto("mailto:jane%2Bnews@example.com?subject=Hello%20there").unwrap();
+/// assert_eq!(mailto.email, "jane+news@example.com");
+/// assert!(mailto.address.is_valid);
+/// assert_eq!(mailto.params["subject"], "Hello there");
+/// ```
+pub fn parse_mailto(uri: &str) -> Result<MailtoUri, EmailParseError> {
+    let uri = uri.trim();
+    let rest = match uri.get(..MAILTO_SCHEME.len()) {
+        Some(scheme) if scheme.eq_ignore_ascii_case(MAILTO_SCHEME) => &uri[MAILTO_SCHEME.len()..],
+        _ => return Ok(MailtoUri {
+            email: uri.to_string(),
+            address: EmailParseResult::invalid("Not a mailto URI"),
+            ..Default::default()
+        })
+    };
+
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+    let mut params = BTreeMap::new();
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let name = percent_decode(name).map(|bytes| String::from_utf8_lossy(&bytes).to_lowercase());
+        let value = percent_decode(value).map(|bytes| String::from_utf8_lossy(&bytes).into_owned());
+        if let (Some(name), Some(value)) = (name, value) {
+            params.entry(name).or_insert(value);
+        }
+    }
+
+    let email = match percent_decode(path).and_then(|bytes| String::from_utf8(bytes).ok()) {
+        Some(email) => email,
+        None => return Ok(MailtoUri {
+            email: path.to_string(),
+            address: EmailParseResult::invalid("Invalid percent-encoding in mailto URI"),
+            params
+        })
+    };
+
+    Ok(MailtoUri {
+        address: parse_and_validate_email(&email)?,
+        email,
+        params
+    })
+}
+
+/// Decodes `%XX` escapes; returns `None` for a truncated or non-hex escape
+fn percent_decode(input: &str) -> Option<Vec<u8>> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests scheme stripping, address decoding and query parameter parsing
+    #[test]
+    fn test_parse_mailto() {
+        let mailto = parse_mailto("MAILTO:jane%2Bnews@example.com?Subject=Hello%20there&body=a+b&subject=ignored").unwrap();
+        assert_eq!(mailto.email, "jane+news@example.com");
+        assert!(mailto.address.is_valid);
+        assert_eq!(mailto.address.subaddress, Some("news".to_string()));
+        assert_eq!(mailto.params.get("subject"), Some(&"Hello there".to_string()));
+        assert_eq!(mailto.params.get("body"), Some(&"a+b".to_string()));
+
+        let mailto = parse_mailto("mailto:ops@google.com").unwrap();
+        assert!(mailto.address.is_valid);
+        assert!(mailto.params.is_empty());
+    }
+
+    /// Tests that non-mailto input and bad percent-encoding are reported as invalid
+    #[test]
+    fn test_parse_mailto_invalid() {
+        let mailto = parse_mailto("https://example.com").unwrap();
+        assert_eq!(mailto.address.error_message, Some("Not a mailto URI".to_string()));
+
+        let mailto = parse_mailto("mailto:jane%2@example.com?subject=Hi").unwrap();
+        assert_eq!(mailto.address.error_message, Some("Invalid percent-encoding in mailto URI".to_string()));
+        assert_eq!(mailto.params.get("subject"), Some(&"Hi".to_string()));
+    }
+}
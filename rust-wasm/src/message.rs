@@ -0,0 +1,52 @@
+//! Validation messages, named before they are turned into text
+//!
+//! Checks report why an address was rejected with a `Message` instead of a
+//! string, so `i18n` can translate it without matching on the English text.
+
+/// Reason an address was rejected, or the text of an "ObsoleteSyntax"
+/// warning
+///
+/// Variants only reported by std-only checks are gated with them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Message {
+    Empty,
+    TooLong,
+    InvalidFormat,
+    QuotedLocalPart,
+    DomainLiteral,
+    #[cfg(feature = "std")]
+    AccessDenied,
+    ControlCharacter,
+    #[cfg(feature = "std")]
+    PatternMismatch,
+    #[cfg(feature = "std")]
+    MisplacedComment,
+    #[cfg(feature = "std")]
+    MixedLocalPart,
+    #[cfg(feature = "std")]
+    SpaceAroundDot
+}
+
+impl Message {
+    /// English text, as reported when no other locale is configured
+    pub(crate) fn english(self) -> &'static str {
+        match self {
+            Message::Empty => "Email cannot be empty",
+            Message::TooLong => "Email exceeds maximum length of 320 characters",
+            Message::InvalidFormat => "Invalid email format",
+            Message::QuotedLocalPart => "Quoted local parts are not accepted",
+            Message::DomainLiteral => "Domain literals are not accepted",
+            #[cfg(feature = "std")]
+            Message::AccessDenied => "Address denied by access policy",
+            Message::ControlCharacter => "Email contains a control character",
+            #[cfg(feature = "std")]
+            Message::PatternMismatch => "Email does not match the required pattern",
+            #[cfg(feature = "std")]
+            Message::MisplacedComment => "Comments are only allowed before or after the local part and domain",
+            #[cfg(feature = "std")]
+            Message::MixedLocalPart => "Local part mixes quoted and unquoted words, which is obsolete RFC 5322 syntax",
+            #[cfg(feature = "std")]
+            Message::SpaceAroundDot => "Whitespace or a comment around a dot is obsolete RFC 5322 syntax"
+        }
+    }
+}
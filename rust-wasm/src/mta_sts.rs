@@ -0,0 +1,96 @@
+//! MTA-STS (RFC 8461) policy discovery
+//!
+//! A domain advertises a policy with a `v=STSv1` TXT record at
+//! `_mta-sts.<domain>` and serves it over HTTPS from
+//! `https://mta-sts.<domain>/.well-known/mta-sts.txt`.
+
+use serde::{Serialize, Deserialize};
+
+/// Mode of a domain's MTA-STS policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyMode {
+    /// Senders must deliver over validated TLS to a listed MX
+    Enforce,
+    /// Senders report failures but still deliver
+    Testing,
+    /// The domain is withdrawing its policy
+    None
+}
+
+/// Largest policy body accepted (RFC 8461 section 3.2)
+#[cfg(any(target_arch = "wasm32", feature = "tls"))]
+const MAX_POLICY_SIZE: usize = 64 * 1024;
+
+/// Extracts the mode from a policy body, or `None` if it is not a valid STSv1 policy
+pub(crate) fn parse_policy(body: &str) -> Option<PolicyMode> {
+    let mut version = None;
+    let mut mode = None;
+    for line in body.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        match key.trim() {
+            "version" => version = Some(value.trim()),
+            "mode" => mode = Some(value.trim()),
+            _ => {}
+        }
+    }
+
+    if version != Some("STSv1") {
+        return None;
+    }
+    match mode? {
+        "enforce" => Some(PolicyMode::Enforce),
+        "testing" => Some(PolicyMode::Testing),
+        "none" => Some(PolicyMode::None),
+        _ => None
+    }
+}
+
+/// Fetches the policy body for `domain` through the host's `fetch`
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn fetch_policy(domain: &str) -> Result<String, String> {
+    let url = format!("https://mta-sts.{}/.well-known/mta-sts.txt", domain);
+    let body = crate::fetch::fetch_text(&url, "text/plain").await.map_err(|e| e.as_string().unwrap_or_default())?;
+    if body.len() > MAX_POLICY_SIZE {
+        return Err("Policy exceeds 64 KiB".to_string());
+    }
+    Ok(body)
+}
+
+/// Fetches the policy body for `domain` over HTTPS, requiring a certificate
+/// that validates against the public roots
+#[cfg(all(feature = "tls", not(target_arch = "wasm32")))]
+pub(crate) async fn fetch_policy(domain: &str) -> Result<String, String> {
+    let host = format!("mta-sts.{}", domain);
+    let (status, body) = crate::http::https_get(&host, "/.well-known/mta-sts.txt", std::time::Duration::from_secs(10), MAX_POLICY_SIZE).await?;
+    if status != 200 {
+        return Err(format!("HTTP status {}", status));
+    }
+    Ok(body)
+}
+
+/// Policies cannot be fetched without TLS support
+#[cfg(all(not(feature = "tls"), not(target_arch = "wasm32")))]
+pub(crate) async fn fetch_policy(_domain: &str) -> Result<String, String> {
+    Err("Fetching MTA-STS policies requires the `tls` feature".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests mode extraction and rejection of invalid policies
+    #[test]
+    fn test_parse_policy() {
+        let policy = "version: STSv1\r\nmode: enforce\r\nmx: mail.example.com\r\nmx: *.example.net\r\nmax_age: 604800\r\n";
+        assert_eq!(parse_policy(policy), Some(PolicyMode::Enforce));
+        assert_eq!(parse_policy("version: STSv1\nmode: testing\n"), Some(PolicyMode::Testing));
+        assert_eq!(parse_policy("version: STSv1\nmode: none\n"), Some(PolicyMode::None));
+        assert_eq!(parse_policy("version: STSv2\nmode: enforce\n"), None);
+        assert_eq!(parse_policy("version: STSv1\nmode: strict\n"), None);
+        assert_eq!(parse_policy("<html>Not found</html>"), None);
+    }
+}
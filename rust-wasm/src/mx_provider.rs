@@ -0,0 +1,82 @@
+//! Mail hosting provider identification from MX host names
+
+use serde::{Serialize, Deserialize};
+
+use crate::dns::MxRecord;
+
+/// Service hosting a domain's mail, as identified from its MX records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum MxProvider {
+    GoogleWorkspace,
+    Microsoft365,
+    ProtonMail,
+    Zoho,
+    Fastmail,
+    Yahoo,
+    ICloud,
+    Yandex,
+    /// Amazon SES / WorkMail inbound
+    AmazonSes,
+    /// Security gateways that front another mailbox provider
+    Mimecast,
+    Proofpoint,
+    Barracuda
+}
+
+/// MX host suffixes and the provider they belong to, matched on label boundaries
+const MX_SUFFIXES: [(&str, MxProvider); 17] = [
+    ("google.com", MxProvider::GoogleWorkspace),
+    ("googlemail.com", MxProvider::GoogleWorkspace),
+    ("mail.protection.outlook.com", MxProvider::Microsoft365),
+    ("outlook.com", MxProvider::Microsoft365),
+    ("protonmail.ch", MxProvider::ProtonMail),
+    ("zoho.com", MxProvider::Zoho),
+    ("zoho.eu", MxProvider::Zoho),
+    ("messagingengine.com", MxProvider::Fastmail),
+    ("yahoodns.net", MxProvider::Yahoo),
+    ("icloud.com", MxProvider::ICloud),
+    ("yandex.net", MxProvider::Yandex),
+    ("yandex.ru", MxProvider::Yandex),
+    ("amazonaws.com", MxProvider::AmazonSes),
+    ("mimecast.com", MxProvider::Mimecast),
+    ("pphosted.com", MxProvider::Proofpoint),
+    ("ppe-hosted.com", MxProvider::Proofpoint),
+    ("barracudanetworks.com", MxProvider::Barracuda)
+];
+
+/// Provider behind the most preferred MX host that matches a known provider
+pub(crate) fn identify_mx_provider(records: &[MxRecord]) -> Option<MxProvider> {
+    let mut records: Vec<&MxRecord> = records.iter().collect();
+    records.sort_by_key(|record| record.preference);
+
+    records.iter().find_map(|record| {
+        let host = record.exchange.trim_end_matches('.').to_lowercase();
+        MX_SUFFIXES
+            .iter()
+            .find(|(suffix, _)| host == *suffix || host.strip_suffix(suffix).is_some_and(|rest| rest.ends_with('.')))
+            .map(|(_, provider)| *provider)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mx(preference: u16, exchange: &str) -> MxRecord {
+        MxRecord { preference, exchange: exchange.to_string() }
+    }
+
+    /// Tests suffix matching on label boundaries and preference ordering
+    #[test]
+    fn test_identify_mx_provider() {
+        assert_eq!(identify_mx_provider(&[mx(1, "ASPMX.L.GOOGLE.COM.")]), Some(MxProvider::GoogleWorkspace));
+        assert_eq!(identify_mx_provider(&[mx(0, "example-com.mail.protection.outlook.com")]), Some(MxProvider::Microsoft365));
+        assert_eq!(identify_mx_provider(&[mx(10, "mail.protonmail.ch")]), Some(MxProvider::ProtonMail));
+        assert_eq!(identify_mx_provider(&[mx(10, "mx1.notgoogle.com")]), None);
+        assert_eq!(identify_mx_provider(&[mx(20, "alt1.aspmx.l.google.com"), mx(10, "us-smtp-inbound-1.mimecast.com")]), Some(MxProvider::Mimecast));
+        assert_eq!(identify_mx_provider(&[mx(10, "mail.example.com"), mx(20, "alt1.aspmx.l.google.com")]), Some(MxProvider::GoogleWorkspace));
+        assert_eq!(identify_mx_provider(&[]), None);
+    }
+}
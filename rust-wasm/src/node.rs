@@ -0,0 +1,196 @@
+//! Native Node.js addon (napi-rs), an alternative to the wasm build for servers
+//!
+//! Exports use the same names, return shapes and field naming as the wasm
+//! exports so the TS SDK can load either backend behind one interface;
+//! `set_snake_case_output` switches both. `verify_email_wasm` and the chunked
+//! batch export depend on browser APIs and stay wasm-only.
+
+// napi registers the exports at module load; test builds skip that registration
+#![cfg_attr(test, allow(dead_code))]
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+
+use crate::casing::{self, AcceptCamelCase, Cased};
+use crate::{EmailParseError, EmailParseResult, ValidationOptions};
+
+/// Native counterpart of `init_panic_hook`: writes the panic message and a
+/// backtrace to stderr
+///
+/// Calling it again has no effect.
+#[napi(js_name = "init_panic_hook")]
+pub fn init_panic_hook_node() {
+    static INSTALL: std::sync::Once = std::sync::Once::new();
+    INSTALL.call_once(|| {
+        std::panic::set_hook(Box::new(|info| {
+            eprintln!("{}\n{}", info, std::backtrace::Backtrace::force_capture());
+        }));
+    });
+}
+
+/// Native counterpart of `version`
+#[napi(js_name = "version")]
+pub fn version_node() -> String {
+    crate::version().to_string()
+}
+
+/// Native counterpart of `build_info`
+#[napi(js_name = "build_info")]
+pub fn build_info_node() -> serde_json::Value {
+    to_value(Ok(crate::build_info()))
+}
+
+/// Native counterpart of `set_snake_case_output`
+#[napi(js_name = "set_snake_case_output")]
+pub fn set_snake_case_output_node(enabled: bool) {
+    casing::set_snake_case_output(enabled);
+}
+
+/// Serializes an `Ok` value or an `EmailParseError` as a plain JS value,
+/// matching the wasm exports
+fn to_value<T: Serialize>(result: std::result::Result<T, EmailParseError>) -> serde_json::Value {
+    let value = match result {
+        Ok(value) => serde_json::to_value(Cased(&value)),
+        Err(e) => serde_json::to_value(Cased(&e))
+    };
+    value.unwrap_or(serde_json::Value::Null)
+}
+
+/// Native counterpart of `parse_and_validate_email_wasm`
+#[napi(js_name = "parse_and_validate_email_wasm")]
+pub fn parse_and_validate_email_node(email: String) -> serde_json::Value {
+    to_value(crate::parse_and_validate_email(&email))
+}
+
+/// Native counterpart of `parse_and_validate_email_with_options_wasm`,
+/// returning a Promise as it does
+///
+/// Accepts the same options object, except `domainScorer` callbacks, which
+/// are ignored. Validation runs on the libuv thread pool.
+#[napi(js_name = "parse_and_validate_email_with_options_wasm")]
+pub fn parse_and_validate_email_with_options_node(email: String, options: Option<serde_json::Value>) -> AsyncTask<ValidateWithOptions> {
+    AsyncTask::new(ValidateWithOptions { email, options: options_from_json(options) })
+}
+
+/// Pending `parse_and_validate_email_with_options_wasm` call
+pub struct ValidateWithOptions {
+    email: String,
+    options: std::result::Result<ValidationOptions, EmailParseError>
+}
+
+impl Task for ValidateWithOptions {
+    type Output = std::result::Result<EmailParseResult, EmailParseError>;
+    type JsValue = JsonValue;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        // `compute` runs once per task
+        let options = std::mem::replace(&mut self.options, Ok(ValidationOptions::default()));
+        Ok(options.and_then(|options| crate::parse_and_validate_email_with_options(&self.email, &options)))
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(JsonValue(to_value(output)))
+    }
+}
+
+/// A serialized result resolved by a `Task`, which must name its JS type
+pub struct JsonValue(serde_json::Value);
+
+impl TypeName for JsonValue {
+    fn type_name() -> &'static str {
+        "Object"
+    }
+
+    fn value_type() -> ValueType {
+        ValueType::Object
+    }
+}
+
+impl ToNapiValue for JsonValue {
+    unsafe fn to_napi_value(env: napi::sys::napi_env, value: Self) -> Result<napi::sys::napi_value> {
+        // SAFETY: napi passes a valid env when resolving the task
+        unsafe { serde_json::Value::to_napi_value(env, value.0) }
+    }
+}
+
+/// Native counterpart of `validate_partial_wasm`
+#[napi(js_name = "validate_partial_wasm")]
+pub fn validate_partial_node(input: String, options: Option<serde_json::Value>) -> serde_json::Value {
+    to_value(options_from_json(options).map(|options| crate::validate_partial(&input, &options)))
+}
+
+/// Options from a JS options object with camelCase or snake_case keys, or
+/// the defaults when none was passed
+fn options_from_json(options: Option<serde_json::Value>) -> std::result::Result<ValidationOptions, EmailParseError> {
+    match options {
+        Some(options) if options.is_object() => ValidationOptions::deserialize(AcceptCamelCase(options)).map_err(|e| EmailParseError {
+            error_type: "InvalidOptions".to_string(),
+            message: "Failed to parse validation options".to_string(),
+            details: Some(e.to_string()),
+            position: None
+        }),
+        _ => Ok(ValidationOptions::default())
+    }
+}
+
+/// Native counterpart of `is_header_safe_wasm`
+#[napi(js_name = "is_header_safe_wasm")]
+pub fn is_header_safe_node(email: String) -> bool {
+    crate::is_header_safe(&email)
+}
+
+/// Native counterpart of `is_valid_html5_email_wasm`
+#[napi(js_name = "is_valid_html5_email_wasm")]
+pub fn is_valid_html5_email_node(value: String) -> bool {
+    crate::is_valid_html5_email(&value)
+}
+
+/// Native counterpart of `hash_email_wasm`
+#[napi(js_name = "hash_email_wasm")]
+pub fn hash_email_node(email: String) -> String {
+    crate::hash_email(&email)
+}
+
+/// Native counterpart of `load_domain_lists_wasm`; throws on invalid JSON
+#[napi(js_name = "load_domain_lists_wasm")]
+pub fn load_domain_lists_node(json: String) -> Result<()> {
+    crate::load_domain_lists(&json).map_err(|e| Error::from_reason(e.to_string()))
+}
+
+/// Native counterpart of `parse_mailbox_wasm`
+#[napi(js_name = "parse_mailbox_wasm")]
+pub fn parse_mailbox_node(input: String) -> serde_json::Value {
+    to_value(crate::parse_mailbox(&input))
+}
+
+/// Native counterpart of `parse_address_list_wasm`
+#[napi(js_name = "parse_address_list_wasm")]
+pub fn parse_address_list_node(input: String) -> serde_json::Value {
+    to_value(crate::parse_address_list(&input))
+}
+
+/// Native counterpart of `extract_emails_wasm`
+#[napi(js_name = "extract_emails_wasm")]
+pub fn extract_emails_node(text: String) -> serde_json::Value {
+    to_value(crate::extract_emails(&text))
+}
+
+/// Native counterpart of `suggest_corrections_wasm`
+#[napi(js_name = "suggest_corrections_wasm")]
+pub fn suggest_corrections_node(email: String) -> serde_json::Value {
+    to_value(Ok(crate::suggest_corrections(&email)))
+}
+
+/// Native counterpart of `parse_mailto_wasm`
+#[napi(js_name = "parse_mailto_wasm")]
+pub fn parse_mailto_node(uri: String) -> serde_json::Value {
+    to_value(crate::parse_mailto(&uri))
+}
+
+/// Native counterpart of `validate_packed_wasm`: newline-delimited addresses
+/// in, one JSON record per non-blank line out
+#[napi(js_name = "validate_packed_wasm")]
+pub fn validate_packed_node(input: Buffer) -> Buffer {
+    crate::stream::validate_packed(&input).into()
+}
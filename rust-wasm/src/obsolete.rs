@@ -0,0 +1,221 @@
+//! RFC 5322 obsolete (`obs-`) address syntax in strict mode
+//!
+//! RFC 5322 still defines how to read the older forms it no longer lets
+//! senders generate: a local part mixing quoted and unquoted words
+//! (`john."q".public@example.com`, `obs-local-part`) and folding whitespace
+//! or comments around the dots (`john . smith@example . com`, `obs-local-part`
+//! and `obs-domain`). Strict validation rewrites them to the equivalent
+//! modern address and flags each form with an "ObsoleteSyntax" warning.
+
+use crate::message::Message;
+
+/// An obsolete production an address relies on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ObsoleteForm {
+    /// Quoted and unquoted words joined by dots in the local part
+    MixedLocalPart,
+    /// Whitespace or a comment next to a dot
+    SpaceAroundDot
+}
+
+impl ObsoleteForm {
+    /// The "ObsoleteSyntax" warning for this form
+    pub(crate) fn message(&self) -> Message {
+        match self {
+            ObsoleteForm::MixedLocalPart => Message::MixedLocalPart,
+            ObsoleteForm::SpaceAroundDot => Message::SpaceAroundDot
+        }
+    }
+}
+
+/// Characters allowed unquoted in a rewritten local part, as in the parser
+fn is_atom_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '%' | '+' | '-')
+}
+
+/// Splits `text` on dots outside quoted strings
+fn split_words(text: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (index, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if in_quotes {
+            match c {
+                '\\' => escaped = true,
+                '"' => in_quotes = false,
+                _ => {}
+            }
+        } else if c == '"' {
+            in_quotes = true;
+        } else if c == '.' {
+            words.push(&text[start..index]);
+            start = index + 1;
+        }
+    }
+    words.push(&text[start..]);
+    words
+}
+
+/// Splits `address` at its last `@` outside quoted strings
+fn split_at_sign(address: &str) -> Option<(&str, &str)> {
+    let mut at = None;
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (index, c) in address.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if in_quotes {
+            match c {
+                '\\' => escaped = true,
+                '"' => in_quotes = false,
+                _ => {}
+            }
+        } else {
+            match c {
+                '"' => in_quotes = true,
+                '@' => at = Some(index),
+                _ => {}
+            }
+        }
+    }
+    at.map(|at| (&address[..at], &address[at + 1..]))
+}
+
+/// Removes the whitespace next to each dot, returning the words and whether
+/// any was found
+fn trim_around_dots<'a>(words: &[&'a str]) -> (Vec<&'a str>, bool) {
+    let last = words.len() - 1;
+    let trimmed: Vec<&str> = words
+        .iter()
+        .enumerate()
+        .map(|(index, word)| {
+            let word = if index > 0 { word.trim_start() } else { word };
+            if index < last { word.trim_end() } else { word }
+        })
+        .collect();
+    let changed = trimmed.iter().zip(words).any(|(trimmed, word)| trimmed.len() != word.len());
+    (trimmed, changed)
+}
+
+/// The content of a quoted string with its quoted pairs unescaped, or None
+/// if `word` is not exactly one quoted string
+fn unquote(word: &str) -> Option<String> {
+    let inner = word.strip_prefix('"')?.strip_suffix('"')?;
+    let mut content = String::with_capacity(inner.len());
+    let mut escaped = false;
+    for c in inner.chars() {
+        match c {
+            _ if escaped => {
+                escaped = false;
+                content.push(c);
+            }
+            '\\' => escaped = true,
+            '"' => return None,
+            c => content.push(c)
+        }
+    }
+    (!escaped).then_some(content)
+}
+
+/// The semantic value of an `obs-local-part`: its words unquoted and joined
+/// by dots, quoted again if the result is not a dot-atom
+fn join_words(words: &[&str]) -> Option<String> {
+    let mut joined = Vec::with_capacity(words.len());
+    for word in words {
+        if word.contains('"') {
+            joined.push(unquote(word)?);
+        } else if word.is_empty() {
+            return None;
+        } else {
+            joined.push(word.to_string());
+        }
+    }
+    let joined = joined.join(".");
+
+    let is_dot_atom = joined.split('.').all(|atom| !atom.is_empty() && atom.chars().all(is_atom_char));
+    if is_dot_atom {
+        return Some(joined);
+    }
+    let escaped: String = joined
+        .chars()
+        .flat_map(|c| matches!(c, '"' | '\\').then_some('\\').into_iter().chain([c]))
+        .collect();
+    Some(format!("\"{}\"", escaped))
+}
+
+/// Rewrites `address` without the obsolete syntax it uses
+///
+/// Returns None if the address uses no obsolete form, or is too malformed
+/// to rewrite; it is then validated as given. Domain literals are left
+/// alone.
+///
+/// # Examples
+/// ```ignore
+/// let (address, forms) = rewrite_obsolete("john . \"q\".public@example . com").unwrap();
+/// assert_eq!(address, "john.q.public@example.com");
+/// assert_eq!(forms, [ObsoleteForm::MixedLocalPart, ObsoleteForm::SpaceAroundDot]);
+/// ```
+pub(crate) fn rewrite_obsolete(address: &str) -> Option<(String, Vec<ObsoleteForm>)> {
+    let (local_part, domain) = split_at_sign(address)?;
+
+    let mut forms = Vec::new();
+    let (local_words, local_spaced) = trim_around_dots(&split_words(local_part));
+    if local_words.len() > 1 && local_words.iter().any(|word| word.starts_with('"')) {
+        forms.push(ObsoleteForm::MixedLocalPart);
+    }
+
+    let (domain, domain_spaced) = if domain.trim_start().starts_with('[') {
+        (domain.to_string(), false)
+    } else {
+        let (labels, spaced) = trim_around_dots(&domain.split('.').collect::<Vec<_>>());
+        (labels.join("."), spaced)
+    };
+    if local_spaced || domain_spaced {
+        forms.push(ObsoleteForm::SpaceAroundDot);
+    }
+
+    if forms.is_empty() {
+        return None;
+    }
+    let local_part = if forms.contains(&ObsoleteForm::MixedLocalPart) {
+        join_words(&local_words)?
+    } else {
+        local_words.join(".")
+    };
+    Some((format!("{}@{}", local_part, domain), forms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that each obsolete form is rewritten and reported, and modern syntax is left alone
+    #[test]
+    fn test_rewrite_obsolete() {
+        assert_eq!(rewrite_obsolete("john.smith@example.com"), None);
+        assert_eq!(rewrite_obsolete("\"john. smith\"@example.com"), None);
+        assert_eq!(rewrite_obsolete("\"john\".smith@[192.0.2.1]").map(|(address, _)| address).as_deref(), Some("john.smith@[192.0.2.1]"));
+
+        let (address, forms) = rewrite_obsolete("john.\"q\".public@example.com").unwrap();
+        assert_eq!(address, "john.q.public@example.com");
+        assert_eq!(forms, [ObsoleteForm::MixedLocalPart]);
+
+        let (address, _) = rewrite_obsolete("\"john doe\".smith@example.com").unwrap();
+        assert_eq!(address, "\"john doe.smith\"@example.com");
+        let (address, _) = rewrite_obsolete("\"a\\\"b\".c@example.com").unwrap();
+        assert_eq!(address, "\"a\\\"b.c\"@example.com");
+
+        let (address, forms) = rewrite_obsolete("john . smith@example .com").unwrap();
+        assert_eq!(address, "john.smith@example.com");
+        assert_eq!(forms, [ObsoleteForm::SpaceAroundDot]);
+
+        assert_eq!(rewrite_obsolete("john..\"q\"@example.com"), None);
+        assert_eq!(rewrite_obsolete("\"q\"x.john@example.com"), None);
+        assert_eq!(rewrite_obsolete("john.smith"), None);
+    }
+}
@@ -0,0 +1,299 @@
+//! Hand-written address parser
+//!
+//! A single left-to-right pass over the input bytes that records the span of
+//! the local part, each domain label and the TLD. It accepts the same
+//! dot-atom addresses as the original validation regex, and additionally
+//! recognizes quoted local parts (`"john doe"@example.com`) and domain
+//! literals (`user@[192.0.2.1]`) so callers can tell them apart from garbage.
+
+use core::net::{Ipv4Addr, Ipv6Addr};
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::ToString, vec::Vec};
+
+use crate::message::Message;
+use crate::EmailParseError;
+
+/// Byte range of a component within the parsed input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize
+}
+
+impl Span {
+    /// The component's text within `input`
+    pub fn as_str<'a>(&self, input: &'a str) -> &'a str {
+        &input[self.start..self.end]
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// Form of the local part
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalPartKind {
+    /// Dot-separated atoms (`john.smith`)
+    DotAtom,
+    /// A quoted string (`"john smith"`), span including the quotes
+    Quoted
+}
+
+/// Form of the domain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DomainKind {
+    /// A host name made of labels
+    Name,
+    /// An IPv4 literal (`[192.0.2.1]`)
+    Ipv4Literal,
+    /// An IPv6 literal (`[IPv6:2001:db8::1]`)
+    Ipv6Literal
+}
+
+/// Structure of a syntactically valid address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedAddress {
+    pub local_part: Span,
+    pub local_part_kind: LocalPartKind,
+    pub domain: Span,
+    pub domain_kind: DomainKind,
+    /// Each dot-separated domain label, TLD last (empty for literals)
+    pub labels: Vec<Span>,
+    /// The last label (None for literals)
+    pub tld: Option<Span>
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Before the first local part character, or right after a dot
+    AtomStart,
+    /// Inside an atom
+    Atom,
+    /// Inside a quoted string
+    Quoted,
+    /// After a backslash inside a quoted string
+    QuotedPair,
+    /// After the closing quote; only `@` may follow
+    QuotedEnd,
+    /// Right after `@` or a domain dot
+    LabelStart,
+    /// Inside a label, last character alphanumeric
+    Label,
+    /// Inside a label, last character a hyphen
+    LabelHyphen,
+    /// Inside a `[...]` literal
+    Literal,
+    /// After the closing bracket; nothing may follow
+    LiteralEnd
+}
+
+/// Characters allowed in a local part atom
+fn is_atom_char(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'%' | b'+' | b'-')
+}
+
+/// Printable ASCII and space, as allowed in quoted strings and literals
+fn is_printable(byte: u8) -> bool {
+    (b' '..=b'~').contains(&byte)
+}
+
+/// Parses `input` as `local-part@domain`
+///
+/// Errors carry `error_type` "InvalidFormat" and the character position of
+/// the first byte that cannot continue a valid address.
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::parse_address;
+/// let email = "john.smith@mail.example.com";
+/// let parsed = parse_address(email)?;
+/// assert_eq!(parsed.local_part.as_str(email), "john.smith");
+/// assert_eq!(parsed.labels.len(), 3);
+/// assert_eq!(parsed.tld.unwrap().as_str(email), "com");
+/// # Ok::<(), email_validator_wasm::EmailParseError>(())
+/// ```
+pub fn parse_address(input: &str) -> Result<ParsedAddress, EmailParseError> {
+    parse(input).map_err(|failure| format_error(input, failure.byte_index))
+}
+
+/// Where and how parsing failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ParseFailure {
+    pub(crate) byte_index: usize,
+    /// Whether the input ran out before it was complete, so appending to it
+    /// could still make it valid (`user@`, `user@example`)
+    pub(crate) incomplete: bool
+}
+
+impl ParseFailure {
+    fn invalid(byte_index: usize) -> Self {
+        ParseFailure { byte_index, incomplete: false }
+    }
+
+    fn incomplete(byte_index: usize) -> Self {
+        ParseFailure { byte_index, incomplete: true }
+    }
+}
+
+/// `parse_address` with the failure kept structured
+pub(crate) fn parse(input: &str) -> Result<ParsedAddress, ParseFailure> {
+    let bytes = input.as_bytes();
+    let mut state = State::AtomStart;
+    let mut local_part_kind = LocalPartKind::DotAtom;
+    let mut at = None;
+    let mut labels = Vec::new();
+    let mut label_start = 0;
+
+    for (index, &byte) in bytes.iter().enumerate() {
+        state = match (state, byte) {
+            (State::AtomStart, b'"') if index == 0 => {
+                local_part_kind = LocalPartKind::Quoted;
+                State::Quoted
+            }
+            (State::AtomStart | State::Atom, byte) if is_atom_char(byte) => State::Atom,
+            (State::Atom, b'.') => State::AtomStart,
+            (State::Atom | State::QuotedEnd, b'@') => {
+                at = Some(index);
+                label_start = index + 1;
+                State::LabelStart
+            }
+
+            (State::Quoted, b'"') => State::QuotedEnd,
+            (State::Quoted, b'\\') => State::QuotedPair,
+            (State::Quoted | State::QuotedPair, byte) if is_printable(byte) => State::Quoted,
+
+            (State::LabelStart, b'[') if labels.is_empty() => State::Literal,
+            (State::LabelStart | State::Label | State::LabelHyphen, byte) if byte.is_ascii_alphanumeric() => State::Label,
+            (State::Label | State::LabelHyphen, b'-') => State::LabelHyphen,
+            (State::Label, b'.') => {
+                labels.push(Span { start: label_start, end: index });
+                label_start = index + 1;
+                State::LabelStart
+            }
+
+            (State::Literal, b']') => State::LiteralEnd,
+            (State::Literal, byte) if is_printable(byte) && !matches!(byte, b'[' | b'\\') => State::Literal,
+
+            _ => return Err(ParseFailure::invalid(index))
+        };
+    }
+
+    let Some(at) = at else {
+        return Err(ParseFailure::incomplete(input.len()));
+    };
+    let local_part = Span { start: 0, end: at };
+    let domain = Span { start: at + 1, end: input.len() };
+
+    match state {
+        State::Label => {
+            labels.push(Span { start: label_start, end: input.len() });
+            let tld = labels[labels.len() - 1];
+            let tld_text = tld.as_str(input);
+            // Another label may still follow, so a bad TLD is only incomplete
+            if labels.len() < 2 || tld_text.len() < 2 || !tld_text.bytes().all(|byte| byte.is_ascii_alphabetic()) {
+                return Err(ParseFailure::incomplete(tld.start));
+            }
+            Ok(ParsedAddress { local_part, local_part_kind, domain, domain_kind: DomainKind::Name, labels, tld: Some(tld) })
+        }
+        State::LiteralEnd => {
+            let literal = &input[domain.start + 1..domain.end - 1];
+            let domain_kind = match literal.strip_prefix("IPv6:") {
+                Some(address) if address.parse::<Ipv6Addr>().is_ok() => DomainKind::Ipv6Literal,
+                None if literal.parse::<Ipv4Addr>().is_ok() => DomainKind::Ipv4Literal,
+                _ => return Err(ParseFailure::invalid(domain.start))
+            };
+            Ok(ParsedAddress { local_part, local_part_kind, domain, domain_kind, labels, tld: None })
+        }
+        _ => Err(ParseFailure::incomplete(input.len()))
+    }
+}
+
+fn format_error(input: &str, byte_index: usize) -> EmailParseError {
+    let position = input[..byte_index.min(input.len())].chars().count();
+    EmailParseError {
+        error_type: "InvalidFormat".to_string(),
+        message: Message::InvalidFormat.english().to_string(),
+        details: Some(format!("Unexpected input at position {}", position)),
+        position: Some(position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests spans of dot-atom addresses
+    #[test]
+    fn test_parse_address_spans() {
+        let email = "john.smith+tag@mail.example.co.uk";
+        let parsed = parse_address(email).unwrap();
+        assert_eq!(parsed.local_part.as_str(email), "john.smith+tag");
+        assert_eq!(parsed.domain.as_str(email), "mail.example.co.uk");
+        let labels: Vec<&str> = parsed.labels.iter().map(|label| label.as_str(email)).collect();
+        assert_eq!(labels, ["mail", "example", "co", "uk"]);
+        assert_eq!(parsed.tld.unwrap().as_str(email), "uk");
+        assert_eq!((parsed.local_part_kind, parsed.domain_kind), (LocalPartKind::DotAtom, DomainKind::Name));
+    }
+
+    /// Tests quoted local parts and domain literals
+    #[test]
+    fn test_parse_address_edges() {
+        let email = r#""john \"jd\" smith"@example.com"#;
+        let parsed = parse_address(email).unwrap();
+        assert_eq!(parsed.local_part_kind, LocalPartKind::Quoted);
+        assert_eq!(parsed.local_part.as_str(email), r#""john \"jd\" smith""#);
+
+        assert_eq!(parse_address("user@[192.0.2.1]").unwrap().domain_kind, DomainKind::Ipv4Literal);
+        assert_eq!(parse_address("user@[IPv6:2001:db8::1]").unwrap().domain_kind, DomainKind::Ipv6Literal);
+        assert!(parse_address("user@[300.0.2.1]").is_err());
+        assert!(parse_address("user@[example.com]").is_err());
+        assert!(parse_address(r#""unterminated@example.com"#).is_err());
+        assert!(parse_address(r#""quoted"x@example.com"#).is_err());
+        assert!(parse_address("user@[192.0.2.1].com").is_err());
+    }
+
+    /// Tests rejection of malformed addresses with the failing position
+    #[test]
+    fn test_parse_address_errors() {
+        for email in [
+            "", "@example.com", "user@", "user@@example.com", "user@example", "user@example.c",
+            "user@example.c0m", ".user@example.com", "user.@example.com", "us..er@example.com",
+            "user@-example.com", "user@example-.com", "user@example..com", "user@example.com.",
+            "us er@example.com", "user#@example.com", "user@exa_mple.com", "úser@example.com"
+        ] {
+            assert!(parse_address(email).is_err(), "{} should not parse", email);
+        }
+
+        let error = parse_address("ú.ser#@example.com").unwrap_err();
+        assert_eq!(error.position, Some(0));
+        assert_eq!(parse_address("user#@example.com").unwrap_err().position, Some(4));
+        assert_eq!(parse_address("user@example").unwrap_err().position, Some(5));
+    }
+
+    /// Tests that dot-atom addresses parse exactly when the original regex matched them
+    #[test]
+    fn test_parse_address_matches_regex() {
+        let regex = regex::Regex::new(r"^[a-zA-Z0-9_%+-](?:[a-zA-Z0-9._%+-]*[a-zA-Z0-9_%+-])?@[a-zA-Z0-9](?:[a-zA-Z0-9-]*[a-zA-Z0-9])?(?:\.[a-zA-Z0-9](?:[a-zA-Z0-9-]*[a-zA-Z0-9])?)*\.[a-zA-Z]{2,}$").unwrap();
+        let alphabet = ["a", "Z", "9", ".", "-", "_", "%", "+", "@", "#", "..", "co", "1"];
+        let mut checked = 0;
+        for a in alphabet {
+            for b in alphabet {
+                for c in alphabet {
+                    for suffix in ["@example.com", ".com", "@x.y", ""] {
+                        let email = format!("{}{}{}{}", a, b, c, suffix);
+                        let parsed = parse_address(&email).is_ok_and(|parsed| parsed.local_part_kind == LocalPartKind::DotAtom && parsed.domain_kind == DomainKind::Name);
+                        let matched = regex.is_match(&email) && !email.split('@').next().unwrap().contains("..");
+                        assert_eq!(parsed, matched, "{}", email);
+                        checked += 1;
+                    }
+                }
+            }
+        }
+        assert_eq!(checked, 13 * 13 * 13 * 4);
+    }
+}
@@ -0,0 +1,139 @@
+//! Validation of input that is still being typed
+//!
+//! Live form feedback should not flash an error on every keystroke: `jane@`
+//! and `jane@example` are not addresses yet, but typing more can make them
+//! one. `validate_partial` only reports an error once no continuation of the
+//! input could be valid.
+
+use serde::{Serialize, Deserialize};
+
+use crate::message::Message;
+use crate::{parser, parse_and_validate_email_with_options, ValidationMode, ValidationOptions};
+
+/// Longest accepted address, as checked by full validation
+const MAX_EMAIL_LENGTH: usize = 320;
+
+/// How an input stands while it is being typed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum PartialState {
+    /// Not an address yet, but typing more could make it valid
+    Incomplete,
+    /// Invalid whatever is typed next
+    Invalid,
+    /// A valid address as it stands
+    Valid
+}
+
+impl PartialState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PartialState::Incomplete => "incomplete",
+            PartialState::Invalid => "invalid",
+            PartialState::Valid => "valid"
+        }
+    }
+}
+
+/// Result of `validate_partial`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PartialValidation {
+    pub state: PartialState,
+    /// Character position of the earliest error, when it is known (invalid
+    /// inputs only)
+    pub error_position: Option<usize>,
+    /// Why the input is invalid
+    pub error_message: Option<String>
+}
+
+impl PartialValidation {
+    fn incomplete() -> Self {
+        PartialValidation { state: PartialState::Incomplete, error_position: None, error_message: None }
+    }
+
+    fn invalid(message: impl Into<String>, position: Option<usize>) -> Self {
+        PartialValidation { state: PartialState::Invalid, error_position: position, error_message: Some(message.into()) }
+    }
+}
+
+/// Validates `input` as typed so far
+///
+/// Complete addresses get the full validation under `options`, so a blocked
+/// domain is reported as soon as it has been typed. Incomplete input is only
+/// checked for what can no longer be fixed by typing more: a character that
+/// cannot appear where it is, a quoted local part or domain literal outside
+/// `ValidationMode::Strict`, or excess length.
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::{validate_partial, PartialState, ValidationOptions};
+/// # let options = ValidationOptions::default();
+/// assert_eq!(validate_partial("jane@exam", &options).state, PartialState::Incomplete);
+/// let typo = validate_partial("jane@@", &options);
+/// assert_eq!((typo.state, typo.error_position), (PartialState::Invalid, Some(5)));
+/// assert_eq!(validate_partial("jane@example.com", &options).state, PartialState::Valid);
+/// ```
+pub fn validate_partial(input: &str, options: &ValidationOptions) -> PartialValidation {
+    if let Some(position) = input.chars().position(char::is_control) {
+        return PartialValidation::invalid(Message::ControlCharacter.text(options.locale), Some(position));
+    }
+    if input.len() > MAX_EMAIL_LENGTH {
+        let position = input.char_indices().take_while(|(index, _)| *index < MAX_EMAIL_LENGTH).count();
+        return PartialValidation::invalid(Message::TooLong.text(options.locale), Some(position));
+    }
+
+    match parser::parse(input) {
+        Ok(_) => match parse_and_validate_email_with_options(input, options) {
+            Ok(result) if result.is_valid => PartialValidation { state: PartialState::Valid, error_position: None, error_message: None },
+            Ok(result) => PartialValidation::invalid(result.error_message.unwrap_or_default(), None),
+            Err(e) => PartialValidation::invalid(e.message, e.position)
+        },
+        Err(failure) if failure.incomplete => {
+            if options.mode == ValidationMode::Standard {
+                if input.starts_with('"') {
+                    return PartialValidation::invalid(Message::QuotedLocalPart.text(options.locale), Some(0));
+                }
+                if let Some(index) = input.find("@[") {
+                    return PartialValidation::invalid(Message::DomainLiteral.text(options.locale), Some(input[..index].chars().count() + 1));
+                }
+            }
+            PartialValidation::incomplete()
+        }
+        Err(failure) => PartialValidation::invalid(Message::InvalidFormat.text(options.locale), Some(input[..failure.byte_index].chars().count()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Locale;
+
+    /// Tests that prefixes of valid addresses are incomplete rather than invalid
+    #[test]
+    fn test_validate_partial() {
+        let options = ValidationOptions::default();
+        for input in ["", "j", "jane", "jane.", "jane@", "jane@example", "jane@example.", "jane@example.c", "jane@example.c0"] {
+            assert_eq!(validate_partial(input, &options), PartialValidation::incomplete(), "{}", input);
+        }
+        assert_eq!(validate_partial("jane@example.com", &options).state, PartialState::Valid);
+
+        let typo = validate_partial("jane@@example", &options);
+        assert_eq!((typo.state, typo.error_position), (PartialState::Invalid, Some(5)));
+        assert_eq!(validate_partial(".jane", &options).error_position, Some(0));
+        assert_eq!(validate_partial("jané@", &options).error_position, Some(3));
+        assert_eq!(validate_partial("jane@exa\nmple", &options).error_position, Some(8));
+        assert_eq!(validate_partial("\"jane", &options).state, PartialState::Invalid);
+        assert_eq!(validate_partial("jane@[192.0", &options).error_position, Some(5));
+        assert_eq!(validate_partial("jane@[192.0.2.1]", &options).state, PartialState::Invalid);
+
+        let strict = ValidationOptions { mode: ValidationMode::Strict, ..Default::default() };
+        assert_eq!(validate_partial("\"jane", &strict).state, PartialState::Incomplete);
+        assert_eq!(validate_partial("jane@[192.0", &strict).state, PartialState::Incomplete);
+        assert_eq!(validate_partial("jane@[example.com]", &strict).error_position, Some(5));
+
+        let spanish = ValidationOptions { locale: Locale::Es, ..Default::default() };
+        assert_eq!(validate_partial("jane@@example", &spanish).error_message.as_deref(), Some("Formato de correo electrónico no válido"));
+    }
+}
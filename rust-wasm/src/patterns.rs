@@ -0,0 +1,91 @@
+//! Detection of auto-generated address patterns (`user1@`, `test+0001@`)
+
+use std::collections::HashMap;
+
+use crate::{EmailParseError, EmailParseResult};
+
+/// Local-part words commonly used by scripts generating throwaway accounts
+const TEMPLATE_WORDS: [&str; 9] = ["test", "user", "demo", "temp", "fake", "sample", "qa", "dummy", "account"];
+
+/// Minimum number of addresses sharing a stem for a batch to be flagged
+const SEQUENTIAL_GROUP_MIN: usize = 3;
+
+/// Whether a local part matches a known generated-address template:
+/// a template word followed by a number (`user12`, `test_007`) or a
+/// numeric plus tag of at least three digits (`jane+0001`)
+pub(crate) fn matches_known_template(local_part: &str) -> bool {
+    let local_part = local_part.to_lowercase();
+
+    if let Some((_, tag)) = local_part.split_once('+') {
+        if tag.len() >= 3 && tag.bytes().all(|b| b.is_ascii_digit()) {
+            return true;
+        }
+    }
+
+    TEMPLATE_WORDS.iter().any(|word| {
+        local_part
+            .strip_prefix(word)
+            .map(|rest| rest.trim_start_matches(['.', '_', '-']))
+            .is_some_and(|number| !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()))
+    })
+}
+
+/// Splits an address into a stem and a trailing sequence number, if any
+///
+/// `user12@x.com` and `jane+0042@x.com` yield stems `user@x.com` and
+/// `jane+@x.com`, so members of the same numbered series share a stem.
+fn sequence_stem(local_part: &str, domain: &str) -> Option<String> {
+    let local_part = local_part.to_lowercase();
+    let stem = local_part.trim_end_matches(|c: char| c.is_ascii_digit());
+    if stem.len() == local_part.len() || stem.is_empty() {
+        return None;
+    }
+    Some(format!("{}@{}", stem, domain.to_lowercase()))
+}
+
+/// Sets `is_suspicious_pattern` on valid results that match a known template
+/// or belong to a numbered series of at least three addresses in the batch
+pub(crate) fn flag_suspicious_patterns(results: &mut [Result<EmailParseResult, EmailParseError>]) {
+    let mut series: HashMap<String, usize> = HashMap::new();
+    let stems: Vec<Option<String>> = results
+        .iter()
+        .map(|result| match result {
+            Ok(EmailParseResult { is_valid: true, local_part: Some(local_part), domain: Some(domain), .. }) => {
+                sequence_stem(local_part, domain)
+            }
+            _ => None
+        })
+        .collect();
+    for stem in stems.iter().flatten() {
+        *series.entry(stem.clone()).or_default() += 1;
+    }
+
+    for (result, stem) in results.iter_mut().zip(stems) {
+        if let Ok(result) = result {
+            if !result.is_valid {
+                continue;
+            }
+            let in_series = stem.is_some_and(|stem| series[&stem] >= SEQUENTIAL_GROUP_MIN);
+            let templated = result.local_part.as_deref().is_some_and(matches_known_template);
+            result.is_suspicious_pattern = Some(in_series || templated);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the single-address generated templates
+    #[test]
+    fn test_matches_known_template() {
+        assert!(matches_known_template("user1"));
+        assert!(matches_known_template("Test_0042"));
+        assert!(matches_known_template("jane+0001"));
+        assert!(!matches_known_template("jane+news"));
+        assert!(!matches_known_template("jane+12"));
+        assert!(!matches_known_template("username"));
+        assert!(!matches_known_template("test"));
+        assert!(!matches_known_template("jdoe1987"));
+    }
+}
@@ -0,0 +1,56 @@
+//! Detection of obvious test and placeholder addresses
+//!
+//! People filling in forms by hand reach for the same throwaway inputs
+//! (`test@test.com`, `asdf@asdf.com`, `foo@bar.com`), and documentation uses
+//! the domains RFC 2606 reserves. Flagging these lets analytics exclude them
+//! without every team maintaining its own regexes.
+
+/// Words typed as filler in either half of an address
+const TEST_WORDS: [&str; 20] = [
+    "test", "testing", "tester", "asdf", "asdfgh", "qwerty", "foo", "bar", "baz",
+    "foobar", "example", "sample", "dummy", "fake", "null", "none", "nobody",
+    "abc", "xyz", "xxx"
+];
+
+/// Second-level domains reserved for documentation by RFC 2606
+const RESERVED_DOMAINS: [&str; 3] = ["example.com", "example.net", "example.org"];
+
+/// TLDs reserved for testing and documentation by RFC 2606
+const RESERVED_TLDS: [&str; 4] = ["test", "example", "invalid", "localhost"];
+
+/// Longest local part that, repeated as the domain name, reads as filler
+/// (`a@a.com`, `aa@aa.com`)
+const MAX_ECHOED_LENGTH: usize = 3;
+
+/// Whether an address is an obvious test input: a reserved documentation
+/// domain, a filler word as the domain name (`foo@bar.com`), or a very
+/// short local part repeated as the domain name (`a@a.com`)
+///
+/// Expects the canonical local part (without subaddress tag) and domain.
+pub(crate) fn is_test_address(local_part: &str, domain: &str) -> bool {
+    let local_part = local_part.to_lowercase();
+    let domain = domain.trim_end_matches('.').to_lowercase();
+    let tld = domain.rsplit('.').next().unwrap_or_default();
+    let name = domain.split('.').next().unwrap_or_default();
+
+    RESERVED_DOMAINS.iter().any(|reserved| domain == *reserved || domain.ends_with(&format!(".{}", reserved)))
+        || RESERVED_TLDS.contains(&tld)
+        || TEST_WORDS.contains(&name)
+        || (local_part == name && local_part.chars().count() <= MAX_ECHOED_LENGTH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests filler words, echoed names and reserved domains
+    #[test]
+    fn test_is_test_address() {
+        for (local_part, domain) in [("test", "test.com"), ("asdf", "asdf.com"), ("a", "a.com"), ("foo", "bar.com"), ("jane", "example.com"), ("user", "mail.example"), ("Test", "TEST.COM")] {
+            assert!(is_test_address(local_part, domain), "{}@{}", local_part, domain);
+        }
+        for (local_part, domain) in [("jane", "gmail.com"), ("test", "gmail.com"), ("john", "john.com"), ("support", "acme.test.io")] {
+            assert!(!is_test_address(local_part, domain), "{}@{}", local_part, domain);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Cheap prevalidation for batch mode
+//!
+//! Scraped lists are dominated by inputs that fail for trivial reasons: no
+//! `@`, two of them, a space or a stray comma. `quick_reject` finds those with
+//! vectorized scans (`is_ascii`, `memchr`) and a byte table before the full
+//! parser and scoring run. It only answers when the answer is certain, and
+//! then gives the same message full validation would.
+
+use crate::message::Message;
+#[cfg(feature = "std")]
+use crate::{parse_and_validate_email_with_options, EmailParseError, EmailParseResult, ValidationMode, ValidationOptions};
+#[cfg(feature = "metrics")]
+use crate::telemetry;
+
+/// Bytes that can appear in an accepted dot-atom address
+const ALLOWED: [bool; 128] = {
+    let mut table = [false; 128];
+    let mut byte = 0;
+    while byte < 128 {
+        table[byte] = matches!(byte as u8, b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'.' | b'_' | b'%' | b'+' | b'-' | b'@');
+        byte += 1;
+    }
+    table
+};
+
+/// Returns the message full validation would give `email` when it is
+/// invalid for a reason visible without parsing, or None when it needs the
+/// full parser
+///
+/// Inputs with non-ASCII or control characters, quotes or brackets always
+/// return None, so their specific errors come from the full path.
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::quick_reject;
+/// assert_eq!(quick_reject("not an email"), Some("Invalid email format"));
+/// assert_eq!(quick_reject("user@example.com"), None);
+/// ```
+pub fn quick_reject(email: &str) -> Option<&'static str> {
+    reject_reason(email).map(Message::english)
+}
+
+/// Why `quick_reject` settles `email`, or None when it needs the full parser
+fn reject_reason(email: &str) -> Option<Message> {
+    if email.is_empty() {
+        return Some(Message::Empty);
+    }
+    let bytes = email.as_bytes();
+    if !bytes.is_ascii() || memchr::memchr2(b'"', b'[', bytes).is_some() {
+        return None;
+    }
+
+    let mut illegal = false;
+    for &byte in bytes {
+        if byte < b' ' || byte == 0x7f {
+            return None;
+        }
+        illegal |= !ALLOWED[byte as usize];
+    }
+
+    if bytes.len() > 320 {
+        return Some(Message::TooLong);
+    }
+    let at_signs = memchr::memchr_iter(b'@', bytes).take(2).count();
+    (illegal || at_signs != 1).then_some(Message::InvalidFormat)
+}
+
+/// Validates `email` like `parse_and_validate_email_with_options`, skipping
+/// the full parse for inputs `quick_reject` settles
+///
+/// A settled input still gets its `suggestion`: a written-out or missing `@`
+/// is exactly what the prefilter rejects.
+///
+/// Input clean-up options can repair addresses, a custom pattern may accept
+/// what the built-in rules do not, and `ValidationMode::Strict` accepts
+/// comments and spaces around dots, so they disable the prefilter.
+#[cfg(feature = "std")]
+pub(crate) fn validate_prefiltered(email: &str, options: &ValidationOptions) -> Result<EmailParseResult, EmailParseError> {
+    if options.lenient_input || options.normalize_unicode || options.custom_pattern.is_some() || options.mode == ValidationMode::Strict {
+        return parse_and_validate_email_with_options(email, options);
+    }
+    match reject_reason(email) {
+        Some(message) => {
+            let result = Ok(EmailParseResult::invalid_syntax(email, message, options.locale));
+            #[cfg(feature = "metrics")]
+            telemetry::record_validation(&result);
+            result
+        }
+        None => parse_and_validate_email_with_options(email, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests which inputs are settled without parsing
+    #[test]
+    fn test_quick_reject() {
+        assert_eq!(quick_reject(""), Some("Email cannot be empty"));
+        assert_eq!(quick_reject("no-at-sign"), Some("Invalid email format"));
+        assert_eq!(quick_reject("a@b@example.com"), Some("Invalid email format"));
+        assert_eq!(quick_reject("John Doe <john@example.com>"), Some("Invalid email format"));
+        assert_eq!(quick_reject(&format!("{}@example.com", "a".repeat(320))), Some("Email exceeds maximum length of 320 characters"));
+
+        for email in ["user@example.com", "user@example", "\"john doe\"@example.com", "user@[192.0.2.1]", "úser@example.com", "user\r\n@example.com"] {
+            assert_eq!(quick_reject(email), None, "{}", email);
+        }
+    }
+
+    /// Tests that prefiltered validation matches full validation
+    #[test]
+    fn test_validate_prefiltered_matches_full() {
+        let long = format!("{} x@example.com", "a".repeat(320));
+        let inputs = [
+            "", "user@example.com", "invalid", "a@@b.com", "a,b@example.com", "us er@example.com", "user@exa mple.com",
+            "\"quoted\"@example.com", "user@[192.0.2.1]", "tab\t@example.com", "ü@example.com", long.as_str(), "@", "a@b.c",
+            "a,b@example..com", "jane(at)example.com", "jane example.com", "jane at example.com",
+            "john.smith(comment)@example.com(comment)", "john . smith@example.com", "jo(x)hn@example.com"
+        ];
+        let strict = ValidationOptions { mode: ValidationMode::Strict, ..Default::default() };
+        for options in [ValidationOptions::default(), strict] {
+            for email in inputs {
+                let full = serde_json::to_value(parse_and_validate_email_with_options(email, &options).map_err(|e| e.error_type)).unwrap();
+                let prefiltered = serde_json::to_value(validate_prefiltered(email, &options).map_err(|e| e.error_type)).unwrap();
+                assert_eq!(prefiltered, full, "{} ({})", email, options.mode.as_str());
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Verification profiles: a whole pipeline configuration as one document
+//!
+//! A profile holds the validation options (mode, lists, scoring weights),
+//! the network stages to run, their blocklists and timeouts. It serializes
+//! to JSON (and TOML with the `toml` feature), so a team can check its
+//! verification policy into version control and load the same file in the
+//! server, native code and the browser.
+
+use std::time::Duration;
+
+use serde::{Serialize, Deserialize};
+
+use crate::breach::BreachRangeSource;
+use crate::dns::DnsResolver;
+use crate::{Blocklist, EmailParseError, DEFAULT_CT_LOG_ENDPOINT, StageTimeouts, ValidationOptions, Verifier};
+
+/// Network stages to run after syntax validation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct Stages {
+    /// Look up MX records for the domain
+    pub dns: bool,
+    /// Also check SPF and DMARC (implies `dns`)
+    pub mail_auth: bool,
+    /// Also check MTA-STS and DANE (implies `dns`)
+    pub security: bool,
+    /// Also query the configured DNS blocklists (implies `dns`)
+    pub blocklist: bool,
+    /// Probe the mailbox over SMTP (implies `dns`)
+    pub smtp: bool,
+    /// Check whether the address has a Gravatar
+    pub gravatar: bool,
+    /// Look up the domain's registration date over RDAP and flag domains
+    /// younger than the profile's `min_domain_age_days` (implies `dns`)
+    pub domain_age: bool,
+    /// Search the Certificate Transparency logs for the domain through the
+    /// profile's `ct_log_endpoint` (implies `dns`)
+    pub ct_log: bool,
+    /// Check the address against the breach range API at the profile's
+    /// `breach_endpoint`
+    pub breach: bool
+}
+
+#[cfg(feature = "server")]
+impl Stages {
+    pub(crate) fn needs_network(&self) -> bool {
+        self.dns || self.mail_auth || self.security || self.blocklist || self.smtp || self.gravatar || self.domain_age || self.ct_log || self.breach
+    }
+}
+
+/// A complete, serializable verification configuration
+///
+/// Omitted fields take their defaults, so a profile only needs the settings
+/// it changes.
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::{SandboxResolver, VerificationProfile, Verifier};
+/// # let resolver = SandboxResolver::new();
+/// let profile = VerificationProfile::from_json(r#"{
+///     "validation": { "mode": "strict", "domain_lists": { "blocked": ["competitor.example"] } },
+///     "stages": { "dns": true, "mail_auth": true },
+///     "timeouts": { "dns_ms": 2000 }
+/// }"#)?;
+/// let verifier = profile.apply(Verifier::new(resolver));
+/// # Ok::<(), email_validator_wasm::EmailParseError>(())
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VerificationProfile {
+    /// Syntax mode, normalization, domain lists and scoring weights
+    pub validation: ValidationOptions,
+    /// Network stages to run
+    pub stages: Stages,
+    /// Lists queried by the blocklist stage
+    pub blocklists: Vec<Blocklist>,
+    /// Age in days below which the domain age stage flags a domain as newly
+    /// registered
+    pub min_domain_age_days: u32,
+    /// Search API queried by the CT log stage; `{domain}` is replaced by the
+    /// domain
+    pub ct_log_endpoint: String,
+    /// HIBP-compatible range API queried by the breach stage; `{prefix}` is
+    /// replaced by the first five characters of the address hash
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub breach_endpoint: Option<String>,
+    /// Upper bounds for the network stages, in milliseconds
+    pub timeouts: StageTimeouts,
+    /// How long results stay fresh, in milliseconds (None keeps the 30-day default)
+    #[serde(rename = "result_ttl_ms", with = "crate::time::option_duration_ms", skip_serializing_if = "Option::is_none")]
+    pub result_ttl: Option<Duration>
+}
+
+impl Default for VerificationProfile {
+    fn default() -> Self {
+        VerificationProfile {
+            validation: ValidationOptions::default(),
+            stages: Stages::default(),
+            blocklists: vec![Blocklist::spamhaus_dbl()],
+            min_domain_age_days: DEFAULT_MIN_DOMAIN_AGE_DAYS,
+            ct_log_endpoint: DEFAULT_CT_LOG_ENDPOINT.to_string(),
+            breach_endpoint: None,
+            timeouts: StageTimeouts::default(),
+            result_ttl: None
+        }
+    }
+}
+
+impl VerificationProfile {
+    /// Parses a profile from JSON
+    pub fn from_json(json: &str) -> Result<Self, EmailParseError> {
+        serde_json::from_str(json).map_err(|e| invalid_profile(e.to_string()))
+    }
+
+    /// Serializes the profile as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String, EmailParseError> {
+        serde_json::to_string_pretty(self).map_err(|e| invalid_profile(e.to_string()))
+    }
+
+    /// Parses a profile from TOML
+    #[cfg(feature = "toml")]
+    pub fn from_toml(toml: &str) -> Result<Self, EmailParseError> {
+        toml::from_str(toml).map_err(|e| invalid_profile(e.to_string()))
+    }
+
+    /// Serializes the profile as TOML
+    #[cfg(feature = "toml")]
+    pub fn to_toml(&self) -> Result<String, EmailParseError> {
+        toml::to_string_pretty(self).map_err(|e| invalid_profile(e.to_string()))
+    }
+
+    /// `min_domain_age_days` as a duration
+    pub fn min_domain_age(&self) -> Duration {
+        Duration::from_secs(u64::from(self.min_domain_age_days) * 24 * 60 * 60)
+    }
+
+    /// Configures `verifier` with the profile's options, stages, blocklists
+    /// and timeouts
+    ///
+    /// The resolver, cache and SMTP identity are deployment details rather
+    /// than policy, so the caller still attaches them; when `stages.smtp` is
+    /// set, attach an `SmtpVerifier` with `Verifier::with_smtp`, and when
+    /// `stages.breach` is set, a source for `breach_endpoint` (such as
+    /// `HttpsBreachSource`) with `Verifier::with_breach_check`.
+    pub fn apply<R: DnsResolver, B: BreachRangeSource>(&self, verifier: Verifier<R, B>) -> Verifier<R, B> {
+        let verifier = verifier
+            .with_validation_options(self.validation.clone())
+            .with_mail_auth_checks(self.stages.mail_auth)
+            .with_security_checks(self.stages.security)
+            .with_blocklists(if self.stages.blocklist { self.blocklists.clone() } else { Vec::new() })
+            .with_gravatar_check(self.stages.gravatar)
+            .with_domain_age_check(self.stages.domain_age.then(|| self.min_domain_age()))
+            .with_ct_log_check(self.stages.ct_log.then_some(self.ct_log_endpoint.as_str()))
+            .with_timeouts(self.timeouts);
+        match self.result_ttl {
+            Some(ttl) => verifier.with_result_ttl(ttl),
+            None => verifier
+        }
+    }
+}
+
+/// Default `min_domain_age_days`: a month covers the window in which
+/// throwaway domains are typically used and abandoned
+const DEFAULT_MIN_DOMAIN_AGE_DAYS: u32 = 30;
+
+/// "InvalidProfile" error with `details`
+fn invalid_profile(details: String) -> EmailParseError {
+    EmailParseError {
+        error_type: "InvalidProfile".to_string(),
+        message: "Failed to read verification profile".to_string(),
+        details: Some(details),
+        position: None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ValidationMode;
+
+    /// Tests that a profile survives a JSON round trip and fills in defaults
+    #[test]
+    fn test_profile_json_round_trip() {
+        let profile = VerificationProfile::from_json(r#"{
+            "validation": { "mode": "strict", "tld_scores": { "xyz": 10 } },
+            "stages": { "dns": true, "blocklist": true },
+            "timeouts": { "dns_ms": 2000 }
+        }"#).unwrap();
+        assert_eq!(profile.validation.mode, ValidationMode::Strict);
+        assert_eq!(profile.timeouts.dns, Some(Duration::from_secs(2)));
+        assert_eq!(profile.timeouts.smtp, None);
+        assert_eq!(profile.blocklists, vec![Blocklist::spamhaus_dbl()]);
+
+        let reloaded = VerificationProfile::from_json(&profile.to_json().unwrap()).unwrap();
+        assert_eq!(reloaded.stages, profile.stages);
+        assert_eq!(reloaded.timeouts, profile.timeouts);
+        assert_eq!(reloaded.validation.tld_scores, profile.validation.tld_scores);
+
+        let error = VerificationProfile::from_json(r#"{"timeouts": {"dns_ms": "soon"}}"#).unwrap_err();
+        assert_eq!(error.error_type, "InvalidProfile");
+    }
+
+    /// Tests that a profile survives a TOML round trip
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_profile_toml_round_trip() {
+        let profile = VerificationProfile::from_toml(r#"
+            result_ttl_ms = 86400000
+
+            [validation]
+            mode = "strict"
+            max_length = 254
+
+            [stages]
+            dns = true
+            smtp = true
+        "#).unwrap();
+        assert_eq!(profile.validation.max_length, Some(254));
+        assert!(profile.stages.smtp);
+        assert_eq!(profile.result_ttl, Some(Duration::from_secs(86_400)));
+
+        let reloaded = VerificationProfile::from_toml(&profile.to_toml().unwrap()).unwrap();
+        assert_eq!(reloaded.stages, profile.stages);
+        assert_eq!(reloaded.result_ttl, profile.result_ttl);
+        assert_eq!(reloaded.validation.max_length, Some(254));
+    }
+}
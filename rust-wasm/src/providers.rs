@@ -0,0 +1,213 @@
+//! Mailbox-provider addressing rules
+//!
+//! Providers disagree on which spellings reach the same mailbox: Gmail
+//! ignores dots in the local part and also answers at `googlemail.com`,
+//! Yahoo tags addresses with `-` instead of `+`, while most hosts treat dots
+//! as significant. Generic normalization (case and plus tags) is applied
+//! everywhere else.
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+/// Consumer mailbox provider identified from the address's domain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum MailboxProvider {
+    /// `gmail.com` and `googlemail.com`
+    Gmail,
+    /// Outlook.com and its Hotmail, Live, MSN and Windows Live domains,
+    /// including the country variants (`hotmail.co.uk`, `live.fr`, ...)
+    Microsoft,
+    /// Yahoo Mail, including its country domains, `ymail.com` and
+    /// `rocketmail.com`
+    Yahoo
+}
+
+impl MailboxProvider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MailboxProvider::Gmail => "gmail",
+            MailboxProvider::Microsoft => "microsoft",
+            MailboxProvider::Yahoo => "yahoo"
+        }
+    }
+}
+
+/// How one provider maps addresses to mailboxes
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+struct ProviderRules {
+    provider: MailboxProvider,
+    /// Domains the provider serves
+    domains: &'static [&'static str],
+    /// Domain every address is folded into, when the provider's domains
+    /// share one namespace of mailboxes
+    canonical_domain: Option<&'static str>,
+    /// Whether dots in the local part are ignored
+    ignores_dots: bool,
+    /// Character separating the base name from a subaddress tag
+    tag_separator: char,
+    /// Whether tagged addresses are disposable aliases the user can delete,
+    /// rather than plain subaddresses
+    tags_are_disposable: bool
+}
+
+/// Microsoft consumer domains; each is its own namespace (`jane@hotmail.com`
+/// and `jane@outlook.com` are different mailboxes)
+const MICROSOFT_DOMAINS: [&str; 70] = [
+    "outlook.com", "hotmail.com", "live.com", "msn.com", "passport.com", "windowslive.com",
+    "outlook.at", "outlook.be", "outlook.cl", "outlook.co.id", "outlook.co.il", "outlook.co.nz",
+    "outlook.co.th", "outlook.com.ar", "outlook.com.au", "outlook.com.br", "outlook.com.gr",
+    "outlook.com.tr", "outlook.com.vn", "outlook.cz", "outlook.de", "outlook.dk", "outlook.es",
+    "outlook.fr", "outlook.hu", "outlook.ie", "outlook.in", "outlook.it", "outlook.jp",
+    "outlook.kr", "outlook.lv", "outlook.my", "outlook.ph", "outlook.pt", "outlook.sa",
+    "outlook.sg", "outlook.sk", "hotmail.co.uk", "hotmail.fr", "hotmail.de", "hotmail.it",
+    "hotmail.es", "hotmail.be", "hotmail.nl", "hotmail.ca", "hotmail.co.jp",
+    "hotmail.com.ar", "hotmail.com.au", "hotmail.com.br", "hotmail.com.mx",
+    "hotmail.dk", "hotmail.se", "hotmail.no", "hotmail.fi", "hotmail.gr", "hotmail.ch",
+    "live.co.uk", "live.fr", "live.de", "live.it", "live.nl", "live.be", "live.ca", "live.com.au",
+    "live.com.mx", "live.com.ar", "live.dk", "live.se", "live.no", "live.jp"
+];
+
+/// Yahoo Mail domains (Yahoo Japan is a separate service and not included)
+const YAHOO_DOMAINS: [&str; 24] = [
+    "yahoo.com", "ymail.com", "rocketmail.com", "yahoo.co.uk", "yahoo.fr", "yahoo.de",
+    "yahoo.it", "yahoo.es", "yahoo.ca", "yahoo.ie", "yahoo.gr", "yahoo.se", "yahoo.dk",
+    "yahoo.no", "yahoo.fi", "yahoo.in", "yahoo.co.in", "yahoo.co.id", "yahoo.com.au",
+    "yahoo.com.br", "yahoo.com.mx", "yahoo.com.ar", "yahoo.com.sg", "yahoo.com.ph"
+];
+
+/// Providers with addressing rules beyond case and plus tags
+const PROVIDERS: [ProviderRules; 3] = [
+    ProviderRules {
+        provider: MailboxProvider::Gmail,
+        domains: &["gmail.com", "googlemail.com"],
+        canonical_domain: Some("gmail.com"),
+        ignores_dots: true,
+        tag_separator: '+',
+        tags_are_disposable: false
+    },
+    ProviderRules {
+        provider: MailboxProvider::Microsoft,
+        domains: &MICROSOFT_DOMAINS,
+        canonical_domain: None,
+        ignores_dots: false,
+        tag_separator: '+',
+        tags_are_disposable: false
+    },
+    // Yahoo base names cannot contain `-`, so `base-keyword` is always one
+    // of the account's disposable addresses
+    ProviderRules {
+        provider: MailboxProvider::Yahoo,
+        domains: &YAHOO_DOMAINS,
+        canonical_domain: None,
+        ignores_dots: false,
+        tag_separator: '-',
+        tags_are_disposable: true
+    }
+];
+
+/// Rules for a domain (case-insensitive, trailing dot ignored), if its
+/// provider has any
+fn rules_for(domain: &str) -> Option<&'static ProviderRules> {
+    let domain = domain.trim_end_matches('.');
+    PROVIDERS.iter().find(|rules| rules.domains.iter().any(|known| known.eq_ignore_ascii_case(domain)))
+}
+
+/// The consumer mailbox provider serving `domain`, if it is a known one
+///
+/// # Examples
+/// ```ignore
+/// assert_eq!(mailbox_provider("Hotmail.co.uk"), Some(MailboxProvider::Microsoft));
+/// assert_eq!(mailbox_provider("example.com"), None);
+/// ```
+#[cfg(feature = "std")]
+pub(crate) fn mailbox_provider(domain: &str) -> Option<MailboxProvider> {
+    rules_for(domain).map(|rules| rules.provider)
+}
+
+/// Every domain served by a known provider
+#[cfg(feature = "std")]
+pub(crate) fn provider_domains() -> impl Iterator<Item = &'static str> {
+    PROVIDERS.iter().flat_map(|rules| rules.domains.iter().copied())
+}
+
+/// Splits a local part into its base and subaddress tag, at the first `+`
+/// or, at Yahoo, the first `-`
+pub(crate) fn split_subaddress<'a>(local_part: &'a str, domain: &str) -> (&'a str, Option<&'a str>) {
+    let separator = rules_for(domain).map_or('+', |rules| rules.tag_separator);
+    match local_part.split_once(separator) {
+        Some((base, tag)) => (base, Some(tag)),
+        None => (local_part, None)
+    }
+}
+
+/// Whether the address is one of a provider's disposable aliases
+/// (`base-keyword@yahoo.com`)
+#[cfg(feature = "std")]
+pub(crate) fn is_disposable_alias(local_part: &str, domain: &str) -> bool {
+    rules_for(domain).is_some_and(|rules| rules.tags_are_disposable && split_subaddress(local_part, domain).1.is_some())
+}
+
+/// Canonical form of an address: lowercased, without its subaddress tag,
+/// and folded by the provider's rules
+///
+/// Microsoft mailboxes take plus tags like Gmail but keep their dots and
+/// their own domain; Yahoo aliases fold into their base address. Returns None
+/// when either side is empty once the tag is removed.
+#[cfg(feature = "std")]
+pub(crate) fn canonicalize(local_part: &str, domain: &str) -> Option<String> {
+    let domain = domain.trim_end_matches('.').to_lowercase();
+    let local_part = split_subaddress(local_part, &domain).0.to_lowercase();
+    if local_part.is_empty() || domain.is_empty() {
+        return None;
+    }
+
+    match rules_for(&domain) {
+        Some(rules) => {
+            let local_part = if rules.ignores_dots { local_part.replace('.', "") } else { local_part };
+            Some(format!("{}@{}", local_part, rules.canonical_domain.unwrap_or(&domain)))
+        }
+        None => Some(format!("{}@{}", local_part, domain))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests Gmail dot and domain folding against generic normalization
+    #[test]
+    fn test_gmail_rules() {
+        assert_eq!(canonicalize("J.Doe+news", "GoogleMail.com").as_deref(), Some("jdoe@gmail.com"));
+        assert_eq!(canonicalize("j.d.o.e", "gmail.com.").as_deref(), Some("jdoe@gmail.com"));
+        assert_eq!(canonicalize("j.doe+news", "example.com").as_deref(), Some("j.doe@example.com"));
+        assert_eq!(canonicalize("+news", "gmail.com"), None);
+    }
+
+    /// Tests Microsoft provider identity and subaddress handling
+    #[test]
+    fn test_microsoft_rules() {
+        for domain in ["outlook.com", "Hotmail.co.uk", "live.fr", "msn.com", "outlook.com.br"] {
+            assert_eq!(mailbox_provider(domain), Some(MailboxProvider::Microsoft), "{}", domain);
+        }
+        assert_eq!(mailbox_provider("gmail.com"), Some(MailboxProvider::Gmail));
+        assert_eq!(mailbox_provider("hotmail.example"), None);
+
+        assert_eq!(canonicalize("J.Doe+shop", "Hotmail.co.uk").as_deref(), Some("j.doe@hotmail.co.uk"));
+        assert_eq!(canonicalize("j.doe", "outlook.com").as_deref(), Some("j.doe@outlook.com"));
+    }
+
+    /// Tests decomposition of Yahoo `-` aliases
+    #[test]
+    fn test_yahoo_aliases() {
+        assert_eq!(split_subaddress("jane-shopping", "Yahoo.com"), ("jane", Some("shopping")));
+        assert_eq!(split_subaddress("jane-shopping", "example.com"), ("jane-shopping", None));
+        assert_eq!(split_subaddress("jane+news", "ymail.com"), ("jane+news", None));
+        assert!(is_disposable_alias("jane-shopping", "yahoo.co.uk"));
+        assert!(!is_disposable_alias("jane", "yahoo.com"));
+        assert!(!is_disposable_alias("jane+news", "gmail.com"));
+        assert_eq!(canonicalize("Jane-Shopping", "yahoo.com").as_deref(), Some("jane@yahoo.com"));
+    }
+}
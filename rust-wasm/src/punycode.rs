@@ -0,0 +1,113 @@
+//! Punycode (RFC 3492) decoding for internationalized domain labels
+
+const BASE: u32 = 36;
+const T_MIN: u32 = 1;
+const T_MAX: u32 = 26;
+const SKEW: u32 = 38;
+const DAMP: u32 = 700;
+const INITIAL_BIAS: u32 = 72;
+const INITIAL_N: u32 = 128;
+
+/// ACE prefix marking a punycode-encoded label
+pub(crate) const ACE_PREFIX: &str = "xn--";
+
+/// Decodes a punycode string (without the `xn--` prefix)
+///
+/// Returns `None` if the input is not valid punycode.
+pub(crate) fn decode(input: &str) -> Option<String> {
+    let (basic, encoded) = match input.rfind('-') {
+        Some(position) => (&input[..position], &input[position + 1..]),
+        None => ("", input)
+    };
+    if !basic.is_ascii() {
+        return None;
+    }
+
+    let mut output: Vec<char> = basic.chars().collect();
+    let mut n = INITIAL_N;
+    let mut i: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+    let mut digits = encoded.bytes().peekable();
+
+    while digits.peek().is_some() {
+        let old_i = i;
+        let mut weight: u32 = 1;
+        let mut k = BASE;
+        loop {
+            let digit = decode_digit(digits.next()?)?;
+            i = i.checked_add(digit.checked_mul(weight)?)?;
+            let threshold = if k <= bias {
+                T_MIN
+            } else if k >= bias + T_MAX {
+                T_MAX
+            } else {
+                k - bias
+            };
+            if digit < threshold {
+                break;
+            }
+            weight = weight.checked_mul(BASE - threshold)?;
+            k += BASE;
+        }
+
+        let length = output.len() as u32 + 1;
+        bias = adapt(i - old_i, length, old_i == 0);
+        n = n.checked_add(i / length)?;
+        i %= length;
+        output.insert(i as usize, char::from_u32(n)?);
+        i += 1;
+    }
+
+    Some(output.into_iter().collect())
+}
+
+/// Converts each `xn--` label of a domain to Unicode, leaving other labels as is
+///
+/// Returns `None` if any `xn--` label is not valid punycode.
+pub(crate) fn domain_to_unicode(domain: &str) -> Option<String> {
+    let labels = domain
+        .split('.')
+        .map(|label| match label.get(..ACE_PREFIX.len()) {
+            Some(prefix) if prefix.eq_ignore_ascii_case(ACE_PREFIX) => decode(&label[ACE_PREFIX.len()..]),
+            _ => Some(label.to_string())
+        })
+        .collect::<Option<Vec<String>>>()?;
+    Some(labels.join("."))
+}
+
+fn decode_digit(byte: u8) -> Option<u32> {
+    match byte {
+        b'0'..=b'9' => Some((byte - b'0') as u32 + 26),
+        b'a'..=b'z' => Some((byte - b'a') as u32),
+        b'A'..=b'Z' => Some((byte - b'A') as u32),
+        _ => None
+    }
+}
+
+fn adapt(delta: u32, length: u32, first_time: bool) -> u32 {
+    let mut delta = if first_time { delta / DAMP } else { delta / 2 };
+    delta += delta / length;
+
+    let mut k = 0;
+    while delta > ((BASE - T_MIN) * T_MAX) / 2 {
+        delta /= BASE - T_MIN;
+        k += BASE;
+    }
+    k + (BASE - T_MIN + 1) * delta / (delta + SKEW)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests decoding of known punycode labels and rejection of malformed input
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("mnchen-3ya"), Some("münchen".to_string()));
+        assert_eq!(decode("ggle-55da"), Some("g\u{43e}\u{43e}gle".to_string()));
+        assert_eq!(decode("abc-"), Some("abc".to_string()));
+        assert_eq!(decode("ab!"), None);
+        assert_eq!(domain_to_unicode("mail.XN--mnchen-3ya.de"), Some("mail.münchen.de".to_string()));
+        assert_eq!(domain_to_unicode("xn--zz!.com"), None);
+    }
+}
@@ -0,0 +1,81 @@
+//! Python bindings (PyO3), built with `maturin` from `pyproject.toml`
+//!
+//! Results are returned as plain dicts with the same keys as the wasm exports,
+//! and options are passed as a dict of `ValidationOptions` fields.
+//!
+//! # Examples
+//! ```python
+//! import ts_email_verification as tev
+//!
+//! tev.parse_and_validate_email("user@example.com")["is_valid"]  # True
+//! df["email_valid"] = [r["is_valid"] for r in tev.parse_and_validate_emails(df["email"].tolist())]
+//! ```
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::Serialize;
+
+use crate::{batch, EmailParseError, ValidationOptions};
+
+create_exception!(ts_email_verification, EmailValidationError, PyValueError, "Invalid options or an internal validation error");
+
+impl From<EmailParseError> for PyErr {
+    fn from(error: EmailParseError) -> Self {
+        EmailValidationError::new_err(error.to_string())
+    }
+}
+
+/// Reads an optional options dict into `ValidationOptions`
+fn read_options(options: Option<&Bound<'_, PyAny>>) -> Result<ValidationOptions, EmailParseError> {
+    match options {
+        Some(options) if !options.is_none() => pythonize::depythonize(options).map_err(|e| EmailParseError {
+            error_type: "InvalidOptions".to_string(),
+            message: "Failed to parse validation options".to_string(),
+            details: Some(e.to_string()),
+            position: None
+        }),
+        _ => Ok(ValidationOptions::default())
+    }
+}
+
+/// Converts a serializable value into Python dicts and lists
+fn to_python<'py, T: Serialize>(py: Python<'py>, value: &T) -> PyResult<Bound<'py, PyAny>> {
+    pythonize::pythonize(py, value).map_err(|e| EmailValidationError::new_err(e.to_string()))
+}
+
+/// Validates one address; raises `EmailValidationError` for bad options or
+/// control characters, otherwise returns the result dict
+#[pyfunction]
+#[pyo3(signature = (email, options = None))]
+fn parse_and_validate_email<'py>(py: Python<'py>, email: &str, options: Option<&Bound<'py, PyAny>>) -> PyResult<Bound<'py, PyAny>> {
+    let options = read_options(options)?;
+    let result = crate::parse_and_validate_email_with_options(email, &options)?;
+    to_python(py, &result)
+}
+
+/// Validates a list of addresses without holding the GIL; each entry is a
+/// result dict or, for inputs that could not be processed, an error dict
+#[pyfunction]
+#[pyo3(signature = (emails, options = None))]
+fn parse_and_validate_emails<'py>(py: Python<'py>, emails: Vec<String>, options: Option<&Bound<'py, PyAny>>) -> PyResult<Bound<'py, PyAny>> {
+    let options = read_options(options)?;
+    let results = py.detach(|| batch::parse_and_validate_emails(&emails, &options));
+    let entries: Vec<serde_json::Value> = results
+        .into_iter()
+        .map(|result| match result {
+            Ok(result) => serde_json::to_value(result),
+            Err(e) => serde_json::to_value(e)
+        })
+        .collect::<Result<_, _>>()
+        .map_err(|e| EmailValidationError::new_err(e.to_string()))?;
+    to_python(py, &entries)
+}
+
+#[pymodule]
+fn ts_email_verification(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(parse_and_validate_email, module)?)?;
+    module.add_function(wrap_pyfunction!(parse_and_validate_emails, module)?)?;
+    module.add("EmailValidationError", module.py().get_type::<EmailValidationError>())?;
+    Ok(())
+}
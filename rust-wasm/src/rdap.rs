@@ -0,0 +1,162 @@
+//! Domain registration date over RDAP
+//!
+//! Newly registered domains are the strongest fraud signal we have: throwaway
+//! signups and phishing campaigns run on domains that are days old. The
+//! registration date comes from the registry's RDAP service (RFC 9083), the
+//! structured successor to WHOIS.
+
+/// RDAP service (host and path prefix) for each TLD whose registry runs one,
+/// from the IANA bootstrap registry
+#[cfg(any(target_arch = "wasm32", feature = "tls", test))]
+const RDAP_SERVERS: [(&str, &str, &str); 20] = [
+    ("com", "rdap.verisign.com", "/com/v1/"),
+    ("net", "rdap.verisign.com", "/net/v1/"),
+    ("cc", "rdap.verisign.com", "/cc/v1/"),
+    ("tv", "rdap.verisign.com", "/tv/v1/"),
+    ("org", "rdap.publicinterestregistry.org", "/rdap/"),
+    ("info", "rdap.identitydigital.services", "/rdap/"),
+    ("io", "rdap.identitydigital.services", "/rdap/"),
+    ("biz", "rdap.nic.biz", "/"),
+    ("xyz", "rdap.centralnic.com", "/xyz/"),
+    ("online", "rdap.centralnic.com", "/online/"),
+    ("site", "rdap.centralnic.com", "/site/"),
+    ("store", "rdap.centralnic.com", "/store/"),
+    ("app", "pubapi.registry.google", "/rdap/"),
+    ("dev", "pubapi.registry.google", "/rdap/"),
+    ("page", "pubapi.registry.google", "/rdap/"),
+    ("uk", "rdap.nominet.uk", "/uk/"),
+    ("fr", "rdap.nic.fr", "/"),
+    ("nl", "rdap.sidn.nl", "/"),
+    ("br", "rdap.registro.br", "/"),
+    ("ca", "rdap.ca.fury.ca", "/rdap/")
+];
+
+/// Second-level labels under which ccTLD registries sell names (`co.uk`)
+#[cfg(any(target_arch = "wasm32", feature = "tls", test))]
+const REGISTRY_SLDS: [&str; 9] = ["co", "com", "org", "net", "ac", "gov", "edu", "me", "ltd"];
+
+/// Largest RDAP response read
+#[cfg(all(feature = "tls", not(target_arch = "wasm32")))]
+const MAX_RESPONSE_SIZE: usize = 256 * 1024;
+
+/// The name the registry holds for `domain`: `mail.example.com` is
+/// registered as `example.com`, `shop.example.co.uk` as `example.co.uk`
+#[cfg(any(target_arch = "wasm32", feature = "tls", test))]
+fn registered_domain(domain: &str) -> String {
+    let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+    let labels: Vec<&str> = domain.split('.').collect();
+    let keep = match labels.as_slice() {
+        [.., sld, tld] if tld.len() == 2 && REGISTRY_SLDS.contains(sld) => 3,
+        _ => 2
+    };
+    labels[labels.len().saturating_sub(keep)..].join(".")
+}
+
+/// Host and path of the RDAP domain query for `domain`, if its TLD's
+/// registry is known
+#[cfg(any(target_arch = "wasm32", feature = "tls", test))]
+fn rdap_query(domain: &str) -> Result<(&'static str, String), String> {
+    let domain = registered_domain(domain);
+    let tld = domain.rsplit('.').next().unwrap_or_default();
+    let (_, host, prefix) = RDAP_SERVERS
+        .iter()
+        .find(|(known, _, _)| *known == tld)
+        .ok_or_else(|| format!("No RDAP service known for .{}", tld))?;
+    Ok((host, format!("{}domain/{}", prefix, domain)))
+}
+
+/// Registration time from an RDAP domain response, in Unix milliseconds
+#[cfg(any(target_arch = "wasm32", feature = "tls", test))]
+fn parse_registration_date(body: &str) -> Result<Option<u64>, String> {
+    let response: serde_json::Value = serde_json::from_str(body).map_err(|e| format!("Malformed RDAP response: {}", e))?;
+    let registration = response["events"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|event| event["eventAction"] == "registration");
+    Ok(registration.and_then(|event| event["eventDate"].as_str()).and_then(parse_timestamp))
+}
+
+/// Parses an RFC 3339 timestamp (`2019-03-04T17:20:01Z`) into Unix
+/// milliseconds
+///
+/// Fractional seconds and the UTC offset are ignored; they shift the result by
+/// at most a day, which does not matter for domain age.
+#[cfg(any(target_arch = "wasm32", feature = "tls", test))]
+fn parse_timestamp(timestamp: &str) -> Option<u64> {
+    let number = |range: std::ops::Range<usize>| timestamp.get(range)?.parse::<u64>().ok();
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    // Days since 1970-01-01 in the proleptic Gregorian calendar
+    let (year, month) = if month <= 2 { (year.checked_sub(1)?, month + 9) } else { (year, month - 3) };
+    let era = year / 400;
+    let day_of_era = (year % 400) * 365 + (year % 400) / 4 - (year % 400) / 100 + (153 * month + 2) / 5 + day - 1;
+    let days = (era * 146_097 + day_of_era).checked_sub(719_468)?;
+    Some(((days * 24 + hour) * 60 + minute) * 60_000 + second * 1000)
+}
+
+/// Registration time of `domain` in Unix milliseconds through the host's
+/// `fetch`, or None when the registry does not publish one
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn registration_date(domain: &str) -> Result<Option<u64>, String> {
+    let (host, path) = rdap_query(domain)?;
+    let url = format!("https://{}{}", host, path);
+    let body = crate::fetch::fetch_text(&url, "application/rdap+json").await.map_err(|e| e.as_string().unwrap_or_default())?;
+    parse_registration_date(&body)
+}
+
+/// Registration time of `domain` in Unix milliseconds over HTTPS, or None
+/// when the registry does not publish one
+#[cfg(all(feature = "tls", not(target_arch = "wasm32")))]
+pub(crate) async fn registration_date(domain: &str) -> Result<Option<u64>, String> {
+    let (host, path) = rdap_query(domain)?;
+    let (status, body) = crate::http::https_get(host, &path, std::time::Duration::from_secs(10), MAX_RESPONSE_SIZE).await?;
+    if status != 200 {
+        return Err(format!("HTTP status {}", status));
+    }
+    parse_registration_date(&body)
+}
+
+/// RDAP cannot be queried without TLS support
+#[cfg(all(not(feature = "tls"), not(target_arch = "wasm32")))]
+pub(crate) async fn registration_date(_domain: &str) -> Result<Option<u64>, String> {
+    Err("Checking domain age requires the `tls` feature".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests registered-domain extraction and registry lookup
+    #[test]
+    fn test_rdap_query() {
+        assert_eq!(registered_domain("Mail.Example.com."), "example.com");
+        assert_eq!(registered_domain("shop.example.co.uk"), "example.co.uk");
+        assert_eq!(registered_domain("example.org"), "example.org");
+        assert_eq!(rdap_query("mail.example.com"), Ok(("rdap.verisign.com", "/com/v1/domain/example.com".to_string())));
+        assert_eq!(rdap_query("example.co.uk"), Ok(("rdap.nominet.uk", "/uk/domain/example.co.uk".to_string())));
+        assert!(rdap_query("example.de").is_err());
+    }
+
+    /// Tests registration event and timestamp parsing
+    #[test]
+    fn test_parse_registration_date() {
+        let body = r#"{"objectClassName":"domain","ldhName":"EXAMPLE.COM","events":[
+            {"eventAction":"expiration","eventDate":"2026-08-13T04:00:00Z"},
+            {"eventAction":"registration","eventDate":"1995-08-14T04:00:00Z"}
+        ]}"#;
+        assert_eq!(parse_registration_date(body), Ok(Some(808_372_800_000)));
+        assert_eq!(parse_registration_date(r#"{"events":[]}"#), Ok(None));
+        assert_eq!(parse_registration_date("{}"), Ok(None));
+        assert!(parse_registration_date("<html>").is_err());
+
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(parse_timestamp("2000-03-01T00:00:01.250+02:00"), Some(951_868_801_000));
+        assert_eq!(parse_timestamp("2024-13-01T00:00:00Z"), None);
+        assert_eq!(parse_timestamp("yesterday"), None);
+    }
+}
@@ -0,0 +1,89 @@
+//! Redaction of addresses in logs, traces and pipeline events
+//!
+//! Every place the verifier reports an address outside the result itself
+//! goes through `redact_email` with the verifier's `LogRedaction`, so turning
+//! on tracing or an event observer in production does not leak personal data.
+//! Domains are kept: they are what DNS and SMTP problems are diagnosed by.
+
+use serde::{Serialize, Deserialize};
+
+use crate::hash::hash_email;
+
+/// How addresses appear in log, trace and event output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum LogRedaction {
+    /// Addresses are logged as given
+    None,
+    /// The local part is reduced to its first character (`u***@example.com`)
+    #[default]
+    Mask,
+    /// The address is replaced by its `hash_email` digest, which still joins
+    /// against hashed analytics data
+    Hash
+}
+
+impl LogRedaction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogRedaction::None => "none",
+            LogRedaction::Mask => "mask",
+            LogRedaction::Hash => "hash"
+        }
+    }
+}
+
+impl std::str::FromStr for LogRedaction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(LogRedaction::None),
+            "mask" => Ok(LogRedaction::Mask),
+            "hash" => Ok(LogRedaction::Hash),
+            other => Err(format!("Unknown log redaction: {}", other))
+        }
+    }
+}
+
+/// `email` as it may appear in log output under `redaction`
+///
+/// Input without an `@` is masked entirely, since it cannot be told apart
+/// from a mistyped address.
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::{hash_email, redact_email, LogRedaction};
+/// assert_eq!(redact_email("user@example.com", LogRedaction::Mask), "u***@example.com");
+/// assert_eq!(redact_email("user@example.com", LogRedaction::Hash), hash_email("user@example.com"));
+/// ```
+pub fn redact_email(email: &str, redaction: LogRedaction) -> String {
+    match redaction {
+        LogRedaction::None => email.to_string(),
+        LogRedaction::Hash => hash_email(email),
+        LogRedaction::Mask => match email.rsplit_once('@') {
+            Some((local_part, domain)) => format!("{}***@{}", local_part.chars().take(1).collect::<String>(), domain),
+            None => "***".to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests each redaction mode on addresses and malformed input
+    #[test]
+    fn test_redact_email() {
+        assert_eq!(redact_email("user@example.com", LogRedaction::None), "user@example.com");
+        assert_eq!(redact_email("user@example.com", LogRedaction::Mask), "u***@example.com");
+        assert_eq!(redact_email("\"a@b\"@example.com", LogRedaction::Mask), "\"***@example.com");
+        assert_eq!(redact_email("éric@example.fr", LogRedaction::Mask), "é***@example.fr");
+        assert_eq!(redact_email("@example.com", LogRedaction::Mask), "***@example.com");
+        assert_eq!(redact_email("not-an-address", LogRedaction::Mask), "***");
+        assert_eq!(redact_email("User@Example.com", LogRedaction::Hash), hash_email("user@example.com"));
+        assert_eq!("hash".parse::<LogRedaction>(), Ok(LogRedaction::Hash));
+        assert!("partial".parse::<LogRedaction>().is_err());
+    }
+}
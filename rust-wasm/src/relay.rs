@@ -0,0 +1,47 @@
+//! Privacy relay services
+//!
+//! Relays such as Apple's Hide My Email hand out per-site addresses that
+//! forward to a real inbox. They are legitimate and deliverable, unlike
+//! disposable inboxes, but the user can switch one off at any time, so they
+//! are a poor target for marketing re-permission flows.
+
+/// Relay domains; subdomains match too (`user@alias.anonaddy.com`)
+const RELAY_DOMAINS: [&str; 13] = [
+    "privaterelay.appleid.com",
+    "mozmail.com",
+    "duck.com",
+    "simplelogin.com",
+    "simplelogin.co",
+    "simplelogin.fr",
+    "slmail.me",
+    "aleeas.com",
+    "silomails.com",
+    "anonaddy.com",
+    "anonaddy.me",
+    "addy.io",
+    "passmail.net"
+];
+
+/// Whether `domain` (lowercased) belongs to a privacy relay service
+pub(crate) fn is_relay_domain(domain: &str) -> bool {
+    let domain = domain.trim_end_matches('.');
+    RELAY_DOMAINS.iter().any(|relay| {
+        domain == *relay || domain.strip_suffix(relay).is_some_and(|rest| rest.ends_with('.'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests exact and subdomain matches on label boundaries
+    #[test]
+    fn test_is_relay_domain() {
+        assert!(is_relay_domain("privaterelay.appleid.com"));
+        assert!(is_relay_domain("duck.com"));
+        assert!(is_relay_domain("jane.anonaddy.com"));
+        assert!(!is_relay_domain("notduck.com"));
+        assert!(!is_relay_domain("appleid.com"));
+        assert!(!is_relay_domain("mailinator.com"));
+    }
+}
@@ -0,0 +1,321 @@
+//! Aggregate reports over batch results
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use serde::{Serialize, Deserialize};
+
+use crate::{DomainCategory, EmailParseError, EmailParseResult, VerificationResult, Verdict};
+
+/// Number of addresses per verdict
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct VerdictCounts {
+    pub deliverable: usize,
+    pub risky: usize,
+    pub undeliverable: usize,
+    pub invalid: usize,
+    pub unknown: usize
+}
+
+impl VerdictCounts {
+    fn add(&mut self, verdict: Verdict) {
+        match verdict {
+            Verdict::Deliverable => self.deliverable += 1,
+            Verdict::Risky => self.risky += 1,
+            Verdict::Undeliverable => self.undeliverable += 1,
+            Verdict::Invalid => self.invalid += 1,
+            Verdict::Unknown => self.unknown += 1
+        }
+    }
+
+    fn rows(&self) -> [(Verdict, usize); 5] {
+        [
+            (Verdict::Deliverable, self.deliverable),
+            (Verdict::Risky, self.risky),
+            (Verdict::Undeliverable, self.undeliverable),
+            (Verdict::Invalid, self.invalid),
+            (Verdict::Unknown, self.unknown)
+        ]
+    }
+}
+
+/// Breakdown for one domain
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DomainReport {
+    pub total: usize,
+    pub verdicts: VerdictCounts,
+    pub disposable: bool
+}
+
+/// Aggregate summary of a batch run
+///
+/// Built incrementally, so a report over millions of results never holds
+/// the results themselves.
+///
+/// # Examples
+/// ```no_run
+/// # use email_validator_wasm::{BatchReport, SandboxResolver, Verifier};
+/// # async fn example(verifier: Verifier<SandboxResolver>, emails: Vec<String>) {
+/// let mut report = BatchReport::new();
+/// for email in emails {
+///     match verifier.verify(&email).await {
+///         Ok(result) => report.add_verification(&result),
+///         Err(e) => report.add_error(&e)
+///     }
+/// }
+/// println!("{}", report.to_markdown(10));
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BatchReport {
+    /// Addresses seen, including errors
+    pub total: usize,
+    pub verdicts: VerdictCounts,
+    /// Why invalid addresses were rejected, with counts
+    pub invalid_reasons: BTreeMap<String, usize>,
+    /// Addresses at disposable inbox services
+    pub disposable: usize,
+    /// Per-domain breakdown, keyed by lowercased domain
+    pub domains: BTreeMap<String, DomainReport>
+}
+
+impl BatchReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a report from a batch of verification outcomes
+    pub fn from_verifications<'a, I>(outcomes: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Result<VerificationResult, EmailParseError>>
+    {
+        let mut report = Self::new();
+        for outcome in outcomes {
+            match outcome {
+                Ok(result) => report.add_verification(result),
+                Err(e) => report.add_error(e)
+            }
+        }
+        report
+    }
+
+    /// Builds a report from a batch of syntax validation outcomes, such as
+    /// those of `parse_and_validate_emails`
+    pub fn from_validations<'a, I>(outcomes: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Result<EmailParseResult, EmailParseError>>
+    {
+        let mut report = Self::new();
+        for outcome in outcomes {
+            match outcome {
+                Ok(result) => report.add_validation(result),
+                Err(e) => report.add_error(e)
+            }
+        }
+        report
+    }
+
+    /// Counts a verification result
+    pub fn add_verification(&mut self, result: &VerificationResult) {
+        self.add(&result.email, result.verdict());
+    }
+
+    /// Counts a syntax-only result; valid addresses count as `unknown`
+    pub fn add_validation(&mut self, result: &EmailParseResult) {
+        self.add(result, if result.is_valid { Verdict::Unknown } else { Verdict::Invalid });
+    }
+
+    /// Counts an address the parser rejected outright
+    pub fn add_error(&mut self, error: &EmailParseError) {
+        self.total += 1;
+        self.verdicts.add(Verdict::Invalid);
+        *self.invalid_reasons.entry(error.message.clone()).or_default() += 1;
+    }
+
+    fn add(&mut self, result: &EmailParseResult, verdict: Verdict) {
+        self.total += 1;
+        self.verdicts.add(verdict);
+        if verdict == Verdict::Invalid {
+            let reason = result.error_message.clone().unwrap_or_else(|| "Invalid email".to_string());
+            *self.invalid_reasons.entry(reason).or_default() += 1;
+        }
+
+        let disposable = result.domain_category == Some(DomainCategory::Disposable);
+        if disposable {
+            self.disposable += 1;
+        }
+        if let Some(domain) = &result.domain {
+            let entry = self.domains.entry(domain.to_lowercase()).or_default();
+            entry.total += 1;
+            entry.verdicts.add(verdict);
+            entry.disposable |= disposable;
+        }
+    }
+
+    /// Share of addresses at disposable services (0 for an empty report)
+    pub fn disposable_rate(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.disposable as f64 / self.total as f64
+        }
+    }
+
+    /// The `limit` most common invalid reasons, most common first
+    pub fn top_invalid_reasons(&self, limit: usize) -> Vec<(&str, usize)> {
+        let mut reasons: Vec<(&str, usize)> = self.invalid_reasons.iter().map(|(reason, count)| (reason.as_str(), *count)).collect();
+        reasons.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        reasons.truncate(limit);
+        reasons
+    }
+
+    /// The `limit` domains with the most addresses, largest first
+    pub fn top_domains(&self, limit: usize) -> Vec<(&str, &DomainReport)> {
+        let mut domains: Vec<(&str, &DomainReport)> = self.domains.iter().map(|(domain, report)| (domain.as_str(), report)).collect();
+        domains.sort_by(|a, b| b.1.total.cmp(&a.1.total).then(a.0.cmp(b.0)));
+        domains.truncate(limit);
+        domains
+    }
+
+    /// Renders the report as Markdown, listing the `limit` top reasons and domains
+    pub fn to_markdown(&self, limit: usize) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# Batch report\n");
+        let _ = writeln!(out, "{} addresses, {} disposable ({}).\n", self.total, self.disposable, percent(self.disposable, self.total));
+
+        let _ = writeln!(out, "| Verdict | Count | Share |\n|---------|------:|------:|");
+        for (verdict, count) in self.verdicts.rows() {
+            let _ = writeln!(out, "| {} | {} | {} |", verdict.as_str(), count, percent(count, self.total));
+        }
+
+        let reasons = self.top_invalid_reasons(limit);
+        if !reasons.is_empty() {
+            let _ = writeln!(out, "\n## Top invalid reasons\n\n| Reason | Count |\n|--------|------:|");
+            for (reason, count) in reasons {
+                let _ = writeln!(out, "| {} | {} |", reason.replace('|', "\\|"), count);
+            }
+        }
+
+        let domains = self.top_domains(limit);
+        if !domains.is_empty() {
+            let _ = writeln!(out, "\n## Top domains\n\n| Domain | Total | Deliverable | Risky | Undeliverable | Invalid | Unknown |");
+            let _ = writeln!(out, "|--------|------:|------:|------:|------:|------:|------:|");
+            for (domain, report) in domains {
+                let v = &report.verdicts;
+                let _ = writeln!(out, "| {} | {} | {} | {} | {} | {} | {} |", domain, report.total, v.deliverable, v.risky, v.undeliverable, v.invalid, v.unknown);
+            }
+        }
+        out
+    }
+
+    /// Renders the report as a standalone HTML fragment, listing the `limit`
+    /// top reasons and domains
+    pub fn to_html(&self, limit: usize) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "<h1>Batch report</h1>");
+        let _ = writeln!(out, "<p>{} addresses, {} disposable ({}).</p>", self.total, self.disposable, percent(self.disposable, self.total));
+
+        let _ = writeln!(out, "<table>\n<tr><th>Verdict</th><th>Count</th><th>Share</th></tr>");
+        for (verdict, count) in self.verdicts.rows() {
+            let _ = writeln!(out, "<tr><td>{}</td><td>{}</td><td>{}</td></tr>", verdict.as_str(), count, percent(count, self.total));
+        }
+        let _ = writeln!(out, "</table>");
+
+        let reasons = self.top_invalid_reasons(limit);
+        if !reasons.is_empty() {
+            let _ = writeln!(out, "<h2>Top invalid reasons</h2>\n<table>\n<tr><th>Reason</th><th>Count</th></tr>");
+            for (reason, count) in reasons {
+                let _ = writeln!(out, "<tr><td>{}</td><td>{}</td></tr>", escape_html(reason), count);
+            }
+            let _ = writeln!(out, "</table>");
+        }
+
+        let domains = self.top_domains(limit);
+        if !domains.is_empty() {
+            let _ = writeln!(out, "<h2>Top domains</h2>\n<table>");
+            let _ = writeln!(out, "<tr><th>Domain</th><th>Total</th><th>Deliverable</th><th>Risky</th><th>Undeliverable</th><th>Invalid</th><th>Unknown</th></tr>");
+            for (domain, report) in domains {
+                let v = &report.verdicts;
+                let _ = writeln!(
+                    out,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    escape_html(domain), report.total, v.deliverable, v.risky, v.undeliverable, v.invalid, v.unknown
+                );
+            }
+            let _ = writeln!(out, "</table>");
+        }
+        out
+    }
+}
+
+fn percent(count: usize, total: usize) -> String {
+    if total == 0 {
+        return "0.0%".to_string();
+    }
+    format!("{:.1}%", count as f64 * 100.0 / total as f64)
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c)
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::{MockResolver, MxRecord};
+    use crate::test_util::block_on;
+    use crate::{parse_and_validate_emails, ValidationOptions, Verifier};
+
+    /// Tests verdict totals, reasons, disposable rate and the per-domain breakdown
+    #[test]
+    fn test_batch_report() {
+        let resolver = MockResolver::new().with_mx("example.com", vec![MxRecord { preference: 10, exchange: "mx.example.com".to_string() }]);
+        let verifier = Verifier::new(resolver);
+        let outcomes: Vec<_> = ["a@example.com", "b@Example.com", "c@missing.com", "invalid", "also-invalid", "bad\r@example.com"]
+            .iter()
+            .map(|email| block_on(verifier.verify(email)))
+            .collect();
+        let report = BatchReport::from_verifications(&outcomes);
+
+        assert_eq!(report.total, 6);
+        assert_eq!(report.verdicts, VerdictCounts { deliverable: 0, risky: 0, undeliverable: 1, invalid: 3, unknown: 2 });
+        assert_eq!(report.top_invalid_reasons(1)[0].1, 2);
+        assert_eq!(report.domains["example.com"].total, 2);
+        assert_eq!(report.top_domains(1)[0].0, "example.com");
+        assert_eq!(report.domains["missing.com"].verdicts.undeliverable, 1);
+
+        let markdown = report.to_markdown(5);
+        assert!(markdown.contains("| undeliverable | 1 | 16.7% |"), "{}", markdown);
+        assert!(markdown.contains("| example.com | 2 | 0 | 0 | 0 | 0 | 2 |"), "{}", markdown);
+        assert!(report.to_html(5).contains("<tr><td>invalid</td><td>3</td><td>50.0%</td></tr>"));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["verdicts"]["invalid"], 3);
+    }
+
+    /// Tests reports over syntax-only results
+    #[test]
+    fn test_batch_report_validations() {
+        let outcomes = parse_and_validate_emails(&["user@mailinator.com", "user@example.com", "not-an-email"], &ValidationOptions::default());
+        let report = BatchReport::from_validations(&outcomes);
+        assert_eq!(report.disposable, 1);
+        assert!((report.disposable_rate() - 1.0 / 3.0).abs() < 1e-9);
+        assert!(report.domains["mailinator.com"].disposable);
+        assert_eq!(report.verdicts.invalid, 1);
+        assert_eq!(BatchReport::new().disposable_rate(), 0.0);
+        assert_eq!(escape_html("<a & b>"), "&lt;a &amp; b&gt;");
+    }
+}
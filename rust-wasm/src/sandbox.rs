@@ -0,0 +1,225 @@
+//! Deterministic sandbox for end-to-end tests without network access
+//!
+//! DNS and SMTP answers are canned and keyed by reserved names under
+//! `sandbox.test` (a reserved TLD, so these never collide with real mail):
+//!
+//! | Address                          | Outcome                                   |
+//! |----------------------------------|-------------------------------------------|
+//! | `deliverable@sandbox.test`       | MX found, mailbox accepted                |
+//! | `undeliverable@sandbox.test`     | MX found, mailbox rejected (550)          |
+//! | `catchall@sandbox.test`          | Mailbox accepted, domain is catch-all     |
+//! | `greylisted@sandbox.test`        | Temporary failure (451), greylisted       |
+//! | any other `@sandbox.test`        | Mailbox rejected (550)                    |
+//! | `*@nomx.sandbox.test`            | Domain exists without MX records          |
+//! | `*@nullmx.sandbox.test`          | Null MX: the domain accepts no mail       |
+//! | `*@nxdomain.sandbox.test`        | Domain does not exist                     |
+//! | `*@dnserror.sandbox.test`        | DNS server failure                        |
+//!
+//! `sandbox.test` publishes SPF, an enforcing DMARC policy and a TLSA record
+//! for its MX host (DANE). Every other domain resolves to `NxDomain`; nothing
+//! leaves the process.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::{self, Read, Write};
+use std::net::Ipv4Addr;
+
+use crate::dns::{DnsError, DnsResolver, MockResolver, MxRecord, TlsaRecord};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::smtp::{SmtpConnector, SmtpStream, SmtpVerifier};
+use crate::Verifier;
+
+/// Domain whose reserved local parts select SMTP outcomes
+pub const SANDBOX_DOMAIN: &str = "sandbox.test";
+
+/// Resolver answering from the sandbox's canned DNS data
+#[derive(Debug, Clone)]
+pub struct SandboxResolver {
+    answers: MockResolver
+}
+
+impl SandboxResolver {
+    pub fn new() -> Self {
+        let mx = |exchange: &str| vec![MxRecord { preference: 10, exchange: exchange.to_string() }];
+        let answers = MockResolver::new()
+            .with_mx(SANDBOX_DOMAIN, mx("mx.sandbox.test"))
+            .with_txt(SANDBOX_DOMAIN, vec!["v=spf1 -all".to_string()])
+            .with_txt("_dmarc.sandbox.test", vec!["v=DMARC1; p=reject".to_string()])
+            .with_tlsa("_25._tcp.mx.sandbox.test", vec![TlsaRecord { usage: 3, selector: 1, matching_type: 1, data: "0".repeat(64) }])
+            .with_txt("nomx.sandbox.test", vec!["v=spf1 -all".to_string()])
+            .with_mx("nullmx.sandbox.test", vec![MxRecord { preference: 0, exchange: ".".to_string() }])
+            .with_error("dnserror.sandbox.test", DnsError::new("ServerFailure", "DNS server returned an error", Some("rcode 2".to_string())));
+        SandboxResolver { answers }
+    }
+}
+
+impl Default for SandboxResolver {
+    fn default() -> Self {
+        SandboxResolver::new()
+    }
+}
+
+impl DnsResolver for SandboxResolver {
+    async fn lookup_mx(&self, domain: &str) -> Result<Vec<MxRecord>, DnsError> {
+        self.answers.lookup_mx(domain).await
+    }
+
+    async fn lookup_txt(&self, domain: &str) -> Result<Vec<String>, DnsError> {
+        self.answers.lookup_txt(domain).await
+    }
+
+    async fn lookup_a(&self, name: &str) -> Result<Vec<Ipv4Addr>, DnsError> {
+        self.answers.lookup_a(name).await
+    }
+
+    async fn lookup_tlsa(&self, name: &str) -> Result<Vec<TlsaRecord>, DnsError> {
+        self.answers.lookup_tlsa(name).await
+    }
+}
+
+impl Verifier<SandboxResolver> {
+    /// A verifier wired to the sandbox: canned DNS, SPF/DMARC and DANE checks
+    /// and (on native targets) a simulated SMTP server
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use email_validator_wasm::Verifier;
+    /// # async fn example() -> Result<(), email_validator_wasm::EmailParseError> {
+    /// let result = Verifier::sandbox().verify("undeliverable@sandbox.test").await?;
+    /// assert_eq!(result.smtp.unwrap().is_deliverable, Some(false));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sandbox() -> Self {
+        let verifier = Verifier::new(SandboxResolver::new()).with_mail_auth_checks(true).with_security_checks(true);
+        #[cfg(not(target_arch = "wasm32"))]
+        let verifier = verifier.with_smtp(smtp_verifier());
+        verifier
+    }
+}
+
+/// SMTP verifier that talks to the simulated server
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn smtp_verifier() -> SmtpVerifier {
+    SmtpVerifier::new("verifier.sandbox.test", "probe@verifier.sandbox.test").with_connector(SandboxConnector)
+}
+
+/// Connector to a simulated SMTP server that answers `RCPT TO` according to
+/// the reserved sandbox addresses
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SandboxConnector;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SmtpConnector for SandboxConnector {
+    fn connect(&self, host: &str, _port: u16) -> io::Result<Box<dyn SmtpStream>> {
+        Ok(Box::new(SandboxSession {
+            replies: format!("220 {} ESMTP sandbox\r\n", host).into_bytes(),
+            command: Vec::new(),
+            catch_all: false
+        }))
+    }
+}
+
+/// One simulated SMTP connection: commands written are answered immediately
+#[cfg(not(target_arch = "wasm32"))]
+struct SandboxSession {
+    replies: Vec<u8>,
+    command: Vec<u8>,
+    /// Set once `catchall@` was accepted, so later probes are accepted too
+    catch_all: bool
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SandboxSession {
+    fn reply(&mut self, command: &str) -> &'static str {
+        let upper = command.to_ascii_uppercase();
+        if upper.starts_with("EHLO") || upper.starts_with("HELO") {
+            return "250 sandbox.test";
+        }
+        if upper.starts_with("MAIL FROM") {
+            return "250 2.1.0 OK";
+        }
+        if upper.starts_with("QUIT") {
+            return "221 2.0.0 Bye";
+        }
+        let Some(recipient) = command.get(8..).filter(|_| upper.starts_with("RCPT TO:")) else {
+            return "502 5.5.2 Command not recognized";
+        };
+
+        let recipient = recipient.trim().trim_start_matches('<').trim_end_matches('>').to_lowercase();
+        match recipient.rsplit_once('@') {
+            Some(("deliverable", SANDBOX_DOMAIN)) => "250 2.1.5 OK",
+            Some(("catchall", SANDBOX_DOMAIN)) => {
+                self.catch_all = true;
+                "250 2.1.5 OK"
+            }
+            Some(("greylisted", SANDBOX_DOMAIN)) => "451 4.7.1 Greylisted, try again in 300 seconds",
+            _ if self.catch_all => "250 2.1.5 OK",
+            _ => "550 5.1.1 No such user"
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Read for SandboxSession {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = buf.len().min(self.replies.len());
+        buf[..len].copy_from_slice(&self.replies[..len]);
+        self.replies.drain(..len);
+        Ok(len)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Write for SandboxSession {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.command.extend_from_slice(buf);
+        while let Some(end) = self.command.windows(2).position(|pair| pair == b"\r\n") {
+            let line: Vec<u8> = self.command.drain(..end + 2).collect();
+            let reply = self.reply(String::from_utf8_lossy(&line[..end]).as_ref());
+            self.replies.extend_from_slice(reply.as_bytes());
+            self.replies.extend_from_slice(b"\r\n");
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::block_on;
+
+    /// Tests each reserved address against the sandbox verifier
+    #[test]
+    fn test_sandbox_outcomes() {
+        let verifier = Verifier::sandbox();
+        let verify = |email: &str| block_on(verifier.verify(email)).unwrap();
+
+        let result = verify("deliverable@sandbox.test");
+        assert_eq!(result.domain_checks.has_mx, Some(true));
+        assert_eq!(result.domain_checks.has_dmarc, Some(true));
+        assert_eq!(result.domain_checks.dane, Some(true));
+        let smtp = result.smtp.unwrap();
+        assert_eq!((smtp.is_deliverable, smtp.is_catch_all), (Some(true), Some(false)));
+
+        let smtp = verify("undeliverable@sandbox.test").smtp.unwrap();
+        assert_eq!((smtp.is_deliverable, smtp.response_code), (Some(false), Some(550)));
+
+        let smtp = verify("catchall@sandbox.test").smtp.unwrap();
+        assert_eq!((smtp.is_deliverable, smtp.is_catch_all), (Some(true), Some(true)));
+
+        let smtp = verify("greylisted@sandbox.test").smtp.unwrap();
+        assert_eq!((smtp.is_deliverable, smtp.is_greylisted, smtp.retry_after), (None, Some(true), Some(300)));
+
+        let checks = verify("user@nomx.sandbox.test").domain_checks;
+        assert_eq!((checks.domain_exists, checks.has_mx), (Some(true), Some(false)));
+        assert_eq!(verify("user@nullmx.sandbox.test").domain_checks.error_code.as_deref(), Some("NullMx"));
+        assert_eq!(verify("user@nxdomain.sandbox.test").domain_checks.error_code.as_deref(), Some("NxDomain"));
+        assert_eq!(verify("user@dnserror.sandbox.test").domain_checks.dns_error.unwrap().error_type, "ServerFailure");
+        assert_eq!(verify("user@example.com").domain_checks.error_code.as_deref(), Some("NxDomain"));
+    }
+}
@@ -0,0 +1,614 @@
+//! HTTP service exposing validation and verification (`server` feature)
+//!
+//! Routes:
+//! - `POST /validate` with `{"email": "...", "options": {...}, "stages": {...}}`
+//! - `POST /validate/batch` with `{"emails": [...], "options": {...}, "stages": {...}}`
+//!
+//! `options` takes the same `ValidationOptions` keys as the JS options object.
+//! Requests that omit `options` or `stages` get `ServerConfig::default_options`
+//! and `default_stages`, which `ServerConfig::with_profile` takes from a
+//! `VerificationProfile`.
+//! Without `stages` a response is a serialized `EmailParseResult`; with
+//! `stages.dns` (or `stages.smtp`, which implies DNS) it is a serialized
+//! `VerificationResult`. Errors are serialized `EmailParseError`s.
+//!
+//! With `ServerConfig::sandbox` set, network stages answer from the
+//! deterministic sandbox (`deliverable@sandbox.test` and friends) instead of
+//! the network, for integration tests against the service.
+//!
+//! Network stages are bounded by `ServerConfig::timeouts`, and a request's
+//! remaining verifications are cancelled when its client disconnects.
+//!
+//! `GET /metrics` serves Prometheus metrics when `ServerConfig::metrics` is set.
+//! `GET /openapi.json` serves an OpenAPI 3.0 description of these routes for
+//! client code generation.
+//!
+//! With the `storage` feature and `ServerConfig::history` set, every
+//! verification is recorded, and `GET /history` (filtered by `email`,
+//! `domain`, `verdict`, `since`, `until` and `limit` query parameters) and
+//! `GET /history/changes?since=<unix ms>` serve the recorded history.
+//!
+//! With `ServerConfig::audit_log` set, every decision is appended to the
+//! audit log with its configuration version and the stages that ran.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "storage")]
+use axum::extract::Query;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use metrics_exporter_prometheus::PrometheusHandle;
+use schemars::generate::SchemaSettings;
+use serde::{Serialize, Deserialize};
+use serde_json::json;
+
+use crate::breach::{BreachRangeSource, HttpsBreachSource};
+use crate::dns::{CachingResolver, DnsCache, DnsResolver, SystemResolver};
+use crate::smtp::{ProbeScheduler, SmtpVerifier, TcpConnector};
+#[cfg(feature = "storage")]
+use crate::{HistoryQuery, StorageError, VerificationStore};
+use crate::{batch, config_version, sandbox, AuditEntry, AuditLog, AuditStage, Blocklist, CancellationToken, DEFAULT_CT_LOG_ENDPOINT, LogRedaction, PipelineEvent, Proxy, EmailParseError, EmailParseResult, LruCache, SandboxResolver, StageTimeouts, Stages, ValidationOptions, VerificationProfile, VerificationResult, Verifier};
+
+/// How long domain checks are reused within one batch request
+const BATCH_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Body of `POST /validate`
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ValidateRequest {
+    pub email: String,
+    /// Falls back to `ServerConfig::default_options` when omitted
+    #[serde(default)]
+    pub options: Option<ValidationOptions>,
+    /// Falls back to `ServerConfig::default_stages` when omitted
+    #[serde(default)]
+    pub stages: Option<Stages>
+}
+
+/// Body of `POST /validate/batch`
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BatchRequest {
+    pub emails: Vec<String>,
+    /// Falls back to `ServerConfig::default_options` when omitted
+    #[serde(default)]
+    pub options: Option<ValidationOptions>,
+    /// Falls back to `ServerConfig::default_stages` when omitted
+    #[serde(default)]
+    pub stages: Option<Stages>
+}
+
+/// Outcome for one address
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum ValidateResponse {
+    Validated(Box<EmailParseResult>),
+    Verified(Box<VerificationResult>),
+    Error(EmailParseError)
+}
+
+impl IntoResponse for ValidateResponse {
+    fn into_response(self) -> Response {
+        let status = match self {
+            ValidateResponse::Error(_) => StatusCode::BAD_REQUEST,
+            _ => StatusCode::OK
+        };
+        (status, Json(self)).into_response()
+    }
+}
+
+/// Service configuration shared by all requests
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// Resolver for the DNS stage (None disables network stages)
+    pub resolver: Option<SystemResolver>,
+    /// `HELO` name and `MAIL FROM` address for the SMTP stage (None disables it)
+    pub smtp_identity: Option<(String, String)>,
+    /// Largest accepted batch
+    pub max_batch_size: usize,
+    /// Installed Prometheus recorder to render at `GET /metrics`
+    pub metrics: Option<PrometheusHandle>,
+    /// Answer network stages from the sandbox instead of `resolver` and SMTP
+    pub sandbox: bool,
+    /// Upper bounds for the network stages of each address
+    pub timeouts: StageTimeouts,
+    /// Paces SMTP probes across all requests
+    pub smtp_scheduler: ProbeScheduler,
+    /// Egress proxy for the SMTP stage (route DNS with `SystemResolver::with_proxy`)
+    pub smtp_proxy: Option<Proxy>,
+    /// MX/TXT answers shared across all requests
+    pub dns_cache: DnsCache,
+    /// Lists queried by the blocklist stage
+    pub blocklists: Vec<Blocklist>,
+    /// Age below which the domain age stage flags a domain as newly registered
+    pub min_domain_age: Duration,
+    /// Search API queried by the CT log stage
+    pub ct_log_endpoint: String,
+    /// Range API queried by the breach stage (None disables it)
+    pub breach_endpoint: Option<String>,
+    /// How addresses appear in stage spans (masked by default)
+    pub log_redaction: LogRedaction,
+    /// Options for requests that send none
+    pub default_options: ValidationOptions,
+    /// Stages for requests that send none
+    pub default_stages: Stages,
+    /// How long verification results stay fresh (None keeps the 30-day default)
+    pub result_ttl: Option<Duration>,
+    /// Append-only log recording every decision
+    pub audit_log: Option<Arc<AuditLog>>,
+    /// Store recording every verification
+    #[cfg(feature = "storage")]
+    pub history: Option<Arc<VerificationStore>>
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            resolver: None,
+            smtp_identity: None,
+            max_batch_size: 1000,
+            metrics: None,
+            sandbox: false,
+            timeouts: StageTimeouts::default(),
+            smtp_scheduler: ProbeScheduler::default(),
+            smtp_proxy: None,
+            dns_cache: DnsCache::default(),
+            blocklists: vec![Blocklist::spamhaus_dbl()],
+            min_domain_age: VerificationProfile::default().min_domain_age(),
+            ct_log_endpoint: DEFAULT_CT_LOG_ENDPOINT.to_string(),
+            breach_endpoint: None,
+            log_redaction: LogRedaction::default(),
+            default_options: ValidationOptions::default(),
+            default_stages: Stages::default(),
+            result_ttl: None,
+            audit_log: None,
+            #[cfg(feature = "storage")]
+            history: None
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Takes the policy settings from `profile`: default options and stages,
+    /// blocklists, minimum domain age, CT log and breach endpoints, timeouts
+    /// and result TTL
+    pub fn with_profile(mut self, profile: VerificationProfile) -> Self {
+        self.min_domain_age = profile.min_domain_age();
+        self.ct_log_endpoint = profile.ct_log_endpoint;
+        self.breach_endpoint = profile.breach_endpoint;
+        self.default_options = profile.validation;
+        self.default_stages = profile.stages;
+        self.blocklists = profile.blocklists;
+        self.timeouts = profile.timeouts;
+        self.result_ttl = profile.result_ttl;
+        self
+    }
+
+    /// A request's options and stages, or the configured defaults
+    fn request_defaults(&self, options: Option<ValidationOptions>, stages: Option<Stages>) -> (ValidationOptions, Stages) {
+        (options.unwrap_or_else(|| self.default_options.clone()), stages.unwrap_or(self.default_stages))
+    }
+
+    /// Validates `emails` and runs the requested stages, one response per address
+    ///
+    /// Blocks on the network stages, so it runs on tokio's blocking pool.
+    /// Cancelling `cancel` fails the remaining addresses with "Cancelled".
+    fn run(&self, emails: &[String], options: ValidationOptions, stages: Stages, cancel: CancellationToken) -> Vec<ValidateResponse> {
+        if !stages.needs_network() {
+            let audit = self.audit(&options, &stages);
+            return batch::parse_and_validate_emails(emails, &options)
+                .into_iter()
+                .zip(emails)
+                .map(|(result, email)| match result {
+                    Ok(result) => {
+                        if let Some((audit_log, version)) = &audit {
+                            // The audit log is best effort, like history
+                            let _ = audit_log.record(&AuditEntry::for_validation(email, &result, version));
+                        }
+                        ValidateResponse::Validated(Box::new(result))
+                    }
+                    Err(e) => ValidateResponse::Error(e)
+                })
+                .collect();
+        }
+
+        if self.sandbox {
+            let mut verifier = Verifier::new(SandboxResolver::new());
+            if stages.smtp {
+                verifier = verifier.with_smtp(sandbox::smtp_verifier());
+            }
+            return self.verify_all(verifier, emails, options, stages, cancel);
+        }
+
+        let unavailable = |stage: &str| {
+            let message = format!("The {} stage is not configured on this server", stage);
+            emails.iter().map(|_| ValidateResponse::Error(server_error("StageUnavailable", &message))).collect()
+        };
+        let Some(resolver) = self.resolver.clone() else {
+            return unavailable("DNS");
+        };
+        let breach_source = match (&self.breach_endpoint, stages.breach) {
+            (Some(endpoint), true) => {
+                let source = HttpsBreachSource::new(endpoint);
+                Some(match self.timeouts.breach {
+                    Some(timeout) => source.with_timeout(timeout),
+                    None => source
+                })
+            }
+            (None, true) => return unavailable("breach"),
+            (_, false) => None
+        };
+
+        let mut verifier = Verifier::new(CachingResolver::new(resolver, self.dns_cache.clone()));
+        if stages.smtp {
+            let Some((helo_name, mail_from)) = &self.smtp_identity else {
+                return unavailable("SMTP");
+            };
+            let mut smtp = SmtpVerifier::new(helo_name, mail_from).with_scheduler(self.smtp_scheduler.clone());
+            if let Some(proxy) = &self.smtp_proxy {
+                smtp = smtp.with_connector(TcpConnector::default().with_proxy(proxy.clone()));
+            }
+            verifier = verifier.with_smtp(smtp);
+        }
+        match breach_source {
+            Some(source) => self.verify_all(verifier.with_breach_check(source), emails, options, stages, cancel),
+            None => self.verify_all(verifier, emails, options, stages, cancel)
+        }
+    }
+
+    /// The audit log and the version of the configuration deciding a request,
+    /// when auditing is enabled
+    fn audit(&self, options: &ValidationOptions, stages: &Stages) -> Option<(&AuditLog, String)> {
+        let audit_log = self.audit_log.as_deref()?;
+        let min_domain_age_days = self.min_domain_age.as_secs() / (24 * 60 * 60);
+        Some((audit_log, config_version(&(options, stages, &self.blocklists, min_domain_age_days, &self.ct_log_endpoint, &self.breach_endpoint))))
+    }
+
+    /// Runs `verifier` over `emails`, blocking on each verification
+    fn verify_all<R: DnsResolver, B: BreachRangeSource>(
+        &self,
+        verifier: Verifier<R, B>,
+        emails: &[String],
+        options: ValidationOptions,
+        stages: Stages,
+        cancel: CancellationToken
+    ) -> Vec<ValidateResponse> {
+        let audit = self.audit(&options, &stages);
+        let stages_run = Rc::new(RefCell::new(Vec::new()));
+        let verifier = verifier
+            .with_validation_options(options)
+            .with_mail_auth_checks(stages.mail_auth)
+            .with_security_checks(stages.security)
+            .with_blocklists(if stages.blocklist { self.blocklists.clone() } else { Vec::new() })
+            .with_gravatar_check(stages.gravatar)
+            .with_domain_age_check(stages.domain_age.then_some(self.min_domain_age))
+            .with_ct_log_check(stages.ct_log.then_some(self.ct_log_endpoint.as_str()))
+            .with_cache(LruCache::new(emails.len().max(1)), BATCH_CACHE_TTL)
+            .with_timeouts(self.timeouts)
+            .with_cancellation(cancel)
+            .with_log_redaction(self.log_redaction);
+        let verifier = match self.result_ttl {
+            Some(ttl) => verifier.with_result_ttl(ttl),
+            None => verifier
+        };
+        let verifier = match audit {
+            Some(_) => {
+                let stages_run = stages_run.clone();
+                verifier.with_observer(move |event: &PipelineEvent| {
+                    if let PipelineEvent::StageFinished { stage, outcome, .. } = event {
+                        if *stage != "verify" {
+                            stages_run.borrow_mut().push(AuditStage { stage: stage.to_string(), outcome: outcome.clone() });
+                        }
+                    }
+                })
+            }
+            None => verifier
+        };
+
+        let runtime = tokio::runtime::Handle::current();
+        emails
+            .iter()
+            .map(|email| {
+                let result = runtime.block_on(verifier.verify(email));
+                let stages = stages_run.take();
+                match result {
+                    Ok(result) => {
+                        if let Some((audit_log, version)) = &audit {
+                            let _ = audit_log.record(&AuditEntry::for_verification(email, &result, stages, version));
+                        }
+                        #[cfg(feature = "storage")]
+                        if let Some(history) = &self.history {
+                            // History is best effort; failing to record doesn't fail the verification
+                            let _ = history.record(email, &result);
+                        }
+                        ValidateResponse::Verified(Box::new(result))
+                    }
+                    Err(e) => ValidateResponse::Error(e)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Builds the service's routes
+///
+/// # Examples
+/// ```no_run
+/// # use email_validator_wasm::dns::SystemResolver;
+/// # use email_validator_wasm::server::{router, ServerConfig};
+/// # async fn example() -> std::io::Result<()> {
+/// let config = ServerConfig { resolver: SystemResolver::from_system_config().ok(), ..Default::default() };
+/// let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
+/// axum::serve(listener, router(config)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn router(config: ServerConfig) -> Router {
+    let mut router = Router::new()
+        .route("/validate", post(validate))
+        .route("/validate/batch", post(validate_batch))
+        .route("/openapi.json", get(|| async { Json(openapi()) }));
+    if let Some(handle) = config.metrics.clone() {
+        router = router.route("/metrics", get(move || async move { handle.render() }));
+    }
+    #[cfg(feature = "storage")]
+    if config.history.is_some() {
+        router = router.route("/history", get(history)).route("/history/changes", get(history_changes));
+    }
+    router.with_state(Arc::new(config))
+}
+
+async fn validate(State(config): State<Arc<ServerConfig>>, Json(request): Json<ValidateRequest>) -> ValidateResponse {
+    let (options, stages) = config.request_defaults(request.options, request.stages);
+    let mut responses = run_blocking(config, vec![request.email], options, stages).await;
+    responses.pop().unwrap_or_else(|| ValidateResponse::Error(server_error("InternalError", "Validation produced no result")))
+}
+
+async fn validate_batch(State(config): State<Arc<ServerConfig>>, Json(request): Json<BatchRequest>) -> Response {
+    if request.emails.len() > config.max_batch_size {
+        let error = server_error("BatchTooLarge", &format!("Batches are limited to {} addresses", config.max_batch_size));
+        return (StatusCode::PAYLOAD_TOO_LARGE, Json(error)).into_response();
+    }
+
+    let (options, stages) = config.request_defaults(request.options, request.stages);
+    Json(run_blocking(config, request.emails, options, stages).await).into_response()
+}
+
+async fn run_blocking(config: Arc<ServerConfig>, emails: Vec<String>, options: ValidationOptions, stages: Stages) -> Vec<ValidateResponse> {
+    // Dropped with this future when the client disconnects
+    let cancel = CancellationToken::new();
+    let _guard = cancel.clone().drop_guard();
+    tokio::task::spawn_blocking(move || config.run(&emails, options, stages, cancel))
+        .await
+        .unwrap_or_else(|e| vec![ValidateResponse::Error(server_error("InternalError", &e.to_string()))])
+}
+
+/// Query string of `GET /history/changes`
+#[cfg(feature = "storage")]
+#[derive(Debug, Deserialize)]
+struct ChangesQuery {
+    since: u64
+}
+
+#[cfg(feature = "storage")]
+async fn history(State(config): State<Arc<ServerConfig>>, Query(query): Query<HistoryQuery>) -> Response {
+    let Some(store) = config.history.clone() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    match tokio::task::spawn_blocking(move || store.query(&query)).await {
+        Ok(Ok(entries)) => Json(entries).into_response(),
+        Ok(Err(e)) => storage_error(e),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(server_error("InternalError", &e.to_string()))).into_response()
+    }
+}
+
+#[cfg(feature = "storage")]
+async fn history_changes(State(config): State<Arc<ServerConfig>>, Query(query): Query<ChangesQuery>) -> Response {
+    let Some(store) = config.history.clone() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    match tokio::task::spawn_blocking(move || store.changes_since(query.since)).await {
+        Ok(Ok(changes)) => Json(changes).into_response(),
+        Ok(Err(e)) => storage_error(e),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(server_error("InternalError", &e.to_string()))).into_response()
+    }
+}
+
+#[cfg(feature = "storage")]
+fn storage_error(e: StorageError) -> Response {
+    let error = EmailParseError {
+        error_type: e.error_type,
+        message: e.message,
+        details: e.details,
+        position: None
+    };
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+}
+
+/// OpenAPI 3.0 document describing the service's routes and schemas
+pub fn openapi() -> serde_json::Value {
+    let mut requests = SchemaSettings::openapi3().for_deserialize().into_generator();
+    let mut responses = SchemaSettings::openapi3().for_serialize().into_generator();
+
+    let error = responses.subschema_for::<EmailParseError>();
+    let operation = |summary: &str, request: serde_json::Value, response: serde_json::Value| json!({
+        "summary": summary,
+        "requestBody": {
+            "required": true,
+            "content": { "application/json": { "schema": request } }
+        },
+        "responses": {
+            "200": {
+                "description": "Validation outcome",
+                "content": { "application/json": { "schema": response } }
+            },
+            "4XX": {
+                "description": "Rejected request",
+                "content": { "application/json": { "schema": error } }
+            }
+        }
+    });
+
+    let validate = operation(
+        "Validate one address",
+        requests.subschema_for::<ValidateRequest>().to_value(),
+        responses.subschema_for::<ValidateResponse>().to_value()
+    );
+    let validate_batch = operation(
+        "Validate a batch of addresses",
+        requests.subschema_for::<BatchRequest>().to_value(),
+        responses.subschema_for::<Vec<ValidateResponse>>().to_value()
+    );
+
+    let mut schemas = requests.take_definitions(true);
+    schemas.extend(responses.take_definitions(true));
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Email verification service",
+            "version": env!("CARGO_PKG_VERSION")
+        },
+        "paths": {
+            "/validate": { "post": validate },
+            "/validate/batch": { "post": validate_batch }
+        },
+        "components": { "schemas": schemas }
+    })
+}
+
+fn server_error(error_type: &str, message: &str) -> EmailParseError {
+    EmailParseError {
+        error_type: error_type.to_string(),
+        message: message.to_string(),
+        details: None,
+        position: None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    async fn post_json(path: &str, body: &str) -> (StatusCode, serde_json::Value) {
+        post_json_to(ServerConfig { max_batch_size: 2, ..Default::default() }, path, body).await
+    }
+
+    async fn post_json_to(config: ServerConfig, path: &str, body: &str) -> (StatusCode, serde_json::Value) {
+        let request = Request::post(path)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = router(config).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    /// Tests single and batch validation, batch limits and unconfigured stages
+    #[tokio::test]
+    async fn test_server_routes() {
+        let (status, body) = post_json("/validate", r#"{"email": "user@example.com"}"#).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["is_valid"], true);
+
+        let (status, body) = post_json("/validate", "{\"email\": \"us\\rer@example.com\"}").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error_type"], "ControlCharacter");
+
+        let (status, body) = post_json("/validate/batch", r#"{"emails": ["a@example.com", "invalid"]}"#).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body[0]["is_valid"], true);
+        assert_eq!(body[1]["is_valid"], false);
+
+        let (status, body) = post_json("/validate/batch", r#"{"emails": ["a@x.com", "b@x.com", "c@x.com"]}"#).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(body["error_type"], "BatchTooLarge");
+
+        let (_, body) = post_json("/validate", r#"{"email": "user@example.com", "stages": {"dns": true}}"#).await;
+        assert_eq!(body["error_type"], "StageUnavailable");
+
+        let config = ServerConfig { resolver: Some(SystemResolver::new("127.0.0.1:53".parse().unwrap())), ..Default::default() };
+        let (_, body) = post_json_to(config, "/validate", r#"{"email": "user@example.com", "stages": {"breach": true}}"#).await;
+        assert_eq!(body["error_type"], "StageUnavailable");
+        assert_eq!(body["message"], "The breach stage is not configured on this server");
+    }
+
+    /// Tests that sandbox mode answers network stages without the network
+    #[tokio::test]
+    async fn test_server_sandbox() {
+        let config = ServerConfig { sandbox: true, ..Default::default() };
+        let body = r#"{"emails": ["deliverable@sandbox.test", "undeliverable@sandbox.test"], "stages": {"smtp": true}}"#;
+        let (status, body) = post_json_to(config, "/validate/batch", body).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body[0]["smtp"]["is_deliverable"], true);
+        assert_eq!(body[1]["smtp"]["is_deliverable"], false);
+    }
+
+    /// Tests that sandbox verifications are recorded and served from history
+    #[cfg(feature = "storage")]
+    #[tokio::test]
+    async fn test_server_history() {
+        let history = Arc::new(VerificationStore::open_in_memory().unwrap());
+        let config = ServerConfig { sandbox: true, history: Some(history.clone()), ..Default::default() };
+        let body = r#"{"emails": ["deliverable@sandbox.test", "undeliverable@sandbox.test"], "stages": {"smtp": true}}"#;
+        let app = router(config);
+        let request = Request::post("/validate/batch").header("content-type", "application/json").body(Body::from(body)).unwrap();
+        assert_eq!(app.clone().oneshot(request).await.unwrap().status(), StatusCode::OK);
+        assert_eq!(history.len().unwrap(), 2);
+
+        let request = Request::get("/history?verdict=undeliverable&domain=sandbox.test").body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+        assert_eq!(body.as_array().unwrap().len(), 1);
+        assert_eq!(body[0]["email"], "undeliverable@sandbox.test");
+
+        let request = Request::get("/history/changes?since=0").body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+        assert_eq!(body, serde_json::json!([]));
+    }
+
+    /// Tests that sandbox verifications are appended to the audit log with the stages that ran
+    #[tokio::test]
+    async fn test_server_audit_log() {
+        let path = std::env::temp_dir().join(format!("server-audit-test-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let audit_log = Arc::new(AuditLog::open(&path).unwrap());
+        let config = ServerConfig { sandbox: true, audit_log: Some(audit_log), ..Default::default() };
+        let body = r#"{"emails": ["undeliverable@sandbox.test"], "stages": {"smtp": true}}"#;
+        let (status, _) = post_json_to(config, "/validate/batch", body).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let entries: Vec<AuditEntry> = contents.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].verdict, crate::Verdict::Undeliverable);
+        assert!(entries[0].reason.as_deref().unwrap().starts_with("SMTP 550"));
+        assert!(entries[0].stages.iter().any(|stage| stage.stage == "smtp"));
+    }
+
+    /// Tests that the OpenAPI document references schemas it defines
+    #[test]
+    fn test_openapi() {
+        let document = openapi();
+        let schemas = &document["components"]["schemas"];
+        for name in ["ValidateRequest", "BatchRequest", "EmailParseResult", "EmailParseError", "VerificationResult", "ValidationOptions"] {
+            assert!(schemas.get(name).is_some(), "missing schema {}", name);
+        }
+
+        assert_eq!(schemas["AccessPolicy"]["type"], "array", "{}", schemas["AccessPolicy"]);
+    }
+}
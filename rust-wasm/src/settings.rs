@@ -0,0 +1,301 @@
+//! Deployment settings for native use: `verification.toml` plus environment
+//!
+//! Settings are merged in increasing order of precedence: built-in defaults,
+//! the TOML file, the unprefixed variables the server has always read
+//! (`DNS_NAMESERVER`, `SMTP_HELO_NAME`, ...) and finally `TS_EMAIL_*`
+//! variables. A value set at a later level replaces the earlier one; unset
+//! variables leave it alone.
+
+use std::fs;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Serialize, Deserialize};
+
+use crate::{domain_lists, DomainLists, EmailParseError, StageTimeouts, VerificationProfile};
+
+/// File read by `Settings::load` when `TS_EMAIL_CONFIG` is not set
+pub const DEFAULT_SETTINGS_FILE: &str = "verification.toml";
+
+/// Files holding domain lists, one domain per line (`#` starts a comment)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ListPaths {
+    pub disposable: Option<PathBuf>,
+    pub trusted: Option<PathBuf>,
+    pub blocked: Option<PathBuf>,
+    pub spamtrap: Option<PathBuf>,
+    pub expired: Option<PathBuf>,
+    pub government: Option<PathBuf>
+}
+
+/// DNS stage settings
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DnsSettings {
+    /// Nameserver for lookups (`host:port`); the system's when None
+    pub nameserver: Option<SocketAddr>
+}
+
+/// SMTP stage identity; the stage stays disabled unless both are set
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SmtpSettings {
+    /// Name announced in `HELO`/`EHLO`
+    pub helo_name: Option<String>,
+    /// Envelope sender for probes
+    pub mail_from: Option<String>
+}
+
+/// Native deployment settings
+///
+/// # Examples
+/// ```no_run
+/// # use email_validator_wasm::Settings;
+/// let settings = Settings::load()?;
+/// settings.load_lists()?;
+/// let profile = settings.profile()?;
+/// # Ok::<(), email_validator_wasm::EmailParseError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Verification profile (`.json` or `.toml`) holding the policy
+    pub profile: Option<PathBuf>,
+    /// Domain list files, loaded in addition to the built-in lists
+    pub lists: ListPaths,
+    pub dns: DnsSettings,
+    pub smtp: SmtpSettings,
+    /// Per-stage timeouts; these override the profile's
+    pub timeouts: StageTimeouts
+}
+
+impl Settings {
+    /// Parses settings from TOML
+    pub fn from_toml(toml: &str) -> Result<Self, EmailParseError> {
+        toml::from_str(toml).map_err(|e| invalid_settings("Failed to parse settings", e.to_string()))
+    }
+
+    /// Reads the settings file (`TS_EMAIL_CONFIG`, else `verification.toml`
+    /// when it exists) and applies the process environment on top
+    ///
+    /// A file named by `TS_EMAIL_CONFIG` must exist; the default file is
+    /// optional.
+    pub fn load() -> Result<Self, EmailParseError> {
+        let mut settings = match std::env::var("TS_EMAIL_CONFIG") {
+            Ok(path) => Settings::from_file(Path::new(&path))?,
+            Err(_) if Path::new(DEFAULT_SETTINGS_FILE).exists() => Settings::from_file(Path::new(DEFAULT_SETTINGS_FILE))?,
+            Err(_) => Settings::default()
+        };
+        settings.apply_env(std::env::vars())?;
+        Ok(settings)
+    }
+
+    /// Parses the TOML settings file at `path`
+    pub fn from_file(path: &Path) -> Result<Self, EmailParseError> {
+        Settings::from_toml(&read_file(path)?)
+    }
+
+    /// Overrides settings with environment variables from `vars`
+    ///
+    /// Recognized variables, each also read without the `TS_EMAIL_` prefix
+    /// at lower precedence: `TS_EMAIL_PROFILE`, `TS_EMAIL_DISPOSABLE_LIST`,
+    /// `TS_EMAIL_TRUSTED_LIST`, `TS_EMAIL_BLOCKED_LIST`,
+    /// `TS_EMAIL_SPAMTRAP_LIST`, `TS_EMAIL_EXPIRED_LIST`, `TS_EMAIL_GOVERNMENT_LIST`,
+    /// `TS_EMAIL_DNS_NAMESERVER`, `TS_EMAIL_SMTP_HELO_NAME`,
+    /// `TS_EMAIL_SMTP_MAIL_FROM` and `TS_EMAIL_{DNS,SMTP,BREACH,GRAVATAR,RDAP,CT_LOG}_TIMEOUT_MS`.
+    pub fn apply_env(&mut self, vars: impl IntoIterator<Item = (String, String)>) -> Result<(), EmailParseError> {
+        let vars: Vec<(String, String)> = vars.into_iter().collect();
+        let lookup = |name: &str| -> Option<&str> {
+            let prefixed = format!("TS_EMAIL_{}", name);
+            let find = |key: &str| vars.iter().find(|(var, _)| var == key).map(|(_, value)| value.as_str());
+            find(&prefixed).or_else(|| find(name))
+        };
+        let parse_ms = |name: &str, current: Option<Duration>| -> Result<Option<Duration>, EmailParseError> {
+            match lookup(name) {
+                Some(ms) => ms
+                    .parse()
+                    .map(|ms| Some(Duration::from_millis(ms)))
+                    .map_err(|_| invalid_settings("Invalid timeout", format!("{}={}", name, ms))),
+                None => Ok(current)
+            }
+        };
+
+        if let Some(path) = lookup("PROFILE") {
+            self.profile = Some(PathBuf::from(path));
+        }
+        let lists = [
+            ("DISPOSABLE_LIST", &mut self.lists.disposable),
+            ("TRUSTED_LIST", &mut self.lists.trusted),
+            ("BLOCKED_LIST", &mut self.lists.blocked),
+            ("SPAMTRAP_LIST", &mut self.lists.spamtrap),
+            ("EXPIRED_LIST", &mut self.lists.expired),
+            ("GOVERNMENT_LIST", &mut self.lists.government)
+        ];
+        for (name, list) in lists {
+            if let Some(path) = lookup(name) {
+                *list = Some(PathBuf::from(path));
+            }
+        }
+        if let Some(nameserver) = lookup("DNS_NAMESERVER") {
+            let nameserver = nameserver
+                .parse()
+                .map_err(|_| invalid_settings("Invalid DNS nameserver", format!("DNS_NAMESERVER={}", nameserver)))?;
+            self.dns.nameserver = Some(nameserver);
+        }
+        if let Some(helo_name) = lookup("SMTP_HELO_NAME") {
+            self.smtp.helo_name = Some(helo_name.to_string());
+        }
+        if let Some(mail_from) = lookup("SMTP_MAIL_FROM") {
+            self.smtp.mail_from = Some(mail_from.to_string());
+        }
+        self.timeouts = StageTimeouts {
+            dns: parse_ms("DNS_TIMEOUT_MS", self.timeouts.dns)?,
+            smtp: parse_ms("SMTP_TIMEOUT_MS", self.timeouts.smtp)?,
+            breach: parse_ms("BREACH_TIMEOUT_MS", self.timeouts.breach)?,
+            gravatar: parse_ms("GRAVATAR_TIMEOUT_MS", self.timeouts.gravatar)?,
+            rdap: parse_ms("RDAP_TIMEOUT_MS", self.timeouts.rdap)?,
+            ct_log: parse_ms("CT_LOG_TIMEOUT_MS", self.timeouts.ct_log)?
+        };
+        Ok(())
+    }
+
+    /// The SMTP identity, when both parts are set
+    pub fn smtp_identity(&self) -> Option<(String, String)> {
+        Some((self.smtp.helo_name.clone()?, self.smtp.mail_from.clone()?))
+    }
+
+    /// Loads the configured profile, with the settings' timeouts on top
+    pub fn profile(&self) -> Result<VerificationProfile, EmailParseError> {
+        let mut profile = match &self.profile {
+            Some(path) if path.extension().is_some_and(|extension| extension == "toml") => VerificationProfile::from_toml(&read_file(path)?)?,
+            Some(path) => VerificationProfile::from_json(&read_file(path)?)?,
+            None => VerificationProfile::default()
+        };
+        let timeouts = &mut profile.timeouts;
+        timeouts.dns = self.timeouts.dns.or(timeouts.dns);
+        timeouts.smtp = self.timeouts.smtp.or(timeouts.smtp);
+        timeouts.breach = self.timeouts.breach.or(timeouts.breach);
+        timeouts.gravatar = self.timeouts.gravatar.or(timeouts.gravatar);
+        timeouts.rdap = self.timeouts.rdap.or(timeouts.rdap);
+        timeouts.ct_log = self.timeouts.ct_log.or(timeouts.ct_log);
+        Ok(profile)
+    }
+
+    /// Reads the configured list files
+    pub fn domain_lists(&self) -> Result<DomainLists, EmailParseError> {
+        let read = |path: &Option<PathBuf>| -> Result<Vec<String>, EmailParseError> {
+            let Some(path) = path else {
+                return Ok(Vec::new());
+            };
+            Ok(read_file(path)?
+                .lines()
+                .map(|line| line.split('#').next().unwrap_or_default().trim())
+                .filter(|domain| !domain.is_empty())
+                .map(str::to_string)
+                .collect())
+        };
+        Ok(DomainLists {
+            disposable: read(&self.lists.disposable)?,
+            trusted: read(&self.lists.trusted)?,
+            blocked: read(&self.lists.blocked)?,
+            spamtrap: read(&self.lists.spamtrap)?,
+            expired: read(&self.lists.expired)?,
+            government: read(&self.lists.government)?
+        })
+    }
+
+    /// Reads the configured list files and makes them the loaded lists, as
+    /// `load_domain_lists` would
+    pub fn load_lists(&self) -> Result<(), EmailParseError> {
+        if self.lists != ListPaths::default() {
+            domain_lists::install(self.domain_lists()?);
+        }
+        Ok(())
+    }
+}
+
+/// Contents of `path`, or an "InvalidSettings" error naming it
+fn read_file(path: &Path) -> Result<String, EmailParseError> {
+    fs::read_to_string(path).map_err(|e| invalid_settings("Failed to read settings file", format!("{}: {}", path.display(), e)))
+}
+
+/// "InvalidSettings" error
+fn invalid_settings(message: &str, details: String) -> EmailParseError {
+    EmailParseError {
+        error_type: "InvalidSettings".to_string(),
+        message: message.to_string(),
+        details: Some(details),
+        position: None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    /// Tests that environment variables override the file, prefixed ones last
+    #[test]
+    fn test_settings_precedence() {
+        let mut settings = Settings::from_toml(r#"
+            [dns]
+            nameserver = "192.0.2.53:53"
+
+            [smtp]
+            helo_name = "file.example"
+            mail_from = "probe@file.example"
+
+            [timeouts]
+            dns_ms = 1000
+            smtp_ms = 5000
+        "#).unwrap();
+        settings.apply_env(vars(&[
+            ("SMTP_HELO_NAME", "legacy.example"),
+            ("TS_EMAIL_SMTP_HELO_NAME", "env.example"),
+            ("DNS_TIMEOUT_MS", "1500"),
+            ("UNRELATED", "1")
+        ])).unwrap();
+
+        assert_eq!(settings.dns.nameserver, Some("192.0.2.53:53".parse().unwrap()));
+        assert_eq!(settings.smtp_identity(), Some(("env.example".to_string(), "probe@file.example".to_string())));
+        assert_eq!(settings.timeouts.dns, Some(Duration::from_millis(1500)));
+        assert_eq!(settings.timeouts.smtp, Some(Duration::from_secs(5)));
+
+        let error = settings.apply_env(vars(&[("TS_EMAIL_DNS_NAMESERVER", "not an address")])).unwrap_err();
+        assert_eq!(error.error_type, "InvalidSettings");
+    }
+
+    /// Tests reading list files and overlaying timeouts on the profile
+    #[test]
+    fn test_settings_lists_and_profile() {
+        let dir = std::env::temp_dir().join(format!("ts-email-settings-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("blocked.txt"), "# competitors\ncompetitor.example\n\nspam.example  # added 2026-01\n").unwrap();
+        fs::write(dir.join("profile.json"), r#"{"stages": {"dns": true}, "timeouts": {"dns_ms": 3000, "smtp_ms": 9000}}"#).unwrap();
+
+        let mut settings = Settings::default();
+        settings.apply_env(vars(&[
+            ("TS_EMAIL_BLOCKED_LIST", dir.join("blocked.txt").to_str().unwrap()),
+            ("TS_EMAIL_PROFILE", dir.join("profile.json").to_str().unwrap()),
+            ("TS_EMAIL_DNS_TIMEOUT_MS", "1000")
+        ])).unwrap();
+
+        let lists = settings.domain_lists().unwrap();
+        assert_eq!(lists.blocked, vec!["competitor.example", "spam.example"]);
+        assert!(lists.disposable.is_empty());
+
+        let profile = settings.profile().unwrap();
+        assert!(profile.stages.dns);
+        assert_eq!(profile.timeouts.dns, Some(Duration::from_secs(1)));
+        assert_eq!(profile.timeouts.smtp, Some(Duration::from_secs(9)));
+
+        settings.lists.trusted = Some(dir.join("missing.txt"));
+        assert_eq!(settings.domain_lists().unwrap_err().error_type, "InvalidSettings");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,77 @@
+use serde::{Serialize, Deserialize};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod scheduler;
+#[cfg(all(feature = "tls", not(target_arch = "wasm32")))]
+pub(crate) mod tls;
+#[cfg(not(target_arch = "wasm32"))]
+mod verifier;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use scheduler::{ProbeLimits, ProbeScheduler};
+#[cfg(not(target_arch = "wasm32"))]
+pub use verifier::{RetryPolicy, SmtpConnector, SmtpStream, SmtpVerifier, TcpConnector};
+#[cfg(test)]
+pub(crate) use verifier::tests as verifier_tests;
+
+/// Outcome of probing a mailbox over SMTP
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SmtpCheck {
+    /// MX host that answered the probe
+    pub mx_host: Option<String>,
+    /// Whether the server accepted the recipient (None if inconclusive)
+    pub is_deliverable: Option<bool>,
+    /// Whether the server also accepts a random, nonexistent mailbox at the
+    /// domain; an accepted recipient on a catch-all domain carries little signal
+    pub is_catch_all: Option<bool>,
+    /// SMTP reply code to `RCPT TO` for the probed address
+    pub response_code: Option<u16>,
+    /// SMTP reply text to `RCPT TO` for the probed address
+    pub response_message: Option<String>,
+    /// Whether the server temporarily deferred the recipient in a way that
+    /// looks like greylisting (450/451 or an explicit greylisting message)
+    pub is_greylisted: Option<bool>,
+    /// Suggested number of seconds to wait before probing again, set when the
+    /// final attempt still ended in a temporary failure
+    pub retry_after: Option<u64>,
+    /// Whether the MX advertised STARTTLS in its EHLO reply
+    pub supports_starttls: Option<bool>,
+    /// Whether the MX presented a certificate that chains to a public root and
+    /// matches its host name (None unless STARTTLS was negotiated, which needs
+    /// the `tls` feature)
+    pub tls_certificate_valid: Option<bool>,
+    /// TLS version negotiated through STARTTLS (e.g. "TLSv1.3")
+    pub tls_version: Option<String>,
+    /// Failure that prevented the probe from completing
+    pub error: Option<SmtpError>
+}
+
+/// Error structure for SMTP probe failures
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SmtpError {
+    /// Type of error that occurred (e.g., "ConnectionFailed", "ProtocolError")
+    pub error_type: String,
+    /// Human-readable error message
+    pub message: String,
+    /// Additional error details if available
+    pub details: Option<String>
+}
+
+impl SmtpError {
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub(crate) fn new(error_type: &str, message: &str, details: Option<String>) -> Self {
+        SmtpError {
+            error_type: error_type.to_string(),
+            message: message.to_string(),
+            details
+        }
+    }
+}
+
+impl std::fmt::Display for SmtpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}", self.error_type, self.message)
+    }
+}
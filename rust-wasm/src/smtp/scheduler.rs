@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use super::SmtpError;
+
+/// How often a waiting probe re-checks its deadline and cancellation
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Limits applied by a `ProbeScheduler`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProbeLimits {
+    /// Most simultaneous connections to any one MX host (0 is treated as 1)
+    pub max_connections_per_mx: usize,
+    /// Least time between the starts of two probes for the same domain
+    pub min_interval_per_domain: Duration
+}
+
+impl Default for ProbeLimits {
+    fn default() -> Self {
+        ProbeLimits {
+            max_connections_per_mx: 2,
+            min_interval_per_domain: Duration::from_secs(1)
+        }
+    }
+}
+
+/// Paces SMTP probes so large batches don't get the verifier's IP blocklisted
+///
+/// Each connection first waits until its MX host has a free connection slot
+/// and the domain's previous probe started at least `min_interval_per_domain`
+/// ago. Clones share state, so one scheduler can pace every `SmtpVerifier`
+/// (and thread) in a process.
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::smtp::{ProbeLimits, ProbeScheduler, SmtpVerifier};
+/// let scheduler = ProbeScheduler::new(ProbeLimits { max_connections_per_mx: 1, ..ProbeLimits::default() });
+/// let smtp = SmtpVerifier::new("verifier.example.com", "probe@example.com").with_scheduler(scheduler.clone());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ProbeScheduler {
+    inner: Arc<Inner>
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    limits: ProbeLimits,
+    state: Mutex<State>,
+    condvar: Condvar
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Open connections by lowercased MX host
+    active: HashMap<String, usize>,
+    /// Earliest start of the next probe by lowercased domain
+    next_probe: HashMap<String, Instant>
+}
+
+impl ProbeScheduler {
+    pub fn new(limits: ProbeLimits) -> Self {
+        ProbeScheduler {
+            inner: Arc::new(Inner { limits, ..Inner::default() })
+        }
+    }
+
+    /// Returns the limits being applied
+    pub fn limits(&self) -> ProbeLimits {
+        self.inner.limits
+    }
+
+    /// Waits for a slot to probe `domain` through `mx_host`
+    ///
+    /// `interrupted` is polled while waiting; its error is returned if the
+    /// probe is cancelled or out of time before a slot frees up.
+    pub(crate) fn acquire(&self, mx_host: &str, domain: &str, interrupted: impl Fn() -> Result<(), SmtpError>) -> Result<Permit, SmtpError> {
+        let (mx_host, domain) = (mx_host.to_lowercase(), domain.to_lowercase());
+        let max_connections = self.inner.limits.max_connections_per_mx.max(1);
+
+        let mut state = self.state();
+        loop {
+            interrupted()?;
+
+            let now = Instant::now();
+            state.next_probe.retain(|_, next| *next > now);
+            let ready_at = state.next_probe.get(&domain).copied();
+            let active = state.active.get(&mx_host).copied().unwrap_or_default();
+            if active < max_connections && ready_at.is_none() {
+                *state.active.entry(mx_host.clone()).or_default() += 1;
+                if let Some(next) = now.checked_add(self.inner.limits.min_interval_per_domain) {
+                    state.next_probe.insert(domain, next);
+                }
+                return Ok(Permit { scheduler: self.clone(), mx_host });
+            }
+
+            let wait = ready_at.map_or(POLL_INTERVAL, |next| (next - now).min(POLL_INTERVAL));
+            state = self.inner.condvar.wait_timeout(state, wait).unwrap_or_else(|e| e.into_inner()).0;
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.inner.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A connection slot, released when dropped
+pub(crate) struct Permit {
+    scheduler: ProbeScheduler,
+    mx_host: String
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let mut state = self.scheduler.state();
+        if let Some(active) = state.active.get_mut(&self.mx_host) {
+            *active -= 1;
+            if *active == 0 {
+                state.active.remove(&self.mx_host);
+            }
+        }
+        drop(state);
+        self.scheduler.inner.condvar.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timeout_after(duration: Duration) -> impl Fn() -> Result<(), SmtpError> {
+        let deadline = Instant::now() + duration;
+        move || {
+            if Instant::now() >= deadline {
+                return Err(SmtpError::new("Timeout", "SMTP stage timed out", None));
+            }
+            Ok(())
+        }
+    }
+
+    /// Tests that probes to one domain are spaced and that busy MX hosts
+    /// make later probes wait
+    #[test]
+    fn test_probe_scheduler() {
+        let scheduler = ProbeScheduler::new(ProbeLimits { max_connections_per_mx: 1, min_interval_per_domain: Duration::from_millis(100) });
+
+        let started = Instant::now();
+        drop(scheduler.acquire("mx.example.com", "example.com", || Ok(())).unwrap());
+        drop(scheduler.acquire("MX.example.com", "other.com", || Ok(())).unwrap());
+        assert!(started.elapsed() < Duration::from_millis(100));
+        drop(scheduler.acquire("mx.example.com", "Example.com", || Ok(())).unwrap());
+        assert!(started.elapsed() >= Duration::from_millis(100));
+
+        let permit = scheduler.acquire("mx.busy.com", "a.com", || Ok(())).unwrap();
+        let error = scheduler.acquire("mx.busy.com", "b.com", timeout_after(Duration::from_millis(20))).err().unwrap();
+        assert_eq!(error.error_type, "Timeout");
+        drop(permit);
+        assert!(scheduler.acquire("mx.busy.com", "b.com", timeout_after(Duration::from_millis(20))).is_ok());
+    }
+}
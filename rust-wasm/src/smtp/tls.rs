@@ -0,0 +1,113 @@
+use std::sync::{Arc, Mutex, OnceLock};
+
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::crypto::{ring, CryptoProvider};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{ClientConfig, ClientConnection, DigitallySignedStruct, ProtocolVersion, RootCertStore, SignatureScheme, StreamOwned};
+
+use super::{SmtpError, SmtpStream};
+
+/// TLS properties of an MX host, learned from a STARTTLS handshake
+pub(crate) struct TlsPosture {
+    /// Whether the certificate chains to a public root and matches the host name
+    pub(crate) certificate_valid: bool,
+    /// Negotiated protocol version (e.g. "TLSv1.3")
+    pub(crate) version: Option<String>
+}
+
+/// Performs the TLS handshake over `stream` after a successful `STARTTLS`
+///
+/// Like an MTA using opportunistic TLS, the handshake proceeds even when the
+/// certificate does not validate; the validation outcome is reported instead.
+pub(crate) fn handshake(stream: Box<dyn SmtpStream>, host: &str) -> Result<(Box<dyn SmtpStream>, TlsPosture), SmtpError> {
+    let tls_error = |message: &str, details: String| SmtpError::new("TlsError", message, Some(details));
+
+    let server_name = ServerName::try_from(host.to_string()).map_err(|e| tls_error("MX host name is not valid for TLS", e.to_string()))?;
+    let verifier = Arc::new(RecordingVerifier { inner: webpki_verifier()?, valid: Mutex::new(None) });
+    let config = ClientConfig::builder_with_provider(provider())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| tls_error("Failed to configure TLS", e.to_string()))?
+        .dangerous()
+        .with_custom_certificate_verifier(verifier.clone())
+        .with_no_client_auth();
+
+    let connection = ClientConnection::new(Arc::new(config), server_name).map_err(|e| tls_error("Failed to configure TLS", e.to_string()))?;
+    let mut stream = StreamOwned::new(connection, stream);
+    while stream.conn.is_handshaking() {
+        stream.conn.complete_io(&mut stream.sock).map_err(|e| tls_error("TLS handshake failed", e.to_string()))?;
+    }
+
+    let posture = TlsPosture {
+        certificate_valid: verifier.valid.lock().unwrap_or_else(|e| e.into_inner()).unwrap_or(false),
+        version: stream.conn.protocol_version().map(|version| match version {
+            ProtocolVersion::TLSv1_2 => "TLSv1.2".to_string(),
+            ProtocolVersion::TLSv1_3 => "TLSv1.3".to_string(),
+            other => format!("{:?}", other)
+        })
+    };
+    Ok((Box::new(stream), posture))
+}
+
+pub(crate) fn provider() -> Arc<CryptoProvider> {
+    static PROVIDER: OnceLock<Arc<CryptoProvider>> = OnceLock::new();
+    PROVIDER.get_or_init(|| Arc::new(ring::default_provider())).clone()
+}
+
+/// Verifier against the bundled Mozilla roots, built once
+pub(crate) fn webpki_verifier() -> Result<Arc<WebPkiServerVerifier>, SmtpError> {
+    static VERIFIER: OnceLock<Result<Arc<WebPkiServerVerifier>, String>> = OnceLock::new();
+    VERIFIER
+        .get_or_init(|| {
+            let roots = RootCertStore { roots: webpki_roots::TLS_SERVER_ROOTS.to_vec() };
+            WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider())
+                .build()
+                .map_err(|e| e.to_string())
+        })
+        .clone()
+        .map_err(|e| SmtpError::new("TlsError", "Failed to load TLS root certificates", Some(e)))
+}
+
+/// Records whether the certificate validates but accepts it either way
+#[derive(Debug)]
+struct RecordingVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    valid: Mutex<Option<bool>>
+}
+
+impl ServerCertVerifier for RecordingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let valid = self.inner.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now).is_ok();
+        *self.valid.lock().unwrap_or_else(|e| e.into_inner()) = Some(valid);
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
@@ -0,0 +1,767 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::{ProbeScheduler, SmtpCheck, SmtpError};
+use crate::cancel::CancellationToken;
+use crate::dns::MxRecord;
+use crate::proxy::Proxy;
+
+/// Longest reply line accepted, CRLF included: RFC 5321 §4.5.3.1.5 allows
+/// 512 octets, doubled for servers that stretch it
+const MAX_REPLY_LINE_LENGTH: usize = 1024;
+
+/// Most lines accepted in one multi-line reply
+const MAX_REPLY_LINES: usize = 128;
+
+/// A bidirectional byte stream to an SMTP server
+pub trait SmtpStream: Read + Write {}
+
+impl<T: Read + Write> SmtpStream for T {}
+
+/// Opens connections to mail exchangers
+///
+/// The default `TcpConnector` connects directly; custom connectors can route
+/// through proxies or replay scripted sessions in tests. Connectors are
+/// shared with the worker thread that runs each probe.
+pub trait SmtpConnector: Send + Sync {
+    fn connect(&self, host: &str, port: u16) -> io::Result<Box<dyn SmtpStream>>;
+}
+
+/// Connects to mail exchangers over TCP, directly or through a proxy
+#[derive(Debug, Clone)]
+pub struct TcpConnector {
+    timeout: Duration,
+    proxy: Option<Proxy>
+}
+
+impl TcpConnector {
+    /// Creates a connector whose connect, read and write operations time out after `timeout`
+    pub fn new(timeout: Duration) -> Self {
+        TcpConnector { timeout, proxy: None }
+    }
+
+    /// Tunnels every connection through `proxy`, which also resolves the MX host names
+    pub fn with_proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+}
+
+impl Default for TcpConnector {
+    fn default() -> Self {
+        TcpConnector::new(Duration::from_secs(10))
+    }
+}
+
+impl SmtpConnector for TcpConnector {
+    fn connect(&self, host: &str, port: u16) -> io::Result<Box<dyn SmtpStream>> {
+        if let Some(proxy) = &self.proxy {
+            return Ok(Box::new(proxy.connect(host, port, self.timeout)?));
+        }
+
+        let mut last_error = io::Error::new(io::ErrorKind::NotFound, format!("No addresses found for {}", host));
+        for address in (host, port).to_socket_addrs()? {
+            match TcpStream::connect_timeout(&address, self.timeout) {
+                Ok(stream) => {
+                    stream.set_read_timeout(Some(self.timeout))?;
+                    stream.set_write_timeout(Some(self.timeout))?;
+                    return Ok(Box::new(stream));
+                }
+                Err(e) => last_error = e
+            }
+        }
+        Err(last_error)
+    }
+}
+
+/// Retry behavior for temporary (4xx) `RCPT TO` failures such as greylisting
+///
+/// Attempt `n` waits `initial_delay * backoff_factor^(n-1)` (capped at
+/// `max_delay`) before reconnecting, unless the server's reply names its own
+/// delay. The default makes a single attempt and only reports `retry_after`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub initial_delay: Duration,
+    /// Multiplier applied to the delay after each retry
+    pub backoff_factor: f64,
+    /// Upper bound for any single delay
+    pub max_delay: Duration
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            initial_delay: Duration::from_secs(60),
+            backoff_factor: 2.0,
+            max_delay: Duration::from_secs(600)
+        }
+    }
+}
+
+/// Checks whether a mailbox exists by starting an SMTP transaction with the
+/// domain's MX and stopping after `RCPT TO`
+///
+/// No message is ever sent. When catch-all detection is enabled and the
+/// recipient is accepted, a second `RCPT TO` with a random local part tells
+/// whether the server accepts every address at the domain. Temporary failures
+/// are retried according to the configured `RetryPolicy`.
+///
+/// # Examples
+/// ```no_run
+/// # use email_validator_wasm::dns::MxRecord;
+/// # use email_validator_wasm::smtp::SmtpVerifier;
+/// # let mx_records = [MxRecord { preference: 10, exchange: "mx.example.com".to_string() }];
+/// let smtp = SmtpVerifier::new("verifier.example.com", "probe@example.com");
+/// let check = smtp.check("user@example.com", &mx_records);
+/// println!("{:?} {:?}", check.is_deliverable, check.is_catch_all);
+/// ```
+pub struct SmtpVerifier {
+    connector: Arc<dyn SmtpConnector>,
+    helo_name: String,
+    mail_from: String,
+    port: u16,
+    detect_catch_all: bool,
+    retry: RetryPolicy,
+    scheduler: Option<ProbeScheduler>,
+    #[cfg(feature = "tls")]
+    starttls: bool
+}
+
+impl SmtpVerifier {
+    /// Creates a verifier announcing itself as `helo_name` and using
+    /// `mail_from` as the envelope sender
+    pub fn new(helo_name: &str, mail_from: &str) -> Self {
+        SmtpVerifier {
+            connector: Arc::new(TcpConnector::default()),
+            helo_name: helo_name.to_string(),
+            mail_from: mail_from.to_string(),
+            port: 25,
+            detect_catch_all: true,
+            retry: RetryPolicy::default(),
+            scheduler: None,
+            #[cfg(feature = "tls")]
+            starttls: true
+        }
+    }
+
+    /// Replaces the connector used to reach mail exchangers
+    pub fn with_connector(mut self, connector: impl SmtpConnector + 'static) -> Self {
+        self.connector = Arc::new(connector);
+        self
+    }
+
+    /// Sets the SMTP port (default 25)
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Enables or disables the catch-all probe (enabled by default)
+    pub fn with_catch_all_detection(mut self, enabled: bool) -> Self {
+        self.detect_catch_all = enabled;
+        self
+    }
+
+    /// Sets the retry behavior for temporary failures
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Enables or disables STARTTLS negotiation when the MX offers it (enabled
+    /// by default); with it the TLS version and certificate validity are reported
+    #[cfg(feature = "tls")]
+    pub fn with_starttls(mut self, enabled: bool) -> Self {
+        self.starttls = enabled;
+        self
+    }
+
+    /// Paces connections through `scheduler`, which may be shared with other
+    /// verifiers (unpaced by default)
+    pub fn with_scheduler(mut self, scheduler: ProbeScheduler) -> Self {
+        self.scheduler = Some(scheduler);
+        self
+    }
+
+    /// Probes `email` against the given MX records, trying hosts in preference
+    /// order until one accepts a connection, and retrying temporary failures
+    pub fn check(&self, email: &str, mx_records: &[MxRecord]) -> SmtpCheck {
+        self.check_bounded(email, mx_records, None, None)
+    }
+
+    /// Like `check`, but gives up with a "Timeout" error once `timeout` has
+    /// elapsed and with a "Cancelled" error once `cancel` is cancelled
+    ///
+    /// Both are checked between SMTP commands and interrupt retry delays; a
+    /// single blocked read is bounded by the connector's own timeout. The
+    /// verification pipeline runs this on a worker thread and stops waiting
+    /// at the stage timeout.
+    pub(crate) fn check_bounded(&self, email: &str, mx_records: &[MxRecord], timeout: Option<Duration>, cancel: Option<&CancellationToken>) -> SmtpCheck {
+        let limits = Limits {
+            deadline: timeout.and_then(|timeout| Instant::now().checked_add(timeout)),
+            cancel
+        };
+        let mut delay = self.retry.initial_delay;
+        let mut greylisted = false;
+        let mut attempt = 1;
+
+        loop {
+            let mut check = self.check_once(email, mx_records, limits);
+            let (code, message) = match (check.response_code, &check.response_message) {
+                (Some(code), Some(message)) if code / 100 == 4 => (code, message.clone()),
+                _ => {
+                    if greylisted {
+                        check.is_greylisted = Some(true);
+                    }
+                    return check;
+                }
+            };
+
+            greylisted |= is_greylisting(code, &message);
+            check.is_greylisted = Some(greylisted);
+
+            let wait = parse_retry_after(&message).unwrap_or(delay).min(self.retry.max_delay);
+            if attempt >= self.retry.max_attempts {
+                check.retry_after = Some(wait.as_secs());
+                return check;
+            }
+
+            if !limits.sleep(wait) {
+                check.retry_after = Some(wait.as_secs());
+                return check;
+            }
+            delay = delay.mul_f64(self.retry.backoff_factor.max(1.0));
+            attempt += 1;
+        }
+    }
+
+    fn check_once(&self, email: &str, mx_records: &[MxRecord], limits: Limits) -> SmtpCheck {
+        let mut hosts: Vec<&MxRecord> = mx_records.iter().filter(|mx| !mx.exchange.is_empty()).collect();
+        hosts.sort_by_key(|mx| mx.preference);
+
+        let mut last_error = SmtpError::new("NoMxHost", "No MX host to connect to", None);
+        for mx in hosts {
+            let permit = match &self.scheduler {
+                Some(scheduler) => scheduler.acquire(&mx.exchange, email.rsplit_once('@').map_or("", |(_, domain)| domain), || limits.check()).map(Some),
+                None => limits.check().map(|_| None)
+            };
+            let _permit = match permit {
+                Ok(permit) => permit,
+                Err(e) => {
+                    last_error = e;
+                    break;
+                }
+            };
+            let stream = match self.connector.connect(&mx.exchange, self.port) {
+                Ok(stream) => stream,
+                Err(e) => {
+                    last_error = SmtpError::new("ConnectionFailed", "Failed to connect to MX host", Some(format!("{}: {}", mx.exchange, e)));
+                    continue;
+                }
+            };
+
+            let mut check = SmtpCheck {
+                mx_host: Some(mx.exchange.clone()),
+                ..SmtpCheck::default()
+            };
+            if let Err(e) = self.probe(&mut SmtpSession::new(stream, limits), &mx.exchange, email, &mut check, true) {
+                check.error = Some(e);
+            }
+
+            // A failed handshake leaves the connection unusable; probe again in plaintext
+            #[cfg(feature = "tls")]
+            if check.error.as_ref().is_some_and(|e| e.error_type == "TlsError") {
+                if let Ok(stream) = self.connector.connect(&mx.exchange, self.port) {
+                    let mut plaintext = SmtpCheck {
+                        mx_host: Some(mx.exchange.clone()),
+                        tls_certificate_valid: check.tls_certificate_valid,
+                        ..SmtpCheck::default()
+                    };
+                    if let Err(e) = self.probe(&mut SmtpSession::new(stream, limits), &mx.exchange, email, &mut plaintext, false) {
+                        plaintext.error = Some(e);
+                    }
+                    check = plaintext;
+                }
+            }
+            return check;
+        }
+
+        SmtpCheck {
+            error: Some(last_error),
+            ..SmtpCheck::default()
+        }
+    }
+
+    fn probe(&self, session: &mut SmtpSession, mx_host: &str, email: &str, check: &mut SmtpCheck, use_tls: bool) -> Result<(), SmtpError> {
+        session.expect_reply(2)?;
+
+        let ehlo = session.command(&format!("EHLO {}", self.helo_name))?;
+        if ehlo.code / 100 != 2 {
+            session.command(&format!("HELO {}", self.helo_name))?.expect(2)?;
+        }
+        check.supports_starttls = Some(ehlo.code / 100 == 2 && has_extension(&ehlo.message, "STARTTLS"));
+
+        #[cfg(feature = "tls")]
+        if self.starttls && use_tls && check.supports_starttls == Some(true) {
+            session.command("STARTTLS")?.expect(2)?;
+            let posture = session.start_tls(mx_host)?;
+            check.tls_certificate_valid = Some(posture.certificate_valid);
+            check.tls_version = posture.version;
+            // The pre-TLS EHLO is discarded (RFC 3207 section 4.2)
+            session.command(&format!("EHLO {}", self.helo_name))?.expect(2)?;
+        }
+        #[cfg(not(feature = "tls"))]
+        let _ = (mx_host, use_tls);
+
+        session.command(&format!("MAIL FROM:<{}>", self.mail_from))?.expect(2)?;
+
+        let rcpt = session.command(&format!("RCPT TO:<{}>", email))?;
+        check.response_code = Some(rcpt.code);
+        check.response_message = Some(rcpt.message.clone());
+        check.is_deliverable = match rcpt.code / 100 {
+            2 => Some(true),
+            5 => Some(false),
+            _ => None
+        };
+
+        if self.detect_catch_all && check.is_deliverable == Some(true) {
+            if let Some((_, domain)) = email.rsplit_once('@') {
+                let probe = session.command(&format!("RCPT TO:<{}@{}>", random_local_part(), domain))?;
+                check.is_catch_all = match probe.code / 100 {
+                    2 => Some(true),
+                    5 => Some(false),
+                    _ => None
+                };
+            }
+        }
+
+        // The verdict is already known; a failed QUIT does not change it
+        let _ = session.command("QUIT");
+        Ok(())
+    }
+}
+
+/// A complete (possibly multi-line) SMTP reply
+#[derive(Debug)]
+struct Reply {
+    code: u16,
+    message: String
+}
+
+impl Reply {
+    fn expect(self, class: u16) -> Result<Reply, SmtpError> {
+        if self.code / 100 == class {
+            Ok(self)
+        } else {
+            Err(SmtpError::new("UnexpectedReply", "MX host rejected the SMTP transaction", Some(format!("{} {}", self.code, self.message))))
+        }
+    }
+}
+
+/// Overall deadline and cancellation for one probe
+#[derive(Clone, Copy)]
+struct Limits<'a> {
+    deadline: Option<Instant>,
+    cancel: Option<&'a CancellationToken>
+}
+
+impl Limits<'_> {
+    fn check(&self) -> Result<(), SmtpError> {
+        if self.cancel.is_some_and(CancellationToken::is_cancelled) {
+            return Err(SmtpError::new("Cancelled", "SMTP probe was cancelled", None));
+        }
+        if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return Err(SmtpError::new("Timeout", "SMTP stage timed out", None));
+        }
+        Ok(())
+    }
+
+    /// Waits `duration` before a retry, returning false instead if the wait
+    /// would outlast the deadline or is cancelled
+    fn sleep(&self, duration: Duration) -> bool {
+        if self.deadline.is_some_and(|deadline| Instant::now() + duration > deadline) {
+            return false;
+        }
+        match self.cancel {
+            Some(cancel) => !cancel.wait_timeout(duration),
+            None => {
+                std::thread::sleep(duration);
+                true
+            }
+        }
+    }
+}
+
+struct SmtpSession<'a> {
+    stream: BufReader<Box<dyn SmtpStream>>,
+    limits: Limits<'a>
+}
+
+impl<'a> SmtpSession<'a> {
+    fn new(stream: Box<dyn SmtpStream>, limits: Limits<'a>) -> Self {
+        SmtpSession { stream: BufReader::new(stream), limits }
+    }
+
+    fn command(&mut self, command: &str) -> Result<Reply, SmtpError> {
+        self.limits.check()?;
+        let stream = self.stream.get_mut();
+        stream.write_all(command.as_bytes())
+            .and_then(|_| stream.write_all(b"\r\n"))
+            .and_then(|_| stream.flush())
+            .map_err(|e| SmtpError::new("IoError", "Failed to send SMTP command", Some(e.to_string())))?;
+        self.read_reply()
+    }
+
+    /// Replaces the plaintext stream with a TLS session to `host`
+    #[cfg(feature = "tls")]
+    fn start_tls(&mut self, host: &str) -> Result<super::tls::TlsPosture, SmtpError> {
+        let placeholder: Box<dyn SmtpStream> = Box::new(io::Cursor::new(Vec::new()));
+        let stream = std::mem::replace(&mut self.stream, BufReader::new(placeholder)).into_inner();
+        let (stream, posture) = super::tls::handshake(stream, host)?;
+        self.stream = BufReader::new(stream);
+        Ok(posture)
+    }
+
+    fn expect_reply(&mut self, class: u16) -> Result<Reply, SmtpError> {
+        self.limits.check()?;
+        self.read_reply()?.expect(class)
+    }
+
+    /// Reads one reply, failing on a line longer than `MAX_REPLY_LINE_LENGTH`
+    /// or a reply of more than `MAX_REPLY_LINES` lines so a hostile server
+    /// cannot grow it without limit
+    fn read_reply(&mut self) -> Result<Reply, SmtpError> {
+        let mut lines = Vec::new();
+        loop {
+            if lines.len() == MAX_REPLY_LINES {
+                return Err(SmtpError::new("ProtocolError", "SMTP reply has too many lines", Some(format!("More than {} lines", MAX_REPLY_LINES))));
+            }
+            let mut bytes = Vec::new();
+            let len = self.stream.by_ref().take(MAX_REPLY_LINE_LENGTH as u64 + 1).read_until(b'\n', &mut bytes)
+                .map_err(|e| SmtpError::new("IoError", "Failed to read SMTP reply", Some(e.to_string())))?;
+            if len == 0 {
+                return Err(SmtpError::new("ProtocolError", "MX host closed the connection", None));
+            }
+            if len > MAX_REPLY_LINE_LENGTH {
+                return Err(SmtpError::new("ProtocolError", "SMTP reply line is too long", Some(format!("More than {} bytes", MAX_REPLY_LINE_LENGTH))));
+            }
+
+            let line = String::from_utf8_lossy(&bytes);
+            let line = line.trim_end_matches(['\r', '\n']);
+            let code = line.get(..3).and_then(|code| code.parse::<u16>().ok())
+                .ok_or_else(|| SmtpError::new("ProtocolError", "Malformed SMTP reply", Some(line.to_string())))?;
+            lines.push(line.get(4..).unwrap_or_default().to_string());
+
+            // "250-" continues a multi-line reply, "250 " ends it
+            if line.as_bytes().get(3) != Some(&b'-') {
+                return Ok(Reply { code, message: lines.join("\n") });
+            }
+        }
+    }
+}
+
+/// Whether an EHLO reply advertises `keyword` (the first line is the greeting)
+fn has_extension(ehlo: &str, keyword: &str) -> bool {
+    ehlo.lines().skip(1).any(|line| line.split_whitespace().next().is_some_and(|extension| extension.eq_ignore_ascii_case(keyword)))
+}
+
+/// Whether a temporary failure looks like greylisting rather than, say, a full mailbox
+fn is_greylisting(code: u16, message: &str) -> bool {
+    let message = message.to_lowercase();
+    matches!(code, 450 | 451) || message.contains("greylist") || message.contains("graylist")
+}
+
+/// Extracts a delay such as "try again in 300 seconds" or "retry in 5 minutes"
+fn parse_retry_after(message: &str) -> Option<Duration> {
+    let words: Vec<String> = message
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '.')
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect();
+
+    words.windows(2).find_map(|pair| {
+        let amount: u64 = pair[0].parse().ok()?;
+        if pair[1].starts_with("sec") || pair[1] == "s" {
+            Some(Duration::from_secs(amount))
+        } else if pair[1].starts_with("min") {
+            Some(Duration::from_secs(amount.saturating_mul(60)))
+        } else {
+            None
+        }
+    })
+}
+
+/// Generates a local part that is vanishingly unlikely to exist
+fn random_local_part() -> String {
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(crate::time::unix_time_ms() as u128);
+    format!("catchall-probe-{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::sync::Mutex;
+
+    /// Stream that replays canned server replies and records client commands
+    pub(crate) struct ScriptedStream {
+        replies: Cursor<Vec<u8>>,
+        sent: Arc<Mutex<Vec<u8>>>
+    }
+
+    impl Read for ScriptedStream {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.replies.read(buf)
+        }
+    }
+
+    impl Write for ScriptedStream {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.sent.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Connector whose every connection replays the same script
+    #[derive(Clone, Default)]
+    pub(crate) struct ScriptedConnector {
+        pub(crate) script: String,
+        pub(crate) sent: Arc<Mutex<Vec<u8>>>
+    }
+
+    impl ScriptedConnector {
+        pub(crate) fn new(replies: &[&str]) -> Self {
+            ScriptedConnector {
+                script: replies.iter().map(|reply| format!("{}\r\n", reply)).collect(),
+                sent: Arc::default()
+            }
+        }
+
+        pub(crate) fn sent(&self) -> String {
+            String::from_utf8(self.sent.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    impl SmtpConnector for ScriptedConnector {
+        fn connect(&self, _host: &str, _port: u16) -> io::Result<Box<dyn SmtpStream>> {
+            Ok(Box::new(ScriptedStream {
+                replies: Cursor::new(self.script.clone().into_bytes()),
+                sent: self.sent.clone()
+            }))
+        }
+    }
+
+    fn mx(preference: u16, exchange: &str) -> MxRecord {
+        MxRecord { preference, exchange: exchange.to_string() }
+    }
+
+    /// Tests a strict server: the mailbox is accepted and the random probe rejected
+    #[test]
+    fn test_deliverable_strict_server() {
+        let connector = ScriptedConnector::new(&[
+            "220 mx.example.com ESMTP",
+            "250-mx.example.com",
+            "250 PIPELINING",
+            "250 OK",
+            "250 Accepted",
+            "550 5.1.1 No such user",
+            "221 Bye"
+        ]);
+        let smtp = SmtpVerifier::new("verifier.test", "probe@verifier.test").with_connector(connector.clone());
+
+        let check = smtp.check("user@example.com", &[mx(20, "backup.example.com"), mx(10, "mx.example.com")]);
+        assert_eq!(check.error, None);
+        assert_eq!(check.mx_host, Some("mx.example.com".to_string()));
+        assert_eq!(check.is_deliverable, Some(true));
+        assert_eq!(check.is_catch_all, Some(false));
+        assert_eq!(check.response_code, Some(250));
+        assert_eq!(check.supports_starttls, Some(false));
+
+        let sent = connector.sent();
+        assert!(sent.starts_with("EHLO verifier.test\r\nMAIL FROM:<probe@verifier.test>\r\nRCPT TO:<user@example.com>\r\n"));
+        assert!(sent.contains("RCPT TO:<catchall-probe-"));
+        assert!(sent.ends_with("QUIT\r\n"));
+    }
+
+    /// Tests catch-all detection and rejected mailboxes
+    #[test]
+    fn test_catch_all_and_rejection() {
+        let catch_all = ScriptedConnector::new(&["220 ready", "250 hello", "250 OK", "250 OK", "250 OK", "221 Bye"]);
+        let smtp = SmtpVerifier::new("verifier.test", "probe@verifier.test").with_connector(catch_all);
+        let check = smtp.check("anyone@example.com", &[mx(10, "mx.example.com")]);
+        assert_eq!(check.is_deliverable, Some(true));
+        assert_eq!(check.is_catch_all, Some(true));
+
+        let rejecting = ScriptedConnector::new(&["220 ready", "250 hello", "250 OK", "550 5.1.1 User unknown", "221 Bye"]);
+        let smtp = SmtpVerifier::new("verifier.test", "probe@verifier.test").with_connector(rejecting.clone());
+        let check = smtp.check("nobody@example.com", &[mx(10, "mx.example.com")]);
+        assert_eq!(check.is_deliverable, Some(false));
+        assert_eq!(check.is_catch_all, None);
+        assert_eq!(check.response_message, Some("5.1.1 User unknown".to_string()));
+        assert!(!rejecting.sent().contains("catchall-probe"));
+
+        let check = smtp.check("nobody@example.com", &[]);
+        assert_eq!(check.error.unwrap().error_type, "NoMxHost");
+    }
+
+    /// Tests that greylisting is flagged, retried and reported with a retry hint
+    #[test]
+    fn test_greylisting_retry() {
+        let greylisting = ScriptedConnector::new(&[
+            "220 ready", "250 hello", "250 OK",
+            "451 4.7.1 Greylisted, please try again in 300 seconds",
+            "221 Bye"
+        ]);
+        let retry = RetryPolicy { max_attempts: 3, initial_delay: Duration::ZERO, backoff_factor: 2.0, max_delay: Duration::ZERO };
+        let smtp = SmtpVerifier::new("verifier.test", "probe@verifier.test")
+            .with_connector(greylisting.clone())
+            .with_retry_policy(retry);
+
+        let check = smtp.check("user@example.com", &[mx(10, "mx.example.com")]);
+        assert_eq!(check.is_deliverable, None);
+        assert_eq!(check.is_greylisted, Some(true));
+        assert_eq!(check.response_code, Some(451));
+        assert_eq!(check.retry_after, Some(0)); // Capped by max_delay
+        assert_eq!(greylisting.sent().matches("RCPT TO:<user@example.com>").count(), 3);
+
+        // Without retries the server's own delay is reported
+        let smtp = SmtpVerifier::new("verifier.test", "probe@verifier.test").with_connector(greylisting);
+        let check = smtp.check("user@example.com", &[mx(10, "mx.example.com")]);
+        assert_eq!(check.retry_after, Some(300));
+
+        assert_eq!(parse_retry_after("retry in 5 minutes"), Some(Duration::from_secs(300)));
+        assert_eq!(parse_retry_after("try again in 999999999999999999 minutes"), Some(Duration::from_secs(u64::MAX)));
+
+        // Permanent rejections are never retried or flagged
+        let rejecting = ScriptedConnector::new(&["220 ready", "250 hello", "250 OK", "550 User unknown", "221 Bye"]);
+        let smtp = SmtpVerifier::new("verifier.test", "probe@verifier.test")
+            .with_connector(rejecting.clone())
+            .with_retry_policy(RetryPolicy { max_attempts: 3, ..RetryPolicy::default() });
+        let check = smtp.check("user@example.com", &[mx(10, "mx.example.com")]);
+        assert_eq!(check.is_deliverable, Some(false));
+        assert_eq!(check.is_greylisted, None);
+        assert_eq!(check.retry_after, None);
+        assert_eq!(rejecting.sent().matches("RCPT TO").count(), 1);
+    }
+
+    /// Tests that oversized reply lines and endless continuations are cut off
+    #[test]
+    fn test_reply_limits() {
+        let check = |connector: ScriptedConnector| {
+            let smtp = SmtpVerifier::new("verifier.test", "probe@verifier.test").with_connector(connector);
+            smtp.check("user@example.com", &[mx(10, "mx.example.com")]).error.unwrap()
+        };
+
+        let long_line = format!("220 {}", "x".repeat(MAX_REPLY_LINE_LENGTH));
+        let error = check(ScriptedConnector::new(&[&long_line]));
+        assert_eq!((error.error_type.as_str(), error.message.as_str()), ("ProtocolError", "SMTP reply line is too long"));
+
+        let mut endless = vec!["220 ready"];
+        endless.extend(["250-PIPELINING"; MAX_REPLY_LINES + 1]);
+        let error = check(ScriptedConnector::new(&endless));
+        assert_eq!((error.error_type.as_str(), error.message.as_str()), ("ProtocolError", "SMTP reply has too many lines"));
+
+        let mut longest = vec!["220 ready".to_string()];
+        longest.extend(vec!["250-PIPELINING".to_string(); MAX_REPLY_LINES - 1]);
+        longest.extend([format!("250 {}", "x".repeat(MAX_REPLY_LINE_LENGTH - 6)), "250 OK".into(), "250 Accepted".into(), "550 No".into(), "221 Bye".into()]);
+        let smtp = SmtpVerifier::new("verifier.test", "probe@verifier.test")
+            .with_connector(ScriptedConnector::new(&longest.iter().map(String::as_str).collect::<Vec<_>>()));
+        assert_eq!(smtp.check("user@example.com", &[mx(10, "mx.example.com")]).is_deliverable, Some(true));
+    }
+
+    /// Tests that a deadline cuts retry delays short and that a cancelled
+    /// probe sends nothing
+    #[test]
+    fn test_bounded_check() {
+        let greylisting = ScriptedConnector::new(&["220 ready", "250 hello", "250 OK", "451 4.7.1 Greylisted, try again in 300 seconds"]);
+        let smtp = SmtpVerifier::new("verifier.test", "probe@verifier.test")
+            .with_connector(greylisting.clone())
+            .with_retry_policy(RetryPolicy { max_attempts: 3, ..RetryPolicy::default() });
+        let check = smtp.check_bounded("user@example.com", &[mx(10, "mx.example.com")], Some(Duration::from_secs(5)), None);
+        assert_eq!((check.is_greylisted, check.retry_after), (Some(true), Some(300)));
+        assert_eq!(greylisting.sent().matches("RCPT TO").count(), 1);
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let connector = ScriptedConnector::new(&["220 ready"]);
+        let smtp = SmtpVerifier::new("verifier.test", "probe@verifier.test").with_connector(connector.clone());
+        let check = smtp.check_bounded("user@example.com", &[mx(10, "mx.example.com")], None, Some(&cancel));
+        assert_eq!(check.error.unwrap().error_type, "Cancelled");
+        assert!(connector.sent().is_empty());
+    }
+
+    /// Connects every MX host to a local test server
+    #[cfg(feature = "tls")]
+    struct LocalConnector(std::net::SocketAddr);
+
+    #[cfg(feature = "tls")]
+    impl SmtpConnector for LocalConnector {
+        fn connect(&self, _host: &str, _port: u16) -> io::Result<Box<dyn SmtpStream>> {
+            Ok(Box::new(TcpStream::connect(self.0)?))
+        }
+    }
+
+    /// Tests STARTTLS negotiation against a server with a self-signed certificate
+    #[cfg(feature = "tls")]
+    #[test]
+    fn test_starttls() {
+        use std::net::TcpListener;
+        use std::sync::Arc;
+        use rustls::pki_types::{PrivateKeyDer, PrivatePkcs8KeyDer};
+
+        let certified = rcgen::generate_simple_self_signed(vec!["mx.example.com".to_string()]).unwrap();
+        let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(certified.signing_key.serialize_der()));
+        let config = rustls::ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_no_client_auth()
+            .with_single_cert(vec![certified.cert.der().clone()], key)
+            .unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            stream.write_all(b"220 mx.example.com ESMTP\r\n").unwrap();
+            reader.read_line(&mut line).unwrap();
+            stream.write_all(b"250-mx.example.com\r\n250 STARTTLS\r\n").unwrap();
+            line.clear();
+            reader.read_line(&mut line).unwrap();
+            assert_eq!(line, "STARTTLS\r\n");
+            stream.write_all(b"220 Ready to start TLS\r\n").unwrap();
+
+            let connection = rustls::ServerConnection::new(Arc::new(config)).unwrap();
+            let mut tls = BufReader::new(rustls::StreamOwned::new(connection, stream));
+            for reply in ["250 mx.example.com", "250 OK", "250 Accepted", "550 No such user", "221 Bye"] {
+                line.clear();
+                tls.read_line(&mut line).unwrap();
+                tls.get_mut().write_all(format!("{}\r\n", reply).as_bytes()).unwrap();
+            }
+        });
+
+        let smtp = SmtpVerifier::new("verifier.test", "probe@verifier.test").with_connector(LocalConnector(address));
+        let check = smtp.check("user@example.com", &[mx(10, "mx.example.com")]);
+        server.join().unwrap();
+
+        assert_eq!(check.error, None);
+        assert_eq!(check.is_deliverable, Some(true));
+        assert_eq!(check.supports_starttls, Some(true));
+        assert_eq!(check.tls_certificate_valid, Some(false));
+        assert_eq!(check.tls_version.as_deref(), Some("TLSv1.3"));
+    }
+}
@@ -0,0 +1,79 @@
+//! Spamtrap heuristics
+//!
+//! Mailbox providers and blocklist operators seed traps to catch senders
+//! with poor list hygiene: role mailboxes nobody has read in years, domains
+//! run purely as traps, and lapsed domains re-registered to collect mail
+//! for their old addresses. A hit can get a sender blocklisted, so list
+//! cleaning should treat these addresses as the costliest to keep.
+
+use serde::{Serialize, Deserialize};
+
+use crate::{domain_lists, DomainLists};
+
+/// Likelihood that an address is a spamtrap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum SpamtrapRisk {
+    Low,
+    /// A long-dormant role mailbox (`postmaster@`, `webmaster@`, ...)
+    Medium,
+    /// A trap-named mailbox, or a domain on a spamtrap or expired-domain list
+    High
+}
+
+impl SpamtrapRisk {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpamtrapRisk::Low => "low",
+            SpamtrapRisk::Medium => "medium",
+            SpamtrapRisk::High => "high"
+        }
+    }
+}
+
+/// Local parts that name a trap outright
+const TRAP_LOCAL_PARTS: [&str; 6] = ["spamtrap", "spam-trap", "spam.trap", "honeypot", "trap", "spamcatcher"];
+
+/// Role mailboxes that exist on most domains but are rarely read, and are
+/// often left in place (or repurposed) as pristine traps
+const DORMANT_ROLE_LOCAL_PARTS: [&str; 14] = [
+    "abuse", "postmaster", "hostmaster", "webmaster", "noc", "spam", "root",
+    "nobody", "usenet", "news", "uucp", "ftp", "www", "mailer-daemon"
+];
+
+/// Spamtrap risk of an address, from its canonical local part (without
+/// subaddress tag) and lowercased domain
+pub(crate) fn spamtrap_risk(canonical_local_part: &str, domain: &str, lists: &DomainLists) -> SpamtrapRisk {
+    let local_part = canonical_local_part.to_lowercase();
+    if TRAP_LOCAL_PARTS.contains(&local_part.as_str()) || domain_lists::is_trap_domain(lists, domain) {
+        SpamtrapRisk::High
+    } else if DORMANT_ROLE_LOCAL_PARTS.contains(&local_part.as_str()) {
+        SpamtrapRisk::Medium
+    } else {
+        SpamtrapRisk::Low
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests trap names, dormant roles and per-call trap lists
+    #[test]
+    fn test_spamtrap_risk() {
+        let none = DomainLists::default();
+        assert_eq!(spamtrap_risk("Honeypot", "example.com", &none), SpamtrapRisk::High);
+        assert_eq!(spamtrap_risk("postmaster", "example.com", &none), SpamtrapRisk::Medium);
+        assert_eq!(spamtrap_risk("jane", "example.com", &none), SpamtrapRisk::Low);
+
+        let lists = DomainLists {
+            spamtrap: vec!["traps.example".to_string()],
+            expired: vec!["Lapsed.example".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(spamtrap_risk("jane", "traps.example", &lists), SpamtrapRisk::High);
+        assert_eq!(spamtrap_risk("jane", "lapsed.example", &lists), SpamtrapRisk::High);
+        assert!(SpamtrapRisk::High > SpamtrapRisk::Medium);
+    }
+}
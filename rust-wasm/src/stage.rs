@@ -0,0 +1,79 @@
+//! Per-stage instrumentation for the verification pipeline
+//!
+//! Each stage reports its start and its outcome to the verifier's
+//! `EventObserver`, if one is attached. With the `tracing` feature it also
+//! runs inside a span carrying `stage`, `domain`, `email`, `outcome` and
+//! `duration_ms`, and emits a "stage finished" event when it completes, so
+//! slow verifications can be traced to a specific DNS or SMTP target. The
+//! address is already redacted when a stage receives it.
+
+use std::future::Future;
+
+#[cfg(feature = "tracing")]
+use tracing::Instrument;
+
+use crate::events::{EventObserver, PipelineEvent};
+
+/// A running pipeline stage
+pub(crate) struct Stage<'a> {
+    name: &'static str,
+    domain: &'a str,
+    email: Option<String>,
+    observer: Option<&'a dyn EventObserver>,
+    started_ms: u64,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span
+}
+
+impl<'a> Stage<'a> {
+    /// Starts `stage` on `domain` (and the redacted `email`, for stages that
+    /// work on the whole address), notifying `observer`
+    pub(crate) fn start(stage: &'static str, domain: &'a str, email: Option<String>, observer: Option<&'a dyn EventObserver>) -> Self {
+        if let Some(observer) = observer {
+            observer.on_event(&PipelineEvent::StageStarted { stage, domain: domain.to_string(), email: email.clone() });
+        }
+        Stage {
+            name: stage,
+            domain,
+            observer,
+            started_ms: crate::time::unix_time_ms(),
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "verification_stage",
+                stage,
+                domain,
+                email = email.as_deref(),
+                outcome = tracing::field::Empty,
+                duration_ms = tracing::field::Empty
+            ),
+            email
+        }
+    }
+
+    /// Runs `future` inside the stage's span
+    pub(crate) async fn run<F: Future>(&self, future: F) -> F::Output {
+        #[cfg(feature = "tracing")]
+        let future = future.instrument(self.span.clone());
+        future.await
+    }
+
+    /// Reports the stage's outcome and duration and closes its span
+    pub(crate) fn finish(self, outcome: &str) {
+        let duration_ms = crate::time::unix_time_ms().saturating_sub(self.started_ms);
+        #[cfg(feature = "tracing")]
+        {
+            self.span.record("outcome", outcome);
+            self.span.record("duration_ms", duration_ms);
+            tracing::info!(parent: &self.span, outcome, duration_ms, "stage finished");
+        }
+        if let Some(observer) = self.observer {
+            observer.on_event(&PipelineEvent::StageFinished {
+                stage: self.name,
+                domain: self.domain.to_string(),
+                email: self.email,
+                outcome: outcome.to_string(),
+                duration_ms
+            });
+        }
+    }
+}
@@ -0,0 +1,358 @@
+//! Persistent verification history in an embedded SQLite database (`storage` feature)
+//!
+//! Every recorded verification keeps its verdict, timestamps and the full
+//! serialized result, so past runs can be queried by domain, verdict or date
+//! and compared ("what changed since last month?").
+
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+
+use rusqlite::types::ToSql;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Serialize, Deserialize};
+
+use crate::time::unix_time_ms;
+use crate::{VerificationResult, Verdict};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS verifications (
+        id INTEGER PRIMARY KEY,
+        email TEXT NOT NULL,
+        domain TEXT,
+        verdict TEXT NOT NULL,
+        verified_at INTEGER NOT NULL,
+        expires_at INTEGER,
+        result TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS verifications_email ON verifications (email, verified_at);
+    CREATE INDEX IF NOT EXISTS verifications_domain ON verifications (domain, verified_at);
+    CREATE INDEX IF NOT EXISTS verifications_verified_at ON verifications (verified_at);
+";
+
+/// Error structure for history store failures
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageError {
+    /// Type of error that occurred (e.g., "DatabaseError", "CorruptRecord")
+    pub error_type: String,
+    /// Human-readable error message
+    pub message: String,
+    /// Additional error details if available
+    pub details: Option<String>
+}
+
+impl StorageError {
+    pub(crate) fn new(error_type: &str, message: &str, details: Option<String>) -> Self {
+        StorageError {
+            error_type: error_type.to_string(),
+            message: message.to_string(),
+            details
+        }
+    }
+}
+
+impl std::fmt::Display for StorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}", self.error_type, self.message)
+    }
+}
+
+fn database_error(e: rusqlite::Error) -> StorageError {
+    StorageError::new("DatabaseError", "History database operation failed", Some(e.to_string()))
+}
+
+/// Filter for `VerificationStore::query`; unset fields match everything
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct HistoryQuery {
+    /// Exact address (case-insensitive)
+    pub email: Option<String>,
+    /// Domain of the address (case-insensitive)
+    pub domain: Option<String>,
+    pub verdict: Option<Verdict>,
+    /// Earliest `verified_at`, inclusive (Unix milliseconds)
+    pub since: Option<u64>,
+    /// Latest `verified_at`, exclusive (Unix milliseconds)
+    pub until: Option<u64>,
+    /// Most entries returned, newest first
+    pub limit: Option<usize>
+}
+
+/// A recorded verification
+#[derive(Debug, Serialize)]
+pub struct HistoryEntry {
+    /// Normalized (trimmed, lowercased) address
+    pub email: String,
+    pub domain: Option<String>,
+    pub verdict: Verdict,
+    /// Unix milliseconds
+    pub verified_at: u64,
+    pub expires_at: Option<u64>,
+    /// The full result as recorded
+    pub result: VerificationResult
+}
+
+/// An address whose verdict differs between two points in time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct VerdictChange {
+    pub email: String,
+    /// Latest verdict before the cut-off
+    pub previous: Verdict,
+    /// Latest verdict since the cut-off
+    pub current: Verdict,
+    /// When the current verdict was recorded (Unix milliseconds)
+    pub verified_at: u64
+}
+
+/// Embedded store of verification history
+///
+/// Safe to share between threads; writes are serialized.
+///
+/// # Examples
+/// ```no_run
+/// # use email_validator_wasm::{StorageError, VerificationResult, VerificationStore};
+/// # fn unix_time_ms() -> u64 { 0 }
+/// # fn example(result: VerificationResult) -> Result<(), StorageError> {
+/// let store = VerificationStore::open("history.db")?;
+/// store.record("user@example.com", &result)?;
+///
+/// let month_ago = unix_time_ms() - 30 * 24 * 60 * 60 * 1000;
+/// for change in store.changes_since(month_ago)? {
+///     println!("{}: {:?} -> {:?}", change.email, change.previous, change.current);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct VerificationStore {
+    connection: Mutex<Connection>
+}
+
+impl VerificationStore {
+    /// Opens (creating if needed) the database at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        Self::with_connection(Connection::open(path).map_err(database_error)?)
+    }
+
+    /// Opens a store that lives only as long as this value
+    pub fn open_in_memory() -> Result<Self, StorageError> {
+        Self::with_connection(Connection::open_in_memory().map_err(database_error)?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, StorageError> {
+        connection.execute_batch(SCHEMA).map_err(database_error)?;
+        Ok(VerificationStore { connection: Mutex::new(connection) })
+    }
+
+    /// Records the verification of `email`
+    ///
+    /// Results without network checks are stamped with the current time.
+    pub fn record(&self, email: &str, result: &VerificationResult) -> Result<(), StorageError> {
+        let json = serde_json::to_string(result).map_err(|e| StorageError::new("SerializationError", "Failed to serialize result", Some(e.to_string())))?;
+        self.lock()
+            .execute(
+                "INSERT INTO verifications (email, domain, verdict, verified_at, expires_at, result) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    normalize(email),
+                    result.email.domain.as_deref().map(str::to_lowercase),
+                    result.verdict().as_str(),
+                    result.verified_at.unwrap_or_else(unix_time_ms) as i64,
+                    result.expires_at.map(|at| at as i64),
+                    json
+                ]
+            )
+            .map_err(database_error)?;
+        Ok(())
+    }
+
+    /// Returns the most recent entry for `email`
+    pub fn latest(&self, email: &str) -> Result<Option<HistoryEntry>, StorageError> {
+        let query = HistoryQuery { email: Some(email.to_string()), limit: Some(1), ..HistoryQuery::default() };
+        Ok(self.query(&query)?.pop())
+    }
+
+    /// Returns the entries matching `query`, newest first
+    pub fn query(&self, query: &HistoryQuery) -> Result<Vec<HistoryEntry>, StorageError> {
+        let mut conditions = Vec::new();
+        let mut values: Vec<Box<dyn ToSql>> = Vec::new();
+        if let Some(email) = &query.email {
+            conditions.push("email = ?");
+            values.push(Box::new(normalize(email)));
+        }
+        if let Some(domain) = &query.domain {
+            conditions.push("domain = ?");
+            values.push(Box::new(domain.trim().to_lowercase()));
+        }
+        if let Some(verdict) = query.verdict {
+            conditions.push("verdict = ?");
+            values.push(Box::new(verdict.as_str()));
+        }
+        if let Some(since) = query.since {
+            conditions.push("verified_at >= ?");
+            values.push(Box::new(since as i64));
+        }
+        if let Some(until) = query.until {
+            conditions.push("verified_at < ?");
+            values.push(Box::new(until as i64));
+        }
+
+        let mut sql = "SELECT email, domain, verdict, verified_at, expires_at, result FROM verifications".to_string();
+        if !conditions.is_empty() {
+            sql += &format!(" WHERE {}", conditions.join(" AND "));
+        }
+        sql += " ORDER BY verified_at DESC, id DESC";
+        if let Some(limit) = query.limit {
+            sql += &format!(" LIMIT {}", limit);
+        }
+
+        let connection = self.lock();
+        let mut statement = connection.prepare(&sql).map_err(database_error)?;
+        let rows = statement
+            .query_map(rusqlite::params_from_iter(values.iter()), |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, i64>(3)?,
+                    row.get::<_, Option<i64>>(4)?,
+                    row.get::<_, String>(5)?
+                ))
+            })
+            .map_err(database_error)?;
+
+        rows.map(|row| {
+            let (email, domain, verdict, verified_at, expires_at, result) = row.map_err(database_error)?;
+            Ok(HistoryEntry {
+                verdict: verdict.parse().map_err(|e| StorageError::new("CorruptRecord", "Stored verdict is not recognized", Some(e)))?,
+                result: serde_json::from_str(&result).map_err(|e| StorageError::new("CorruptRecord", "Stored result could not be read", Some(e.to_string())))?,
+                email,
+                domain,
+                verified_at: verified_at as u64,
+                expires_at: expires_at.map(|at| at as u64)
+            })
+        })
+        .collect()
+    }
+
+    /// Returns the addresses whose latest verdict since `since` (Unix
+    /// milliseconds) differs from their latest verdict before it
+    pub fn changes_since(&self, since: u64) -> Result<Vec<VerdictChange>, StorageError> {
+        // SQLite returns the other columns from the row holding MAX()
+        let sql = "
+            WITH before AS (
+                SELECT email, verdict, MAX(verified_at) FROM verifications WHERE verified_at < ?1 GROUP BY email
+            ), after AS (
+                SELECT email, verdict, MAX(verified_at) AS verified_at FROM verifications WHERE verified_at >= ?1 GROUP BY email
+            )
+            SELECT after.email, before.verdict, after.verdict, after.verified_at
+            FROM after JOIN before ON before.email = after.email
+            WHERE before.verdict != after.verdict
+            ORDER BY after.email";
+
+        let connection = self.lock();
+        let mut statement = connection.prepare(sql).map_err(database_error)?;
+        let rows = statement
+            .query_map([since as i64], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?, row.get::<_, i64>(3)?)))
+            .map_err(database_error)?;
+
+        rows.map(|row| {
+            let (email, previous, current, verified_at) = row.map_err(database_error)?;
+            let verdict = |name: String| name.parse().map_err(|e| StorageError::new("CorruptRecord", "Stored verdict is not recognized", Some(e)));
+            Ok(VerdictChange { email, previous: verdict(previous)?, current: verdict(current)?, verified_at: verified_at as u64 })
+        })
+        .collect()
+    }
+
+    /// Deletes entries verified before `before` (Unix milliseconds), returning how many were removed
+    pub fn prune(&self, before: u64) -> Result<usize, StorageError> {
+        self.lock()
+            .execute("DELETE FROM verifications WHERE verified_at < ?1", [before as i64])
+            .map_err(database_error)
+    }
+
+    /// Number of recorded verifications
+    pub fn len(&self) -> Result<usize, StorageError> {
+        self.lock()
+            .query_row("SELECT COUNT(*) FROM verifications", [], |row| row.get::<_, i64>(0))
+            .optional()
+            .map(|count| count.unwrap_or_default() as usize)
+            .map_err(database_error)
+    }
+
+    pub fn is_empty(&self) -> Result<bool, StorageError> {
+        Ok(self.len()? == 0)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Connection> {
+        self.connection.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn normalize(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::MockResolver;
+    use crate::dns::MxRecord;
+    use crate::test_util::block_on;
+    use crate::Verifier;
+
+    fn verify(email: &str) -> VerificationResult {
+        let resolver = MockResolver::new().with_mx("example.com", vec![MxRecord { preference: 10, exchange: "mx.example.com".to_string() }]);
+        block_on(Verifier::new(resolver).verify(email)).unwrap()
+    }
+
+    /// Tests recording, filtered queries and round-tripping the stored result
+    #[test]
+    fn test_store_query() {
+        let store = VerificationStore::open_in_memory().unwrap();
+        for email in ["a@example.com", "b@example.com", "c@missing.com", "not-an-email"] {
+            store.record(email, &verify(email)).unwrap();
+        }
+        assert_eq!(store.len().unwrap(), 4);
+
+        let by_domain = store.query(&HistoryQuery { domain: Some("Example.com".to_string()), ..HistoryQuery::default() }).unwrap();
+        assert_eq!(by_domain.len(), 2);
+        assert!(by_domain.iter().all(|entry| entry.verdict == Verdict::Unknown));
+
+        let undeliverable = store.query(&HistoryQuery { verdict: Some(Verdict::Undeliverable), ..HistoryQuery::default() }).unwrap();
+        assert_eq!(undeliverable.len(), 1);
+        assert_eq!(undeliverable[0].email, "c@missing.com");
+        assert_eq!(undeliverable[0].result.domain_checks.domain_exists, Some(false));
+
+        let latest = store.latest(" A@Example.com ").unwrap().unwrap();
+        assert_eq!(latest.result.email.local_part.as_deref(), Some("a"));
+        assert_eq!(store.latest("not-an-email").unwrap().unwrap().verdict, Verdict::Invalid);
+
+        let future = unix_time_ms() + 60_000;
+        assert!(store.query(&HistoryQuery { since: Some(future), ..HistoryQuery::default() }).unwrap().is_empty());
+        assert_eq!(store.query(&HistoryQuery { until: Some(future), limit: Some(3), ..HistoryQuery::default() }).unwrap().len(), 3);
+        assert_eq!(store.prune(future).unwrap(), 4);
+        assert!(store.is_empty().unwrap());
+    }
+
+    /// Tests detecting verdict changes across a cut-off
+    #[test]
+    fn test_changes_since() {
+        let store = VerificationStore::open_in_memory().unwrap();
+        let mut old = verify("moved@example.com");
+        old.verified_at = Some(1_000);
+        store.record("moved@example.com", &old).unwrap();
+        let mut steady = verify("steady@example.com");
+        steady.verified_at = Some(1_000);
+        store.record("steady@example.com", &steady).unwrap();
+
+        store.record("moved@example.com", &verify("moved@missing.com")).unwrap();
+        store.record("steady@example.com", &verify("steady@example.com")).unwrap();
+        store.record("new@example.com", &verify("new@example.com")).unwrap();
+
+        let changes = store.changes_since(2_000).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].email, "moved@example.com");
+        assert_eq!((changes[0].previous, changes[0].current), (Verdict::Unknown, Verdict::Undeliverable));
+    }
+}
@@ -0,0 +1,249 @@
+use std::io::{self, BufRead, Read, Write};
+
+use serde::Serialize;
+
+#[cfg(any(feature = "wasm", feature = "node"))]
+use crate::casing;
+use crate::{prefilter, EmailParseError, EmailParseResult, ValidationOptions};
+
+/// Longest line read whole, in bytes; longer lines are skipped with a
+/// "LineTooLong" record so a missing newline cannot buffer the whole input
+const MAX_LINE_LENGTH: usize = 4096;
+
+/// Outcome of validating a single streamed address
+/// Mirrors what the wasm entry point returns: either a result or an error
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum StreamOutcome {
+    Result(Box<EmailParseResult>),
+    Error(EmailParseError)
+}
+
+/// One line of JSONL output produced by `validate_stream`
+#[derive(Debug, Serialize)]
+struct StreamRecord<'a> {
+    /// The input address as read from the line
+    email: &'a str,
+    #[serde(flatten)]
+    outcome: StreamOutcome
+}
+
+/// Validates newline-delimited email addresses from `reader`, writing one JSON
+/// object per line to `writer`
+///
+/// Each input line is processed and written before the next one is read, so
+/// memory usage stays bounded regardless of the input size. Blank lines are
+/// skipped; every output record carries the original `email` so results can be
+/// joined back to their input. A line that is not valid UTF-8 gets an
+/// "InvalidEncoding" error record, and one longer than 4096 bytes a
+/// "LineTooLong" record carrying its first 4096 bytes; either way the stream
+/// carries on.
+///
+/// # Arguments
+/// * `reader` - Source of addresses, one per line
+/// * `writer` - Destination for JSONL results
+///
+/// # Returns
+/// * `io::Result<usize>` - Number of addresses processed
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::validate_stream;
+/// let input = "user@example.com\ninvalid-email\n";
+/// let mut output = Vec::new();
+/// let count = validate_stream(input.as_bytes(), &mut output).unwrap();
+/// assert_eq!(count, 2);
+/// ```
+pub fn validate_stream<R: BufRead, W: Write>(reader: R, writer: W) -> io::Result<usize> {
+    validate_records(reader, writer, |writer, record| serde_json::to_writer(writer, record))
+}
+
+/// `validate_stream` with each record written by `write_record`
+fn validate_records<R: BufRead, W: Write>(
+    mut reader: R,
+    mut writer: W,
+    write_record: impl Fn(&mut W, &StreamRecord) -> serde_json::Result<()>
+) -> io::Result<usize> {
+    let mut bytes = Vec::new();
+    let mut processed = 0;
+    let options = ValidationOptions::default();
+
+    loop {
+        bytes.clear();
+        if reader.by_ref().take(MAX_LINE_LENGTH as u64 + 1).read_until(b'\n', &mut bytes)? == 0 {
+            break;
+        }
+        let too_long = bytes.len() > MAX_LINE_LENGTH && bytes.last() != Some(&b'\n');
+        if too_long {
+            bytes.truncate(MAX_LINE_LENGTH);
+            skip_line(&mut reader)?;
+        }
+
+        let line = String::from_utf8_lossy(&bytes);
+        let email = line.trim_end_matches(['\n', '\r']);
+        if email.trim().is_empty() && !too_long {
+            continue;
+        }
+
+        let outcome = match std::str::from_utf8(&bytes) {
+            _ if too_long => StreamOutcome::Error(line_too_long()),
+            Ok(_) => match prefilter::validate_prefiltered(email, &options) {
+                Ok(result) => StreamOutcome::Result(Box::new(result)),
+                Err(e) => StreamOutcome::Error(e)
+            },
+            Err(e) => StreamOutcome::Error(invalid_encoding(&bytes, e))
+        };
+
+        write_record(&mut writer, &StreamRecord { email, outcome })?;
+        writer.write_all(b"\n")?;
+        processed += 1;
+    }
+
+    writer.flush()?;
+    Ok(processed)
+}
+
+/// Discards the rest of the current line, up to and including its newline
+fn skip_line<R: BufRead>(reader: &mut R) -> io::Result<()> {
+    loop {
+        let buffer = reader.fill_buf()?;
+        if buffer.is_empty() {
+            return Ok(());
+        }
+        match buffer.iter().position(|&byte| byte == b'\n') {
+            Some(end) => {
+                reader.consume(end + 1);
+                return Ok(());
+            }
+            None => {
+                let length = buffer.len();
+                reader.consume(length);
+            }
+        }
+    }
+}
+
+/// Error record for a line longer than `MAX_LINE_LENGTH`
+fn line_too_long() -> EmailParseError {
+    EmailParseError {
+        error_type: "LineTooLong".to_string(),
+        message: "Line is too long to be an email address".to_string(),
+        details: Some(format!("More than {} bytes", MAX_LINE_LENGTH)),
+        position: None
+    }
+}
+
+/// Error record for a line that is not valid UTF-8; its `email` is the line
+/// with the invalid bytes replaced by U+FFFD
+fn invalid_encoding(bytes: &[u8], e: std::str::Utf8Error) -> EmailParseError {
+    let position = String::from_utf8_lossy(&bytes[..e.valid_up_to()]).chars().count();
+    EmailParseError {
+        error_type: "InvalidEncoding".to_string(),
+        message: "Line is not valid UTF-8".to_string(),
+        details: Some(e.to_string()),
+        position: Some(position)
+    }
+}
+
+/// Validates newline-delimited addresses packed in a byte buffer, returning
+/// the JSONL output of `validate_stream` as bytes, with the JS bindings'
+/// field naming
+///
+/// Buffers can be transferred to and from Web Workers without copying, which
+/// makes this the unit of work for browser worker pools.
+#[cfg(any(feature = "wasm", feature = "node"))]
+pub(crate) fn validate_packed(input: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(input.len() * 8);
+    // Reading from a slice and writing to a `Vec` cannot fail
+    let _ = validate_records(input, &mut output, |writer, record| serde_json::to_writer(writer, &casing::Cased(record)));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that each input line produces one JSONL record in order
+    #[test]
+    fn test_validate_stream() {
+        let input = "user@example.com\r\n\ninvalid-email\nother@google.com";
+        let mut output = Vec::new();
+
+        let count = validate_stream(input.as_bytes(), &mut output).unwrap();
+        assert_eq!(count, 3);
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(output).unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+
+        assert_eq!(lines[0]["email"], "user@example.com");
+        assert_eq!(lines[0]["is_valid"], true);
+        assert_eq!(lines[0]["domain"], "example.com");
+
+        assert_eq!(lines[1]["email"], "invalid-email");
+        assert_eq!(lines[1]["is_valid"], false);
+        assert_eq!(lines[1]["error_message"], "Invalid email format");
+
+        assert_eq!(lines[2]["email"], "other@google.com");
+        assert_eq!(lines[2]["domain_score"], 80.0);
+    }
+
+    /// Tests packed batches
+    #[cfg(any(feature = "wasm", feature = "node"))]
+    #[test]
+    fn test_validate_packed() {
+        let output = validate_packed(b"user@example.com\ninvalid-email\n");
+        let lines: Vec<serde_json::Value> = output.split(|&b| b == b'\n').filter(|line| !line.is_empty()).map(|line| serde_json::from_slice(line).unwrap()).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1]["email"], "invalid-email");
+
+        assert!(validate_packed(b"").is_empty());
+    }
+
+    /// Tests that a line that is not UTF-8 is reported and the lines after it still validated
+    #[test]
+    fn test_validate_stream_invalid_encoding() {
+        let mut output = Vec::new();
+        let count = validate_stream(&b"user@example.com\nus\xffer@example.com\nother@example.com\n"[..], &mut output).unwrap();
+        assert_eq!(count, 3);
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(output).unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines[0]["is_valid"], true);
+        assert_eq!(lines[1]["email"], "us\u{FFFD}er@example.com");
+        assert_eq!(lines[1]["error_type"], "InvalidEncoding");
+        assert_eq!(lines[1]["position"], 2);
+        assert_eq!(lines[2]["email"], "other@example.com");
+        assert_eq!(lines[2]["is_valid"], true);
+    }
+
+    /// Tests that an overlong line is reported and skipped without being buffered whole
+    #[test]
+    fn test_validate_stream_line_too_long() {
+        let mut input = b"user@example.com\n".to_vec();
+        input.extend(vec![b'a'; MAX_LINE_LENGTH * 3]);
+        input.extend(b"@example.com\n");
+        input.extend(vec![b'b'; MAX_LINE_LENGTH]);
+        input.extend(b"\nother@example.com");
+
+        let mut output = Vec::new();
+        let count = validate_stream(io::BufReader::with_capacity(64, &input[..]), &mut output).unwrap();
+        assert_eq!(count, 4);
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(output).unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines[0]["is_valid"], true);
+        assert_eq!(lines[1]["error_type"], "LineTooLong");
+        assert_eq!(lines[1]["email"].as_str().unwrap().len(), MAX_LINE_LENGTH);
+        assert_eq!(lines[2]["is_valid"], false);
+        assert_ne!(lines[2]["error_type"], "LineTooLong");
+        assert_eq!(lines[3]["email"], "other@example.com");
+        assert_eq!(lines[3]["is_valid"], true);
+    }
+}
@@ -0,0 +1,342 @@
+//! Ranked correction candidates for mistyped addresses
+//!
+//! Most fixable typos are in the domain of a well-known mailbox provider:
+//! a swapped TLD (`gmail.cmo`), a dropped dot (`gmailcom`), transposed
+//! letters (`gmial.com`) or a dropped `@` (`janegmail.com`). Domain fixes
+//! are only proposed towards known domains, so a legitimate domain that
+//! merely looks unusual is not "corrected" into something else.
+
+use serde::{Serialize, Deserialize};
+
+use crate::typosquat::edit_distance;
+use crate::{domain_lists, parser, providers};
+
+/// Popular mailbox domains beyond those with provider addressing rules
+const KNOWN_MAILBOX_DOMAINS: [&str; 14] = [
+    "icloud.com", "me.com", "mac.com", "aol.com", "protonmail.com", "proton.me", "gmx.com",
+    "gmx.de", "gmx.net", "web.de", "mail.com", "yandex.ru", "comcast.net", "att.net"
+];
+
+/// Classic TLD typos and the TLD meant; together they account for most
+/// fixable bounces
+const TLD_TYPOS: [(&str, &str); 6] = [
+    ("con", "com"),
+    ("cmo", "com"),
+    ("cm", "com"),
+    ("co,", "com"),
+    ("comm", "com"),
+    ("ocm", "com")
+];
+
+/// Ways of writing `@` out to hide an address from scrapers
+const AT_SPELLINGS: [&str; 3] = ["(at)", "[at]", "{at}"];
+
+/// Confidence of a known domain behind a classic TLD typo (`TLD_TYPOS`)
+const TLD_TYPO_CONFIDENCE: f64 = 0.95;
+
+/// Confidence of a domain that only differs from a known one by a missing dot
+const MISSING_DOT_CONFIDENCE: f64 = 0.9;
+
+/// Confidence of an input with its `@` written out (`jane(at)example.com`)
+/// or replaced by a space
+const RECOVERED_AT_CONFIDENCE: f64 = 0.9;
+
+/// Confidence of an input without `@` that ends in a known domain
+const MISSING_AT_CONFIDENCE: f64 = 0.85;
+
+/// Confidence of a known name under a TLD one edit away from its own
+const TLD_SWAP_CONFIDENCE: f64 = 0.85;
+
+/// Confidence of a domain one adjacent transposition away from a known one
+const TRANSPOSITION_CONFIDENCE: f64 = 0.8;
+
+/// Confidence of a domain one other edit away from a known one
+const MISSPELLING_CONFIDENCE: f64 = 0.7;
+
+/// Subtracted from a candidate's confidence for each edit beyond the first
+const EXTRA_EDIT_PENALTY: f64 = 0.25;
+
+/// Kind of mistake a candidate corrects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CorrectionKind {
+    /// Wrong TLD on a known domain (`gmail.cmo`)
+    TldSwap,
+    /// Dot left out of a known domain (`gmailcom`)
+    MissingDot,
+    /// Adjacent letters swapped (`gmial.com`)
+    Transposition,
+    /// Other misspelling of a known domain (`gmaill.com`)
+    Misspelling,
+    /// `@` left out before a known domain (`janegmail.com`), written out
+    /// (`jane(at)example.com`) or replaced by a space
+    MissingAt
+}
+
+impl CorrectionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CorrectionKind::TldSwap => "tld_swap",
+            CorrectionKind::MissingDot => "missing_dot",
+            CorrectionKind::Transposition => "transposition",
+            CorrectionKind::Misspelling => "misspelling",
+            CorrectionKind::MissingAt => "missing_at"
+        }
+    }
+}
+
+/// A plausible fix for a mistyped address
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CorrectionCandidate {
+    /// The corrected address
+    pub email: String,
+    pub kind: CorrectionKind,
+    /// How likely this is the intended address (0-1)
+    pub confidence: f64
+}
+
+/// Ranked corrections for `email`, most likely first
+///
+/// Every candidate is a syntactically valid address. Domain fixes only point
+/// at known mailbox domains (built-in providers and the trusted lists); an
+/// input whose `@` was written out or replaced by a space is also offered
+/// with the `@` restored, whatever its domain. The list is empty when the
+/// domain is already a known one or nothing known is close to it.
+///
+/// # Examples
+/// ```
+/// # use email_validator_wasm::suggest_corrections;
+/// let candidates = suggest_corrections("jane@gmial.con");
+/// assert_eq!(candidates[0].email, "jane@gmail.com");
+/// ```
+pub fn suggest_corrections(email: &str) -> Vec<CorrectionCandidate> {
+    let email = email.trim();
+    let known = known_domains();
+    let mut candidates = Vec::new();
+
+    match email.rsplit_once('@') {
+        Some((local_part, domain)) if !local_part.is_empty() => {
+            let domain = domain.to_lowercase();
+            if !known.contains(&domain) {
+                for known_domain in &known {
+                    if let Some((kind, confidence)) = domain_correction(&domain, known_domain) {
+                        candidates.push(CorrectionCandidate { email: format!("{}@{}", local_part, known_domain), kind, confidence });
+                    }
+                }
+            }
+        }
+        Some(_) => {}
+        None => match recover_missing_at(email) {
+            Some(recovered) => {
+                // A typo in the recovered domain is a second mistake, so fixing it costs confidence
+                candidates.extend(suggest_corrections(&recovered).into_iter().map(|candidate| CorrectionCandidate {
+                    confidence: candidate.confidence * RECOVERED_AT_CONFIDENCE,
+                    ..candidate
+                }));
+                candidates.push(CorrectionCandidate { email: recovered, kind: CorrectionKind::MissingAt, confidence: RECOVERED_AT_CONFIDENCE });
+            }
+            None => {
+                // The longest match wins: `janegmail.com` ends in both `gmail.com` and `mail.com`
+                let lower = email.to_ascii_lowercase();
+                let longest = known.iter().filter(|known_domain| lower.ends_with(known_domain.as_str())).max_by_key(|known_domain| known_domain.len());
+                if let Some(known_domain) = longest {
+                    // The mailbox part ends where the known domain starts, minus any dot typed in place of `@`
+                    let local_part = email[..email.len() - known_domain.len()].trim_end_matches('.');
+                    if !local_part.is_empty() {
+                        candidates.push(CorrectionCandidate {
+                            email: format!("{}@{}", local_part, known_domain),
+                            kind: CorrectionKind::MissingAt,
+                            confidence: MISSING_AT_CONFIDENCE
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    candidates.retain(|candidate| parser::parse_address(&candidate.email).is_ok());
+    // Stable, so equally confident candidates keep the more popular domain first
+    candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    candidates
+}
+
+/// The address meant by `email`, when it can be told with near certainty:
+/// an `@` written out or replaced by a space (`jane(at)example.com`,
+/// `jane example.com`), or a known domain behind a classic TLD typo
+/// (`jane@gmail.con` for `jane@gmail.com`)
+///
+/// Unlike `suggest_corrections`, only fixes that are near-certain are made,
+/// so the result can be offered without a picker.
+pub(crate) fn suggestion(email: &str) -> Option<String> {
+    let email = email.trim();
+    if !email.contains('@') {
+        let recovered = recover_missing_at(email)?;
+        return Some(fix_tld_typo(&recovered).unwrap_or(recovered));
+    }
+    fix_tld_typo(email)
+}
+
+/// `email` with the TLD of a known domain fixed, if it is a `TLD_TYPOS` typo
+fn fix_tld_typo(email: &str) -> Option<String> {
+    let (local_part, domain) = email.rsplit_once('@')?;
+    if local_part.is_empty() {
+        return None;
+    }
+    let domain = domain.to_lowercase();
+    let (name, tld) = domain.rsplit_once('.')?;
+    let (_, fixed_tld) = TLD_TYPOS.iter().find(|(typo, _)| *typo == tld)?;
+    let fixed = format!("{}.{}", name, fixed_tld);
+    known_domains().contains(&fixed).then(|| format!("{}@{}", local_part, fixed))
+}
+
+/// `email` with its missing `@` restored, when it was written out
+/// (`(at)`, `[at]`, `{at}`, or ` at ` between words) or replaced by a space,
+/// and the result is a well-formed address
+fn recover_missing_at(email: &str) -> Option<String> {
+    let lower = email.to_ascii_lowercase();
+    let bracketed = AT_SPELLINGS.iter().find_map(|spelling| {
+        let index = lower.find(spelling)?;
+        (lower.matches(spelling).count() == 1).then(|| format!("{}@{}", email[..index].trim(), email[index + spelling.len()..].trim()))
+    });
+    let candidate = bracketed.or_else(|| {
+        let words: Vec<&str> = email.split_whitespace().collect();
+        match words[..] {
+            [local_part, domain] => Some(format!("{}@{}", local_part, domain)),
+            [local_part, at, domain] if at.eq_ignore_ascii_case("at") => Some(format!("{}@{}", local_part, domain)),
+            _ => None
+        }
+    })?;
+    parser::parse_address(&candidate).is_ok().then_some(candidate)
+}
+
+/// Built-in and trusted mailbox domains, lowercased, most popular first
+fn known_domains() -> Vec<String> {
+    let mut known: Vec<String> = Vec::new();
+    let domains = providers::provider_domains()
+        .chain(KNOWN_MAILBOX_DOMAINS)
+        .map(str::to_string)
+        .chain(domain_lists::trusted_domains().into_iter().map(|domain| domain.to_lowercase()));
+    for domain in domains {
+        if !known.contains(&domain) {
+            known.push(domain);
+        }
+    }
+    known
+}
+
+/// How `domain` could be a mistyping of `known`, with the confidence
+fn domain_correction(domain: &str, known: &str) -> Option<(CorrectionKind, f64)> {
+    if domain.replace('.', "") == known.replace('.', "") && domain.matches('.').count() < known.matches('.').count() {
+        return Some((CorrectionKind::MissingDot, MISSING_DOT_CONFIDENCE));
+    }
+
+    let (name, tld) = domain.rsplit_once('.')?;
+    let (known_name, known_tld) = known.rsplit_once('.')?;
+    if name == known_name {
+        if TLD_TYPOS.contains(&(tld, known_tld)) {
+            return Some((CorrectionKind::TldSwap, TLD_TYPO_CONFIDENCE));
+        }
+        let distance = edit_distance(tld, known_tld);
+        return (distance <= 2).then(|| (CorrectionKind::TldSwap, penalize(TLD_SWAP_CONFIDENCE, distance)));
+    }
+
+    // Short names are mostly changed by two edits, so they get one
+    let max_distance = if known_name.chars().count() <= 4 { 1 } else { 2 };
+    let distance = edit_distance(domain, known);
+    if distance > max_distance {
+        return None;
+    }
+    if distance == 1 && is_transposition(domain, known) {
+        return Some((CorrectionKind::Transposition, TRANSPOSITION_CONFIDENCE));
+    }
+    Some((CorrectionKind::Misspelling, penalize(MISSPELLING_CONFIDENCE, distance)))
+}
+
+/// `confidence` lowered for each edit beyond the first
+fn penalize(confidence: f64, distance: usize) -> f64 {
+    confidence - EXTRA_EDIT_PENALTY * distance.saturating_sub(1) as f64
+}
+
+/// Whether `a` is `b` with two adjacent characters swapped
+fn is_transposition(a: &str, b: &str) -> bool {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    if a.len() != b.len() {
+        return false;
+    }
+    let differences: Vec<usize> = (0..a.len()).filter(|&i| a[i] != b[i]).collect();
+    matches!(differences[..], [i, j] if j == i + 1 && a[i] == b[j] && a[j] == b[i])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn top(email: &str) -> Option<(String, CorrectionKind)> {
+        suggest_corrections(email).into_iter().next().map(|candidate| (candidate.email, candidate.kind))
+    }
+
+    /// Tests each kind of correction and the ranking between them
+    #[test]
+    fn test_suggest_corrections() {
+        assert_eq!(top("jane@gmail.cmo"), Some(("jane@gmail.com".to_string(), CorrectionKind::TldSwap)));
+        assert_eq!(top("jane@gmailcom"), Some(("jane@gmail.com".to_string(), CorrectionKind::MissingDot)));
+        assert_eq!(top("jane@hotmailco.uk"), Some(("jane@hotmail.co.uk".to_string(), CorrectionKind::MissingDot)));
+        assert_eq!(top("Jane@GMIAL.com"), Some(("Jane@gmail.com".to_string(), CorrectionKind::Transposition)));
+        assert_eq!(top("jane@gmial.con"), Some(("jane@gmail.com".to_string(), CorrectionKind::Misspelling)));
+        assert_eq!(top("jane@gmaill.com"), Some(("jane@gmail.com".to_string(), CorrectionKind::Misspelling)));
+        assert_eq!(top("jane.doegmail.com"), Some(("jane.doe@gmail.com".to_string(), CorrectionKind::MissingAt)));
+        assert_eq!(top("jane.doe.gmail.com"), Some(("jane.doe@gmail.com".to_string(), CorrectionKind::MissingAt)));
+
+        let candidates = suggest_corrections("jane@yahoo.cm");
+        assert!(candidates.len() > 1);
+        assert!(candidates.windows(2).all(|pair| pair[0].confidence >= pair[1].confidence));
+        assert_eq!(candidates[0].email, "jane@yahoo.com");
+
+        assert!(suggest_corrections("jane@gmail.com").is_empty());
+        assert!(suggest_corrections("jane@example.com").is_empty());
+        assert!(suggest_corrections("@gmial.com").is_empty());
+    }
+
+    /// Tests that TLD typos are only corrected on known domains
+    #[test]
+    fn test_suggestion() {
+        for typo in ["con", "cmo", "cm", "co,", "comm", "ocm"] {
+            assert_eq!(suggestion(&format!("jane@gmail.{}", typo)).as_deref(), Some("jane@gmail.com"), "{}", typo);
+        }
+        assert_eq!(suggestion("Jane@Hotmail.CON").as_deref(), Some("Jane@hotmail.com"));
+        assert_eq!(suggestion("jane@example.con"), None);
+        assert_eq!(suggestion("jane@gmail.com"), None);
+        assert_eq!(suggestion("jane@gmail.cmo.uk"), None);
+        assert_eq!(top("jane@yahoo.cm"), Some(("jane@yahoo.com".to_string(), CorrectionKind::TldSwap)));
+        assert_eq!(suggest_corrections("jane@yahoo.cm")[0].confidence, TLD_TYPO_CONFIDENCE);
+    }
+
+    /// Tests recovery of an `@` written out or replaced by a space
+    #[test]
+    fn test_recover_missing_at() {
+        for input in ["jane(at)example.com", "jane [AT] example.com", "jane{at}example.com", "jane at example.com", "jane example.com"] {
+            assert_eq!(suggestion(input).as_deref(), Some("jane@example.com"), "{}", input);
+        }
+        assert_eq!(suggestion("jane(at)gmail.con").as_deref(), Some("jane@gmail.com"));
+        assert_eq!(suggestion("jane(at)example(at)com"), None);
+        assert_eq!(suggestion("jane doe example.com"), None);
+        assert_eq!(suggestion("jane example"), None);
+        assert_eq!(suggestion("janeexample.com"), None);
+
+        let candidates = suggest_corrections("jane(at)gmial.com");
+        assert_eq!((candidates[0].email.as_str(), candidates[0].kind), ("jane@gmial.com", CorrectionKind::MissingAt));
+        assert!(candidates.iter().any(|candidate| candidate.email == "jane@gmail.com"));
+    }
+
+    /// Tests adjacent-swap detection
+    #[test]
+    fn test_is_transposition() {
+        assert!(is_transposition("gmial.com", "gmail.com"));
+        assert!(!is_transposition("gmail.com", "gmail.com"));
+        assert!(is_transposition("gamil.com", "gmail.com"));
+        assert!(!is_transposition("gnail.com", "gmail.com"));
+        assert!(!is_transposition("gamil.com", "gmail.coms"));
+    }
+}
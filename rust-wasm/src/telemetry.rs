@@ -0,0 +1,92 @@
+//! Metrics emitted through the `metrics` facade (`metrics` feature)
+//!
+//! The crate only records; install any `metrics` recorder (the `server`
+//! feature uses `metrics-exporter-prometheus`) to collect them.
+
+#[cfg(feature = "net")]
+use std::time::Duration;
+
+use metrics::{counter, describe_counter, describe_histogram, Unit};
+#[cfg(feature = "net")]
+use metrics::histogram;
+
+#[cfg(feature = "net")]
+use crate::dns::DnsError;
+#[cfg(feature = "net")]
+use crate::smtp::SmtpCheck;
+use crate::{DomainCategory, EmailParseError, EmailParseResult};
+
+/// Validations performed, labelled `outcome` (`valid`, `invalid` or `error`)
+pub const VALIDATIONS_TOTAL: &str = "email_validations_total";
+
+/// Valid addresses at disposable domains
+pub const DISPOSABLE_TOTAL: &str = "email_disposable_total";
+
+/// DNS lookups that failed without an answer, labelled `error_type`
+pub const DNS_FAILURES_TOTAL: &str = "email_dns_failures_total";
+
+/// Duration of SMTP mailbox probes, labelled `outcome` (`deliverable`,
+/// `undeliverable` or `inconclusive`)
+pub const SMTP_CHECK_DURATION_SECONDS: &str = "email_smtp_check_duration_seconds";
+
+/// Registers descriptions and units for the metrics above with the installed
+/// recorder; call once after installing it
+pub fn describe_metrics() {
+    describe_counter!(VALIDATIONS_TOTAL, "Email validations performed, by outcome");
+    describe_counter!(DISPOSABLE_TOTAL, "Valid email addresses at disposable domains");
+    describe_counter!(DNS_FAILURES_TOTAL, "DNS lookups that failed without an answer, by error type");
+    describe_histogram!(SMTP_CHECK_DURATION_SECONDS, Unit::Seconds, "SMTP mailbox probe duration, by outcome");
+}
+
+pub(crate) fn record_validation(result: &Result<EmailParseResult, EmailParseError>) {
+    let outcome = match result {
+        Ok(result) if result.is_valid => "valid",
+        Ok(_) => "invalid",
+        Err(_) => "error"
+    };
+    counter!(VALIDATIONS_TOTAL, "outcome" => outcome).increment(1);
+
+    if let Ok(EmailParseResult { is_valid: true, domain_category: Some(DomainCategory::Disposable), .. }) = result {
+        counter!(DISPOSABLE_TOTAL).increment(1);
+    }
+}
+
+#[cfg(feature = "net")]
+pub(crate) fn record_dns_failure(error: &DnsError) {
+    counter!(DNS_FAILURES_TOTAL, "error_type" => error.error_type.clone()).increment(1);
+}
+
+#[cfg(feature = "net")]
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+pub(crate) fn record_smtp_check(check: &SmtpCheck, elapsed: Duration) {
+    let outcome = match check.is_deliverable {
+        Some(true) => "deliverable",
+        Some(false) => "undeliverable",
+        None => "inconclusive"
+    };
+    histogram!(SMTP_CHECK_DURATION_SECONDS, "outcome" => outcome).record(elapsed.as_secs_f64());
+}
+
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use metrics_exporter_prometheus::PrometheusBuilder;
+
+    /// Tests that validations are counted by outcome, with disposable domains
+    /// counted separately
+    #[test]
+    fn test_validation_metrics() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+
+        metrics::with_local_recorder(&recorder, || {
+            for email in ["user@example.com", "user@mailinator.com", "invalid-email"] {
+                let _ = crate::parse_and_validate_email(email);
+            }
+        });
+
+        let rendered = handle.render();
+        assert!(rendered.contains(r#"email_validations_total{outcome="valid"} 2"#), "{}", rendered);
+        assert!(rendered.contains(r#"email_validations_total{outcome="invalid"} 1"#), "{}", rendered);
+        assert!(rendered.contains("email_disposable_total 1"), "{}", rendered);
+    }
+}
@@ -0,0 +1,37 @@
+/// Current Unix time in milliseconds
+///
+/// `std::time::SystemTime` is unavailable on `wasm32-unknown-unknown`, so the
+/// JS clock is used there.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn unix_time_ms() -> u64 {
+    js_sys::Date::now() as u64
+}
+
+/// Current Unix time in milliseconds
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn unix_time_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Serializes `Option<Duration>` as whole milliseconds, for configuration
+/// files where `{ "secs": .., "nanos": .. }` would be unreadable
+#[cfg(feature = "net")]
+pub(crate) mod option_duration_ms {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+        match duration {
+            Some(duration) => serializer.serialize_some(&(duration.as_millis() as u64)),
+            None => serializer.serialize_none()
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+        Ok(Option::<u64>::deserialize(deserializer)?.map(Duration::from_millis))
+    }
+}
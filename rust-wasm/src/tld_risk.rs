@@ -0,0 +1,79 @@
+//! Domain scores for top-level domains with high abuse rates
+
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// Default domain scores for high-abuse TLDs, in place of the neutral 50
+///
+/// Derived from published abuse statistics: the Spamhaus "most abused TLDs"
+/// rankings and the Interisle Phishing Landscape reports. The former free
+/// Freenom ccTLDs and the cheapest new gTLDs dominate both, and score lowest.
+pub(crate) const SUSPICIOUS_TLD_SCORES: [(&str, f64); 24] = [
+    ("tk", 15.0),
+    ("ml", 15.0),
+    ("ga", 15.0),
+    ("cf", 15.0),
+    ("gq", 15.0),
+    ("top", 20.0),
+    ("click", 20.0),
+    ("loan", 20.0),
+    ("icu", 25.0),
+    ("cyou", 25.0),
+    ("sbs", 25.0),
+    ("cfd", 25.0),
+    ("zip", 25.0),
+    ("xyz", 30.0),
+    ("buzz", 30.0),
+    ("rest", 30.0),
+    ("bar", 30.0),
+    ("monster", 30.0),
+    ("work", 30.0),
+    ("lol", 30.0),
+    ("mov", 30.0),
+    ("online", 35.0),
+    ("site", 35.0),
+    ("shop", 40.0)
+];
+
+/// Score for the domain's TLD from `overrides` or the built-in table
+///
+/// Override keys are matched case-insensitively, with or without a leading
+/// dot (`"xyz"`, `".XYZ"`). Returns `None` for TLDs in neither table.
+#[cfg(feature = "std")]
+pub(crate) fn tld_score(domain: &str, overrides: &HashMap<String, f64>) -> Option<f64> {
+    let tld = domain.rsplit('.').next()?.to_lowercase();
+
+    overrides
+        .iter()
+        .find(|(configured, _)| configured.trim_start_matches('.').eq_ignore_ascii_case(&tld))
+        .map(|(_, score)| *score)
+        .or_else(|| builtin_tld_score(&tld))
+}
+
+/// Score for the domain's TLD from the built-in table only
+pub(crate) fn builtin_tld_score(domain: &str) -> Option<f64> {
+    let tld = domain.rsplit('.').next()?;
+    SUSPICIOUS_TLD_SCORES
+        .iter()
+        .find(|(suspicious, _)| suspicious.eq_ignore_ascii_case(tld))
+        .map(|(_, score)| *score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the built-in table and caller overrides
+    #[test]
+    fn test_tld_score() {
+        let no_overrides = HashMap::new();
+        assert_eq!(tld_score("promo.XYZ", &no_overrides), Some(30.0));
+        assert_eq!(tld_score("free.gq", &no_overrides), Some(15.0));
+        assert_eq!(tld_score("example.com", &no_overrides), None);
+
+        let overrides = HashMap::from([(".xyz".to_string(), 45.0), ("com".to_string(), 60.0)]);
+        assert_eq!(tld_score("promo.xyz", &overrides), Some(45.0));
+        assert_eq!(tld_score("example.com", &overrides), Some(60.0));
+        assert_eq!(tld_score("promo.top", &overrides), Some(20.0));
+    }
+}
@@ -0,0 +1,101 @@
+//! Detection of near-miss lookalikes of protected (brand) domains
+
+/// Finds the protected domain that `domain` is a near-miss lookalike of
+///
+/// A domain is a lookalike of a protected domain (and not that domain itself) if:
+/// - its name (everything before the TLD) is within edit distance 2 of the
+///   protected name under the same TLD (distance 1 for names of 4 characters
+///   or fewer, where 2 edits change most of the name),
+/// - it only differs by added or removed hyphens (`my-brand.com` vs `mybrand.com`), or
+/// - it has the same name under a different TLD (`mybrand.co` vs `mybrand.com`)
+///
+/// # Examples
+/// ```ignore
+/// let protected = vec!["mybrand.com".to_string()];
+/// assert_eq!(find_typosquat_target("mybrnad.com", &protected), Some("mybrand.com"));
+/// assert_eq!(find_typosquat_target("mybrand.com", &protected), None);
+/// ```
+pub(crate) fn find_typosquat_target<'a>(domain: &str, protected: &'a [String]) -> Option<&'a str> {
+    let domain = domain.to_lowercase();
+    if protected.iter().any(|p| p.eq_ignore_ascii_case(&domain)) {
+        return None;
+    }
+
+    let (name, tld) = split_tld(&domain);
+    protected.iter().map(String::as_str).find(|p| {
+        let p = p.to_lowercase();
+        let (protected_name, protected_tld) = split_tld(&p);
+
+        let max_distance = if protected_name.chars().count() <= 4 { 1 } else { 2 };
+        let near_miss = tld == protected_tld && edit_distance(name, protected_name) <= max_distance;
+        let hyphenated = domain.replace('-', "") == p.replace('-', "");
+        let swapped_tld = name == protected_name && tld != protected_tld;
+
+        near_miss || hyphenated || swapped_tld
+    })
+}
+
+/// Splits a domain into the part before the last label and the TLD
+fn split_tld(domain: &str) -> (&str, &str) {
+    domain.rsplit_once('.').unwrap_or((domain, ""))
+}
+
+/// Optimal string alignment distance: insertions, deletions, substitutions and
+/// transpositions of adjacent characters each cost 1
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut previous_previous = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1).min(current[j - 1] + 1).min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(previous_previous[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut previous_previous, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the near-miss, hyphen and TLD-swap lookalike rules
+    #[test]
+    fn test_find_typosquat_target() {
+        let protected = vec!["acmebank.com".to_string(), "ibm.com".to_string()];
+
+        assert_eq!(find_typosquat_target("acmebnak.com", &protected), Some("acmebank.com")); // Transposition
+        assert_eq!(find_typosquat_target("acmebanks.com", &protected), Some("acmebank.com")); // Insertion
+        assert_eq!(find_typosquat_target("acnebamk.com", &protected), Some("acmebank.com")); // Two substitutions
+        assert_eq!(find_typosquat_target("acme-bank.com", &protected), Some("acmebank.com"));
+        assert_eq!(find_typosquat_target("acmebank.co", &protected), Some("acmebank.com"));
+        assert_eq!(find_typosquat_target("ACMEBANK.NET", &protected), Some("acmebank.com"));
+        assert_eq!(find_typosquat_target("lbm.com", &protected), Some("ibm.com"));
+
+        assert_eq!(find_typosquat_target("acmebank.com", &protected), None);
+        assert_eq!(find_typosquat_target("abc.com", &protected), None); // Too far from a short name
+        assert_eq!(find_typosquat_target("example.com", &protected), None);
+        assert_eq!(find_typosquat_target("acmebank.com", &[]), None);
+    }
+
+    /// Tests edit distance including adjacent transpositions
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("gmail", "gmail"), 0);
+        assert_eq!(edit_distance("gmail", "gmial"), 1);
+        assert_eq!(edit_distance("gmail", "gmal"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
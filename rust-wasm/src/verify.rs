@@ -0,0 +1,1320 @@
+use std::future::Future;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Serialize, Deserialize};
+
+use crate::breach::{check_breach, BreachError, BreachRangeSource, NoBreachCheck};
+use crate::cache::VerificationCache;
+use crate::cancel::{interruptible, CancellationToken, Interrupted};
+use crate::ct_log::check_ct_logs;
+use crate::dns::{DnsError, DnsResolver, MxRecord};
+use crate::dnsbl::{check_blocklists, Blocklist};
+use crate::enrich::{enrich_result, Enricher};
+use crate::events::EventObserver;
+use crate::gravatar::check_gravatar;
+use crate::mta_sts::{fetch_policy, parse_policy, PolicyMode};
+use crate::mx_provider::{identify_mx_provider, MxProvider};
+use crate::rdap::registration_date;
+use crate::redact::{redact_email, LogRedaction};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::blocking;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::smtp::{SmtpError, SmtpVerifier};
+use crate::smtp::SmtpCheck;
+use crate::stage::Stage;
+use crate::time::unix_time_ms;
+use crate::{parse_and_validate_email_with_options, EmailParseError, EmailParseResult, ScoreBreakdown, ValidationOptions};
+
+/// DNS findings for a domain
+/// Shared by every address at the domain, so it is what the pipeline caches
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DomainChecks {
+    /// Whether the domain exists in DNS (None if the lookup was not performed or failed)
+    pub domain_exists: Option<bool>,
+    /// Whether the domain publishes at least one MX record
+    pub has_mx: Option<bool>,
+    /// MX records ordered by preference
+    pub mx_records: Option<Vec<MxRecord>>,
+    /// Mail hosting provider identified from the MX host names (e.g. Google
+    /// Workspace, Microsoft 365); hosted mail is a sign of an established domain
+    pub mx_provider: Option<MxProvider>,
+    /// Whether the domain publishes a null MX record (RFC 7505), explicitly
+    /// declaring that it accepts no mail
+    pub null_mx: Option<bool>,
+    /// Likelihood that mail to the domain can be delivered (0-100)
+    pub deliverability_score: Option<f64>,
+    /// Code for a domain-level verification failure (e.g., "NxDomain", "NullMx")
+    pub error_code: Option<String>,
+    /// Whether the domain publishes an SPF record (None if the check was not run)
+    pub has_spf: Option<bool>,
+    /// Whether the domain publishes a DMARC record (None if the check was not run)
+    pub has_dmarc: Option<bool>,
+    /// Policy requested by the domain's DMARC record
+    pub dmarc_policy: Option<DmarcPolicy>,
+    /// Whether the domain publishes a BIMI record at `default._bimi` (None if
+    /// the check was not run)
+    pub has_bimi: Option<bool>,
+    /// Brand logo URL from the BIMI record's `l=` tag
+    pub bimi_logo_url: Option<String>,
+    /// Mode of the domain's MTA-STS policy (None if the check was not run or
+    /// no policy is published)
+    pub mta_sts: Option<PolicyMode>,
+    /// Whether the most preferred MX publishes TLSA records for DANE (None if
+    /// the check was not run or the resolver cannot answer TLSA queries)
+    pub dane: Option<bool>,
+    /// Whether a configured DNS blocklist lists the domain (None if the check
+    /// was not run or a list could not be queried)
+    pub is_blocklisted: Option<bool>,
+    /// Name of the first blocklist listing the domain
+    pub blocklist: Option<String>,
+    /// When the domain was registered, in Unix milliseconds, from its
+    /// registry's RDAP service (None if the check was not run, failed or the
+    /// registry does not publish the date)
+    pub registered_at: Option<u64>,
+    /// Whether the domain is younger than the verifier's minimum domain age;
+    /// new domains are heavily penalized
+    pub is_newly_registered: Option<bool>,
+    /// Whether certificates for the domain appear in the Certificate
+    /// Transparency logs, a sign that it runs real services (None if the
+    /// check was not run or failed)
+    pub has_ct_certificates: Option<bool>,
+    /// DNS failure that prevented the checks from completing
+    pub dns_error: Option<DnsError>
+}
+
+/// DMARC policy (`p=` tag) published by a domain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum DmarcPolicy {
+    None,
+    Quarantine,
+    Reject
+}
+
+/// Result of the verification pipeline
+/// Extends the syntax validation result with DNS findings for the domain
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct VerificationResult {
+    /// Syntax validation result
+    #[serde(flatten)]
+    pub email: EmailParseResult,
+    /// DNS findings for the domain (all None if the email is invalid)
+    #[serde(flatten)]
+    pub domain_checks: DomainChecks,
+    /// SMTP mailbox probe outcome (None if the stage is disabled or the domain has no MX)
+    pub smtp: Option<SmtpCheck>,
+    /// Whether the address appears in a known breach (None if the stage is
+    /// disabled, the email is invalid or the lookup failed)
+    pub found_in_breach: Option<bool>,
+    /// Breach lookup failure that prevented the check from completing
+    pub breach_error: Option<BreachError>,
+    /// Whether the address has a Gravatar (None if the stage is disabled, the
+    /// email is invalid or the lookup failed)
+    pub has_gravatar: Option<bool>,
+    /// When the network checks ran, in Unix milliseconds (None if the email is
+    /// invalid, since syntax results never go stale)
+    pub verified_at: Option<u64>,
+    /// When the network findings should be re-verified, in Unix milliseconds
+    pub expires_at: Option<u64>
+}
+
+/// Overall outcome of a verification, for reporting and history queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    /// The mailbox accepted the address over SMTP
+    Deliverable,
+    /// Likely to reach someone, but unconfirmed or suspicious (catch-all
+    /// domain, blocklisted domain, lookalike of a trusted domain)
+    Risky,
+    /// The domain or mailbox cannot receive mail
+    Undeliverable,
+    /// The address failed syntax validation
+    Invalid,
+    /// The checks that ran could not decide
+    Unknown
+}
+
+impl Verdict {
+    /// Name used in serialized results and storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Verdict::Deliverable => "deliverable",
+            Verdict::Risky => "risky",
+            Verdict::Undeliverable => "undeliverable",
+            Verdict::Invalid => "invalid",
+            Verdict::Unknown => "unknown"
+        }
+    }
+}
+
+impl std::str::FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "deliverable" => Ok(Verdict::Deliverable),
+            "risky" => Ok(Verdict::Risky),
+            "undeliverable" => Ok(Verdict::Undeliverable),
+            "invalid" => Ok(Verdict::Invalid),
+            "unknown" => Ok(Verdict::Unknown),
+            other => Err(format!("Unknown verdict: {}", other))
+        }
+    }
+}
+
+impl VerificationResult {
+    /// Summarizes the result into a single verdict
+    /// 
+    /// Only an SMTP acceptance counts as deliverable; an MX record alone
+    /// leaves the verdict unknown.
+    pub fn verdict(&self) -> Verdict {
+        let checks = &self.domain_checks;
+        if !self.email.is_valid {
+            return Verdict::Invalid;
+        }
+        if checks.domain_exists == Some(false) || checks.null_mx == Some(true) {
+            return Verdict::Undeliverable;
+        }
+
+        let suspicious = checks.is_blocklisted == Some(true)
+            || checks.is_newly_registered == Some(true)
+            || self.email.is_confusable_spoof == Some(true)
+            || self.email.is_typosquat == Some(true);
+        match self.smtp.as_ref().map(|smtp| (smtp.is_deliverable, smtp.is_catch_all)) {
+            Some((Some(false), _)) => Verdict::Undeliverable,
+            Some((Some(true), Some(true))) => Verdict::Risky,
+            Some((Some(true), _)) if suspicious => Verdict::Risky,
+            Some((Some(true), _)) => Verdict::Deliverable,
+            _ if suspicious => Verdict::Risky,
+            _ => Verdict::Unknown
+        }
+    }
+
+    /// Whether the result has expired and should be re-verified at `now_ms`
+    pub fn is_due_for_reverification(&self, now_ms: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now_ms)
+    }
+}
+
+/// Returns the prior results that are due for re-verification
+/// 
+/// List hygiene is ongoing: feed the results of the last run back in and
+/// re-verify the addresses returned. Results without network checks (invalid
+/// syntax) never come due.
+/// 
+/// # Examples
+/// ```
+/// # use email_validator_wasm::{due_for_reverification, VerificationResult};
+/// # let previous: Vec<VerificationResult> = Vec::new();
+/// for result in due_for_reverification(&previous) {
+///     let (local_part, domain) = (result.email.local_part.as_deref(), result.email.domain.as_deref());
+///     // queue local_part@domain for another run
+/// }
+/// ```
+pub fn due_for_reverification(results: &[VerificationResult]) -> Vec<&VerificationResult> {
+    let now = unix_time_ms();
+    results.iter().filter(|result| result.is_due_for_reverification(now)).collect()
+}
+
+/// Upper bounds for the network stages (None leaves a stage unbounded)
+/// 
+/// A stage that times out reports a "Timeout" error in its own result field
+/// (`dns_error`, `smtp.error` or `breach_error`) and the pipeline continues.
+/// Serialized as milliseconds (`{"dns_ms": 2000}`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StageTimeouts {
+    /// Each DNS stage (MX, then SPF/DMARC)
+    #[serde(rename = "dns_ms", with = "crate::time::option_duration_ms", skip_serializing_if = "Option::is_none")]
+    pub dns: Option<Duration>,
+    /// The SMTP probe, including retries
+    #[serde(rename = "smtp_ms", with = "crate::time::option_duration_ms", skip_serializing_if = "Option::is_none")]
+    pub smtp: Option<Duration>,
+    /// The breach range lookup
+    #[serde(rename = "breach_ms", with = "crate::time::option_duration_ms", skip_serializing_if = "Option::is_none")]
+    pub breach: Option<Duration>,
+    /// The Gravatar lookup
+    #[serde(rename = "gravatar_ms", with = "crate::time::option_duration_ms", skip_serializing_if = "Option::is_none")]
+    pub gravatar: Option<Duration>,
+    /// The RDAP domain age lookup
+    #[serde(rename = "rdap_ms", with = "crate::time::option_duration_ms", skip_serializing_if = "Option::is_none")]
+    pub rdap: Option<Duration>,
+    /// The Certificate Transparency search
+    #[serde(rename = "ct_log_ms", with = "crate::time::option_duration_ms", skip_serializing_if = "Option::is_none")]
+    pub ct_log: Option<Duration>
+}
+
+/// Email verification pipeline: syntax validation followed by DNS checks
+/// 
+/// Lookups go through the provided `DnsResolver`, so the same pipeline runs
+/// against system DNS, DNS-over-HTTPS, an internal resolver or a mock.
+/// Domain checks can optionally be cached so a batch with many addresses at
+/// the same domain performs a single lookup.
+/// 
+/// # Examples
+/// ```no_run
+/// # use email_validator_wasm::dns::SystemResolver;
+/// # use email_validator_wasm::{LruCache, Verifier};
+/// # use std::time::Duration;
+/// # async fn example(resolver: SystemResolver) -> Result<(), email_validator_wasm::EmailParseError> {
+/// let verifier = Verifier::new(resolver)
+///     .with_cache(LruCache::new(10_000), Duration::from_secs(300));
+/// let result = verifier.verify("user@example.com").await?;
+/// assert_eq!(result.domain_checks.has_mx, Some(true));
+/// # Ok(())
+/// # }
+/// ```
+pub struct Verifier<R, B = NoBreachCheck> {
+    resolver: R,
+    breach_source: Option<B>,
+    validation_options: ValidationOptions,
+    cache: Option<Box<dyn VerificationCache<DomainChecks>>>,
+    cache_ttl: Duration,
+    check_mail_auth: bool,
+    check_security: bool,
+    blocklists: Vec<Blocklist>,
+    check_gravatar: bool,
+    min_domain_age: Option<Duration>,
+    ct_log_endpoint: Option<String>,
+    result_ttl: Duration,
+    timeouts: StageTimeouts,
+    cancel: Option<CancellationToken>,
+    observer: Option<Box<dyn EventObserver>>,
+    log_redaction: LogRedaction,
+    #[cfg(not(target_arch = "wasm32"))]
+    smtp: Option<Arc<SmtpVerifier>>
+}
+
+impl<R: DnsResolver> Verifier<R> {
+    pub fn new(resolver: R) -> Self {
+        Verifier {
+            resolver,
+            breach_source: None,
+            validation_options: ValidationOptions::default(),
+            cache: None,
+            cache_ttl: Duration::ZERO,
+            check_mail_auth: false,
+            check_security: false,
+            blocklists: Vec::new(),
+            check_gravatar: false,
+            min_domain_age: None,
+            ct_log_endpoint: None,
+            result_ttl: DEFAULT_RESULT_TTL,
+            timeouts: StageTimeouts::default(),
+            cancel: None,
+            observer: None,
+            log_redaction: LogRedaction::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            smtp: None
+        }
+    }
+}
+
+impl<R: DnsResolver, B: BreachRangeSource> Verifier<R, B> {
+    /// Sets the options used for the syntax validation stage
+    pub fn with_validation_options(mut self, options: ValidationOptions) -> Self {
+        self.validation_options = options;
+        self
+    }
+
+    /// Caches domain checks in `cache` for `ttl`, keyed by lowercased domain
+    /// 
+    /// Lookups that fail with a DNS error are not cached.
+    pub fn with_cache(mut self, cache: impl VerificationCache<DomainChecks> + 'static, ttl: Duration) -> Self {
+        self.cache = Some(Box::new(cache));
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// Enables the SPF/DMARC stage, which fetches the domain's TXT records and
+    /// adjusts `domain_score` based on the mail authentication it publishes
+    /// (SPF, DMARC and BIMI)
+    pub fn with_mail_auth_checks(mut self, enabled: bool) -> Self {
+        self.check_mail_auth = enabled;
+        self
+    }
+
+    /// Enables the transport security stage, reporting the domain's MTA-STS
+    /// policy mode and whether its primary MX supports DANE
+    /// 
+    /// Policies are fetched over HTTPS, which native builds only support with
+    /// the `tls` feature. TLSA records are reported as found; DNSSEC
+    /// validation is left to the resolver.
+    pub fn with_security_checks(mut self, enabled: bool) -> Self {
+        self.check_security = enabled;
+        self
+    }
+
+    /// Enables the blocklist stage, querying `lists` in order for the domain
+    /// and penalizing `domain_score` heavily when one lists it
+    pub fn with_blocklists(mut self, lists: Vec<Blocklist>) -> Self {
+        self.blocklists = lists;
+        self
+    }
+
+    /// Enables the Gravatar stage, reporting whether the address has an avatar
+    /// on gravatar.com (only its SHA-256 hash is sent)
+    /// 
+    /// Native builds need the `tls` feature to query Gravatar.
+    pub fn with_gravatar_check(mut self, enabled: bool) -> Self {
+        self.check_gravatar = enabled;
+        self
+    }
+
+    /// Enables the domain age stage, looking up the domain's registration
+    /// date over RDAP and flagging domains younger than `min_age` (None
+    /// disables it)
+    /// 
+    /// Native builds need the `tls` feature to query RDAP. Only TLDs whose
+    /// registry is known are checked; others report no date.
+    pub fn with_domain_age_check(mut self, min_age: Option<Duration>) -> Self {
+        self.min_domain_age = min_age;
+        self
+    }
+
+    /// Enables the Certificate Transparency stage, searching the CT logs for
+    /// the domain through `endpoint` (None disables it) and slightly raising
+    /// `domain_score` when certificates are found
+    /// 
+    /// The domain replaces `{domain}` in the endpoint URL, which must return
+    /// matching log entries as a JSON array (see `DEFAULT_CT_LOG_ENDPOINT`).
+    /// Native builds need the `tls` feature to query it.
+    pub fn with_ct_log_check(mut self, endpoint: Option<&str>) -> Self {
+        self.ct_log_endpoint = endpoint.map(str::to_string);
+        self
+    }
+
+    /// Sets how long results stay fresh before `expires_at` (default 30 days)
+    /// 
+    /// Results whose checks failed or were inconclusive expire after an hour
+    /// instead, so they are retried soon.
+    pub fn with_result_ttl(mut self, ttl: Duration) -> Self {
+        self.result_ttl = ttl;
+        self
+    }
+
+    /// Enables the SMTP stage, probing the mailbox on the domain's MX hosts
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_smtp(mut self, smtp: SmtpVerifier) -> Self {
+        self.smtp = Some(Arc::new(smtp));
+        self
+    }
+
+    /// Bounds how long each network stage may take
+    pub fn with_timeouts(mut self, timeouts: StageTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Stops verification once `token` is cancelled: pending stages are
+    /// abandoned and `verify` returns a "Cancelled" error, for this and every
+    /// later address
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Reports each stage's start and outcome to `observer`, for progress
+    /// displays and audit logs
+    pub fn with_observer(mut self, observer: impl EventObserver + 'static) -> Self {
+        self.observer = Some(Box::new(observer));
+        self
+    }
+
+    /// Sets how addresses appear in stage spans and observer events (masked
+    /// to `u***@example.com` by default)
+    pub fn with_log_redaction(mut self, redaction: LogRedaction) -> Self {
+        self.log_redaction = redaction;
+        self
+    }
+
+    /// Enables the breach stage, checking the address against the
+    /// HIBP-compatible range API behind `source` (k-anonymity: only a
+    /// 5-character hash prefix is sent)
+    pub fn with_breach_check<S: BreachRangeSource>(self, source: S) -> Verifier<R, S> {
+        Verifier {
+            resolver: self.resolver,
+            breach_source: Some(source),
+            validation_options: self.validation_options,
+            cache: self.cache,
+            cache_ttl: self.cache_ttl,
+            check_mail_auth: self.check_mail_auth,
+            check_security: self.check_security,
+            blocklists: self.blocklists,
+            check_gravatar: self.check_gravatar,
+            min_domain_age: self.min_domain_age,
+            ct_log_endpoint: self.ct_log_endpoint,
+            result_ttl: self.result_ttl,
+            timeouts: self.timeouts,
+            cancel: self.cancel,
+            observer: self.observer,
+            log_redaction: self.log_redaction,
+            #[cfg(not(target_arch = "wasm32"))]
+            smtp: self.smtp
+        }
+    }
+
+    /// Returns the resolver used for DNS lookups
+    pub fn resolver(&self) -> &R {
+        &self.resolver
+    }
+
+    /// Validates `email` and, if it is syntactically valid, runs the enabled DNS
+    /// stages for its domain (MX always, SPF/DMARC when enabled) and the
+    /// breach stage when enabled
+    /// 
+    /// DNS failures are reported in `dns_error` rather than returned as errors,
+    /// matching how expected validation failures are handled. Cancellation is
+    /// the exception: it returns an error with `error_type` "Cancelled".
+    pub async fn verify(&self, email: &str) -> Result<VerificationResult, EmailParseError> {
+        let stage = self.address_stage("verify", email.rsplit_once('@').map_or("", |(_, domain)| domain), email);
+        let result = stage.run(self.run_stages(email)).await;
+        stage.finish(match &result {
+            Ok(result) if result.email.is_valid => "valid",
+            Ok(_) => "invalid",
+            Err(e) if e.error_type == "Cancelled" => "cancelled",
+            Err(_) => "error"
+        });
+        result
+    }
+
+    /// Verifies `email`, then attaches `enricher`'s metadata to a valid result
+    pub async fn verify_enriched<E: Enricher>(&self, email: &str, enricher: &E) -> Result<VerificationResult, EmailParseError> {
+        let mut result = self.verify(email).await?;
+        enrich_result(&mut result.email, enricher).await;
+        Ok(result)
+    }
+
+    async fn run_stages(&self, email: &str) -> Result<VerificationResult, EmailParseError> {
+        self.ensure_not_cancelled()?;
+        let mut email = parse_and_validate_email_with_options(email, &self.validation_options)?;
+
+        let domain_checks = match (&email.is_valid, &email.domain) {
+            (true, Some(domain)) => self.check_domain(domain).await?,
+            _ => DomainChecks::default()
+        };
+
+        if let Some(breakdown) = email.score_breakdown.as_mut() {
+            // Contributions are recorded unweighted; `score_weights` applies to the final sum
+            let mut score = breakdown.total();
+            ScoreBreakdown::record(&mut score, &mut breakdown.mail_auth, |score| (score + mail_auth_adjustment(&domain_checks)).clamp(0.0, 100.0));
+            if domain_checks.has_bimi == Some(true) {
+                ScoreBreakdown::record(&mut score, &mut breakdown.bimi, |score| (score + BIMI_BOOST).min(100.0));
+            }
+            if domain_checks.is_blocklisted == Some(true) {
+                ScoreBreakdown::record(&mut score, &mut breakdown.blocklist, |score| (score - BLOCKLIST_PENALTY).max(0.0));
+            }
+            if domain_checks.is_newly_registered == Some(true) {
+                ScoreBreakdown::record(&mut score, &mut breakdown.domain_age, |score| (score - NEW_DOMAIN_PENALTY).max(0.0));
+            }
+            if domain_checks.has_ct_certificates == Some(true) {
+                ScoreBreakdown::record(&mut score, &mut breakdown.ct_log, |score| (score + CT_LOG_BOOST).min(100.0));
+            }
+            email.domain_score = Some(self.validation_options.score_weights.map_or(score, |weights| weights.score(breakdown)));
+        }
+
+        let smtp = self.check_smtp(&email, &domain_checks).await?;
+
+        let (found_in_breach, breach_error) = match (&self.breach_source, email.is_valid, &email.local_part, &email.domain) {
+            (Some(source), true, Some(local_part), Some(domain)) => {
+                let stage = self.address_stage("breach", domain, &format!("{}@{}", local_part, domain));
+                let lookup = stage.run(self.bounded(check_breach(source, &format!("{}@{}", local_part, domain)), self.timeouts.breach)).await;
+                let (found_in_breach, breach_error, outcome) = match lookup {
+                    Ok(Some(Ok(found))) => (Some(found), None, if found { "found" } else { "not_found" }),
+                    Ok(Some(Err(e))) => (None, Some(e), "error"),
+                    Ok(None) => (None, Some(BreachError::new("Timeout", "Breach stage timed out", None)), "timeout"),
+                    Err(e) => {
+                        stage.finish("cancelled");
+                        return Err(e);
+                    }
+                };
+                stage.finish(outcome);
+                (found_in_breach, breach_error)
+            }
+            _ => (None, None)
+        };
+
+        let has_gravatar = match (self.check_gravatar, email.is_valid, &email.local_part, &email.domain) {
+            (true, true, Some(local_part), Some(domain)) => {
+                let stage = self.address_stage("gravatar", domain, &format!("{}@{}", local_part, domain));
+                let lookup = stage.run(self.bounded(check_gravatar(&format!("{}@{}", local_part, domain)), self.timeouts.gravatar)).await;
+                stage.finish(match &lookup {
+                    Ok(Some(Ok(true))) => "found",
+                    Ok(Some(Ok(false))) => "not_found",
+                    Ok(Some(Err(_))) => "error",
+                    Ok(None) => "timeout",
+                    Err(_) => "cancelled"
+                });
+                lookup?.and_then(Result::ok)
+            }
+            _ => None
+        };
+
+        let mut result = VerificationResult { email, domain_checks, smtp, found_in_breach, breach_error, has_gravatar, verified_at: None, expires_at: None };
+        if result.email.is_valid {
+            let verified_at = unix_time_ms();
+            let ttl = if is_inconclusive(&result) { RETRY_RESULT_TTL.min(self.result_ttl) } else { self.result_ttl };
+            result.verified_at = Some(verified_at);
+            result.expires_at = Some(verified_at.saturating_add(ttl.as_millis() as u64));
+        }
+        Ok(result)
+    }
+
+    /// Runs `future` within `timeout`, returning None if it timed out and an
+    /// error if verification was cancelled
+    async fn bounded<F: Future>(&self, future: F, timeout: Option<Duration>) -> Result<Option<F::Output>, EmailParseError> {
+        match interruptible(future, timeout, self.cancel.as_ref()).await {
+            Ok(output) => Ok(Some(output)),
+            Err(Interrupted::TimedOut) => Ok(None),
+            Err(Interrupted::Cancelled) => Err(cancelled_error())
+        }
+    }
+
+    /// Starts instrumenting `name` on `domain`
+    fn stage<'a>(&'a self, name: &'static str, domain: &'a str) -> Stage<'a> {
+        Stage::start(name, domain, None, self.observer.as_deref())
+    }
+
+    /// Starts instrumenting `name` on the whole of `email`, which is only
+    /// reported in its redacted form
+    fn address_stage<'a>(&'a self, name: &'static str, domain: &'a str, email: &str) -> Stage<'a> {
+        let reported = (cfg!(feature = "tracing") || self.observer.is_some()).then(|| redact_email(email, self.log_redaction));
+        Stage::start(name, domain, reported, self.observer.as_deref())
+    }
+
+    fn ensure_not_cancelled(&self) -> Result<(), EmailParseError> {
+        match &self.cancel {
+            Some(cancel) if cancel.is_cancelled() => Err(cancelled_error()),
+            _ => Ok(())
+        }
+    }
+
+    async fn check_domain(&self, domain: &str) -> Result<DomainChecks, EmailParseError> {
+        let key = domain.to_lowercase();
+        if let Some(cached) = self.cache.as_ref().and_then(|cache| cache.get(&key)) {
+            return Ok(cached);
+        }
+
+        let mut checks = DomainChecks::default();
+        let stage = self.stage("mx", domain);
+        let completed = stage.run(self.bounded(self.check_mx(domain, &mut checks), self.timeouts.dns)).await;
+        stage.finish(match &completed {
+            Ok(Some(())) => mx_outcome(&checks),
+            Ok(None) => "timeout",
+            Err(_) => "cancelled"
+        });
+        if completed?.is_none() {
+            checks.dns_error = Some(dns_timeout());
+        }
+
+        if self.check_mail_auth && checks.domain_exists == Some(true) {
+            let stage = self.stage("mail_auth", domain);
+            let completed = stage.run(self.bounded(self.check_spf_dmarc(domain, &mut checks), self.timeouts.dns)).await;
+            stage.finish(match &completed {
+                Ok(Some(())) if checks.dns_error.is_some() => "dns_error",
+                Ok(Some(())) => "checked",
+                Ok(None) => "timeout",
+                Err(_) => "cancelled"
+            });
+            if completed?.is_none() {
+                checks.dns_error.get_or_insert_with(dns_timeout);
+            }
+        }
+
+        if self.check_security && checks.domain_exists == Some(true) {
+            let stage = self.stage("security", domain);
+            let completed = stage.run(self.bounded(self.check_transport_security(domain, &mut checks), self.timeouts.dns)).await;
+            stage.finish(match &completed {
+                Ok(Some(())) => "checked",
+                Ok(None) => "timeout",
+                Err(_) => "cancelled"
+            });
+            completed?;
+        }
+
+        if !self.blocklists.is_empty() && checks.domain_exists == Some(true) {
+            let stage = self.stage("blocklist", domain);
+            let completed = stage.run(self.bounded(check_blocklists(&self.resolver, domain, &self.blocklists), self.timeouts.dns)).await;
+            stage.finish(match &completed {
+                Ok(Some((Some(true), _))) => "listed",
+                Ok(Some((Some(false), _))) => "clean",
+                Ok(Some((None, _))) => "dns_error",
+                Ok(None) => "timeout",
+                Err(_) => "cancelled"
+            });
+            if let Some((is_blocklisted, blocklist)) = completed? {
+                checks.is_blocklisted = is_blocklisted;
+                checks.blocklist = blocklist;
+            }
+        }
+
+        if let (Some(min_age), Some(true)) = (self.min_domain_age, checks.domain_exists) {
+            let stage = self.stage("rdap", domain);
+            let lookup = stage.run(self.bounded(registration_date(domain), self.timeouts.rdap)).await;
+            if let Ok(Some(Ok(Some(registered_at)))) = lookup {
+                checks.registered_at = Some(registered_at);
+                checks.is_newly_registered = Some(unix_time_ms().saturating_sub(registered_at) < min_age.as_millis() as u64);
+            }
+            stage.finish(match (&lookup, checks.is_newly_registered) {
+                (Ok(Some(Ok(_))), Some(true)) => "new",
+                (Ok(Some(Ok(_))), Some(false)) => "established",
+                (Ok(Some(Ok(_))), None) => "no_date",
+                (Ok(Some(Err(_))), _) => "error",
+                (Ok(None), _) => "timeout",
+                (Err(_), _) => "cancelled"
+            });
+            lookup?;
+        }
+
+        if let (Some(endpoint), Some(true)) = (&self.ct_log_endpoint, checks.domain_exists) {
+            let stage = self.stage("ct_log", domain);
+            let lookup = stage.run(self.bounded(check_ct_logs(endpoint, domain), self.timeouts.ct_log)).await;
+            stage.finish(match &lookup {
+                Ok(Some(Ok(true))) => "found",
+                Ok(Some(Ok(false))) => "not_found",
+                Ok(Some(Err(_))) => "error",
+                Ok(None) => "timeout",
+                Err(_) => "cancelled"
+            });
+            checks.has_ct_certificates = lookup?.and_then(Result::ok);
+        }
+
+        #[cfg(feature = "metrics")]
+        if let Some(error) = &checks.dns_error {
+            crate::telemetry::record_dns_failure(error);
+        }
+
+        if let (Some(cache), None) = (&self.cache, &checks.dns_error) {
+            cache.insert(&key, checks.clone(), self.cache_ttl);
+        }
+
+        Ok(checks)
+    }
+
+    /// Probes the mailbox on a worker thread, so the stage timeout and
+    /// cancellation take effect even while a socket read is blocked
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn check_smtp(&self, email: &EmailParseResult, checks: &DomainChecks) -> Result<Option<SmtpCheck>, EmailParseError> {
+        let (Some(smtp), Some(local_part), Some(domain), Some(true), Some(mx_records)) =
+            (&self.smtp, &email.local_part, &email.domain, checks.has_mx, checks.mx_records.as_deref())
+        else {
+            return Ok(None);
+        };
+
+        let stage = self.address_stage("smtp", domain, &format!("{}@{}", local_part, domain));
+        #[cfg(feature = "metrics")]
+        let started = std::time::Instant::now();
+        let (smtp, address, mx_records) = (smtp.clone(), format!("{}@{}", local_part, domain), mx_records.to_vec());
+        let (timeout, cancel) = (self.timeouts.smtp, self.cancel.clone());
+        let probe = blocking::run(move || smtp.check_bounded(&address, &mx_records, timeout, cancel.as_ref()));
+        let check = match stage.run(self.bounded(probe, timeout)).await {
+            Ok(Some(check)) => check,
+            Ok(None) => SmtpCheck { error: Some(SmtpError::new("Timeout", "SMTP stage timed out", None)), ..SmtpCheck::default() },
+            Err(e) => {
+                stage.finish("cancelled");
+                return Err(e);
+            }
+        };
+        #[cfg(feature = "metrics")]
+        crate::telemetry::record_smtp_check(&check, started.elapsed());
+        if let Err(e) = self.ensure_not_cancelled() {
+            stage.finish("cancelled");
+            return Err(e);
+        }
+        stage.finish(match (check.is_deliverable, &check.error) {
+            (Some(true), _) => "deliverable",
+            (Some(false), _) => "undeliverable",
+            (None, Some(error)) if error.error_type == "Timeout" => "timeout",
+            (None, _) => "inconclusive"
+        });
+        Ok(Some(check))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    async fn check_smtp(&self, _email: &EmailParseResult, _checks: &DomainChecks) -> Result<Option<SmtpCheck>, EmailParseError> {
+        Ok(None)
+    }
+
+    async fn check_mx(&self, domain: &str, checks: &mut DomainChecks) {
+        match self.resolver.lookup_mx(domain).await {
+            Ok(mut records) => {
+                records.sort_by_key(|record| record.preference);
+                let null_mx = is_null_mx(&records);
+                checks.domain_exists = Some(true);
+                checks.has_mx = Some(!records.is_empty() && !null_mx);
+                checks.null_mx = Some(null_mx);
+                checks.mx_provider = identify_mx_provider(&records);
+                checks.mx_records = Some(records);
+
+                if null_mx {
+                    checks.deliverability_score = Some(0.0);
+                    checks.error_code = Some("NullMx".to_string());
+                } else if checks.has_mx == Some(true) {
+                    checks.deliverability_score = Some(100.0);
+                } else {
+                    // No MX: senders fall back to the domain's address records (RFC 5321 section 5.1)
+                    checks.deliverability_score = Some(50.0);
+                }
+            }
+            Err(e) if e.is_nx_domain() => {
+                checks.domain_exists = Some(false);
+                checks.has_mx = Some(false);
+                checks.deliverability_score = Some(0.0);
+                checks.error_code = Some("NxDomain".to_string());
+            }
+            Err(e) => checks.dns_error = Some(e)
+        }
+    }
+
+    async fn check_spf_dmarc(&self, domain: &str, checks: &mut DomainChecks) {
+        match self.resolver.lookup_txt(domain).await {
+            Ok(records) => checks.has_spf = Some(records.iter().any(|r| has_tag_prefix(r, "v=spf1"))),
+            Err(e) if e.is_nx_domain() => checks.has_spf = Some(false),
+            Err(e) => {
+                checks.dns_error.get_or_insert(e);
+                return;
+            }
+        }
+
+        match self.resolver.lookup_txt(&format!("_dmarc.{}", domain)).await {
+            Ok(records) => {
+                let record = records.iter().find(|r| has_tag_prefix(r, "v=DMARC1"));
+                checks.has_dmarc = Some(record.is_some());
+                checks.dmarc_policy = record.and_then(|r| parse_dmarc_policy(r));
+            }
+            Err(e) if e.is_nx_domain() => checks.has_dmarc = Some(false),
+            Err(e) => {
+                checks.dns_error.get_or_insert(e);
+            }
+        }
+
+        // BIMI is an enrichment, so a failed lookup leaves it unknown rather
+        // than failing the stage
+        match self.resolver.lookup_txt(&format!("default._bimi.{}", domain)).await {
+            Ok(records) => {
+                let record = records.iter().find(|r| has_tag_prefix(r, "v=BIMI1"));
+                checks.has_bimi = Some(record.is_some());
+                checks.bimi_logo_url = record.and_then(|r| tag_value(r, "l")).filter(|url| !url.is_empty());
+            }
+            Err(e) if e.is_nx_domain() => checks.has_bimi = Some(false),
+            Err(_) => {}
+        }
+    }
+
+    /// Checks MTA-STS and DANE; failures leave the fields unset rather than
+    /// failing the domain, since both are optional hardening
+    async fn check_transport_security(&self, domain: &str, checks: &mut DomainChecks) {
+        if let Ok(records) = self.resolver.lookup_txt(&format!("_mta-sts.{}", domain)).await {
+            if records.iter().any(|r| has_tag_prefix(r, "v=STSv1")) {
+                checks.mta_sts = fetch_policy(domain).await.ok().and_then(|body| parse_policy(&body));
+            }
+        }
+
+        let primary_mx = checks.mx_records.as_ref().and_then(|records| records.first()).filter(|_| checks.has_mx == Some(true));
+        if let Some(mx) = primary_mx {
+            checks.dane = match self.resolver.lookup_tlsa(&format!("_25._tcp.{}", mx.exchange)).await {
+                Ok(records) => Some(!records.is_empty()),
+                Err(e) if e.is_nx_domain() => Some(false),
+                Err(_) => None
+            };
+        }
+    }
+}
+
+/// Whether any network check failed or could not decide, so the result
+/// should be retried soon rather than trusted for the full TTL
+fn is_inconclusive(result: &VerificationResult) -> bool {
+    result.domain_checks.dns_error.is_some()
+        || result.breach_error.is_some()
+        || result.smtp.as_ref().is_some_and(|smtp| smtp.is_deliverable.is_none())
+}
+
+fn cancelled_error() -> EmailParseError {
+    EmailParseError {
+        error_type: "Cancelled".to_string(),
+        message: "Verification was cancelled".to_string(),
+        details: None,
+        position: None
+    }
+}
+
+fn dns_timeout() -> DnsError {
+    DnsError::new("Timeout", "DNS stage timed out", None)
+}
+
+/// Outcome of the MX stage, for tracing
+fn mx_outcome(checks: &DomainChecks) -> &'static str {
+    match (&checks.dns_error, checks.domain_exists, checks.null_mx, checks.has_mx) {
+        (Some(_), _, _, _) => "dns_error",
+        (_, Some(false), _, _) => "nx_domain",
+        (_, _, Some(true), _) => "null_mx",
+        (_, _, _, Some(true)) => "has_mx",
+        _ => "no_mx"
+    }
+}
+
+/// Whether a TXT record starts with `prefix`, ignoring ASCII case
+fn has_tag_prefix(record: &str, prefix: &str) -> bool {
+    record.trim_start().get(..prefix.len()).is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+}
+
+/// Value of tag `name` in a `tag=value; ...` record (DMARC, BIMI)
+fn tag_value(record: &str, name: &str) -> Option<String> {
+    record
+        .split(';')
+        .filter_map(|tag| tag.split_once('='))
+        .find(|(tag, _)| tag.trim().eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.trim().to_string())
+}
+
+/// Extracts the `p=` tag from a DMARC record
+fn parse_dmarc_policy(record: &str) -> Option<DmarcPolicy> {
+    tag_value(record, "p")
+        .and_then(|value| match value.to_lowercase().as_str() {
+            "none" => Some(DmarcPolicy::None),
+            "quarantine" => Some(DmarcPolicy::Quarantine),
+            "reject" => Some(DmarcPolicy::Reject),
+            _ => None
+        })
+}
+
+/// How long results stay fresh by default
+const DEFAULT_RESULT_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// How long failed or inconclusive results stay fresh
+const RETRY_RESULT_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Penalty for domains listed on a DNS blocklist
+const BLOCKLIST_PENALTY: f64 = 60.0;
+
+/// Penalty for domains younger than the verifier's minimum domain age
+const NEW_DOMAIN_PENALTY: f64 = 40.0;
+
+/// Boost for domains with certificates in the CT logs
+const CT_LOG_BOOST: f64 = 5.0;
+
+/// Boost for domains publishing BIMI, which requires an enforcing DMARC
+/// policy and is almost exclusively done by established senders
+const BIMI_BOOST: f64 = 10.0;
+
+/// Score adjustment derived from the SPF/DMARC stage
+/// 
+/// Domains without any mail authentication are penalized heavily since they
+/// correlate with throwaway domains; each published mechanism earns a small
+/// boost, with an extra boost for an enforcing DMARC policy.
+fn mail_auth_adjustment(checks: &DomainChecks) -> f64 {
+    match (checks.has_spf, checks.has_dmarc) {
+        (Some(false), Some(false)) => -20.0,
+        (Some(has_spf), Some(has_dmarc)) => {
+            let mut adjustment = 0.0;
+            if has_spf {
+                adjustment += 5.0;
+            }
+            if has_dmarc {
+                adjustment += 5.0;
+            }
+            if matches!(checks.dmarc_policy, Some(DmarcPolicy::Quarantine | DmarcPolicy::Reject)) {
+                adjustment += 5.0;
+            }
+            adjustment
+        }
+        _ => 0.0
+    }
+}
+
+/// Whether the MX set is a null MX (RFC 7505): a single record with
+/// preference 0 whose exchange is the root name
+fn is_null_mx(records: &[MxRecord]) -> bool {
+    matches!(records, [record] if record.preference == 0 && record.exchange.trim_end_matches('.').is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::{MockResolver, TlsaRecord};
+    use crate::smtp::{SmtpConnector, SmtpStream};
+    use crate::test_util::block_on;
+
+    fn mx(preference: u16, exchange: &str) -> MxRecord {
+        MxRecord { preference, exchange: exchange.to_string() }
+    }
+
+    /// Tests MX discovery, missing domains and DNS failures through the pipeline
+    #[test]
+    fn test_verify_with_mock_resolver() {
+        let resolver = MockResolver::new()
+            .with_mx("example.com", vec![mx(20, "mx2.example.com"), mx(10, "mx1.example.com")])
+            .with_mx("hosted.com", vec![mx(1, "aspmx.l.google.com")])
+            .with_txt("nomx.com", vec!["v=spf1 -all".to_string()])
+            .with_error("broken.com", DnsError::new("Timeout", "DNS query timed out", None));
+        let verifier = Verifier::new(resolver);
+
+        let result = block_on(verifier.verify("user@example.com")).unwrap();
+        assert!(result.email.is_valid);
+        assert_eq!(result.domain_checks.domain_exists, Some(true));
+        assert_eq!(result.domain_checks.has_mx, Some(true));
+        assert_eq!(result.domain_checks.mx_records, Some(vec![mx(10, "mx1.example.com"), mx(20, "mx2.example.com")]));
+
+        assert_eq!(result.domain_checks.null_mx, Some(false));
+        assert_eq!(result.domain_checks.deliverability_score, Some(100.0));
+        assert_eq!(result.domain_checks.mx_provider, None);
+
+        let result = block_on(verifier.verify("user@hosted.com")).unwrap();
+        assert_eq!(result.domain_checks.mx_provider, Some(MxProvider::GoogleWorkspace));
+
+        let result = block_on(verifier.verify("user@nomx.com")).unwrap();
+        assert_eq!(result.domain_checks.domain_exists, Some(true));
+        assert_eq!(result.domain_checks.has_mx, Some(false));
+        assert_eq!(result.domain_checks.deliverability_score, Some(50.0));
+
+        let result = block_on(verifier.verify("user@missing.com")).unwrap();
+        assert_eq!(result.domain_checks.domain_exists, Some(false));
+        assert_eq!(result.domain_checks.has_mx, Some(false));
+        assert_eq!(result.domain_checks.mx_records, None);
+        assert_eq!(result.domain_checks.error_code, Some("NxDomain".to_string()));
+
+        let result = block_on(verifier.verify("user@broken.com")).unwrap();
+        assert_eq!(result.domain_checks.has_mx, None);
+        assert_eq!(result.domain_checks.dns_error.unwrap().error_type, "Timeout");
+
+        let result = block_on(verifier.verify("invalid-email")).unwrap();
+        assert!(!result.email.is_valid);
+        assert_eq!(result.domain_checks.has_mx, None);
+    }
+
+    /// Tests that a null MX record marks the domain as not accepting mail
+    #[test]
+    fn test_verify_null_mx() {
+        let resolver = MockResolver::new().with_mx("nomail.com", vec![mx(0, "")]);
+        let verifier = Verifier::new(resolver);
+
+        let result = block_on(verifier.verify("user@nomail.com")).unwrap();
+        assert_eq!(result.domain_checks.domain_exists, Some(true));
+        assert_eq!(result.domain_checks.has_mx, Some(false));
+        assert_eq!(result.domain_checks.null_mx, Some(true));
+        assert_eq!(result.domain_checks.deliverability_score, Some(0.0));
+        assert_eq!(result.domain_checks.error_code, Some("NullMx".to_string()));
+
+        assert!(is_null_mx(&[mx(0, ".")]));
+        assert!(!is_null_mx(&[mx(0, ""), mx(10, "")]));
+        assert!(!is_null_mx(&[mx(10, ".")]));
+        assert!(!is_null_mx(&[]));
+    }
+
+    /// Tests SPF/DMARC discovery and its effect on the domain score
+    #[test]
+    fn test_verify_mail_auth_checks() {
+        let resolver = MockResolver::new()
+            .with_mx("secure.com", vec![mx(10, "mx.secure.com")])
+            .with_txt("secure.com", vec!["google-site-verification=abc".to_string(), "v=spf1 include:_spf.google.com -all".to_string()])
+            .with_txt("_dmarc.secure.com", vec!["v=DMARC1; p=reject; rua=mailto:d@secure.com".to_string()])
+            .with_mx("bare.com", vec![mx(10, "mx.bare.com")])
+            .with_mx("brand.com", vec![mx(10, "mx.brand.com")])
+            .with_txt("brand.com", vec!["v=spf1 -all".to_string()])
+            .with_txt("_dmarc.brand.com", vec!["v=DMARC1; p=quarantine".to_string()])
+            .with_txt("default._bimi.brand.com", vec!["v=BIMI1; l=https://brand.com/logo.svg; a=".to_string()]);
+
+        let verifier = Verifier::new(&resolver).with_mail_auth_checks(true);
+
+        let result = block_on(verifier.verify("user@secure.com")).unwrap();
+        assert_eq!(result.domain_checks.has_spf, Some(true));
+        assert_eq!(result.domain_checks.has_dmarc, Some(true));
+        assert_eq!(result.domain_checks.dmarc_policy, Some(DmarcPolicy::Reject));
+        assert_eq!(result.email.domain_score, Some(65.0));
+        assert_eq!(result.email.score_breakdown.as_ref().map(|b| b.mail_auth), Some(15.0));
+        assert_eq!(result.domain_checks.has_bimi, Some(false));
+
+        let result = block_on(verifier.verify("user@brand.com")).unwrap();
+        assert_eq!(result.domain_checks.has_bimi, Some(true));
+        assert_eq!(result.domain_checks.bimi_logo_url.as_deref(), Some("https://brand.com/logo.svg"));
+        assert_eq!(result.email.domain_score, Some(75.0));
+        assert_eq!(result.email.score_breakdown.as_ref().map(|b| b.bimi), Some(10.0));
+
+        let result = block_on(verifier.verify("user@bare.com")).unwrap();
+        assert_eq!(result.domain_checks.has_spf, Some(false));
+        assert_eq!(result.domain_checks.has_dmarc, Some(false));
+        assert_eq!(result.domain_checks.dmarc_policy, None);
+        assert_eq!(result.email.domain_score, Some(30.0));
+
+        // The stage is opt-in
+        let result = block_on(Verifier::new(&resolver).verify("user@bare.com")).unwrap();
+        assert_eq!(result.domain_checks.has_spf, None);
+        assert_eq!(result.email.domain_score, Some(50.0));
+    }
+
+    /// Tests DANE detection on the primary MX and that the security stage is opt-in
+    #[test]
+    fn test_verify_security_checks() {
+        let tlsa = TlsaRecord { usage: 3, selector: 1, matching_type: 1, data: "ab".repeat(32) };
+        let resolver = MockResolver::new()
+            .with_mx("dane.com", vec![mx(20, "backup.dane.com"), mx(10, "mx.dane.com")])
+            .with_tlsa("_25._tcp.mx.dane.com", vec![tlsa.clone()])
+            .with_mx("backup-only.com", vec![mx(10, "mx.backup-only.com"), mx(20, "mx.dane.com")])
+            .with_txt("nomx.com", vec!["v=spf1 -all".to_string()]);
+
+        let verifier = Verifier::new(&resolver).with_security_checks(true);
+
+        let result = block_on(verifier.verify("user@dane.com")).unwrap();
+        assert_eq!(result.domain_checks.dane, Some(true));
+        assert_eq!(result.domain_checks.mta_sts, None);
+        assert_eq!(result.domain_checks.dns_error, None);
+
+        let result = block_on(verifier.verify("user@backup-only.com")).unwrap();
+        assert_eq!(result.domain_checks.dane, Some(false));
+
+        let result = block_on(verifier.verify("user@nomx.com")).unwrap();
+        assert_eq!(result.domain_checks.dane, None);
+
+        // Resolvers without TLSA support leave DANE unknown
+        struct MxTxtOnly<'a>(&'a MockResolver);
+        impl DnsResolver for MxTxtOnly<'_> {
+            async fn lookup_mx(&self, domain: &str) -> Result<Vec<MxRecord>, DnsError> {
+                self.0.lookup_mx(domain).await
+            }
+
+            async fn lookup_txt(&self, domain: &str) -> Result<Vec<String>, DnsError> {
+                self.0.lookup_txt(domain).await
+            }
+        }
+        let result = block_on(Verifier::new(MxTxtOnly(&resolver)).with_security_checks(true).verify("user@dane.com")).unwrap();
+        assert_eq!(result.domain_checks.dane, None);
+        assert_eq!(result.domain_checks.has_mx, Some(true));
+
+        let result = block_on(Verifier::new(&resolver).verify("user@dane.com")).unwrap();
+        assert_eq!(result.domain_checks.dane, None);
+    }
+
+    /// Tests result timestamps, the shorter lifetime of failed checks and
+    /// selecting results due for re-verification
+    #[test]
+    fn test_verify_result_expiry() {
+        let resolver = MockResolver::new()
+            .with_mx("example.com", vec![mx(10, "mx.example.com")])
+            .with_error("broken.com", DnsError::new("Timeout", "DNS query timed out", None));
+        let verifier = Verifier::new(&resolver).with_result_ttl(Duration::from_secs(86_400));
+
+        let fresh = block_on(verifier.verify("user@example.com")).unwrap();
+        let verified_at = fresh.verified_at.unwrap();
+        assert_eq!(fresh.expires_at, Some(verified_at + 86_400_000));
+
+        let failed = block_on(verifier.verify("user@broken.com")).unwrap();
+        assert_eq!(failed.expires_at, Some(failed.verified_at.unwrap() + 3_600_000));
+
+        let invalid = block_on(verifier.verify("invalid-email")).unwrap();
+        assert_eq!((invalid.verified_at, invalid.expires_at), (None, None));
+
+        let mut stale = block_on(verifier.verify("old@example.com")).unwrap();
+        stale.expires_at = Some(verified_at - 1);
+        let results = [fresh, failed, invalid, stale];
+        let due: Vec<_> = due_for_reverification(&results).into_iter().filter_map(|result| result.email.local_part.as_deref()).collect();
+        assert_eq!(due, ["old"]);
+        assert!(results[1].is_due_for_reverification(verified_at + 3_600_000 + 1_000));
+    }
+
+    /// Tests that a listed domain is flagged and penalized
+    #[test]
+    fn test_verify_blocklists() {
+        let resolver = MockResolver::new()
+            .with_mx("spammy.com", vec![mx(10, "mx.spammy.com")])
+            .with_mx("clean.com", vec![mx(10, "mx.clean.com")])
+            .with_a("spammy.com.dbl.example", vec![std::net::Ipv4Addr::new(127, 0, 1, 2)]);
+        let verifier = Verifier::new(&resolver).with_blocklists(vec![Blocklist::new("Example DBL", "dbl.example")]);
+
+        let result = block_on(verifier.verify("user@spammy.com")).unwrap();
+        assert_eq!(result.domain_checks.is_blocklisted, Some(true));
+        assert_eq!(result.domain_checks.blocklist.as_deref(), Some("Example DBL"));
+        assert_eq!(result.email.domain_score, Some(0.0));
+        assert_eq!(result.email.score_breakdown.as_ref().map(|b| b.blocklist), Some(-50.0));
+
+        let result = block_on(verifier.verify("user@clean.com")).unwrap();
+        assert_eq!(result.domain_checks.is_blocklisted, Some(false));
+        assert_eq!(result.email.domain_score, Some(50.0));
+
+        // The stage only runs with lists configured
+        let result = block_on(Verifier::new(&resolver).verify("user@spammy.com")).unwrap();
+        assert_eq!(result.domain_checks.is_blocklisted, None);
+    }
+
+    /// Tests that the observer sees every stage start and finish in order
+    #[test]
+    fn test_verify_observer() {
+        use crate::events::PipelineEvent;
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let resolver = MockResolver::new().with_mx("example.com", vec![mx(10, "mx.example.com")]);
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let recorded = events.clone();
+        let verifier = Verifier::new(resolver).with_observer(move |event: &PipelineEvent| recorded.borrow_mut().push(event.clone()));
+
+        block_on(verifier.verify("user@example.com")).unwrap();
+        let events = events.borrow();
+        let stages: Vec<_> = events.iter().map(|event| event.stage()).collect();
+        assert_eq!(stages, ["verify", "mx", "mx", "verify"]);
+        assert_eq!(events[0], PipelineEvent::StageStarted { stage: "verify", domain: "example.com".to_string(), email: Some("u***@example.com".to_string()) });
+        assert!(matches!(&events[2], PipelineEvent::StageFinished { outcome, email: None, .. } if outcome == "has_mx"));
+        assert!(matches!(&events[3], PipelineEvent::StageFinished { outcome, .. } if outcome == "valid"));
+    }
+
+    /// Tests that addresses in events follow the configured redaction
+    #[test]
+    fn test_verify_log_redaction() {
+        use crate::events::PipelineEvent;
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let reported = |redaction: LogRedaction| {
+            let emails = Rc::new(RefCell::new(Vec::new()));
+            let recorded = emails.clone();
+            let verifier = Verifier::new(MockResolver::new())
+                .with_log_redaction(redaction)
+                .with_observer(move |event: &PipelineEvent| {
+                    if let PipelineEvent::StageStarted { email: Some(email), .. } = event {
+                        recorded.borrow_mut().push(email.clone());
+                    }
+                });
+            block_on(verifier.verify("jane.doe@example.com")).unwrap();
+            let emails = emails.borrow().clone();
+            emails
+        };
+        assert_eq!(reported(LogRedaction::Mask), ["j***@example.com"]);
+        assert_eq!(reported(LogRedaction::Hash), [crate::hash::hash_email("jane.doe@example.com")]);
+        assert_eq!(reported(LogRedaction::None), ["jane.doe@example.com"]);
+    }
+
+    /// Tests that the SMTP stage runs only for domains with MX records
+    #[test]
+    fn test_verify_smtp_stage() {
+        use crate::smtp::verifier_tests::ScriptedConnector;
+
+        let connector = ScriptedConnector::new(&["220 ready", "250 hello", "250 OK", "250 OK", "550 No such user", "221 Bye"]);
+        let smtp = SmtpVerifier::new("verifier.test", "probe@verifier.test").with_connector(connector);
+        let resolver = MockResolver::new()
+            .with_mx("example.com", vec![mx(10, "mx.example.com")])
+            .with_mx("nomail.com", vec![mx(0, "")]);
+        let verifier = Verifier::new(resolver).with_smtp(smtp);
+
+        let smtp = block_on(verifier.verify("user@example.com")).unwrap().smtp.unwrap();
+        assert_eq!(smtp.is_deliverable, Some(true));
+        assert_eq!(smtp.is_catch_all, Some(false));
+
+        assert_eq!(block_on(verifier.verify("user@nomail.com")).unwrap().smtp, None);
+        assert_eq!(block_on(verifier.verify("invalid-email")).unwrap().smtp, None);
+    }
+
+    /// Tests that cached domain checks are reused instead of querying again
+    #[test]
+    fn test_verify_uses_cache() {
+        use crate::cache::LruCache;
+        use std::sync::Arc;
+
+        let cache = Arc::new(LruCache::new(100));
+        let resolver = MockResolver::new().with_mx("example.com", vec![mx(10, "mx.example.com")]);
+        let verifier = Verifier::new(resolver).with_cache(cache.clone(), Duration::from_secs(60));
+
+        block_on(verifier.verify("a@example.com")).unwrap();
+        assert_eq!(cache.len(), 1);
+
+        // A cached entry wins over what the resolver would now return
+        cache.insert("example.com", DomainChecks { has_mx: Some(false), ..Default::default() }, Duration::from_secs(60));
+        let result = block_on(verifier.verify("b@EXAMPLE.com")).unwrap();
+        assert_eq!(result.domain_checks.has_mx, Some(false));
+    }
+
+    /// Tests the breach stage through the pipeline
+    #[test]
+    fn test_verify_breach_check() {
+        use crate::breach::tests::MockBreachSource;
+
+        let source = MockBreachSource::new(&["leaked@example.com"]);
+        let resolver = MockResolver::new().with_mx("example.com", vec![mx(10, "mx.example.com")]);
+        let verifier = Verifier::new(resolver).with_breach_check(&source);
+
+        assert_eq!(block_on(verifier.verify("leaked@example.com")).unwrap().found_in_breach, Some(true));
+        assert_eq!(block_on(verifier.verify("safe@example.com")).unwrap().found_in_breach, Some(false));
+        assert_eq!(block_on(verifier.verify("invalid-email")).unwrap().found_in_breach, None);
+        assert_eq!(source.queried.borrow().len(), 2);
+
+        let verifier = Verifier::new(MockResolver::new());
+        assert_eq!(block_on(verifier.verify("leaked@example.com")).unwrap().found_in_breach, None);
+    }
+
+    /// Resolver whose lookups never complete
+    struct StalledResolver;
+
+    impl DnsResolver for StalledResolver {
+        async fn lookup_mx(&self, _domain: &str) -> Result<Vec<MxRecord>, DnsError> {
+            std::future::pending().await
+        }
+
+        async fn lookup_txt(&self, _domain: &str) -> Result<Vec<String>, DnsError> {
+            std::future::pending().await
+        }
+    }
+
+    /// Tests that a stalled DNS stage times out and that cancellation fails
+    /// every later verification
+    #[test]
+    fn test_verify_timeout_and_cancellation() {
+        let token = CancellationToken::new();
+        let verifier = Verifier::new(StalledResolver)
+            .with_timeouts(StageTimeouts { dns: Some(Duration::from_millis(10)), ..StageTimeouts::default() })
+            .with_cancellation(token.clone());
+
+        let result = block_on(verifier.verify("user@slow.com")).unwrap();
+        assert!(result.email.is_valid);
+        assert_eq!(result.domain_checks.has_mx, None);
+        assert_eq!(result.domain_checks.dns_error.unwrap().error_type, "Timeout");
+
+        token.cancel();
+        assert_eq!(block_on(verifier.verify("user@slow.com")).unwrap_err().error_type, "Cancelled");
+        assert_eq!(block_on(verifier.verify("invalid-email")).unwrap_err().error_type, "Cancelled");
+    }
+
+    /// Connector whose connections hang until long after any stage timeout
+    struct HangingConnector;
+
+    impl SmtpConnector for HangingConnector {
+        fn connect(&self, _host: &str, _port: u16) -> std::io::Result<Box<dyn SmtpStream>> {
+            std::thread::sleep(Duration::from_secs(5));
+            Err(std::io::ErrorKind::TimedOut.into())
+        }
+    }
+
+    /// Tests that stage timeouts interrupt a resolver and an SMTP probe that
+    /// block on their sockets
+    #[test]
+    fn test_verify_timeout_interrupts_blocking_stages() {
+        use crate::dns::SystemResolver;
+        use std::net::UdpSocket;
+        use std::time::Instant;
+
+        // A nameserver that never answers, queried with a 5 second socket timeout
+        let silent = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let resolver = SystemResolver::new(silent.local_addr().unwrap());
+        let verifier = Verifier::new(resolver)
+            .with_timeouts(StageTimeouts { dns: Some(Duration::from_millis(50)), ..StageTimeouts::default() });
+        let started = Instant::now();
+        let result = block_on(verifier.verify("user@example.com")).unwrap();
+        assert_eq!(result.domain_checks.dns_error.unwrap().error_type, "Timeout");
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        let smtp = SmtpVerifier::new("verifier.test", "probe@verifier.test").with_connector(HangingConnector);
+        let resolver = MockResolver::new().with_mx("example.com", vec![mx(10, "mx.example.com")]);
+        let verifier = Verifier::new(resolver)
+            .with_smtp(smtp)
+            .with_timeouts(StageTimeouts { smtp: Some(Duration::from_millis(50)), ..StageTimeouts::default() });
+        let started = Instant::now();
+        let check = block_on(verifier.verify("user@example.com")).unwrap().smtp.unwrap();
+        assert_eq!(check.is_deliverable, None);
+        assert_eq!(check.error.unwrap().error_type, "Timeout");
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
@@ -0,0 +1,711 @@
+//! JavaScript bindings (`wasm` feature)
+//!
+//! The `#[wasm_bindgen]` entry points used by the TypeScript SDK. Native
+//! consumers build without this feature and skip the wasm-bindgen
+//! dependencies entirely.
+//!
+//! Entry points never panic: a panic aborts the whole module with an opaque
+//! `RuntimeError: unreachable`, so internal failures are returned as
+//! `EmailParseError` values instead.
+//!
+//! Field names are camelCase (`isValid`, `localPart`) unless
+//! `set_snake_case_output(true)` restores the Rust names. Options objects
+//! are accepted with either camelCase or snake_case keys.
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::casing::{self, AcceptCamelCase, Cased};
+use crate::{
+    extract_emails, hash_email, is_header_safe, is_valid_html5_email, load_domain_lists, parse_address_list, parse_and_validate_email,
+    parse_and_validate_email_with_options, parse_mailbox, stream, suggest_corrections, validate_partial, Config, EmailParseError, ScoreBreakdown
+};
+#[cfg(target_arch = "wasm32")]
+use crate::parse_mailto;
+#[cfg(all(feature = "net", target_arch = "wasm32"))]
+use crate::{breach, dns, CancellationToken, PipelineEvent, StageTimeouts, VerificationProfile, Verifier};
+
+/// Installs a panic hook that logs the panic message and stack to
+/// `console.error`
+/// 
+/// Call once after loading the module. The entry points do not panic, so
+/// this only matters for bugs; without it a panic surfaces as an unreadable
+/// `RuntimeError: unreachable`. Calling it again has no effect.
+/// 
+/// # Examples
+/// ```javascript
+/// import { init_panic_hook } from "email-validator-wasm";
+/// init_panic_hook();
+/// ```
+#[wasm_bindgen]
+pub fn init_panic_hook() {
+    console_error_panic_hook::set_once();
+}
+
+/// Crate version of the loaded module, e.g. "0.1.0"
+/// 
+/// # Examples
+/// ```javascript
+/// import { version } from "email-validator-wasm";
+/// console.log(version()); // "0.1.0"
+/// ```
+#[wasm_bindgen(js_name = version)]
+pub fn version_wasm() -> String {
+    crate::version().to_string()
+}
+
+/// Crate version, enabled features and domain-list versions of the loaded
+/// module
+/// 
+/// Log it with verification decisions to record which ruleset made them.
+/// 
+/// # Returns
+/// * `JsValue` - Serialized BuildInfo
+/// 
+/// # Examples
+/// ```javascript
+/// const info = build_info();
+/// console.log(info.version, info.features, info.domainLists);
+/// ```
+#[wasm_bindgen(js_name = build_info)]
+pub fn build_info_wasm() -> JsValue {
+    to_js(&crate::build_info())
+}
+
+/// Compatibility switch for consumers written against the original
+/// snake_case output (`is_valid`, `local_part`)
+/// 
+/// Results use camelCase field names by default. Passing `true` restores
+/// the snake_case names for every entry point until it is switched back.
+/// 
+/// # Examples
+/// ```javascript
+/// set_snake_case_output(true);
+/// console.log(parse_and_validate_email_wasm("user@example.com").is_valid); // true
+/// ```
+#[wasm_bindgen]
+pub fn set_snake_case_output(enabled: bool) {
+    casing::set_snake_case_output(enabled);
+}
+
+/// Serializes a value for JS with the configured field naming, reporting a
+/// serialization failure as an `EmailParseError` value rather than panicking
+pub(crate) fn to_js<T: Serialize>(value: &T) -> JsValue {
+    match serde_wasm_bindgen::to_value(&Cased(value)) {
+        Ok(value) => value,
+        Err(e) => serialization_error(&e)
+    }
+}
+
+/// `EmailParseError` of type "SerializationError" as a JS value
+fn serialization_error(e: &serde_wasm_bindgen::Error) -> JsValue {
+    let error = EmailParseError {
+        error_type: "SerializationError".to_string(),
+        message: "Failed to convert the result to a JavaScript value".to_string(),
+        details: Some(e.to_string()),
+        position: None
+    };
+    to_js(&error)
+}
+
+/// WebAssembly entry point for email validation
+/// 
+/// This function is exposed to JavaScript via wasm-bindgen and provides
+/// the main interface for email validation from the TypeScript SDK.
+/// 
+/// # Arguments
+/// * `email` - The email string to validate
+/// 
+/// # Returns
+/// * `JsValue` - Serialized EmailParseResult or EmailParseError
+/// 
+/// # Examples
+/// ```javascript
+/// // From JavaScript/TypeScript
+/// const result = parse_and_validate_email_wasm("user@example.com");
+/// console.log(result.isValid); // true
+/// ```
+#[wasm_bindgen]
+pub fn parse_and_validate_email_wasm(email: &str) -> JsValue {
+    match parse_and_validate_email(email) {
+        Ok(result) => to_js(&result),
+        Err(e) => to_js(&e)
+    }
+}
+
+/// WebAssembly entry point for the header-injection guard
+/// 
+/// # Arguments
+/// * `email` - The address to check before placing it in a mail header
+/// 
+/// # Returns
+/// * `bool` - `true` if no raw or encoded CR/LF/NUL was found
+/// 
+/// # Examples
+/// ```javascript
+/// if (!is_header_safe_wasm(replyTo)) throw new Error("Rejected Reply-To");
+/// ```
+#[wasm_bindgen]
+pub fn is_header_safe_wasm(email: &str) -> bool {
+    is_header_safe(email)
+}
+
+/// WebAssembly entry point for the browser's own email field check
+/// 
+/// Implements the HTML standard's "valid email address" production, so it
+/// agrees exactly with `<input type="email">` constraint validation.
+/// 
+/// # Arguments
+/// * `value` - The field's value, with newlines removed and surrounding whitespace trimmed as the browser does
+/// 
+/// # Returns
+/// * `bool` - `true` if the browser would consider the value a valid email address
+/// 
+/// # Examples
+/// ```javascript
+/// // Accept exactly what the form's email field accepts, for input that bypassed it
+/// if (!is_valid_html5_email_wasm(value.replace(/[\r\n]/g, "").trim())) return reject("email");
+/// ```
+#[wasm_bindgen]
+pub fn is_valid_html5_email_wasm(value: &str) -> bool {
+    is_valid_html5_email(value)
+}
+
+/// WebAssembly entry point for privacy-safe address hashing
+/// 
+/// # Arguments
+/// * `email` - The address to hash (trimmed and lowercased first)
+/// 
+/// # Returns
+/// * `String` - Lowercase hex-encoded SHA-256 digest
+/// 
+/// # Examples
+/// ```javascript
+/// analytics.track({ user: hash_email_wasm(email) });
+/// ```
+#[wasm_bindgen]
+pub fn hash_email_wasm(email: &str) -> String {
+    hash_email(email)
+}
+
+/// WebAssembly entry point for refreshing the domain lists at runtime
+/// 
+/// # Arguments
+/// * `json` - JSON object with optional `disposable`, `trusted` and `blocked` domain arrays
+/// 
+/// # Returns
+/// * `Result<(), JsValue>` - Throws a serialized EmailParseError if the JSON is invalid
+/// 
+/// # Examples
+/// ```javascript
+/// const lists = await (await fetch("/domain-lists.json")).text();
+/// load_domain_lists_wasm(lists);
+/// ```
+#[wasm_bindgen]
+pub fn load_domain_lists_wasm(json: &str) -> Result<(), JsValue> {
+    load_domain_lists(json).map_err(|e| to_js(&e))
+}
+
+/// WebAssembly entry point for mailbox parsing
+/// 
+/// Accepts name-addr syntax as pasted from mail clients
+/// (`Jane Doe <jane@example.com>`) as well as bare addresses.
+/// 
+/// # Arguments
+/// * `input` - The mailbox string to parse
+/// 
+/// # Returns
+/// * `JsValue` - Serialized Mailbox or EmailParseError
+/// 
+/// # Examples
+/// ```javascript
+/// const mailbox = parse_mailbox_wasm('"Doe, Jane" <jane@example.com>');
+/// console.log(mailbox.displayName, mailbox.address.isValid); // "Doe, Jane" true
+/// ```
+#[wasm_bindgen]
+pub fn parse_mailbox_wasm(input: &str) -> JsValue {
+    match parse_mailbox(input) {
+        Ok(mailbox) => to_js(&mailbox),
+        Err(e) => to_js(&e)
+    }
+}
+
+/// WebAssembly entry point for address-list parsing
+/// 
+/// Splits a pasted `To:` line on `,`/`;` (respecting quotes, comments and
+/// angle brackets) and validates each entry.
+/// 
+/// # Arguments
+/// * `input` - The address list to parse
+/// 
+/// # Returns
+/// * `JsValue` - Array of serialized Mailbox, or a serialized EmailParseError
+/// 
+/// # Examples
+/// ```javascript
+/// const mailboxes = parse_address_list_wasm('"Doe, Jane" <jane@example.com>; bob@example.com');
+/// console.log(mailboxes.length); // 2
+/// ```
+#[wasm_bindgen]
+pub fn parse_address_list_wasm(input: &str) -> JsValue {
+    match parse_address_list(input) {
+        Ok(mailboxes) => to_js(&mailboxes),
+        Err(e) => to_js(&e)
+    }
+}
+
+/// WebAssembly entry point for extracting addresses from free text
+/// 
+/// Offsets are UTF-8 byte offsets into `text`, not JS string indices.
+/// 
+/// # Arguments
+/// * `text` - The text to scan (e.g. a support-ticket body)
+/// 
+/// # Returns
+/// * `JsValue` - Array of serialized ExtractedEmail, or a serialized EmailParseError
+/// 
+/// # Examples
+/// ```javascript
+/// const found = extract_emails_wasm("Please reply to jane@example.com.");
+/// console.log(found[0].email, found[0].start, found[0].end); // "jane@example.com" 16 32
+/// ```
+#[wasm_bindgen]
+pub fn extract_emails_wasm(text: &str) -> JsValue {
+    match extract_emails(text) {
+        Ok(found) => to_js(&found),
+        Err(e) => to_js(&e)
+    }
+}
+
+/// WebAssembly entry point for ranked correction candidates
+/// 
+/// # Arguments
+/// * `email` - The address as entered
+/// 
+/// # Returns
+/// * `JsValue` - Array of serialized CorrectionCandidate (`email`, `kind`, `confidence`), most likely first
+/// 
+/// # Examples
+/// ```javascript
+/// const candidates = suggest_corrections_wasm("jane@gmial.con");
+/// picker.show(candidates.map((candidate) => candidate.email)); // ["jane@gmail.com"]
+/// ```
+#[wasm_bindgen]
+pub fn suggest_corrections_wasm(email: &str) -> JsValue {
+    to_js(&suggest_corrections(email))
+}
+
+/// WebAssembly entry point for validating a packed batch in a worker pool
+/// 
+/// Takes UTF-8, newline-delimited addresses and returns one JSON record per
+/// non-blank line (`{"email": ..., ...result}`), UTF-8 encoded, with the
+/// same field naming as the other entry points. Both buffers
+/// can be transferred between threads, so a large list can be split across
+/// `navigator.hardwareConcurrency` Web Workers without copying; the TS SDK's
+/// `validateInWorkers` does the splitting and decoding.
+/// 
+/// # Arguments
+/// * `input` - Newline-delimited addresses
+/// 
+/// # Returns
+/// * `Uint8Array` - JSONL records; a line that is not UTF-8 gets an `InvalidEncoding` error record
+/// 
+/// # Examples
+/// ```javascript
+/// // worker.js
+/// self.onmessage = ({ data }) => {
+///     const output = validate_packed_wasm(data);
+///     self.postMessage(output, [output.buffer]);
+/// };
+/// ```
+#[wasm_bindgen]
+pub fn validate_packed_wasm(input: &[u8]) -> Vec<u8> {
+    stream::validate_packed(input)
+}
+
+/// WebAssembly entry point for `mailto:` URI parsing
+/// 
+/// # Arguments
+/// * `uri` - The URI to parse, e.g. `mailto:jane@example.com?subject=Hi`
+/// 
+/// # Returns
+/// * `Result<JsValue, JsValue>` - Serialized MailtoUri (with `params` as a plain object) or EmailParseError
+/// 
+/// # Examples
+/// ```javascript
+/// const mailto = parse_mailto_wasm("mailto:jane%2Bnews@example.com?subject=Hello");
+/// console.log(mailto.email, mailto.params.subject); // "jane+news@example.com" "Hello"
+/// ```
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn parse_mailto_wasm(uri: &str) -> Result<JsValue, JsValue> {
+    match parse_mailto(uri) {
+        Ok(mailto) => Ok(to_js_object(&mailto)),
+        Err(e) => Ok(to_js(&e))
+    }
+}
+
+/// WebAssembly entry point for email validation with host-provided options
+/// 
+/// Accepts a plain JS `Config` object, so behavior can be tuned without
+/// rebuilding the module. Supported keys:
+/// - any `Config` field, in camelCase or snake_case, e.g. `mode: "strict"`,
+///   `normalizeUnicode: true`, `domainLists: { blocked: string[] }`,
+///   `maxLength: 254`, `lengthPolicy: "warn"` or `protectedDomains: string[]`
+/// - `domainScorer` (or `domain_scorer`): `(domain, defaultScore) => number | undefined`, optionally
+///   returning a Promise. A returned number (clamped to 0-100) replaces the
+///   built-in domain score; `undefined`/`null` keeps the built-in score.
+/// 
+/// # Arguments
+/// * `email` - The email string to validate
+/// * `options` - Options object (may be `undefined`)
+/// 
+/// # Returns
+/// * `Promise<JsValue>` - Serialized EmailParseResult or EmailParseError
+/// 
+/// # Examples
+/// ```javascript
+/// const result = await parse_and_validate_email_with_options_wasm("user@corp.com", {
+///     domainScorer: async (domain, score) => (await blocklist.has(domain)) ? 0 : score,
+/// });
+/// ```
+#[wasm_bindgen]
+pub async fn parse_and_validate_email_with_options_wasm(email: String, options: JsValue) -> Result<JsValue, JsValue> {
+    let validation_options = match options_from_js(&options) {
+        Ok(validation_options) => validation_options,
+        Err(e) => return Ok(to_js(&e))
+    };
+
+    let mut result = match parse_and_validate_email_with_options(&email, &validation_options) {
+        Ok(result) => result,
+        Err(e) => return Ok(to_js(&e))
+    };
+
+    let scorer = if options.is_object() {
+        match js_sys::Reflect::get(&options, &JsValue::from_str("domainScorer"))? {
+            scorer if scorer.is_undefined() => js_sys::Reflect::get(&options, &JsValue::from_str("domain_scorer"))?,
+            scorer => scorer
+        }
+    } else {
+        JsValue::UNDEFINED
+    };
+
+    if let (Some(scorer), Some(domain), Some(score)) = (scorer.dyn_ref::<js_sys::Function>(), &result.domain, result.domain_score) {
+        match call_domain_scorer(scorer, domain, score).await {
+            Ok(Some(custom_score)) => {
+                let mut breakdown = result.score_breakdown.take().unwrap_or_default();
+                let mut domain_score = breakdown.total();
+                ScoreBreakdown::record(&mut domain_score, &mut breakdown.custom_scorer, |_| custom_score.clamp(0.0, 100.0));
+                result.domain_score = Some(validation_options.score_weights.map_or(domain_score, |weights| weights.score(&breakdown)));
+                result.score_breakdown = Some(breakdown);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                let error = EmailParseError {
+                    error_type: "ScorerError".to_string(),
+                    message: "Custom domain scorer failed".to_string(),
+                    details: e.as_string().or_else(|| e.dyn_ref::<js_sys::Error>().map(|e| String::from(e.message()))),
+                    position: None
+                };
+                return Ok(to_js(&error));
+            }
+        }
+    }
+
+    Ok(to_js(&result))
+}
+
+/// Options from a JS `Config` object with camelCase or snake_case keys, or
+/// the defaults when `options` is not an object
+fn options_from_js(options: &JsValue) -> Result<Config, EmailParseError> {
+    if !options.is_object() {
+        return Ok(Config::default());
+    }
+    let deserializer = serde_wasm_bindgen::Deserializer::from(options.clone());
+    Config::deserialize(AcceptCamelCase(deserializer)).map_err(|e| EmailParseError {
+        error_type: "InvalidOptions".to_string(),
+        message: "Failed to parse validation options".to_string(),
+        details: Some(e.to_string()),
+        position: None
+    })
+}
+
+/// WebAssembly entry point for validating input as it is typed
+/// 
+/// Reports `incomplete` rather than an error while typing more could still
+/// make the input valid (`jane@`, `jane@example`), so form fields only turn
+/// red once the input can no longer become an address.
+/// 
+/// # Arguments
+/// * `input` - The field's current value
+/// * `options` - `Config` object as for `parse_and_validate_email_with_options_wasm`, without `domainScorer` (may be `undefined`)
+/// 
+/// # Returns
+/// * `JsValue` - Serialized PartialValidation (`state` is `incomplete`, `invalid` or `valid`) or EmailParseError
+/// 
+/// # Examples
+/// ```javascript
+/// input.addEventListener("input", () => {
+///     const { state, errorPosition } = validate_partial_wasm(input.value);
+///     input.classList.toggle("error", state === "invalid");
+/// });
+/// ```
+#[wasm_bindgen]
+pub fn validate_partial_wasm(input: &str, options: JsValue) -> JsValue {
+    match options_from_js(&options) {
+        Ok(options) => to_js(&validate_partial(input, &options)),
+        Err(e) => to_js(&e)
+    }
+}
+
+/// Invokes a JS domain scorer, awaiting it if it returns a Promise
+async fn call_domain_scorer(scorer: &js_sys::Function, domain: &str, score: f64) -> Result<Option<f64>, JsValue> {
+    let mut value = scorer.call2(&JsValue::NULL, &JsValue::from_str(domain), &JsValue::from_f64(score))?;
+    if let Some(promise) = value.dyn_ref::<js_sys::Promise>() {
+        value = wasm_bindgen_futures::JsFuture::from(promise.clone()).await?;
+    }
+
+    if value.is_undefined() || value.is_null() {
+        return Ok(None);
+    }
+
+    match value.as_f64() {
+        Some(custom_score) if custom_score.is_finite() => Ok(Some(custom_score)),
+        _ => Err(JsValue::from_str("domainScorer must return a finite number, undefined or null"))
+    }
+}
+
+/// WebAssembly entry point for email verification with DNS checks
+/// 
+/// Validates the email and looks up its domain's MX records over
+/// DNS-over-HTTPS, using `doh_endpoint` (a JSON API endpoint) or Cloudflare's
+/// public resolver when omitted. When `breach_endpoint` is given, the address
+/// is also checked against that HIBP-compatible range API (`{prefix}` in the
+/// URL is replaced by the 5-character hash prefix, otherwise it is appended).
+/// 
+/// `timeout_ms` bounds each network stage, which then reports a "Timeout"
+/// error in its result field. Aborting `signal` stops the verification with a
+/// "Cancelled" error. `on_event` is called with an event object as each stage
+/// starts and finishes.
+/// 
+/// # Arguments
+/// * `email` - The email string to verify
+/// * `doh_endpoint` - Optional DNS-over-HTTPS JSON endpoint URL
+/// * `breach_endpoint` - Optional breach range endpoint URL
+/// * `timeout_ms` - Optional per-stage timeout in milliseconds
+/// * `signal` - Optional `AbortSignal`
+/// * `on_event` - Optional callback receiving `{ event: "stage_started" | "stage_finished", stage, domain, outcome, durationMs }`
+/// 
+/// # Returns
+/// * `Promise<JsValue>` - Serialized VerificationResult or EmailParseError
+/// 
+/// # Examples
+/// ```javascript
+/// const result = await verify_email_wasm("user@example.com", "https://dns.google/resolve");
+/// console.log(result.hasMx); // true
+/// 
+/// const controller = new AbortController();
+/// const pending = verify_email_wasm("user@example.com", undefined, undefined, 2000, controller.signal);
+/// controller.abort();
+/// console.log((await pending).errorType); // "Cancelled"
+/// 
+/// await verify_email_wasm("user@example.com", undefined, undefined, undefined, undefined,
+///   (event) => console.log(event.event, event.stage, event.outcome));
+/// ```
+#[cfg(all(feature = "net", target_arch = "wasm32"))]
+#[wasm_bindgen]
+pub async fn verify_email_wasm(
+    email: String,
+    doh_endpoint: Option<String>,
+    breach_endpoint: Option<String>,
+    timeout_ms: Option<u32>,
+    signal: JsValue,
+    on_event: Option<js_sys::Function>
+) -> Result<JsValue, JsValue> {
+    let resolver = cached_doh_resolver(doh_endpoint);
+    let timeout = timeout_ms.map(|ms| std::time::Duration::from_millis(ms.into()));
+    let mut verifier = Verifier::new(resolver).with_timeouts(StageTimeouts { dns: timeout, smtp: timeout, breach: timeout, gravatar: timeout, rdap: timeout, ct_log: timeout });
+    if !signal.is_undefined() && !signal.is_null() {
+        verifier = verifier.with_cancellation(CancellationToken::from_abort_signal(&signal)?);
+    }
+    if let Some(callback) = on_event {
+        verifier = verifier.with_observer(move |event: &PipelineEvent| {
+            // A throwing callback must not abort the verification
+            let _ = callback.call1(&JsValue::NULL, &to_js_object(event));
+        });
+    }
+    let result = match breach_endpoint {
+        Some(endpoint) => verifier.with_breach_check(breach::FetchBreachSource::new(&endpoint)).verify(&email).await,
+        None => verifier.verify(&email).await
+    };
+
+    match result {
+        Ok(result) => Ok(to_js_object(&result)),
+        Err(e) => Ok(to_js(&e))
+    }
+}
+
+/// DNS-over-HTTPS resolver for `endpoint` (Cloudflare when None), sharing
+/// one answer cache across calls so verifying a list one address at a time
+/// still looks each domain up once per TTL
+#[cfg(all(feature = "net", target_arch = "wasm32"))]
+fn cached_doh_resolver(endpoint: Option<String>) -> dns::CachingResolver<dns::DohResolver> {
+    static DNS_CACHE: std::sync::OnceLock<dns::DnsCache> = std::sync::OnceLock::new();
+    let resolver = match endpoint {
+        Some(endpoint) => dns::DohResolver::new(&endpoint),
+        None => dns::DohResolver::default()
+    };
+    dns::CachingResolver::new(resolver, DNS_CACHE.get_or_init(dns::DnsCache::default).clone())
+}
+
+/// WebAssembly entry point for email verification driven by a verification
+/// profile
+/// 
+/// Takes the same JSON profile the server and native code load, so one
+/// checked-in policy file governs every environment. The SMTP stage is not
+/// available in the browser and is ignored.
+/// 
+/// # Arguments
+/// * `email` - The email string to verify
+/// * `profile_json` - VerificationProfile as JSON
+/// * `doh_endpoint` - Optional DNS-over-HTTPS JSON endpoint URL
+/// 
+/// # Returns
+/// * `Promise<JsValue>` - Serialized VerificationResult or EmailParseError
+/// 
+/// # Examples
+/// ```javascript
+/// const profile = await (await fetch("/verification-profile.json")).text();
+/// const result = await verify_email_with_profile_wasm("user@example.com", profile);
+/// ```
+#[cfg(all(feature = "net", target_arch = "wasm32"))]
+#[wasm_bindgen]
+pub async fn verify_email_with_profile_wasm(email: String, profile_json: String, doh_endpoint: Option<String>) -> Result<JsValue, JsValue> {
+    let profile = match VerificationProfile::from_json(&profile_json) {
+        Ok(profile) => profile,
+        Err(e) => return Ok(to_js(&e))
+    };
+    match profile.apply(Verifier::new(cached_doh_resolver(doh_endpoint))).verify(&email).await {
+        Ok(result) => Ok(to_js_object(&result)),
+        Err(e) => Ok(to_js(&e))
+    }
+}
+
+/// WebAssembly entry point for email verification against the sandbox
+/// 
+/// Answers DNS, SPF and DMARC checks from canned data for reserved names under
+/// `sandbox.test` (e.g. `user@nomx.sandbox.test`, `user@nxdomain.sandbox.test`)
+/// without any network access, so integration tests are deterministic.
+/// 
+/// # Arguments
+/// * `email` - The email string to verify
+/// 
+/// # Returns
+/// * `Promise<JsValue>` - Serialized VerificationResult or EmailParseError
+/// 
+/// # Examples
+/// ```javascript
+/// const result = await verify_email_sandbox_wasm("user@nxdomain.sandbox.test");
+/// console.log(result.errorCode); // "NxDomain"
+/// ```
+#[cfg(all(feature = "net", target_arch = "wasm32"))]
+#[wasm_bindgen]
+pub async fn verify_email_sandbox_wasm(email: String) -> Result<JsValue, JsValue> {
+    match Verifier::sandbox().verify(&email).await {
+        Ok(result) => Ok(to_js_object(&result)),
+        Err(e) => Ok(to_js(&e))
+    }
+}
+
+/// Serializes a value for JS, emitting maps (and flattened structs) as plain
+/// objects instead of `Map` instances
+#[cfg(target_arch = "wasm32")]
+fn to_js_object<T: Serialize>(value: &T) -> JsValue {
+    match Cased(value).serialize(&serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true)) {
+        Ok(value) => value,
+        Err(e) => serialization_error(&e)
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    pub(crate) fn set_timeout(handler: &js_sys::Function, timeout: i32) -> JsValue;
+}
+
+/// Resolves on the next macrotask, letting the JS event loop run
+/// pending rendering and input handling before the caller continues
+async fn yield_to_event_loop() -> Result<(), JsValue> {
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        set_timeout(&resolve, 0);
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await?;
+    Ok(())
+}
+
+/// Asynchronous WebAssembly entry point for large batch validation
+/// 
+/// Validates `emails` in chunks of `chunk_size`, yielding to the JS event loop
+/// between chunks so a large batch does not freeze the page. After each chunk
+/// the optional `on_progress` callback is invoked as `on_progress(processed, total)`.
+/// 
+/// # Arguments
+/// * `emails` - The email strings to validate
+/// * `chunk_size` - Number of emails validated between yields (0 is treated as 1)
+/// * `on_progress` - Optional progress callback
+/// 
+/// # Returns
+/// * `Promise<Array>` - Serialized EmailParseResult or EmailParseError per input, in input order
+/// 
+/// # Examples
+/// ```javascript
+/// const results = await parse_and_validate_emails_chunked_wasm(emails, 1000,
+///     (processed, total) => console.log(`${processed}/${total}`));
+/// ```
+#[wasm_bindgen]
+pub async fn parse_and_validate_emails_chunked_wasm(
+    emails: Vec<String>,
+    chunk_size: usize,
+    on_progress: Option<js_sys::Function>
+) -> Result<js_sys::Array, JsValue> {
+    let results = js_sys::Array::new();
+    let total = emails.len();
+    let mut processed = 0;
+
+    for chunk in emails.chunks(chunk_size.max(1)) {
+        for email in chunk {
+            results.push(&parse_and_validate_email_wasm(email));
+        }
+        processed += chunk.len();
+
+        if let Some(callback) = &on_progress {
+            callback.call2(&JsValue::NULL, &JsValue::from(processed as u32), &JsValue::from(total as u32))?;
+        }
+
+        if processed < total {
+            yield_to_event_loop().await?;
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that packed output follows the configured field naming
+    #[test]
+    fn test_validate_packed_output_case() {
+        let output = validate_packed_wasm(b"user@example.com\ninvalid");
+        let records: Vec<serde_json::Value> = output.split(|byte| *byte == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["email"], "user@example.com");
+        assert_eq!(records[0]["isValid"], true);
+        assert_eq!(records[1]["errorMessage"], "Invalid email format");
+        assert!(records[0].get("is_valid").is_none());
+    }
+}
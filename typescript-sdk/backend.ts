@@ -0,0 +1,46 @@
+import * as wasm from "./wasm";
+
+/**
+ * Exports shared by the WASM module and the native Node.js addon
+ */
+type Backend = Pick<typeof wasm, "parse_and_validate_email_wasm" | "init_panic_hook" | "set_snake_case_output">;
+
+/**
+ * Installs the backend's panic hook, so a Rust panic is logged with its
+ * message rather than as `RuntimeError: unreachable` or a bare abort
+ *
+ * Both backends emit camelCase fields by default; snake_case output is kept
+ * until the SDK's result mapping moves over.
+ *
+ * @param backend - The loaded backend
+ * @returns The configured backend
+ */
+function configure(backend: Backend): Backend {
+  backend.init_panic_hook();
+  backend.set_snake_case_output(true);
+  return backend;
+}
+
+/**
+ * Selects the validation backend
+ *
+ * Uses the native addon built with `npm run build:native` when it is present
+ * and the SDK runs under Node.js (CommonJS), avoiding WASM startup and
+ * serialization overhead. Falls back to the WASM module otherwise. Set
+ * `EMAIL_VALIDATOR_BACKEND=wasm` to force the WASM module.
+ *
+ * @returns The loaded backend
+ */
+function loadBackend(): Backend {
+  if (typeof require !== "function" || process.env.EMAIL_VALIDATOR_BACKEND === "wasm") {
+    return configure(wasm);
+  }
+
+  try {
+    return configure(require("./native/email-validator.node") as Backend);
+  } catch {
+    return configure(wasm);
+  }
+}
+
+export const { parse_and_validate_email_wasm } = loadBackend();
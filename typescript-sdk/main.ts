@@ -1,4 +1,4 @@
-import { parse_and_validate_email_wasm } from "./wasm";
+import { parse_and_validate_email_wasm } from "./backend";
 
 /**
  * Result of email validation containing validation status and parsed components
@@ -198,3 +198,10 @@ class EmailValidator {
 }
 
 export { EmailValidator, EmailValidationResult, EmailValidationError };
+export {
+  PackedRecord,
+  packEmails,
+  unpackResults,
+  serveValidationWorker,
+  validateInWorkers,
+} from "./workers";
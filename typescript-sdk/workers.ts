@@ -0,0 +1,149 @@
+/**
+ * Record produced for each address by the packed batch entry point:
+ * the input address plus the serialized validation result or error, with
+ * camelCase field names unless the worker's module enabled
+ * `set_snake_case_output`
+ */
+interface PackedRecord {
+  /** The input address as read from its line */
+  email: string;
+  /** Present on validation results */
+  isValid?: boolean;
+  /** Present when the parser rejected the input outright */
+  errorType?: string;
+  [field: string]: unknown;
+}
+
+/**
+ * Minimal view of a worker, satisfied by browser `Worker`s
+ */
+interface PackedWorker {
+  postMessage(message: Uint8Array, transfer: Transferable[]): void;
+  onmessage: ((event: MessageEvent<Uint8Array>) => void) | null;
+  onerror: ((event: ErrorEvent) => void) | null;
+  terminate(): void;
+}
+
+/**
+ * Minimal view of a worker's global scope
+ */
+interface PackedWorkerScope {
+  postMessage(message: Uint8Array, transfer: Transferable[]): void;
+  onmessage: ((event: MessageEvent<Uint8Array>) => void) | null;
+}
+
+/**
+ * Encodes addresses as UTF-8, one per line, for `validate_packed_wasm`
+ * @param emails - Addresses to encode
+ * @returns The packed buffer
+ */
+function packEmails(emails: string[]): Uint8Array {
+  return new TextEncoder().encode(
+    emails.map((email) => email.replace(/[\r\n]/g, " ")).join("\n")
+  );
+}
+
+/**
+ * Decodes the JSONL output of `validate_packed_wasm`
+ * @param packed - Output buffer from a worker
+ * @returns One record per non-blank input line, in input order
+ */
+function unpackResults(packed: Uint8Array): PackedRecord[] {
+  return new TextDecoder()
+    .decode(packed)
+    .split("\n")
+    .filter((line) => line.length > 0)
+    .map((line) => JSON.parse(line) as PackedRecord);
+}
+
+/**
+ * Turns the current worker into a validation worker
+ *
+ * Call from the worker script after the WASM module has loaded; each message
+ * is a packed buffer and is answered with the packed results.
+ *
+ * @param scope - The worker's global scope (`self`)
+ * @param validatePacked - The module's `validate_packed_wasm` export
+ *
+ * @example
+ * ```typescript
+ * // validation.worker.ts
+ * import init, { validate_packed_wasm } from "email-validator-wasm";
+ * await init();
+ * serveValidationWorker(self, validate_packed_wasm);
+ * ```
+ */
+function serveValidationWorker(
+  scope: PackedWorkerScope,
+  validatePacked: (input: Uint8Array) => Uint8Array
+): void {
+  scope.onmessage = (event) => {
+    const output = validatePacked(event.data);
+    scope.postMessage(output, [output.buffer]);
+  };
+}
+
+/**
+ * Validates a large list across a pool of Web Workers
+ *
+ * The list is split into one contiguous chunk per worker; chunks and results
+ * are transferred rather than copied. Blank addresses produce no record.
+ *
+ * @param emails - Addresses to validate
+ * @param createWorker - Starts a worker running `serveValidationWorker`
+ * @param concurrency - Number of workers (default `navigator.hardwareConcurrency`, or 4)
+ * @returns One record per non-blank address, in input order
+ *
+ * @example
+ * ```typescript
+ * const records = await validateInWorkers(emails, () =>
+ *   new Worker(new URL("./validation.worker.ts", import.meta.url), { type: "module" })
+ * );
+ * const invalid = records.filter((record) => !record.isValid);
+ * ```
+ */
+async function validateInWorkers(
+  emails: string[],
+  createWorker: () => PackedWorker,
+  concurrency: number = (typeof navigator !== "undefined" && navigator.hardwareConcurrency) || 4
+): Promise<PackedRecord[]> {
+  if (emails.length === 0) {
+    return [];
+  }
+
+  const workerCount = Math.max(1, Math.min(concurrency, emails.length));
+  const chunkSize = Math.ceil(emails.length / workerCount);
+
+  const chunks = Array.from({ length: workerCount }, (_, index) =>
+    emails.slice(index * chunkSize, (index + 1) * chunkSize)
+  ).filter((chunk) => chunk.length > 0);
+  const results = await Promise.all(
+    chunks.map(
+      (chunk) =>
+        new Promise<PackedRecord[]>((resolve, reject) => {
+          const worker = createWorker();
+          worker.onmessage = (event) => {
+            worker.terminate();
+            resolve(unpackResults(event.data));
+          };
+          worker.onerror = (event) => {
+            worker.terminate();
+            reject(new Error(event.message));
+          };
+          const packed = packEmails(chunk);
+          worker.postMessage(packed, [packed.buffer]);
+        })
+    )
+  );
+  return results.flat();
+}
+
+export {
+  PackedRecord,
+  PackedWorker,
+  PackedWorkerScope,
+  packEmails,
+  unpackResults,
+  serveValidationWorker,
+  validateInWorkers,
+};